@@ -0,0 +1,64 @@
+/// Example driving Notepad and Calculator concurrently from a tokio runtime
+///
+/// Each application gets its own `AsyncAutomation`, and so its own worker
+/// thread, so the two sequences really run side by side. Calls made through a
+/// single `AsyncAutomation` would be interleaved but still run one at a time.
+///
+/// To test:
+/// 1. Run this example: cargo run --example async_two_apps --features async
+/// 2. Watch Notepad receive text while Calculator computes 12 + 30
+use std::time::Duration;
+use ui_interaction::asynchronous::AsyncAutomation;
+use ui_interaction::core::UIQuery;
+use ui_interaction::error::UiError;
+use ui_interaction::factory::ApplicationManagerFactory;
+
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+async fn type_in_notepad() -> Result<String, UiError> {
+    let automation = AsyncAutomation::new().await?;
+    let window = automation.open_window(|| {
+        ApplicationManagerFactory::new()?.launch_and_wait("notepad.exe", &[], None, LAUNCH_TIMEOUT)
+    }).await?;
+    window.wait_until_ready(Duration::from_secs(5)).await?;
+
+    let editor = window.find_first(&UIQuery::ByType("Document".to_string())).await?
+        .ok_or_else(|| UiError::ElementNotFound("Notepad has no editor".to_string()))?;
+    editor.set_text("Typed from an async task").await?;
+    let text = editor.get_text().await?;
+
+    window.run(|window| {
+        let pid = window.get_process_id()?;
+        ApplicationManagerFactory::new()?.kill_application(pid)
+    }).await?;
+    Ok(text)
+}
+
+async fn add_in_calculator() -> Result<String, UiError> {
+    let automation = AsyncAutomation::new().await?;
+    let window = automation.open_window(|| {
+        ApplicationManagerFactory::new()?.launch_and_wait("calc.exe", &[], Some("Calculator"), LAUNCH_TIMEOUT)
+    }).await?;
+    window.wait_until_ready(Duration::from_secs(5)).await?;
+
+    window.send_keys("12+30=").await?;
+    let display = window.find_first(&UIQuery::ByAutomationId("CalculatorResults".to_string())).await?
+        .ok_or_else(|| UiError::ElementNotFound("Calculator has no display".to_string()))?;
+    let result = display.get_name().await?;
+
+    window.close().await?;
+    Ok(result)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    println!("Async Two Apps");
+    println!("==============");
+
+    let (notepad, calculator) = tokio::join!(type_in_notepad(), add_in_calculator());
+    println!("Notepad:    {}", notepad?);
+    println!("Calculator: {}", calculator?);
+    Ok(())
+}
@@ -15,7 +15,7 @@ fn run_python_code(code: &str) -> Result<String> {
         let globals = PyDict::new_bound(py);
         
         // Create and inject the automation object
-        let automation = Py::new(py, python_bindings::PyAutomation::new()?)?;
+        let automation = Py::new(py, python_bindings::PyAutomation::new(None, None)?)?;
         globals.set_item("automation", automation)?;
 
         // Register the uia_interaction module
@@ -33,18 +33,9 @@ struct TextExtractionResult {
 }
 
 fn extract_to_json(app_name: Option<&str>, focused: bool, selected_only: bool) -> Result<(), Box<dyn Error>> {
-    let window = if focused || app_name.is_none() {
-        // Use focused window
-        if !focused {
-            eprintln!("Waiting 3 seconds for you to focus on target window...");
-            thread::sleep(Duration::from_secs(3));
-        }
-        let automation = UIAutomationFactory::new()?;
-        automation.get_active_window()?
-    } else {
+    let window = if let Some(app_name) = app_name.filter(|_| !focused) {
         // Find by app name
         let app_manager = ApplicationManagerFactory::new()?;
-        let app_name = app_name.unwrap();
 
         // Try process name first
         let apps = app_manager.find_applications_by_name(app_name)?;
@@ -59,6 +50,14 @@ fn extract_to_json(app_name: Option<&str>, focused: bool, selected_only: bool) -
                 return Err(format!("Application '{}' not found", app_name).into());
             }
         }
+    } else {
+        // Use focused window
+        if !focused {
+            eprintln!("Waiting 3 seconds for you to focus on target window...");
+            thread::sleep(Duration::from_secs(3));
+        }
+        let automation = UIAutomationFactory::new()?;
+        automation.get_active_window()?
     };
 
     let window_title = window.get_title()?;
@@ -103,11 +102,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
-            "--app" => {
-                if i + 1 < args.len() {
-                    app_name = Some(&args[i + 1]);
-                    i += 1;
-                }
+            "--app" if i + 1 < args.len() => {
+                app_name = Some(&args[i + 1]);
+                i += 1;
             }
             "--focused" => focused = true,
             "--selected-only" => selected_only = true,
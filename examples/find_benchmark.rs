@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+/// Example timing repeated searches and a full tree walk of the active window
+///
+/// Useful for comparing search performance between versions of the crate.
+/// Apps with deep trees (browsers, Electron apps, Office documents) show the
+/// largest differences.
+///
+/// To test:
+/// 1. Run this example: cargo run --release --example find_benchmark
+/// 2. Switch to the window to measure within 3 seconds
+use std::thread;
+use ui_interaction::core::{UIQuery, UITreeNode, UITreeOptions};
+use ui_interaction::create_automation;
+
+const SEARCH_ROUNDS: u32 = 20;
+
+/// Count the nodes below `node`, returning (node count, max depth)
+fn measure(node: &UITreeNode, depth: usize) -> (usize, usize) {
+    node.children.iter().fold((1, depth), |(count, max_depth), child| {
+        let (child_count, child_depth) = measure(child, depth + 1);
+        (count + child_count, max_depth.max(child_depth))
+    })
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    println!("Find Benchmark");
+    println!("==============");
+    println!("Switch to the window to measure...");
+    thread::sleep(Duration::from_secs(3));
+
+    let automation = create_automation()?;
+    let window = automation.get_active_window()?;
+    println!("Measuring '{}'", window.get_title()?);
+
+    let query = UIQuery::ByType("Button".to_string());
+    let start = Instant::now();
+    let mut matches = 0;
+    for _ in 0..SEARCH_ROUNDS {
+        matches = window.find_elements(&query)?.len();
+    }
+    println!(
+        "find_elements(ByType Button): {} matches, {:?} per search",
+        matches,
+        start.elapsed() / SEARCH_ROUNDS
+    );
+
+    let start = Instant::now();
+    for _ in 0..SEARCH_ROUNDS {
+        window.find_first(&query)?;
+    }
+    println!("find_first(ByType Button): {:?} per search", start.elapsed() / SEARCH_ROUNDS);
+
+    let start = Instant::now();
+    let tree = window.get_ui_tree_with_options(&UITreeOptions::unlimited())?;
+    let elapsed = start.elapsed();
+    let (count, depth) = measure(&tree.root, 0);
+    println!("Full tree walk: {} elements, depth {}, {:?}", count, depth, elapsed);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::time::{Duration, Instant};
+/// Example logging every focus change for 10 seconds
+///
+/// Focus events arrive on a UI Automation worker thread, so the callback only
+/// formats a line and hands it to the main thread through a channel.
+///
+/// To test:
+/// 1. Run this example: cargo run --example focus_logger
+/// 2. Click or Tab around in any application for the next 10 seconds
+use std::sync::mpsc;
+use ui_interaction::create_automation;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    println!("Focus Logger");
+    println!("============");
+    println!("Logging focus changes for 10 seconds...");
+
+    let automation = create_automation()?;
+    let (sender, receiver) = mpsc::channel();
+
+    let subscription = automation.subscribe_focus_changed(Box::new(move |element| {
+        let name = element.get_name().unwrap_or_default();
+        let control_type = element.get_type().unwrap_or_else(|_| "Unknown".to_string());
+        let _ = sender.send(format!("{} ({})", name, control_type));
+    }))?;
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match receiver.recv_timeout(remaining) {
+            Ok(line) => println!("Focus -> {}", line),
+            Err(mpsc::RecvTimeoutError::Timeout) => break,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // Dropping the subscription unregisters the handler
+    drop(subscription);
+    println!("Done.");
+
+    Ok(())
+}
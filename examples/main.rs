@@ -3,7 +3,7 @@ use std::{thread, time::Duration};
 
 // Import our library modules
 use ui_interaction::UIAutomationFactory;
-use ui_interaction::core::{UIAutomation, UIQuery, UITree, AppendPosition};
+use ui_interaction::core::AppendPosition;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize the logger with info level
@@ -17,8 +17,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create UIAutomation instance using the factory
     let automation = UIAutomationFactory::new()?;
     
-    // Get the currently focused window
-    let window = automation.get_focused_window()?;
+    // Get the currently active window
+    let window = automation.get_active_window()?;
     
     // Get window details
     let title = window.get_title()?;
@@ -8,14 +8,21 @@
 //!   cargo run --example text_extraction_demo -- --app outlook
 //!   cargo run --example text_extraction_demo -- --app cursor
 //!   cargo run --example text_extraction_demo -- --app claude
+//!   cargo run --example text_extraction_demo -- --format json > texts.json
+//!   cargo run --example text_extraction_demo -- --app outlook --format json
+//!
+//! With `--format json` only the JSON array of text elements is written to
+//! stdout, so the output can be piped into other tools.
 
 use std::env;
 use std::error::Error;
 use std::thread;
 use std::time::Duration;
 
+use ui_interaction::core::DEFAULT_ROW_TOLERANCE;
 use ui_interaction::{
-    ApplicationManagerFactory, TextElementInfo, TextExtractionOptions, UIAutomationFactory,
+    text_elements_to_json, ApplicationManagerFactory, TextElementInfo, TextExtractionOptions,
+    UIAutomationFactory, Window,
 };
 
 /// Pretty print a TextElementInfo
@@ -195,6 +202,10 @@ fn extract_from_outlook() -> Result<(), Box<dyn Error>> {
         control_types: None, // Get all types to capture email content
         max_depth: Some(25),
         include_names_as_text: true,
+        // Read the message top to bottom rather than in tree order
+        sort_by_position: true,
+        row_tolerance: DEFAULT_ROW_TOLERANCE,
+        cancel: None,
     };
 
     let elements = window.get_text_elements(&options)?;
@@ -261,6 +272,9 @@ fn extract_from_cursor() -> Result<(), Box<dyn Error>> {
         control_types: None,
         max_depth: Some(30),
         include_names_as_text: true,
+        sort_by_position: false,
+        row_tolerance: DEFAULT_ROW_TOLERANCE,
+        cancel: None,
     };
 
     let elements = window.get_text_elements(&options)?;
@@ -320,6 +334,9 @@ fn extract_from_claude() -> Result<(), Box<dyn Error>> {
         control_types: None,
         max_depth: Some(30),
         include_names_as_text: true,
+        sort_by_position: false,
+        row_tolerance: DEFAULT_ROW_TOLERANCE,
+        cancel: None,
     };
 
     let elements = window.get_text_elements(&options)?;
@@ -341,6 +358,30 @@ fn extract_from_claude() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Find the main window of an application by process name, then by title
+fn find_app_window(app_name: &str) -> Result<Box<dyn Window>, Box<dyn Error>> {
+    let app_manager = ApplicationManagerFactory::new()?;
+    let mut apps = app_manager.find_applications_by_name(app_name)?;
+    if apps.is_empty() {
+        apps = app_manager.find_applications_by_title(app_name)?;
+    }
+    let app = apps
+        .first()
+        .ok_or_else(|| format!("Application '{}' not found", app_name))?;
+    Ok(app_manager.get_window_by_process_id(app.process_id)?)
+}
+
+/// Write the text elements of the focused window, or of `app_name`, to stdout as JSON
+fn extract_as_json(app_name: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let window = match app_name {
+        Some(app_name) => find_app_window(app_name)?,
+        None => UIAutomationFactory::new()?.get_active_window()?,
+    };
+    let elements = window.get_text_elements(&TextExtractionOptions::default())?;
+    println!("{}", text_elements_to_json(&elements)?);
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Initialize logging
     std::env::set_var("RUST_LOG", "info");
@@ -349,11 +390,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     let args: Vec<String> = env::args().collect();
 
     // Parse command line arguments
-    let app = if args.len() > 2 && args[1] == "--app" {
-        Some(args[2].as_str())
-    } else {
-        None
+    let option = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+            .map(String::as_str)
     };
+    let app = option("--app");
+
+    match option("--format") {
+        Some("json") => return extract_as_json(app),
+        Some("text") | None => {}
+        Some(other) => return Err(format!("Unknown format '{}', expected 'text' or 'json'", other).into()),
+    }
 
     match app {
         Some("outlook") => {
@@ -0,0 +1,384 @@
+//! Async wrappers over the blocking API, for use from async runtimes such as tokio
+//!
+//! Enabled with the `async` feature. Every call is sent to one long-lived
+//! worker thread, which joins the multithreaded COM apartment once and runs
+//! the blocking call there; the `async fn` only awaits its result, so the
+//! runtime's threads never block on sleeps or COM calls.
+//!
+//! The worker is a single dedicated thread rather than `spawn_blocking`'s
+//! pool: elements and windows are created on it, stay owned by it, and are
+//! released on it, so their handles remain valid for as long as the wrappers
+//! live.
+//!
+//! # Concurrency
+//!
+//! Calls made through one [`AsyncAutomation`] (and the windows and elements
+//! obtained from it) run one at a time, in the order they were made. Driving
+//! two applications concurrently interleaves their calls without blocking
+//! the runtime, but does not make them run in parallel. For real parallelism,
+//! create one `AsyncAutomation` per application: each has its own worker.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ui_interaction::asynchronous::AsyncAutomation;
+//!
+//! # async fn run() -> Result<(), ui_interaction::error::UiError> {
+//! let automation = AsyncAutomation::new().await?;
+//! let window = automation.get_active_window().await?;
+//! println!("Active window: {}", window.get_title().await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use log::{debug, warn};
+use tokio::sync::oneshot;
+
+use crate::core::{UIAutomation, UIElement, UIQuery, Window, WorkerApartment};
+use crate::error::UiError;
+use crate::factory::UIAutomationFactory;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The thread every call of one `AsyncAutomation` runs on
+#[derive(Clone)]
+struct Worker {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl Worker {
+    /// Start the thread; it exits once every handle to it is dropped
+    fn start() -> Result<Self, UiError> {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        thread::Builder::new()
+            .name("uia-async".to_string())
+            .spawn(move || {
+                let _apartment = WorkerApartment::join();
+                for job in receiver {
+                    // A panicking call fails its own future but leaves the worker running
+                    if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                        warn!("Worker - a call panicked");
+                    }
+                }
+                debug!("Worker - no handles left, exiting");
+            })
+            .map_err(|e| UiError::PlatformError(format!("Failed to start the automation worker: {}", e)))?;
+        Ok(Worker { jobs })
+    }
+
+    /// Run `f` on the worker and wait for its result without blocking the caller
+    async fn run<T, F>(&self, f: F) -> Result<T, UiError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, UiError> + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        self.jobs.send(Box::new(move || {
+            // Fails only if the future was dropped, in which case nobody wants the result
+            let _ = sender.send(f());
+        })).map_err(|_| UiError::PlatformError("The automation worker has stopped".to_string()))?;
+        receiver.await
+            .map_err(|_| UiError::PlatformError("The call panicked on the automation worker".to_string()))?
+    }
+
+    /// Drop `value` on the worker, where it was created
+    fn release<T: Send + 'static>(&self, value: T) {
+        // If the worker is gone the value is dropped here instead
+        let _ = self.jobs.send(Box::new(move || drop(value)));
+    }
+}
+
+/// A value that only the worker thread touches
+///
+/// Platform objects are not `Send`; this moves them between threads only to
+/// hand them back to the worker, which is the one thread that uses them.
+struct WorkerOwned<T: ?Sized>(Arc<Mutex<Box<T>>>);
+
+impl<T: ?Sized> WorkerOwned<T> {
+    fn new(value: Box<T>) -> Self {
+        WorkerOwned(Arc::new(Mutex::new(value)))
+    }
+
+    /// Run `f` on the value; only call this from jobs running on the worker
+    fn with<R>(&self, f: impl FnOnce(&T) -> Result<R, UiError>) -> Result<R, UiError> {
+        let value = self.0.lock().map_err(|_| UiError::PlatformError("A previous call panicked".to_string()))?;
+        f(&**value)
+    }
+}
+
+impl<T: ?Sized> Clone for WorkerOwned<T> {
+    fn clone(&self) -> Self {
+        WorkerOwned(Arc::clone(&self.0))
+    }
+}
+
+// Only ever used or dropped on the worker thread, see `Worker::release`
+unsafe impl<T: ?Sized> Send for WorkerOwned<T> {}
+unsafe impl<T: ?Sized> Sync for WorkerOwned<T> {}
+
+/// The async counterpart of a [`UIAutomation`] instance
+///
+/// Created together with its worker thread; windows and elements obtained
+/// from it run their calls on the same worker.
+pub struct AsyncAutomation {
+    worker: Worker,
+    inner: Option<WorkerOwned<dyn UIAutomation>>,
+}
+
+impl AsyncAutomation {
+    /// Start a worker thread and create the automation instance on it
+    pub async fn new() -> Result<Self, UiError> {
+        let worker = Worker::start()?;
+        let inner = worker.run(|| Ok(WorkerOwned::new(UIAutomationFactory::new()?))).await?;
+        Ok(AsyncAutomation { worker, inner: Some(inner) })
+    }
+
+    fn inner(&self) -> WorkerOwned<dyn UIAutomation> {
+        // Only taken in `drop`
+        self.inner.clone().expect("automation used after drop")
+    }
+
+    /// Run any blocking call on the worker with the automation instance
+    ///
+    /// The escape hatch for methods without an async wrapper.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, UiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&(dyn UIAutomation + 'static)) -> Result<T, UiError> + Send + 'static,
+    {
+        let inner = self.inner();
+        self.worker.run(move || inner.with(f)).await
+    }
+
+    /// Create a window on the worker, for example by launching an application
+    ///
+    /// `f` runs on the worker thread, so the window it returns is owned by it
+    /// like the windows returned by [`AsyncAutomation::get_active_window`].
+    pub async fn open_window<F>(&self, f: F) -> Result<AsyncWindow, UiError>
+    where
+        F: FnOnce() -> Result<Box<dyn Window>, UiError> + Send + 'static,
+    {
+        let window = self.worker.run(move || f().map(WorkerOwned::new)).await?;
+        Ok(AsyncWindow::new(self.worker.clone(), window))
+    }
+
+    /// See [`UIAutomation::get_active_window`]
+    pub async fn get_active_window(&self) -> Result<AsyncWindow, UiError> {
+        let inner = self.inner();
+        let window = self.worker.run(move || inner.with(|automation| automation.get_active_window().map(WorkerOwned::new))).await?;
+        Ok(AsyncWindow::new(self.worker.clone(), window))
+    }
+
+    /// See [`UIAutomation::get_focused_element`]
+    pub async fn get_focused_element(&self) -> Result<AsyncUIElement, UiError> {
+        let inner = self.inner();
+        let element = self.worker.run(move || inner.with(|automation| automation.get_focused_element().map(WorkerOwned::new))).await?;
+        Ok(AsyncUIElement::new(self.worker.clone(), element))
+    }
+}
+
+impl Drop for AsyncAutomation {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            self.worker.release(inner);
+        }
+    }
+}
+
+/// The async counterpart of a [`Window`]
+pub struct AsyncWindow {
+    worker: Worker,
+    inner: Option<WorkerOwned<dyn Window>>,
+}
+
+impl AsyncWindow {
+    fn new(worker: Worker, inner: WorkerOwned<dyn Window>) -> Self {
+        AsyncWindow { worker, inner: Some(inner) }
+    }
+
+    /// Run any blocking call on the worker with the window
+    ///
+    /// The escape hatch for methods without an async wrapper.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, UiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&(dyn Window + 'static)) -> Result<T, UiError> + Send + 'static,
+    {
+        let inner = self.inner.clone().expect("window used after drop");
+        self.worker.run(move || inner.with(f)).await
+    }
+
+    /// See [`Window::get_title`]
+    pub async fn get_title(&self) -> Result<String, UiError> {
+        self.run(|window| window.get_title()).await
+    }
+
+    /// See [`Window::get_process_id`]
+    pub async fn get_process_id(&self) -> Result<u32, UiError> {
+        self.run(|window| window.get_process_id()).await
+    }
+
+    /// See [`Window::activate`]
+    pub async fn activate(&self) -> Result<(), UiError> {
+        self.run(|window| window.activate()).await
+    }
+
+    /// See [`Window::send_keys`]
+    pub async fn send_keys(&self, keys: &str) -> Result<(), UiError> {
+        let keys = keys.to_string();
+        self.run(move |window| window.send_keys(&keys)).await
+    }
+
+    /// See [`Window::wait_until_ready`]
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<(), UiError> {
+        self.run(move |window| window.wait_until_ready(timeout)).await
+    }
+
+    /// See [`Window::close`]
+    pub async fn close(&self) -> Result<(), UiError> {
+        self.run(|window| window.close()).await
+    }
+
+    /// See [`Window::find_first`]
+    pub async fn find_first(&self, query: &UIQuery) -> Result<Option<AsyncUIElement>, UiError> {
+        let query = query.clone();
+        let element = self.run(move |window| Ok(window.find_first(&query)?.map(WorkerOwned::new))).await?;
+        Ok(element.map(|element| AsyncUIElement::new(self.worker.clone(), element)))
+    }
+
+    /// See [`Window::find_elements`]
+    pub async fn find_elements(&self, query: &UIQuery) -> Result<Vec<AsyncUIElement>, UiError> {
+        let query = query.clone();
+        let elements = self.run(move |window| {
+            Ok(window.find_elements(&query)?.into_iter().map(WorkerOwned::new).collect::<Vec<_>>())
+        }).await?;
+        Ok(elements.into_iter().map(|element| AsyncUIElement::new(self.worker.clone(), element)).collect())
+    }
+}
+
+impl Drop for AsyncWindow {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            self.worker.release(inner);
+        }
+    }
+}
+
+/// The async counterpart of a [`UIElement`]
+pub struct AsyncUIElement {
+    worker: Worker,
+    inner: Option<WorkerOwned<dyn UIElement>>,
+}
+
+impl AsyncUIElement {
+    fn new(worker: Worker, inner: WorkerOwned<dyn UIElement>) -> Self {
+        AsyncUIElement { worker, inner: Some(inner) }
+    }
+
+    /// Run any blocking call on the worker with the element
+    ///
+    /// The escape hatch for methods without an async wrapper.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, UiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&(dyn UIElement + 'static)) -> Result<T, UiError> + Send + 'static,
+    {
+        let inner = self.inner.clone().expect("element used after drop");
+        self.worker.run(move || inner.with(f)).await
+    }
+
+    /// See [`UIElement::get_name`]
+    pub async fn get_name(&self) -> Result<String, UiError> {
+        self.run(|element| element.get_name()).await
+    }
+
+    /// See [`UIElement::get_type`]
+    pub async fn get_type(&self) -> Result<String, UiError> {
+        self.run(|element| element.get_type()).await
+    }
+
+    /// See [`UIElement::get_text`]
+    pub async fn get_text(&self) -> Result<String, UiError> {
+        self.run(|element| element.get_text()).await
+    }
+
+    /// See [`UIElement::set_text`]
+    pub async fn set_text(&self, text: &str) -> Result<(), UiError> {
+        let text = text.to_string();
+        self.run(move |element| element.set_text(&text)).await
+    }
+
+    /// See [`UIElement::click`]
+    pub async fn click(&self) -> Result<(), UiError> {
+        self.run(|element| element.click()).await
+    }
+
+    /// See [`UIElement::set_focus`]
+    pub async fn set_focus(&self) -> Result<(), UiError> {
+        self.run(|element| element.set_focus()).await
+    }
+}
+
+impl Drop for AsyncUIElement {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            self.worker.release(inner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_worker_runs_every_call_on_one_thread() {
+        let worker = Worker::start().unwrap();
+        let first = worker.run(|| Ok(thread::current().id())).await.unwrap();
+        let second = worker.run(|| Ok(thread::current().id())).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, thread::current().id());
+    }
+
+    #[tokio::test]
+    async fn test_worker_returns_errors_and_survives_panics() {
+        let worker = Worker::start().unwrap();
+
+        let error = worker.run(|| Err::<(), _>(UiError::Timeout("slow".to_string()))).await;
+        assert_eq!(error, Err(UiError::Timeout("slow".to_string())));
+
+        let panicked = worker.run(|| -> Result<(), UiError> { panic!("boom") }).await;
+        assert!(matches!(panicked, Err(UiError::PlatformError(_))));
+        assert_eq!(worker.run(|| Ok(42)).await, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_values_are_released_on_the_worker() {
+        struct Probe(mpsc::Sender<thread::ThreadId>);
+        impl Drop for Probe {
+            fn drop(&mut self) {
+                let _ = self.0.send(thread::current().id());
+            }
+        }
+
+        let worker = Worker::start().unwrap();
+        let worker_thread = worker.run(|| Ok(thread::current().id())).await.unwrap();
+        let (sender, dropped_on) = mpsc::channel();
+        worker.release(WorkerOwned::new(Box::new(Probe(sender))));
+
+        assert_eq!(dropped_on.recv_timeout(Duration::from_secs(5)).unwrap(), worker_thread);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires a desktop session
+    async fn test_active_window_title_from_async_code() {
+        let automation = AsyncAutomation::new().await.unwrap();
+        let window = automation.get_active_window().await.unwrap();
+        assert!(window.get_title().await.is_ok());
+    }
+}
@@ -0,0 +1,96 @@
+//! Control type names and their UI Automation identifiers
+//!
+//! Every backend reports control types by the names used here (see
+//! [`UIElement::get_type`](crate::core::UIElement::get_type)), and queries such
+//! as `UIQuery::ByType` accept the same names. The identifiers are the
+//! `UIA_*ControlTypeId` constants of Windows UI Automation, which
+//! `UIQuery::ByControlTypeId` matches exactly.
+
+/// Every known control type name with its UI Automation identifier, in identifier order
+pub const CONTROL_TYPES: &[(&str, i32)] = &[
+    ("Button", 50000),
+    ("Calendar", 50001),
+    ("CheckBox", 50002),
+    ("ComboBox", 50003),
+    ("Edit", 50004),
+    ("Hyperlink", 50005),
+    ("Image", 50006),
+    ("ListItem", 50007),
+    ("List", 50008),
+    ("Menu", 50009),
+    ("MenuBar", 50010),
+    ("MenuItem", 50011),
+    ("ProgressBar", 50012),
+    ("RadioButton", 50013),
+    ("ScrollBar", 50014),
+    ("Slider", 50015),
+    ("Spinner", 50016),
+    ("StatusBar", 50017),
+    ("Tab", 50018),
+    ("TabItem", 50019),
+    ("Text", 50020),
+    ("ToolBar", 50021),
+    ("ToolTip", 50022),
+    ("Tree", 50023),
+    ("TreeItem", 50024),
+    ("Custom", 50025),
+    ("Group", 50026),
+    ("Thumb", 50027),
+    ("DataGrid", 50028),
+    ("DataItem", 50029),
+    ("Document", 50030),
+    ("SplitButton", 50031),
+    ("Window", 50032),
+    ("Pane", 50033),
+    ("Header", 50034),
+    ("HeaderItem", 50035),
+    ("Table", 50036),
+    ("TitleBar", 50037),
+    ("Separator", 50038),
+    ("SemanticZoom", 50039),
+    ("AppBar", 50040),
+];
+
+/// Look up the identifier of a control type name, e.g. `"Slider"` -> `50015`
+///
+/// Names are case-sensitive, as they are in `UIQuery::ByType`.
+pub fn control_type_id(name: &str) -> Option<i32> {
+    CONTROL_TYPES.iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, id)| *id)
+}
+
+/// Look up the name of a control type identifier, e.g. `50015` -> `"Slider"`
+pub fn control_type_name(id: i32) -> Option<&'static str> {
+    CONTROL_TYPES.iter()
+        .find(|(_, known)| *known == id)
+        .map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_table_is_complete_and_bidirectional() {
+        let ids: Vec<i32> = CONTROL_TYPES.iter().map(|(_, id)| *id).collect();
+        assert_eq!(ids, (50000..=50040).collect::<Vec<i32>>());
+
+        let names: HashSet<&str> = CONTROL_TYPES.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names.len(), CONTROL_TYPES.len());
+
+        for (name, id) in CONTROL_TYPES {
+            assert_eq!(control_type_id(name), Some(*id));
+            assert_eq!(control_type_name(*id), Some(*name));
+        }
+    }
+
+    #[test]
+    fn test_unknown_names_and_ids_are_rejected() {
+        assert_eq!(control_type_id("slider"), None);
+        assert_eq!(control_type_id("Unknown"), None);
+        assert_eq!(control_type_name(49999), None);
+        assert_eq!(control_type_name(50041), None);
+    }
+}
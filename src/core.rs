@@ -15,9 +15,9 @@
 //!
 //! # Example
 //!
-//! ```rust
-//! use uia_interaction::factory::UIAutomationFactory;
-//! use uia_interaction::core::{UIQuery, AppendPosition};
+//! ```rust,ignore
+//! use ui_interaction::factory::UIAutomationFactory;
+//! use ui_interaction::core::{UIQuery, AppendPosition};
 //!
 //! // Create automation instance
 //! let automation = UIAutomationFactory::new()?;
@@ -34,12 +34,21 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
-use std::error::Error;
-use std::collections::HashMap;
+use crate::error::UiError;
+use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Utc};
-use windows::Win32::Foundation::RECT;
 use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use serde::{Serialize, Deserialize};
+use regex::Regex;
+use log::{debug, warn};
+use crate::events::{EventSubscription, FocusChangedCallback, WindowOpenedCallback};
+use crate::control_type;
+use crate::keyboard::KeyChord;
 
 /// Represents a rectangle in screen coordinates
 /// 
@@ -56,7 +65,7 @@ use serde::{Serialize, Deserialize};
 /// # Example
 /// 
 /// ```rust
-/// use uia_interaction::core::Rect;
+/// use ui_interaction::core::Rect;
 /// 
 /// let rect = Rect {
 ///     left: 100,
@@ -69,7 +78,7 @@ use serde::{Serialize, Deserialize};
 /// let height = rect.bottom - rect.top;  // 150
 /// ```
 #[allow(dead_code)]
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
     pub left: i32,
     pub top: i32,
@@ -108,6 +117,74 @@ impl Rect {
         self.left < other.right && self.right > other.left &&
         self.top < other.bottom && self.bottom > other.top
     }
+
+    /// Get the overlap of this rectangle with another, or `None` if they do not intersect
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect::new(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right.min(other.right),
+            self.bottom.min(other.bottom),
+        ))
+    }
+
+    /// Get the area in square pixels (0 for empty or inverted rectangles)
+    pub fn area(&self) -> i64 {
+        i64::from(self.width().max(0)) * i64::from(self.height().max(0))
+    }
+
+    /// Multiply every coordinate by `factor`, rounding to the nearest pixel
+    /// 
+    /// Coordinates are scaled from the origin of the virtual screen, so
+    /// rectangles on monitors left of or above the primary one keep their
+    /// negative coordinates.
+    pub fn scale(&self, factor: f64) -> Rect {
+        let scale = |value: i32| (f64::from(value) * factor).round() as i32;
+        Rect::new(scale(self.left), scale(self.top), scale(self.right), scale(self.bottom))
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl From<windows::Win32::Foundation::RECT> for Rect {
+    fn from(rect: windows::Win32::Foundation::RECT) -> Self {
+        Rect::new(rect.left, rect.top, rect.right, rect.bottom)
+    }
+}
+
+/// The DPI at which logical and physical pixels are the same size (100% scaling)
+pub const BASE_DPI: u32 = 96;
+
+/// Convert a rectangle in logical pixels to physical pixels on a monitor with `dpi`
+/// 
+/// Logical pixels are what DPI-unaware code sees: 1/96 inch whatever the
+/// scaling. UI Automation bounds, mouse input and screen captures use physical
+/// pixels. A `dpi` of 0 is treated as [`BASE_DPI`].
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use ui_interaction::core::{logical_to_physical, Rect};
+/// 
+/// // 150% scaling
+/// let physical = logical_to_physical(&Rect::new(10, 10, 110, 50), 144);
+/// assert_eq!((physical.left, physical.right), (15, 165));
+/// ```
+pub fn logical_to_physical(rect: &Rect, dpi: u32) -> Rect {
+    rect.scale(f64::from(effective_dpi(dpi)) / f64::from(BASE_DPI))
+}
+
+/// Convert a rectangle in physical pixels to logical pixels on a monitor with `dpi`
+/// 
+/// The inverse of [`logical_to_physical`], up to rounding.
+pub fn physical_to_logical(rect: &Rect, dpi: u32) -> Rect {
+    rect.scale(f64::from(BASE_DPI) / f64::from(effective_dpi(dpi)))
+}
+
+fn effective_dpi(dpi: u32) -> u32 {
+    if dpi == 0 { BASE_DPI } else { dpi }
 }
 
 /// Structured information about a UI element that contains text
@@ -132,8 +209,8 @@ impl Rect {
 ///
 /// # Example
 ///
-/// ```rust
-/// use uia_interaction::core::TextElementInfo;
+/// ```rust,ignore
+/// use ui_interaction::core::TextElementInfo;
 ///
 /// // Process text elements from a window
 /// let text_elements: Vec<TextElementInfo> = get_text_elements(window)?;
@@ -157,6 +234,8 @@ pub struct TextElementInfo {
     pub name: String,
     /// The control type (e.g., "Text", "Edit", "Document", "Button")
     pub control_type: String,
+    /// The control type as the platform shows it to users, in the UI language (e.g., "button", "lien")
+    pub localized_control_type: Option<String>,
     /// Automation ID for reliable element identification (if available)
     pub automation_id: Option<String>,
     /// Window class name of the element
@@ -184,6 +263,7 @@ impl TextElementInfo {
             text,
             name: String::new(),
             control_type: String::new(),
+            localized_control_type: None,
             automation_id: None,
             class_name: None,
             bounds: None,
@@ -207,6 +287,28 @@ impl TextElementInfo {
     }
 }
 
+/// Serialize text elements to a JSON array for downstream tools
+///
+/// Each element becomes an object keyed by the `TextElementInfo` field names.
+/// `bounds` is an object with `left`, `top`, `right` and `bottom` keys, and
+/// absent optional fields (`bounds`, `localized_control_type`,
+/// `automation_id`, `class_name`, `parent_name`) are written as `null` rather than omitted, so every object
+/// has the same keys.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ui_interaction::core::{text_elements_to_json, TextExtractionOptions};
+///
+/// let elements = window.get_text_elements(&TextExtractionOptions::default())?;
+/// std::fs::write("texts.json", text_elements_to_json(&elements)?)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn text_elements_to_json(elements: &[TextElementInfo]) -> Result<String, UiError> {
+    serde_json::to_string(elements)
+        .map_err(|e| UiError::PlatformError(format!("Failed to serialize text elements: {}", e)))
+}
+
 /// Information about selected text in a UI element
 ///
 /// This struct captures details about text that is currently selected
@@ -221,8 +323,8 @@ impl TextElementInfo {
 ///
 /// # Example
 ///
-/// ```rust
-/// use uia_interaction::core::SelectedTextInfo;
+/// ```rust,ignore
+/// use ui_interaction::core::SelectedTextInfo;
 ///
 /// // Get selected text from the focused element
 /// if let Some(selection) = get_selected_text(automation)? {
@@ -267,6 +369,57 @@ impl SelectedTextInfo {
     }
 }
 
+/// The result of comparing an element's text with the text expected in it
+/// 
+/// Returned by [`UIElement::verify_text`]. Line endings are normalized to
+/// `\n` on both sides before comparing, so a control that reports `\r\n`
+/// still matches.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use ui_interaction::core::compare_text;
+/// 
+/// let result = compare_text("réunion", "reunion");
+/// assert!(!result.matches);
+/// assert_eq!(result.mismatches, vec![(1, 'é', 'e')]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TextVerification {
+    /// Whether the texts are identical after normalization
+    pub matches: bool,
+    /// (character index, expected, actual) for each position where the texts differ
+    /// 
+    /// Only positions present in both texts are listed; compare `expected_len`
+    /// and `actual_len` to spot missing or extra characters at the end.
+    pub mismatches: Vec<(usize, char, char)>,
+    /// Length of the expected text in characters
+    pub expected_len: usize,
+    /// Length of the element's text in characters
+    pub actual_len: usize,
+    /// The element's text, normalized
+    pub actual: String,
+}
+
+/// Compare `actual` against `expected` character by character, see [`TextVerification`]
+pub fn compare_text(expected: &str, actual: &str) -> TextVerification {
+    let expected = expected.replace("\r\n", "\n");
+    let actual = actual.replace("\r\n", "\n");
+    let mismatches: Vec<(usize, char, char)> = expected.chars()
+        .zip(actual.chars())
+        .enumerate()
+        .filter(|(_, (e, a))| e != a)
+        .map(|(index, (e, a))| (index, e, a))
+        .collect();
+    TextVerification {
+        matches: expected == actual,
+        mismatches,
+        expected_len: expected.chars().count(),
+        actual_len: actual.chars().count(),
+        actual,
+    }
+}
+
 /// Represents a node in the UI tree hierarchy
 /// 
 /// A UI tree node captures the state and properties of a UI element at a specific
@@ -285,8 +438,8 @@ impl SelectedTextInfo {
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use uia_interaction::core::UITreeNode;
+/// ```rust,ignore
+/// use ui_interaction::core::UITreeNode;
 /// use std::collections::HashMap;
 /// 
 /// // Accessing node information
@@ -303,7 +456,7 @@ impl SelectedTextInfo {
 /// };
 /// ```
 #[allow(dead_code)]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UITreeNode {
     pub name: String,
     pub control_type: String,
@@ -326,11 +479,12 @@ pub struct UITreeNode {
 /// * `timestamp` - When this snapshot was captured
 /// * `window_title` - The title of the window when captured
 /// * `window_class` - The window class name when captured
+/// * `truncated` - Whether the node budget in `UITreeOptions::max_nodes` cut the capture short
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use uia_interaction::factory::UIAutomationFactory;
+/// ```rust,ignore
+/// use ui_interaction::factory::UIAutomationFactory;
 /// 
 /// let automation = UIAutomationFactory::new()?;
 /// let window = automation.get_active_window()?;
@@ -343,12 +497,149 @@ pub struct UITreeNode {
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 #[allow(dead_code)]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UITree {
     pub root: UITreeNode,
     pub timestamp: DateTime<Utc>,
     pub window_title: String,
     pub window_class: String,
+    pub truncated: bool,
+}
+
+impl UITree {
+    /// Write the tree to `path` as JSON
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - The file was written
+    /// * `Err(...)` - If the file cannot be written
+    pub fn save(&self, path: &str) -> Result<(), UiError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| UiError::PlatformError(format!("Failed to serialize UI tree: {}", e)))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Read a tree written by [`UITree::save`] or [`Window::save_ui_tree`]
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(UITree)` - The captured tree
+    /// * `Err(UiError::InvalidArgument)` - If the file is not a saved UI tree
+    /// * `Err(...)` - If the file cannot be read
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::{UITree, UIQuery};
+    /// 
+    /// // Develop a query against a capture, without the application running
+    /// let tree = UITree::load("notepad.json")?;
+    /// for node in tree.find(&UIQuery::ByType("MenuItem".to_string())) {
+    ///     println!("{}", node.name);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load(path: &str) -> Result<UITree, UiError> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json)
+            .map_err(|e| UiError::InvalidArgument(format!("'{}' is not a saved UI tree: {}", path, e)))
+    }
+
+    /// Find the captured nodes matching a query
+    /// 
+    /// The offline counterpart of [`UIQuery::find_all`]: nodes are searched
+    /// depth-first in pre-order starting with the root, and matched on what
+    /// the capture recorded. Names, control types, bounds and the enabled and
+    /// visible flags are always available; `ByAutomationId`, `ByClassName`
+    /// and `ByProperty` look in the node's properties, which are only filled
+    /// in when the tree was captured with `include_properties`. `ByText`
+    /// matches the `text` or `value` property. A query that needs something
+    /// the capture lacks simply does not match.
+    /// 
+    /// # Returns
+    /// 
+    /// Matching nodes in document order (may be empty)
+    pub fn find(&self, query: &UIQuery) -> Vec<&UITreeNode> {
+        match query {
+            UIQuery::Nth(query, index) => return self.find(query).into_iter().nth(*index).into_iter().collect(),
+            UIQuery::Limit(query, limit) => return self.find(query).into_iter().take(*limit).collect(),
+            _ => {}
+        }
+
+        let mut found = Vec::new();
+        let mut stack: Vec<(&UITreeNode, Vec<&UITreeNode>)> = vec![(&self.root, Vec::new())];
+        while let Some((node, ancestors)) = stack.pop() {
+            if node_matches(query, node, &ancestors) {
+                found.push(node);
+            }
+            for child in node.children.iter().rev() {
+                let mut path = ancestors.clone();
+                path.push(node);
+                stack.push((child, path));
+            }
+        }
+        found
+    }
+}
+
+impl UITreeNode {
+    /// Look up a property the way `get_properties` reports it, falling back to the node's fields
+    fn property(&self, key: &str) -> Option<&str> {
+        match key {
+            "name" => Some(&self.name),
+            "control_type" => Some(&self.control_type),
+            _ => self.properties.get(key).map(String::as_str),
+        }
+    }
+}
+
+/// Whether a captured node matches `query`, given its ancestors from the root down
+fn node_matches(query: &UIQuery, node: &UITreeNode, ancestors: &[&UITreeNode]) -> bool {
+    match query {
+        UIQuery::ByName(name) => node.name == *name,
+        UIQuery::ByNameMatch(pattern, mode) => mode.matches(pattern, &node.name),
+        UIQuery::ByNameRegex(pattern) => pattern.is_match(&node.name),
+        UIQuery::ByType(control_type) => node.control_type == *control_type,
+        UIQuery::ByTypes(control_types) => control_types.contains(&node.control_type),
+        UIQuery::ByControlTypeId(id) => control_type::control_type_id(&node.control_type) == Some(*id),
+        UIQuery::ByProperty(key, value) => node.property(key) == Some(value.as_str()),
+        UIQuery::ByAutomationId(automation_id) => node.property("automation_id") == Some(automation_id.as_str()),
+        UIQuery::ByClassName(class_name) => node.property("class_name") == Some(class_name.as_str()),
+        UIQuery::ByText(text) => ["text", "value"].iter()
+            .any(|key| node.property(key).is_some_and(|content| content.contains(text.as_str()))),
+        UIQuery::And(queries) => queries.iter().all(|query| node_matches(query, node, ancestors)),
+        UIQuery::Or(queries) => queries.iter().any(|query| node_matches(query, node, ancestors)),
+        UIQuery::Not(query) => !node_matches(query, node, ancestors),
+        UIQuery::Child(query) => {
+            let mut path = ancestors.to_vec();
+            path.push(node);
+            node.children.iter().any(|child| node_matches(query, child, &path))
+        }
+        UIQuery::Descendant(query) => {
+            let mut stack = vec![(node, ancestors.to_vec())];
+            while let Some((current, mut path)) = stack.pop() {
+                path.push(current);
+                for child in &current.children {
+                    if node_matches(query, child, &path) {
+                        return true;
+                    }
+                    stack.push((child, path.clone()));
+                }
+            }
+            false
+        }
+        UIQuery::Parent(query) => match ancestors.split_last() {
+            Some((parent, above)) => node_matches(query, parent, above),
+            None => false,
+        },
+        UIQuery::Ancestor(query) => (0..ancestors.len()).rev()
+            .any(|i| node_matches(query, ancestors[i], &ancestors[..i])),
+        UIQuery::Visible(query) => node.is_visible && node_matches(query, node, ancestors),
+        UIQuery::Enabled(query) => node.is_enabled && node_matches(query, node, ancestors),
+        // As in `UIQuery::matches`, position only has meaning within a search
+        UIQuery::Nth(query, _) | UIQuery::Limit(query, _) => node_matches(query, node, ancestors),
+    }
 }
 
 /// Specifies where to append text when using text append operations
@@ -364,10 +655,10 @@ pub struct UITree {
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use uia_interaction::core::AppendPosition;
-/// use uia_interaction::factory::UIAutomationFactory;
-/// use uia_interaction::core::UIQuery;
+/// ```rust,ignore
+/// use ui_interaction::core::AppendPosition;
+/// use ui_interaction::factory::UIAutomationFactory;
+/// use ui_interaction::core::UIQuery;
 /// 
 /// let automation = UIAutomationFactory::new()?;
 /// let window = automation.get_active_window()?;
@@ -390,6 +681,204 @@ pub enum AppendPosition {
     EndOfText,
 }
 
+/// How `set_text_with_options` recovers when the typed text does not come out right
+///
+/// # Variants
+///
+/// * `None` - Type once and trust the result; the element is not read back
+/// * `WordByWord` - Read the element back and, on a mismatch, clear it and retype
+///   word by word, falling back to single characters for words that fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrectionMode {
+    None,
+    WordByWord,
+}
+
+/// How element interactions reach the application
+///
+/// # Variants
+///
+/// * `Physical` - Clicks and text input may synthesize mouse moves and
+///   keystrokes when no automation pattern does the job. This matches what a
+///   user would do and works with the widest range of controls.
+/// * `PatternsOnly` - Interactions only go through automation patterns
+///   (Invoke, Toggle, SelectionItem, Value, ...) and never move the mouse or
+///   send keystrokes. Operations that would need synthesized input return
+///   `UiError::PatternUnsupported` (or `UiError::Unsupported` when no pattern
+///   could ever do the job) instead of falling back.
+///
+/// `PatternsOnly` is meant for CI machines, RDP sessions and locked screens,
+/// where synthesized input is silently dropped and a "successful" click would
+/// otherwise do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InteractionMode {
+    #[default]
+    Physical,
+    PatternsOnly,
+}
+
+/// Options controlling how `set_text_with_options` types text into an element
+///
+/// The defaults match `set_text`. For trusted input into a responsive control,
+/// a short interval with `CorrectionMode::None` is much faster.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ui_interaction::core::{CorrectionMode, TextInputOptions};
+///
+/// let options = TextInputOptions {
+///     interval_ms: 5,
+///     correction: CorrectionMode::None,
+///     ..Default::default()
+/// };
+/// element.set_text_with_options("fast path", &options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextInputOptions {
+    /// Delay between simulated keystrokes, in milliseconds
+    pub interval_ms: u32,
+    /// Read the text back at the end and fail if it does not match
+    pub verify: bool,
+    /// What to do when the typed text does not match
+    pub correction: CorrectionMode,
+    /// Replace the value directly through the Value pattern instead of typing when possible
+    pub use_value_pattern_if_available: bool,
+    /// Treat the text as a secret: it is never logged, and never read back for
+    /// correction or verification. Password fields (see [`UIElement::is_password`])
+    /// are always handled this way.
+    pub secret: bool,
+}
+
+impl Default for TextInputOptions {
+    fn default() -> Self {
+        TextInputOptions {
+            interval_ms: 30,
+            verify: false,
+            correction: CorrectionMode::WordByWord,
+            use_value_pattern_if_available: true,
+            secret: false,
+        }
+    }
+}
+
+/// Text as it may appear in logs: the text itself, or a placeholder for secrets
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn loggable_text(text: &str, secret: bool) -> &str {
+    if secret {
+        "<redacted>"
+    } else {
+        text
+    }
+}
+
+/// Direction in which to scroll a scrollable element
+///
+/// # Variants
+///
+/// * `Up` - Scroll towards the top of the content
+/// * `Down` - Scroll towards the bottom of the content
+/// * `Left` - Scroll towards the start of the horizontal range
+/// * `Right` - Scroll towards the end of the horizontal range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl ScrollDirection {
+    /// Whether this direction scrolls along the horizontal axis
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, ScrollDirection::Left | ScrollDirection::Right)
+    }
+}
+
+/// Whether a tree item, menu or combo box is showing its children
+/// 
+/// # Variants
+/// 
+/// * `Collapsed` - None of the children are shown
+/// * `Expanded` - All of the children are shown
+/// * `PartiallyExpanded` - Some, but not all, of the children are shown
+/// * `LeafNode` - The element has no children to show and cannot be expanded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpandState {
+    Collapsed,
+    Expanded,
+    PartiallyExpanded,
+    LeafNode,
+}
+
+/// The direction a control is laid out in
+/// 
+/// # Variants
+/// 
+/// * `None` - The control has no orientation
+/// * `Horizontal` - The control runs left to right, like a toolbar or horizontal scroll bar
+/// * `Vertical` - The control runs top to bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    None,
+    Horizontal,
+    Vertical,
+}
+
+/// The numeric value of a slider, progress bar or spinner, with its bounds
+/// 
+/// # Fields
+/// 
+/// * `value` - The current value
+/// * `min` - The smallest value the control accepts
+/// * `max` - The largest value the control accepts
+/// * `small_change` - How far the value moves for a small step, such as an arrow key
+/// * `large_change` - How far the value moves for a large step, such as Page Up
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// let slider = window.find_elements(&UIQuery::ByType("Slider".to_string()))?.remove(0);
+/// if let Some(range) = slider.get_range_value()? {
+///     println!("{} in [{}, {}] ({:.0}%)", range.value, range.min, range.max, range.fraction() * 100.0);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RangeValue {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub small_change: f64,
+    pub large_change: f64,
+}
+
+impl RangeValue {
+    /// Bring `value` within `[min, max]`
+    pub fn clamp(&self, value: f64) -> f64 {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+
+    /// Position of the current value within the range, from 0.0 to 1.0
+    /// 
+    /// An empty range reports 0.0.
+    pub fn fraction(&self) -> f64 {
+        let span = self.max - self.min;
+        if span > 0.0 {
+            (self.clamp(self.value) - self.min) / span
+        } else {
+            0.0
+        }
+    }
+}
+
 /// Trait for interacting with UI elements
 /// 
 /// This trait defines the interface for interacting with UI elements such as buttons,
@@ -398,9 +887,9 @@ pub enum AppendPosition {
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use uia_interaction::factory::UIAutomationFactory;
-/// use uia_interaction::core::{UIQuery, AppendPosition};
+/// ```rust,ignore
+/// use ui_interaction::factory::UIAutomationFactory;
+/// use ui_interaction::core::{UIQuery, AppendPosition};
 /// 
 /// let automation = UIAutomationFactory::new()?;
 /// let window = automation.get_active_window()?;
@@ -437,7 +926,7 @@ pub trait UIElement {
     /// 
     /// * `Ok(String)` - The element's name/label
     /// * `Err(...)` - If the name cannot be retrieved
-    fn get_name(&self) -> Result<String, Box<dyn Error>>;
+    fn get_name(&self) -> Result<String, UiError>;
     
     /// Get the control type of the element
     /// 
@@ -448,7 +937,23 @@ pub trait UIElement {
     /// 
     /// * `Ok(String)` - The control type name
     /// * `Err(...)` - If the type cannot be determined
-    fn get_type(&self) -> Result<String, Box<dyn Error>>;
+    fn get_type(&self) -> Result<String, UiError>;
+
+    /// Get the control type as the platform describes it to users
+    /// 
+    /// This is the role name screen readers announce, in the UI language of
+    /// the system ("button" in English, "bouton" or "lien" in French), and it
+    /// is meant for human-facing output such as selectors shown to a user.
+    /// Queries should keep using `get_type`, which is always the canonical
+    /// English name and does not change with the locale.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` - The localized control type, empty if the application reports none
+    /// * `Err(...)` - If the platform does not report localized control types
+    fn get_localized_control_type(&self) -> Result<String, UiError> {
+        Err(UiError::Unsupported("Localized control types are not reported on this platform".to_string()))
+    }
     
     /// Get the text content of the element
     /// 
@@ -459,7 +964,83 @@ pub trait UIElement {
     /// 
     /// * `Ok(String)` - The current text content
     /// * `Err(...)` - If text cannot be retrieved or element doesn't support text
-    fn get_text(&self) -> Result<String, Box<dyn Error>>;
+    fn get_text(&self) -> Result<String, UiError>;
+
+    /// Get at most `max_chars` characters of the element's text
+    /// 
+    /// Use this instead of `get_text` on content-heavy controls such as a
+    /// large file open in an editor, where the full text can be megabytes.
+    /// Platforms that can read a bounded range do so; others read everything
+    /// and cut it.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok((String, bool))` - The text, and whether it was cut at `max_chars`
+    /// * `Err(...)` - If text cannot be retrieved
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let (preview, truncated) = editor.get_text_limited(200)?;
+    /// println!("{}{}", preview, if truncated { "..." } else { "" });
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_text_limited(&self, max_chars: usize) -> Result<(String, bool), UiError> {
+        Ok(truncate_chars(self.get_text()?, max_chars))
+    }
+
+    /// Compare the element's text with the text expected in it
+    /// 
+    /// Reads the text with `get_text` and reports each character that differs,
+    /// so callers of `set_text` can decide whether a mismatch (say, a dropped
+    /// accent) is acceptable. See [`TextVerification`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `expected` - The text the element should contain
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(TextVerification)` - The comparison
+    /// * `Err(...)` - If the text cannot be retrieved
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// field.set_text("Demain, réunion")?;
+    /// let check = field.verify_text("Demain, réunion")?;
+    /// for (index, expected, actual) in &check.mismatches {
+    ///     println!("Character {}: expected '{}', got '{}'", index, expected, actual);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn verify_text(&self, expected: &str) -> Result<TextVerification, UiError> {
+        Ok(compare_text(expected, &self.get_text()?))
+    }
+
+    /// Get the text content of the element by copying it through the clipboard
+    /// 
+    /// Focuses the element, selects all of its content and copies it, then reads
+    /// the clipboard. This works for editors (Electron, Chromium) whose Text
+    /// pattern is slow or empty, but it is opt-in because it replaces the
+    /// element's selection. The previous clipboard text is restored afterwards.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` - The copied text
+    /// * `Err(UiError::Timeout)` - If the copy did not reach the clipboard in time
+    /// * `Err(...)` - If the element cannot be focused or the clipboard is unavailable
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let editor = window.find_first(&UIQuery::ByType("Document".to_string()))?.unwrap();
+    /// let text = editor.get_text_via_clipboard()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_text_via_clipboard(&self) -> Result<String, UiError> {
+        Err(UiError::Unsupported("Reading text through the clipboard is not supported on this platform".to_string()))
+    }
     
     /// Set the text content of the element
     /// 
@@ -474,69 +1055,409 @@ pub trait UIElement {
     /// 
     /// * `Ok(())` - If text was set successfully
     /// * `Err(...)` - If text cannot be set or element doesn't support text input
-    fn set_text(&self, text: &str) -> Result<(), Box<dyn Error>>;
-    
-    /// Append text to the element's existing content
+    fn set_text(&self, text: &str) -> Result<(), UiError>;
+
+    /// Set the text content of the element with control over how it is typed
     /// 
-    /// Adds text to the element at the specified position without replacing
-    /// existing content. Useful for adding to existing text in fields or documents.
+    /// Platforms that do not simulate keystrokes ignore the typing options and
+    /// behave like `set_text`.
     /// 
     /// # Arguments
     /// 
-    /// * `text` - The text to append
-    /// * `position` - Where to append the text (cursor, end of line, end of text)
+    /// * `text` - The new text content to set
+    /// * `options` - Keystroke interval, verification and correction policy
     /// 
     /// # Returns
     /// 
-    /// * `Ok(())` - If text was appended successfully
-    /// * `Err(...)` - If text cannot be appended or element doesn't support text input
-    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), Box<dyn Error>>;
+    /// * `Ok(())` - If text was set (and matched, when `options.verify` is set)
+    /// * `Err(...)` - If text cannot be set, or verification found a mismatch
+    fn set_text_with_options(&self, text: &str, options: &TextInputOptions) -> Result<(), UiError> {
+        self.set_text(text)?;
+        // Reading a secret back would only put it in the error message
+        if options.verify && !options.secret && !self.is_password()? {
+            let actual = self.get_text()?;
+            if actual != text {
+                return Err(UiError::PlatformError(format!("Text mismatch after input: expected '{}', got '{}'", text, actual)));
+            }
+        }
+        Ok(())
+    }
 
-    /// Click the element
+    /// Check whether the element is a password field
     /// 
-    /// Performs a mouse click on the element. This works with buttons, links,
-    /// and other clickable elements.
+    /// Text typed into password fields is never logged or read back, see
+    /// [`TextInputOptions::secret`].
+    /// 
+    /// The default implementation reads an `is_password` entry of
+    /// `get_properties` and reports `false` without one.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(())` - If the element was clicked successfully
-    /// * `Err(...)` - If the element cannot be clicked or is not accessible
-    fn click(&self) -> Result<(), Box<dyn Error>>;
+    /// * `Ok(true)` - The element holds protected content such as a password
+    /// * `Ok(false)` - It does not, or the platform cannot tell
+    /// * `Err(...)` - If the element could not be inspected
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let field = window.find_first(&UIQuery::ByType("Edit".to_string()))?.unwrap();
+    /// if field.is_password()? {
+    ///     field.set_text(&std::env::var("APP_PASSWORD")?)?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn is_password(&self) -> Result<bool, UiError> {
+        Ok(self.get_properties()?.get("is_password").is_some_and(|value| value == "true"))
+    }
 
-    /// Check if the element is enabled for interaction
+    /// Remove all content from an editable element
     /// 
-    /// Returns whether the element is currently enabled and can be interacted with.
-    /// Disabled elements typically appear grayed out and don't respond to input.
+    /// Cheaper than `set_text("")`: platforms replace the value directly when
+    /// the control allows it and only fall back to select-all and delete
+    /// keystrokes otherwise, checking afterwards that the field is empty.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(true)` - Element is enabled and interactive
-    /// * `Ok(false)` - Element is disabled
-    /// * `Err(...)` - If the enabled state cannot be determined
-    fn is_enabled(&self) -> Result<bool, Box<dyn Error>>;
+    /// * `Ok(())` - The element is now empty
+    /// * `Err(UiError::InvalidArgument)` - If the element is not editable (see [`UIElement::is_editable`])
+    /// * `Err(...)` - If the content could not be removed
+    fn clear(&self) -> Result<(), UiError> {
+        if !self.is_editable()? {
+            return Err(UiError::InvalidArgument("Cannot clear an element that is not editable".to_string()));
+        }
+        self.set_text("")
+    }
 
-    /// Get all properties of the element
+    /// Select the element's entire text content
     /// 
-    /// Returns a map of all available properties for this element, including
-    /// both standard properties and any custom properties specific to the element.
+    /// Platforms select the whole document range when the element exposes
+    /// its text, falling back to a select-all keystroke.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(HashMap)` - Map of property names to values
-    /// * `Err(...)` - If properties cannot be retrieved
-    fn get_properties(&self) -> Result<HashMap<String, String>, Box<dyn Error>>;
+    /// * `Ok(())` - The whole content is selected
+    /// * `Err(UiError::PatternUnsupported)` - If the platform cannot select text in this element
+    fn select_all(&self) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("Text"))
+    }
     
-    /// Get the screen bounds of the element
+    /// Append text to the element's existing content
     /// 
-    /// Returns the rectangular area occupied by the element on screen,
-    /// or None if the element is not visible or bounds cannot be determined.
+    /// Adds text to the element at the specified position without replacing
+    /// existing content. Useful for adding to existing text in fields or documents.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The text to append
+    /// * `position` - Where to append the text (cursor, end of line, end of text)
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Some(Rect))` - The element's screen rectangle
-    /// * `Ok(None)` - Element is not visible or has no bounds
+    /// * `Ok(())` - If text was appended successfully
+    /// * `Err(...)` - If text cannot be appended or element doesn't support text input
+    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), UiError>;
+
+    /// Insert text at a character offset within the element's content
+    /// 
+    /// Moves the caret to `offset` characters from the start of the text and
+    /// inserts `text` there, leaving the surrounding content in place. Offsets
+    /// past the end of the current text insert at the end.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `offset` - Character offset to insert at (0 = start of text)
+    /// * `text` - The text to insert
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the text was inserted
+    /// * `Err(...)` - If the element is not editable or the caret cannot be placed
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // "Hello world" -> "Hello, world"
+    /// element.insert_text_at(5, ",")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn insert_text_at(&self, _offset: usize, _text: &str) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Inserting text at an offset is not supported on this platform".to_string()))
+    }
+
+    /// Click the element
+    /// 
+    /// Performs a mouse click on the element. This works with buttons, links,
+    /// and other clickable elements.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the element was clicked successfully
+    /// * `Err(...)` - If the element cannot be clicked or is not accessible
+    fn click(&self) -> Result<(), UiError>;
+
+    /// Activate the element through its accessibility action, without the mouse
+    /// 
+    /// Unlike `click`, this never moves the cursor or synthesizes input, so it
+    /// works for occluded or offscreen elements and on locked or headless
+    /// sessions where real mouse events are dropped.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the element was invoked
+    /// * `Err(UiError::PatternUnsupported(...))` - If the element cannot be invoked
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let save = window.find_first(&UIQuery::ByName("Save".to_string()))?.unwrap();
+    /// save.invoke()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn invoke(&self) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("Invoke"))
+    }
+
+    /// Right-click the element
+    /// 
+    /// Performs a mouse right-click at the center of the element, typically
+    /// opening its context menu.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the element was right-clicked successfully
+    /// * `Err(...)` - If the element has no bounds or cannot be right-clicked
+    fn right_click(&self) -> Result<(), UiError>;
+
+    /// Double-click the element
+    /// 
+    /// Performs a mouse double-click at the center of the element. This is
+    /// commonly used to open items such as files in Explorer or list entries.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the element was double-clicked successfully
+    /// * `Err(...)` - If the element has no bounds or cannot be double-clicked
+    fn double_click(&self) -> Result<(), UiError>;
+
+    /// Give keyboard focus to the element without clicking it
+    /// 
+    /// Useful for fields where a click has side effects.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - Element now has keyboard focus
+    /// * `Err(...)` - If the element cannot accept focus (e.g. a static text label)
+    fn set_focus(&self) -> Result<(), UiError>;
+
+    /// Check if the element is enabled for interaction
+    /// 
+    /// Returns whether the element is currently enabled and can be interacted with.
+    /// Disabled elements typically appear grayed out and don't respond to input.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Element is enabled and interactive
+    /// * `Ok(false)` - Element is disabled
+    /// * `Err(...)` - If the enabled state cannot be determined
+    fn is_enabled(&self) -> Result<bool, UiError>;
+
+    /// Check if the element is outside the visible area
+    /// 
+    /// Elements scrolled out of view, in a collapsed or hidden container, or
+    /// clipped away by their parent are offscreen. This default treats an
+    /// element without non-empty bounds as offscreen; platforms with a native
+    /// visibility flag report that instead.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Element is not visible on screen
+    /// * `Ok(false)` - Element is at least partly on screen
+    /// * `Err(...)` - If the visibility cannot be determined
+    fn is_offscreen(&self) -> Result<bool, UiError> {
+        Ok(!matches!(self.get_bounds()?, Some(bounds) if bounds.width() > 0 && bounds.height() > 0))
+    }
+
+    /// Check if the element accepts text input
+    /// 
+    /// Platforms ask the control itself, so read-only fields report `false`
+    /// even though they look like edits. This default only guesses from the
+    /// control type. Whether the element is enabled is not taken into account.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Text can be typed or set into the element
+    /// * `Ok(false)` - The element is read-only or not a text control
+    /// * `Err(...)` - If the element could not be inspected
+    fn is_editable(&self) -> Result<bool, UiError> {
+        Ok(matches!(self.get_type()?.as_str(), "Edit" | "Document" | "ComboBox"))
+    }
+
+    /// Check if the element can receive keyboard focus
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - The element can take keyboard focus
+    /// * `Ok(false)` - The element never takes keyboard focus, like most labels
+    /// * `Err(...)` - If the platform does not report focusability
+    fn is_focusable(&self) -> Result<bool, UiError> {
+        Err(UiError::Unsupported("Focusability is not reported on this platform".to_string()))
+    }
+
+    /// Get the direction a scroll bar, slider, toolbar or tab list is laid out in
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Orientation)` - The orientation, `Orientation::None` for controls without one
+    /// * `Err(...)` - If the platform does not report orientation
+    fn get_orientation(&self) -> Result<Orientation, UiError> {
+        Err(UiError::Unsupported("Orientation is not reported on this platform".to_string()))
+    }
+
+    /// Check if the element belongs to the content view of the accessibility tree
+    /// 
+    /// Content elements carry information the user reads, such as documents,
+    /// list items and labels; decorative and structural controls such as
+    /// scroll bars, toolbars and layout panes are left out. Text extraction
+    /// can skip elements where this is false to reduce noise.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - The element is part of the content view
+    /// * `Ok(false)` - The element is a structural or decorative control
+    /// * `Err(...)` - If the platform does not report this
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let content: Vec<_> = window.find_elements(&UIQuery::ByType("Text".to_string()))?
+    ///     .into_iter()
+    ///     .filter(|element| element.is_content_element().unwrap_or(true))
+    ///     .collect();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn is_content_element(&self) -> Result<bool, UiError> {
+        Err(UiError::Unsupported("The content view is not reported on this platform".to_string()))
+    }
+
+    /// Check if the element belongs to the control view of the accessibility tree
+    /// 
+    /// The control view holds every element the user perceives as a control,
+    /// content elements included; only pure layout containers are left out.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - The element is part of the control view
+    /// * `Ok(false)` - The element only exists for layout
+    /// * `Err(...)` - If the platform does not report this
+    fn is_control_element(&self) -> Result<bool, UiError> {
+        Err(UiError::Unsupported("The control view is not reported on this platform".to_string()))
+    }
+
+    /// Get the access key that activates the element, such as "Alt+F" for a "&File" menu
+    /// 
+    /// Menus can then be driven from the keyboard with `Window::send_keys`
+    /// instead of searching for and clicking each item. The string is reported
+    /// by the application as-is, so its format varies between toolkits.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(String))` - The access key
+    /// * `Ok(None)` - The element has no access key, or the platform does not report them
+    /// * `Err(...)` - If the element could not be inspected
+    fn get_access_key(&self) -> Result<Option<String>, UiError> {
+        Ok(None)
+    }
+
+    /// Get the keyboard shortcut that invokes the element's command, such as "Ctrl+S"
+    /// 
+    /// Unlike the access key, the accelerator works without opening the menu first.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(String))` - The accelerator key
+    /// * `Ok(None)` - The element has no accelerator, or the platform does not report them
+    /// * `Err(...)` - If the element could not be inspected
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let save = window.find_first(&UIQuery::ByName("Save".to_string()))?.unwrap();
+    /// if let Some(shortcut) = save.get_accelerator_key()? {
+    ///     println!("Save with {}", shortcut);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_accelerator_key(&self) -> Result<Option<String>, UiError> {
+        Ok(None)
+    }
+
+    /// Get all properties of the element
+    /// 
+    /// Returns a map of all available properties for this element, including
+    /// both standard properties and any custom properties specific to the element.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(HashMap)` - Map of property names to values
+    /// * `Err(...)` - If properties cannot be retrieved
+    fn get_properties(&self) -> Result<HashMap<String, String>, UiError>;
+
+    /// Get exactly the requested properties of the element
+    /// 
+    /// `get_properties` stays limited to a few essential properties so that
+    /// searches stay fast; this reads the ones asked for instead, including
+    /// heavier ones such as `help_text`, `accelerator_key`, `is_password`,
+    /// `orientation` or `localized_control_type`, in a single call where the
+    /// platform allows it. Keys are the snake_case names `get_properties`
+    /// uses. Keys the platform does not know, or properties the element does
+    /// not report, are left out of the map with a warning.
+    /// 
+    /// The default implementation picks the requested keys from `get_properties`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `keys` - The property names to read
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(HashMap)` - The requested properties that could be read
+    /// * `Err(...)` - If the element could not be inspected
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let field = window.find_first(&UIQuery::ByType("Edit".to_string()))?.unwrap();
+    /// let props = field.get_properties_filtered(&["help_text", "is_password"])?;
+    /// if props.get("is_password").is_some_and(|v| v == "true") {
+    ///     println!("Password field: {}", props.get("help_text").cloned().unwrap_or_default());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_properties_filtered(&self, keys: &[&str]) -> Result<HashMap<String, String>, UiError> {
+        let mut properties = self.get_properties()?;
+        let mut selected = HashMap::new();
+        for key in keys {
+            match properties.remove(*key) {
+                Some(value) => {
+                    selected.insert(key.to_string(), value);
+                }
+                None => warn!("get_properties_filtered - skipping unknown property '{}'", key),
+            }
+        }
+        Ok(selected)
+    }
+    
+    /// Get the screen bounds of the element
+    /// 
+    /// Returns the rectangular area occupied by the element on screen,
+    /// or None if the element is not visible or bounds cannot be determined.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(Rect))` - The element's screen rectangle
+    /// * `Ok(None)` - Element is not visible or has no bounds
     /// * `Err(...)` - If bounds cannot be retrieved
-    fn get_bounds(&self) -> Result<Option<Rect>, Box<dyn Error>>;
+    fn get_bounds(&self) -> Result<Option<Rect>, UiError>;
     
     /// Get direct child elements
     /// 
@@ -547,8 +1468,104 @@ pub trait UIElement {
     /// 
     /// * `Ok(Vec<Box<dyn UIElement>>)` - List of child elements
     /// * `Err(...)` - If children cannot be retrieved
-    fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, Box<dyn Error>>;
+    fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError>;
+
+    /// Get the parent element
+    /// 
+    /// Returns the element directly above this one in the UI hierarchy. This allows
+    /// walking up the UI tree structure, e.g. for `UIQuery::Parent` and `UIQuery::Ancestor`.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(Box<dyn UIElement>))` - The parent element
+    /// * `Ok(None)` - Element is the root or the platform cannot navigate upwards
+    /// * `Err(...)` - If the parent cannot be retrieved
+    fn get_parent(&self) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        // Default implementation returns None - platform-specific implementations can override
+        Ok(None)
+    }
+
+    /// Get the position of this element among its parent's children
+    /// 
+    /// The default implementation enumerates the parent's children and compares
+    /// runtime IDs, so it needs both `get_parent` and `runtime_id`.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(usize))` - Zero-based index among the siblings
+    /// * `Ok(None)` - Element has no parent, or is no longer among its parent's children
+    /// * `Err(...)` - If the element has no runtime ID to compare siblings by
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // Column of a table cell
+    /// let column = cell.index_in_parent()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn index_in_parent(&self) -> Result<Option<usize>, UiError> {
+        let parent = match self.get_parent()? {
+            Some(parent) => parent,
+            None => return Ok(None),
+        };
+        let own_id = self.runtime_id()?;
+        Ok(parent.get_children()
+            .unwrap_or_default()
+            .iter()
+            .position(|sibling| sibling.runtime_id().is_ok_and(|id| id == own_id)))
+    }
+
+    /// Get the runtime identifier of this element
+    /// 
+    /// The runtime ID uniquely identifies an element for as long as it exists in the
+    /// UI, even among elements that share a name and control type.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<i32>)` - The element's runtime ID
+    /// * `Err(...)` - If the element has no runtime ID or the platform does not provide one
+    fn runtime_id(&self) -> Result<Vec<i32>, UiError> {
+        Err(UiError::Unsupported("Runtime IDs are not available on this platform".to_string()))
+    }
     
+    /// Capture this element and its descendants down to `max_depth` levels
+    /// 
+    /// `max_depth` 0 captures only this element, 1 adds its children, and so
+    /// on. Each node carries the name, control type, bounds and enabled and
+    /// visible flags. Backends that can prefetch properties for many elements
+    /// at once (Windows does, through a UI Automation cache request) fetch each
+    /// node's children with their properties in one call instead of one round
+    /// trip per property; the default walks `get_children` node by node.
+    /// Nothing below `max_depth` is fetched.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(UITreeNode)` - This element with its descendants attached as children
+    /// * `Err(...)` - If this element cannot be read
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let list = window.find_first(&UIQuery::ByType("List".to_string()))?.unwrap();
+    /// let tree = list.get_subtree(2)?;
+    /// for item in &tree.children {
+    ///     println!("{} ({} children)", item.name, item.children.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_subtree(&self, max_depth: u32) -> Result<UITreeNode, UiError> {
+        let options = UITreeOptions { max_depth: Some(max_depth), ..UITreeOptions::unlimited() };
+        let (root, _) = build_tree_bounded(
+            self.to_tree_node()?,
+            &options,
+            |element| describe_element(element.as_ref()),
+            |element, limit| element.get_children()
+                .map(|children| children.into_iter().take(limit).collect())
+                .unwrap_or_default(),
+        )?;
+        Ok(root)
+    }
+
     /// Convert element to tree node representation
     /// 
     /// Creates a tree node representation of this element for use in UI tree structures.
@@ -558,7 +1575,31 @@ pub trait UIElement {
     /// 
     /// * `Ok(Box<dyn UIElement>)` - Tree node representation
     /// * `Err(...)` - If conversion fails
-    fn to_tree_node(&self) -> Result<Box<dyn UIElement>, Box<dyn Error>>;
+    fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError>;
+
+    /// Snapshot the element's identifying properties for repeated reads
+    /// 
+    /// See [`CachedElement`]: the name, type, bounds, enabled state,
+    /// automation id and class name are read once now and then served
+    /// without querying the UI again, so they go stale if the UI changes.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(CachedElement)` - A wrapper holding this element and the snapshot
+    /// * `Err(...)` - If the properties cannot be read
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let button = window.find_first(&UIQuery::ByName("Save".to_string()))?.unwrap().cached()?;
+    /// if button.is_enabled()? && button.get_bounds()?.is_some() {
+    ///     button.click()?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn cached(&self) -> Result<CachedElement, UiError> {
+        CachedElement::new(self.to_tree_node()?)
+    }
 
     /// Get a reference to the underlying type for downcasting
     ///
@@ -583,15 +1624,16 @@ pub trait UIElement {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let element = window.get_focused_element()?;
     /// let info = element.get_text_element_info()?;
     /// println!("Text: '{}' at {:?}", info.text, info.bounds);
     /// ```
-    fn get_text_element_info(&self) -> Result<TextElementInfo, Box<dyn Error>> {
-        let text = self.get_text().unwrap_or_default();
+    fn get_text_element_info(&self) -> Result<TextElementInfo, UiError> {
+        let (text, _) = self.get_text_limited(DEFAULT_TEXT_LIMIT).unwrap_or_default();
         let name = self.get_name().unwrap_or_default();
         let control_type = self.get_type().unwrap_or_default();
+        let localized_control_type = self.get_localized_control_type().ok().filter(|name| !name.is_empty());
         let bounds = self.get_bounds().unwrap_or(None);
         let is_enabled = self.is_enabled().unwrap_or(true);
 
@@ -599,13 +1641,13 @@ pub trait UIElement {
         let automation_id = props.get("automation_id").cloned();
         let class_name = props.get("class_name").cloned();
 
-        // Determine if element is editable based on control type
-        let is_editable = matches!(control_type.as_str(), "Edit" | "Document" | "ComboBox");
+        let is_editable = self.is_editable().unwrap_or(false);
 
         Ok(TextElementInfo {
             text,
             name,
             control_type,
+            localized_control_type,
             automation_id,
             class_name,
             bounds,
@@ -632,998 +1674,5551 @@ pub trait UIElement {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let element = automation.get_focused_element()?;
     /// if let Some(selection) = element.get_selected_text()? {
     ///     println!("Selected: '{}'", selection.text);
     /// }
     /// ```
-    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, Box<dyn Error>> {
+    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, UiError> {
         // Default implementation returns None - platform-specific implementations can override
         Ok(None)
     }
-}
 
-/// Trait for interacting with application windows
-/// 
-/// This trait provides methods to interact with windows, get window information,
-/// control window state, and find UI elements within the window. It represents
-/// a top-level window or dialog in a desktop application.
-/// 
-/// # Example
-/// 
-/// ```rust
-/// use uia_interaction::factory::UIAutomationFactory;
-/// use uia_interaction::core::UIQuery;
-/// 
-/// let automation = UIAutomationFactory::new()?;
-/// let window = automation.get_active_window()?;
-/// 
-/// // Get window information
-/// println!("Window: {}", window.get_title()?);
-/// println!("Process: {} (PID: {})", window.get_process_name()?, window.get_process_id()?);
-/// 
-/// // Activate the window to ensure it has focus
-/// window.activate()?;
-/// 
-/// // Find all buttons in the window
-/// let button_query = UIQuery::ByType("Button".to_string());
-/// let buttons = window.find_elements(&button_query)?;
-/// println!("Found {} buttons", buttons.len());
-/// 
-/// // Get the UI tree for analysis
-/// let tree = window.get_ui_tree()?;
-/// println!("UI tree captured at: {}", tree.timestamp);
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
-#[allow(dead_code)]
-pub trait Window {
-    /// Get the window title text
-    /// 
-    /// Returns the title bar text of the window, which is typically displayed
-    /// in the window's title bar and taskbar.
-    /// 
+    /// Get the checked state of a toggleable element (CheckBox, RadioButton, toggle button)
+    ///
     /// # Returns
-    /// 
-    /// * `Ok(String)` - The window title
-    /// * `Err(...)` - If the title cannot be retrieved
-    fn get_title(&self) -> Result<String, Box<dyn Error>>;
-    
-    /// Get the window class name
-    /// 
-    /// Returns the window class name, which is a Windows-specific identifier
-    /// that describes the type of window (e.g., "Notepad", "Chrome_WidgetWin_1").
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(String)` - The window class name
-    /// * `Err(...)` - If the class name cannot be retrieved
-    fn get_class_name(&self) -> Result<String, Box<dyn Error>>;
-    
-    /// Get the process ID of the window's owning process
-    /// 
-    /// Returns the process identifier (PID) of the process that owns this window.
-    /// This can be used to identify which application created the window.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(u32)` - The process ID
-    /// * `Err(...)` - If the process ID cannot be retrieved
-    fn get_process_id(&self) -> Result<u32, Box<dyn Error>>;
-    
-    /// Get the thread ID of the window's owning thread
-    /// 
-    /// Returns the thread identifier of the thread that created this window.
-    /// This is primarily useful for low-level Windows programming.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(u32)` - The thread ID
-    /// * `Err(...)` - If the thread ID cannot be retrieved
-    fn get_thread_id(&self) -> Result<u32, Box<dyn Error>>;
-    
-    /// Get the executable name of the window's owning process
-    /// 
-    /// Returns the name of the executable file (e.g., "notepad.exe", "chrome.exe")
-    /// that owns this window.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(String)` - The process executable name
-    /// * `Err(...)` - If the process name cannot be retrieved
-    fn get_process_name(&self) -> Result<String, Box<dyn Error>>;
-    
-    /// Get the full path to the window's owning process executable
-    /// 
-    /// Returns the complete file system path to the executable that owns this window.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(String)` - The full path to the executable
-    /// * `Err(...)` - If the process path cannot be retrieved
-    fn get_process_path(&self) -> Result<String, Box<dyn Error>>;
-    
-    /// Check if the window is visible on screen
-    /// 
-    /// Returns whether the window is currently visible (not hidden).
-    /// Minimized windows are typically still considered visible.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(true)` - Window is visible
-    /// * `Ok(false)` - Window is hidden
-    /// * `Err(...)` - If visibility state cannot be determined
-    fn is_visible(&self) -> Result<bool, Box<dyn Error>>;
-    
-    /// Check if the window is minimized
-    /// 
-    /// Returns whether the window is currently minimized to the taskbar.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(true)` - Window is minimized
-    /// * `Ok(false)` - Window is not minimized
-    /// * `Err(...)` - If minimized state cannot be determined
-    fn is_minimized(&self) -> Result<bool, Box<dyn Error>>;
-    
-    /// Check if the window is maximized
-    /// 
-    /// Returns whether the window is currently maximized to fill the screen
-    /// (or work area).
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(true)` - Window is maximized
-    /// * `Ok(false)` - Window is not maximized
-    /// * `Err(...)` - If maximized state cannot be determined
-    fn is_maximized(&self) -> Result<bool, Box<dyn Error>>;
-    
-    /// Get the window's position and size
-    /// 
-    /// Returns a RECT structure containing the window's screen coordinates:
-    /// left, top, right, and bottom edges in pixels.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(RECT)` - The window's screen rectangle
-    /// * `Err(...)` - If window geometry cannot be retrieved
-    fn get_rect(&self) -> Result<RECT, Box<dyn Error>>;
-    
-    /// Get the window's DPI (dots per inch) scaling
-    /// 
-    /// Returns the DPI scaling factor for this window, which is important
-    /// for high-DPI displays and coordinate calculations.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(u32)` - The DPI value (typically 96, 120, 144, 192, etc.)
-    /// * `Err(...)` - If DPI cannot be retrieved
-    fn get_dpi(&self) -> Result<u32, Box<dyn Error>>;
-    
-    /// Activate the window (bring to foreground with focus)
-    /// 
-    /// Makes this window the active window, bringing it to the foreground
-    /// and giving it keyboard focus. This is the recommended way to ensure
-    /// a window is ready for interaction.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(())` - Window was activated successfully
-    /// * `Err(...)` - If the window cannot be activated
-    /// 
+    ///
+    /// * `Ok(Some(true))` - Element is checked
+    /// * `Ok(Some(false))` - Element is unchecked
+    /// * `Ok(None)` - Element does not support toggling
+    /// * `Err(...)` - If the state cannot be read or the element is in an
+    ///   indeterminate (tri-state) state
+    ///
     /// # Example
-    /// 
-    /// ```rust
-    /// // Ensure the window is active before interacting with it
-    /// window.activate()?;
-    /// 
-    /// // Now it's safe to send keyboard input or interact with elements
-    /// let element = window.get_focused_element()?;
-    /// element.set_text("Hello, World!")?;
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    ///
+    /// ```rust,ignore
+    /// let checkbox = window.find_elements(&UIQuery::ByName("Word wrap".to_string()))?.remove(0);
+    /// if checkbox.get_toggle_state()? == Some(false) {
+    ///     checkbox.set_toggle_state(true)?;
+    /// }
     /// ```
-    fn activate(&self) -> Result<(), Box<dyn Error>>;
-    
-    /// Bring the window to the top of the Z-order
-    /// 
-    /// Moves the window to the top of the window stack without necessarily
-    /// giving it focus. This is less intrusive than `activate()`.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(())` - Window was brought to top successfully
-    /// * `Err(...)` - If the window cannot be brought to top
-    fn bring_to_top(&self) -> Result<(), Box<dyn Error>>;
-    
-    /// Set the window as the foreground window
-    /// 
-    /// Attempts to make this window the foreground window. On some systems,
-    /// this may be restricted by the operating system's focus policies.
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(())` - Window was set as foreground successfully
-    /// * `Err(...)` - If the window cannot be set as foreground
-    fn set_foreground(&self) -> Result<(), Box<dyn Error>>;
-    
-    /// Get the currently focused element within this window
-    /// 
-    /// Returns the UI element that currently has keyboard focus within this window.
-    /// This is useful for determining where text input will go.
-    /// 
+    fn get_toggle_state(&self) -> Result<Option<bool>, UiError> {
+        // Default implementation reports no toggle support - platform-specific implementations can override
+        Ok(None)
+    }
+
+    /// Set the checked state of a toggleable element
+    ///
+    /// Does nothing if the element is already in the requested state.
+    ///
+    /// # Arguments
+    ///
+    /// * `checked` - The desired checked state
+    ///
     /// # Returns
-    /// 
-    /// * `Ok(Box<dyn UIElement>)` - The focused element
-    /// * `Err(...)` - If no element has focus or focus cannot be determined
-    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, Box<dyn Error>>;
+    ///
+    /// * `Ok(())` - Element is now in the requested state
+    /// * `Err(...)` - If the element cannot be toggled, or is (or ends up) in an
+    ///   indeterminate state
+    fn set_toggle_state(&self, _checked: bool) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("Toggle"))
+    }
 
-    /// Get a complete UI tree snapshot of the window
-    /// 
-    /// Captures the entire UI element hierarchy of this window at the current
-    /// moment, creating a tree structure that can be analyzed or traversed.
-    /// This is useful for debugging UI structure or finding elements.
-    /// 
+    /// Get the options offered by a ComboBox or list control
+    ///
+    /// Drop-down lists are expanded while reading so that lazily populated items
+    /// are available, then collapsed again.
+    ///
     /// # Returns
-    /// 
-    /// * `Ok(UITree)` - Complete UI tree snapshot
-    /// * `Err(...)` - If the UI tree cannot be captured
-    /// 
+    ///
+    /// * `Ok(Vec<String>)` - The names of the available options, in display order
+    /// * `Err(...)` - If the element does not offer selectable options
+    ///
     /// # Example
-    /// 
-    /// ```rust
-    /// let tree = window.get_ui_tree()?;
-    /// println!("Window: {} captured at {}", tree.window_title, tree.timestamp);
-    /// 
-    /// // Recursively explore the tree
-    /// fn explore_node(node: &UITreeNode, depth: usize) {
-    ///     let indent = "  ".repeat(depth);
-    ///     println!("{}|- {} ({})", indent, node.name, node.control_type);
-    ///     for child in &node.children {
-    ///         explore_node(child, depth + 1);
-    ///     }
+    ///
+    /// ```rust,ignore
+    /// let combo = window.find_elements(&UIQuery::ByType("ComboBox".to_string()))?.remove(0);
+    /// for option in combo.get_selection_options()? {
+    ///     println!("Option: {}", option);
     /// }
-    /// explore_node(&tree.root, 0);
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    fn get_ui_tree(&self) -> Result<UITree, Box<dyn Error>>;
-    
-    /// Find UI elements matching a query
-    /// 
-    /// Searches for UI elements within this window that match the specified
-    /// query criteria. This is the primary method for locating specific
-    /// elements to interact with.
-    /// 
+    fn get_selection_options(&self) -> Result<Vec<String>, UiError> {
+        Err(UiError::pattern_unsupported("Selection"))
+    }
+
+    /// Select the option with the given name in a ComboBox or list control
+    ///
+    /// Editable combo boxes that accept free text fall back to `set_text` when no
+    /// option matches. The drop-down is collapsed afterward.
+    ///
     /// # Arguments
-    /// 
-    /// * `query` - The search criteria specifying which elements to find
-    /// 
+    ///
+    /// * `value` - The exact name of the option to select
+    ///
     /// # Returns
-    /// 
-    /// * `Ok(Vec<Box<dyn UIElement>>)` - List of matching elements
-    /// * `Err(...)` - If the search fails
-    /// 
+    ///
+    /// * `Ok(())` - Option was selected (or typed into an editable combo box)
+    /// * `Err(...)` - If no option matches and the control does not accept free text
+    fn select_option(&self, _value: &str) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("Selection"))
+    }
+
+    /// Get the selected items of a list, tree, tab or other selection container
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Box<dyn UIElement>>)` - The selected items, empty if nothing is selected
+    /// * `Err(UiError::PatternUnsupported)` - If the element is not a selection container
+    ///
     /// # Example
-    /// 
-    /// ```rust
-    /// use uia_interaction::core::UIQuery;
-    /// 
-    /// // Find all buttons
-    /// let buttons = window.find_elements(&UIQuery::ByType("Button".to_string()))?;
-    /// 
-    /// // Find element by name
-    /// let save_button = window.find_elements(&UIQuery::ByName("Save".to_string()))?;
-    /// 
-    /// // Complex query - find enabled edit controls
-    /// let enabled_edits = window.find_elements(&UIQuery::And(vec![
-    ///     UIQuery::ByType("Edit".to_string()),
-    ///     UIQuery::ByProperty("IsEnabled".to_string(), "True".to_string())
-    /// ]))?;
+    ///
+    /// ```rust,ignore
+    /// let files = window.find_first(&UIQuery::ByType("List".to_string()))?.unwrap();
+    /// for item in files.get_selected_items()? {
+    ///     println!("Selected: {}", item.get_name()?);
+    /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, Box<dyn Error>>;
+    fn get_selected_items(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        Err(UiError::pattern_unsupported("Selection"))
+    }
 
-    /// Get all text-containing elements in the window
+    /// Select this item of a list, tree or other selection container
     ///
-    /// Scans the window's UI tree and returns structured information about all
-    /// elements that contain text. This is useful for extracting readable content
-    /// from applications.
+    /// With `add_to_selection` the item joins the current selection of a
+    /// multi-select container. Containers that only allow one selected item
+    /// cannot add to their selection, so there it behaves like a plain select
+    /// and replaces the selection.
     ///
     /// # Arguments
     ///
-    /// * `options` - Options controlling what elements to include
+    /// * `add_to_selection` - Keep the items that are already selected
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<TextElementInfo>)` - List of text element information
-    /// * `Err(...)` - If elements cannot be retrieved
-    ///
-    /// # Example
+    /// * `Ok(())` - The item is selected
+    /// * `Err(UiError::PatternUnsupported)` - If the element is not a selectable item
+    fn select_item(&self, _add_to_selection: bool) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("SelectionItem"))
+    }
+
+    /// Remove this item from its container's selection
     ///
-    /// ```rust
-    /// let options = TextExtractionOptions::default();
-    /// let text_elements = window.get_text_elements(&options)?;
+    /// # Returns
     ///
-    /// for elem in text_elements {
-    ///     if elem.has_text() {
-    ///         println!("[{}] {}: '{}'", elem.control_type, elem.name, elem.text);
-    ///     }
-    /// }
-    /// ```
-    fn get_text_elements(&self, options: &TextExtractionOptions) -> Result<Vec<TextElementInfo>, Box<dyn Error>>;
+    /// * `Ok(())` - The item is no longer selected
+    /// * `Err(UiError::PatternUnsupported)` - If the element is not a selectable item
+    /// * `Err(...)` - If the container requires a selection and this is its only selected item
+    fn deselect_item(&self) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("SelectionItem"))
+    }
 
-    /// Get the selected text from the currently focused element in this window
-    ///
-    /// Retrieves any text that is currently selected (highlighted) within
-    /// the focused element of this window.
+    /// Get whether a tree item, menu or combo box is showing its children
     ///
     /// # Returns
     ///
-    /// * `Ok(Some(SelectedTextInfo))` - Selected text information
-    /// * `Ok(None)` - No text is currently selected
-    /// * `Err(...)` - If selection cannot be retrieved
-    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, Box<dyn Error>> {
-        let focused = self.get_focused_element()?;
-        focused.get_selected_text()
+    /// * `Ok(Some(ExpandState))` - The current state
+    /// * `Ok(None)` - If the element cannot be expanded or collapsed
+    /// * `Err(...)` - If the state could not be read
+    fn get_expand_state(&self) -> Result<Option<ExpandState>, UiError> {
+        Ok(None)
     }
-}
 
-/// Options for text extraction from UI elements
-///
-/// Controls what elements are included when extracting text from a window.
-///
-/// # Example
-///
-/// ```rust
-/// let options = TextExtractionOptions {
-///     include_hidden: false,
-///     include_disabled: true,
-///     min_text_length: 1,
-///     control_types: Some(vec!["Text".to_string(), "Edit".to_string()]),
-///     max_depth: Some(10),
-/// };
-/// ```
-#[derive(Clone, Debug)]
-pub struct TextExtractionOptions {
-    /// Include elements that are not visible on screen
-    pub include_hidden: bool,
-    /// Include elements that are disabled
-    pub include_disabled: bool,
-    /// Minimum text length to include (0 = include empty)
-    pub min_text_length: usize,
-    /// Only include specific control types (None = all)
-    pub control_types: Option<Vec<String>>,
-    /// Maximum depth in UI tree to traverse (None = unlimited)
-    pub max_depth: Option<u32>,
-    /// Include element names as text if actual text is empty
-    pub include_names_as_text: bool,
-}
+    /// Show the children of a tree item, menu or combo box
+    ///
+    /// Expanding an element that is already expanded does nothing. Children
+    /// revealed this way can then be found with a `UIQuery::Descendant` search.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The element is expanded
+    /// * `Err(UiError::InvalidArgument)` - If the element is a leaf node with nothing to expand
+    /// * `Err(UiError::PatternUnsupported)` - If the element cannot be expanded at all
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let folder = window.find_first(&UIQuery::ByName("Documents".to_string()))?.unwrap();
+    /// folder.expand()?;
+    /// let report = UIQuery::ByName("report.docx".to_string()).find_first(folder.as_ref())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn expand(&self) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("ExpandCollapse"))
+    }
 
-impl Default for TextExtractionOptions {
-    fn default() -> Self {
-        TextExtractionOptions {
-            include_hidden: false,
-            include_disabled: true,
-            min_text_length: 1,
-            control_types: None,
-            max_depth: Some(20),
-            include_names_as_text: true,
-        }
+    /// Hide the children of a tree item, menu or combo box
+    ///
+    /// Collapsing an element that is already collapsed, or a leaf node, does nothing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The element is collapsed
+    /// * `Err(UiError::PatternUnsupported)` - If the element cannot be collapsed at all
+    fn collapse(&self) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("ExpandCollapse"))
     }
-}
 
-impl TextExtractionOptions {
-    /// Create options that extract all text elements
-    pub fn all() -> Self {
-        TextExtractionOptions {
-            include_hidden: true,
-            include_disabled: true,
-            min_text_length: 0,
-            control_types: None,
-            max_depth: None,
-            include_names_as_text: true,
-        }
+    /// Get the value of this element through its value interface
+    ///
+    /// Unlike [`UIElement::get_text`], this never falls back to other text
+    /// sources, so it reports exactly what the control exposes as its value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The current value
+    /// * `Err(UiError::PatternUnsupported)` - If the element has no value interface
+    fn get_value(&self) -> Result<String, UiError> {
+        Err(UiError::pattern_unsupported("Value"))
     }
 
-    /// Create options for extracting only visible, non-empty text
-    pub fn visible_text_only() -> Self {
-        TextExtractionOptions {
-            include_hidden: false,
-            include_disabled: true,
-            min_text_length: 1,
-            control_types: Some(vec![
-                "Text".to_string(),
-                "Edit".to_string(),
-                "Document".to_string(),
-            ]),
-            max_depth: Some(20),
-            include_names_as_text: false,
-        }
+    /// Replace the value of this element directly, without simulating keystrokes
+    ///
+    /// This is instant regardless of the length of `value`, and is what
+    /// [`UIElement::set_text`] uses when the element supports it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Value was replaced
+    /// * `Err(UiError::PatternUnsupported)` - If the element has no value interface
+    /// * `Err(...)` - If the value is read-only
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let field = window.find_elements(&UIQuery::ByAutomationId("SearchBox".to_string()))?.remove(0);
+    /// field.set_value("quarterly report")?;
+    /// assert_eq!(field.get_value()?, "quarterly report");
+    /// ```
+    fn set_value(&self, _value: &str) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("Value"))
     }
 
-    /// Create options for extracting editable text fields
-    pub fn editable_only() -> Self {
-        TextExtractionOptions {
-            include_hidden: false,
-            include_disabled: false,
-            min_text_length: 0,
-            control_types: Some(vec![
-                "Edit".to_string(),
-                "Document".to_string(),
-                "ComboBox".to_string(),
-            ]),
-            max_depth: Some(20),
-            include_names_as_text: false,
+    /// Get the numeric value and bounds of a slider, progress bar or spinner
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(RangeValue))` - The current value with its bounds and step sizes
+    /// * `Ok(None)` - If the element has no range value
+    /// * `Err(...)` - If the range value could not be read
+    fn get_range_value(&self) -> Result<Option<RangeValue>, UiError> {
+        Ok(None)
+    }
+
+    /// Set the numeric value of a slider or spinner
+    ///
+    /// Values outside the control's range are clamped to its minimum or maximum
+    /// instead of being rejected.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Value was set
+    /// * `Err(UiError::PatternUnsupported)` - If the element has no range value
+    /// * `Err(...)` - If the value is read-only, as for progress bars
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let volume = window.find_elements(&UIQuery::ByName("Volume".to_string()))?.remove(0);
+    /// volume.set_range_value(75.0)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn set_range_value(&self, _value: f64) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("RangeValue"))
+    }
+
+    /// Move the mouse cursor over the center of this element without clicking
+    ///
+    /// Useful for revealing tooltips and hover-only toolbars. This moves the real
+    /// system cursor, so it affects whatever else the user is doing; see
+    /// [`UIElement::hover_for`] to dwell and put the cursor back afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let button = window.find_elements(&UIQuery::ByName("Bold".to_string()))?.remove(0);
+    /// button.hover()?;
+    /// ```
+    fn hover(&self) -> Result<(), UiError> {
+        self.hover_for(Duration::ZERO, false)
+    }
+
+    /// Move the mouse cursor over this element and keep it there for `dwell`
+    ///
+    /// Moving the real cursor has global side effects: other applications see
+    /// the mouse move, and hover state elsewhere on screen is lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `dwell` - How long to leave the cursor over the element so hover-triggered UI can render
+    /// * `hover_restore` - Move the cursor back to its original position after dwelling
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    ///
+    /// // Let the tooltip appear, then put the cursor back where it was
+    /// button.hover_for(Duration::from_millis(800), true)?;
+    /// ```
+    fn hover_for(&self, _dwell: Duration, _hover_restore: bool) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Hovering is not available on this platform".to_string()))
+    }
+
+    /// Focus this element and press a key chord
+    ///
+    /// The chord is checked before the element is focused, so an invalid
+    /// chord types nothing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The keys were sent
+    /// * `Err(UiError::InvalidArgument)` - If the chord contains a key that cannot be sent
+    /// * `Err(UiError::Unsupported)` - If the platform or interaction mode cannot synthesize keys
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use ui_interaction::keyboard::{Key, KeyChord};
+    ///
+    /// // Select from the caret to the start of the document
+    /// editor.send_chord(&KeyChord::new(Key::Home).ctrl().shift())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn send_chord(&self, _chord: &KeyChord) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Sending keys to elements is not available on this platform".to_string()))
+    }
+
+    /// Scroll the content of this element
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - Direction to scroll in
+    /// * `amount` - Distance to scroll, as a percentage (0-100) of the scrollable range
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Content was scrolled (clamped to the start or end of the range)
+    /// * `Err(UiError::PatternUnsupported)` - If the element cannot scroll along that axis
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use ui_interaction::core::ScrollDirection;
+    ///
+    /// list.scroll(ScrollDirection::Down, 25.0)?;
+    /// ```
+    fn scroll(&self, _direction: ScrollDirection, _amount: f64) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("Scroll"))
+    }
+
+    /// Check whether any part of this element lies inside the window's [`Window::get_bounds`]
+    ///
+    /// Unlike [`UIElement::is_offscreen`], which UI Automation only sets once
+    /// an element is clipped away entirely, this compares bounds directly, so
+    /// it also answers for elements scrolled partly out of view when combined
+    /// with [`UIElement::visible_fraction`]. The window rectangle includes its
+    /// frame and title bar, and overlapping windows are not taken into account.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The element's bounds intersect the window's bounds
+    /// * `Ok(false)` - They do not, or the element has no bounds
+    /// * `Err(...)` - If the bounds or the window rectangle cannot be read
+    fn is_in_viewport(&self, window: &dyn Window) -> Result<bool, UiError> {
+        let viewport = window.get_bounds()?;
+        Ok(self.get_bounds()?.is_some_and(|bounds| bounds.intersects(&viewport)))
+    }
+
+    /// Get the fraction of this element's area that lies inside the window's bounds
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - From 0.0 (outside, or no bounds) to 1.0 (entirely inside)
+    /// * `Err(...)` - If the bounds or the window rectangle cannot be read
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Only click rows that are fully scrolled into view
+    /// if row.visible_fraction(window.as_ref())? < 1.0 {
+    ///     row.scroll_into_view()?;
+    /// }
+    /// row.click()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn visible_fraction(&self, window: &dyn Window) -> Result<f64, UiError> {
+        let viewport = window.get_bounds()?;
+        Ok(self.get_bounds()?.map_or(0.0, |bounds| fraction_inside(&bounds, &viewport)))
+    }
+
+    /// Scroll the containing view until this element is visible
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Element is visible (including when it already was)
+    /// * `Err(UiError::PatternUnsupported)` - If the element is offscreen and cannot be scrolled into view
+    fn scroll_into_view(&self) -> Result<(), UiError> {
+        Err(UiError::pattern_unsupported("ScrollItem"))
+    }
+
+    /// List the control patterns this element supports
+    ///
+    /// Pattern names match the ones reported by [`UiError::PatternUnsupported`],
+    /// e.g. "Value", "Text", "Toggle", "Invoke", "ExpandCollapse", "Scroll",
+    /// "ScrollItem", "SelectionItem", so callers can check before calling the
+    /// pattern-based methods.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - Names of the supported patterns, in a fixed order
+    /// * `Err(...)` - If the element's pattern availability cannot be read
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if element.supported_patterns()?.iter().any(|p| p == "Value") {
+    ///     element.set_value("42")?;
+    /// }
+    /// ```
+    fn supported_patterns(&self) -> Result<Vec<String>, UiError> {
+        Err(UiError::Unsupported("Pattern introspection is not available on this platform".to_string()))
+    }
+
+    /// Capture the pixels of this element as a PNG image
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` - PNG-encoded image bytes
+    /// * `Err(...)` - If the element has zero-area bounds, is entirely offscreen,
+    ///   or capturing is not supported on this platform
+    fn capture_image(&self) -> Result<Vec<u8>, UiError> {
+        Err(UiError::Unsupported("Screen capture is not available on this platform".to_string()))
+    }
+
+    /// Draw a colored outline around the element on the screen for `duration`
+    ///
+    /// Meant for debugging selectors: it shows which element a query matched.
+    /// The call blocks while the outline is shown and erases it before
+    /// returning. The outline follows the physical bounds of the element, so
+    /// it is placed correctly on scaled monitors. Offscreen elements have
+    /// nothing to outline and succeed without drawing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The outline was shown and erased, or the element is offscreen
+    /// * `Err(UiError::Unsupported)` - If the platform cannot draw on the screen
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use ui_interaction::core::UIQuery;
+    ///
+    /// if let Some(save) = window.find_first(&UIQuery::ByName("Save".to_string()))? {
+    ///     save.highlight(Duration::from_secs(2))?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn highlight(&self, _duration: Duration) -> Result<(), UiError> {
+        if self.is_offscreen().unwrap_or(false) {
+            return Ok(());
         }
+        Err(UiError::Unsupported("Highlighting elements is not available on this platform".to_string()))
+    }
+
+    /// Capture this element and save it as a PNG file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination file path
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let element = window.get_focused_element()?;
+    /// element.save_screenshot("focused.png")?;
+    /// ```
+    fn save_screenshot(&self, path: &str) -> Result<(), UiError> {
+        std::fs::write(path, self.capture_image()?)?;
+        Ok(())
     }
 }
 
-/// Query system for finding UI elements with various criteria
-/// 
-/// This enum provides a flexible query system for finding UI elements based on
-/// different criteria such as name, type, properties, or complex combinations.
-/// It supports logical operations and hierarchical relationships.
+/// The properties a `CachedElement` serves from its snapshot
+#[derive(Clone, Debug)]
+struct ElementSnapshot {
+    name: String,
+    control_type: String,
+    bounds: Option<Rect>,
+    enabled: bool,
+    properties: HashMap<String, String>,
+}
+
+impl ElementSnapshot {
+    fn read(element: &dyn UIElement) -> Result<Self, UiError> {
+        Ok(ElementSnapshot {
+            name: element.get_name()?,
+            control_type: element.get_type()?,
+            bounds: element.get_bounds()?,
+            enabled: element.is_enabled()?,
+            properties: element.get_properties()?,
+        })
+    }
+}
+
+/// An element whose identifying properties are read once and then served from memory
 /// 
-/// # Variants
+/// Created with [`UIElement::cached`]. `get_name`, `get_type`, `get_bounds`,
+/// `is_enabled` and `get_properties` (which holds the automation id and class
+/// name) return the values captured when the snapshot was taken, without a
+/// cross-process call; every other method, text and actions included, goes to
+/// the live element. It implements `UIElement`, so it can be passed anywhere a
+/// live element is expected.
 /// 
-/// * `ByName(String)` - Find elements with a specific accessible name
-/// * `ByType(String)` - Find elements of a specific control type
-/// * `ByProperty(String, String)` - Find elements with a specific property value
-/// * `And(Vec<UIQuery>)` - Find elements matching ALL of the given queries
-/// * `Or(Vec<UIQuery>)` - Find elements matching ANY of the given queries
-/// * `Not(Box<UIQuery>)` - Find elements NOT matching the given query
-/// * `Child(Box<UIQuery>)` - Find elements that have children matching the query
-/// * `Descendant(Box<UIQuery>)` - Find elements that have descendants matching the query
-/// * `Parent(Box<UIQuery>)` - Find elements whose parent matches the query
-/// * `Ancestor(Box<UIQuery>)` - Find elements with an ancestor matching the query
+/// The snapshot is not updated on its own. If the element is renamed, moved,
+/// disabled or destroyed, the cached reads keep returning the old values, and
+/// do not fail even once the element is gone. Call [`CachedElement::refresh`]
+/// after anything that may have changed the UI, and prefer live elements for
+/// state that changes often.
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use uia_interaction::core::UIQuery;
-/// use uia_interaction::factory::UIAutomationFactory;
-/// 
-/// let automation = UIAutomationFactory::new()?;
-/// let window = automation.get_active_window()?;
-/// 
-/// // Simple queries
-/// let buttons = window.find_elements(&UIQuery::ByType("Button".to_string()))?;
-/// let save_btn = window.find_elements(&UIQuery::ByName("Save".to_string()))?;
-/// 
-/// // Complex query - find enabled buttons that contain "OK"
-/// let ok_buttons = window.find_elements(&UIQuery::And(vec![
-///     UIQuery::ByType("Button".to_string()),
-///     UIQuery::ByName("OK".to_string()),
-///     UIQuery::ByProperty("IsEnabled".to_string(), "True".to_string())
-/// ]))?;
-/// 
-/// // Find any text input control (Edit or Document)
-/// let text_inputs = window.find_elements(&UIQuery::Or(vec![
-///     UIQuery::ByType("Edit".to_string()),
-///     UIQuery::ByType("Document".to_string())
-/// ]))?;
-/// 
-/// // Find elements that are NOT buttons
-/// let non_buttons = window.find_elements(&UIQuery::Not(
-///     Box::new(UIQuery::ByType("Button".to_string()))
-/// ))?;
+/// ```rust,ignore
+/// let rows = window.find_elements(&UIQuery::ByType("DataItem".to_string()))?;
+/// let rows: Vec<CachedElement> = rows.iter().map(|row| row.cached()).collect::<Result<_, _>>()?;
+/// // Sorting reads each name many times but queries each row once
+/// let mut names: Vec<String> = rows.iter().map(|row| row.get_name()).collect::<Result<_, _>>()?;
+/// names.sort();
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-pub enum UIQuery {
-    ByName(String),
-    ByType(String),
-    ByProperty(String, String),
-    And(Vec<UIQuery>),
-    Or(Vec<UIQuery>),
-    Not(Box<UIQuery>),
-    Child(Box<UIQuery>),
-    Descendant(Box<UIQuery>),
-    Parent(Box<UIQuery>),
-    Ancestor(Box<UIQuery>),
+pub struct CachedElement {
+    element: Box<dyn UIElement>,
+    snapshot: RefCell<ElementSnapshot>,
 }
 
-impl UIQuery {
-    /// Check if an element matches this query
-    /// 
-    /// Tests whether the given UI element satisfies the conditions of this query.
-    /// This method is used internally by the query system to filter elements.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `element` - The UI element to test against this query
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(true)` - Element matches the query
-    /// * `Ok(false)` - Element does not match
-    /// * `Err(...)` - If the matching process fails
-    #[allow(dead_code)]
-    pub fn matches(&self, element: &dyn UIElement) -> Result<bool, Box<dyn Error>> {
-        match self {
-            UIQuery::ByName(name) => {
-                let props = element.get_properties()?;
-                Ok(props.get("name").map_or(false, |n| n == name))
-            }
-            UIQuery::ByType(control_type) => {
-                let props = element.get_properties()?;
-                Ok(props.get("control_type").map_or(false, |t| t == control_type))
-            }
-            UIQuery::ByProperty(key, value) => {
-                let props = element.get_properties()?;
-                Ok(props.get(key).map_or(false, |v| v == value))
-            }
-            UIQuery::And(queries) => {
-                for query in queries {
-                    if !query.matches(element)? {
-                        return Ok(false);
-                    }
-                }
-                Ok(true)
-            }
-            UIQuery::Or(queries) => {
-                for query in queries {
-                    if query.matches(element)? {
-                        return Ok(true);
-                    }
-                }
-                Ok(false)
-            }
-            UIQuery::Not(query) => {
-                Ok(!query.matches(element)?)
-            }
-            UIQuery::Child(query) => {
-                let children = element.get_children()?;
-                for child in children {
-                    if query.matches(child.as_ref())? {
-                        return Ok(true);
-                    }
-                }
-                Ok(false)
-            }
-            UIQuery::Descendant(query) => {
-                let children = element.get_children()?;
-                for child in children {
-                    if query.matches(child.as_ref())? || 
-                       UIQuery::Descendant(query.clone()).matches(child.as_ref())? {
-                        return Ok(true);
-                    }
-                }
-                Ok(false)
-            }
-            UIQuery::Parent(_query) => {
-                // Implementation here
-                Ok(false)
-            },
-            UIQuery::Ancestor(_query) => {
-                // Implementation here
-                Ok(false)
-            }
-        }
+impl CachedElement {
+    /// Wrap `element`, reading its snapshot now
+    pub fn new(element: Box<dyn UIElement>) -> Result<Self, UiError> {
+        let snapshot = ElementSnapshot::read(element.as_ref())?;
+        Ok(CachedElement { element, snapshot: RefCell::new(snapshot) })
     }
 
-    /// Find all elements matching this query in a tree rooted at the given element
-    /// 
-    /// Recursively searches through the UI element tree starting from the root element
-    /// and returns all elements that match this query. This method performs a
-    /// depth-first search through the entire subtree.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `root` - The root element to start the search from
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(Vec<Box<dyn UIElement>>)` - List of all matching elements
-    /// * `Err(...)` - If the search fails
-    /// 
-    /// # Example
-    /// 
-    /// ```rust
-    /// use uia_interaction::core::UIQuery;
-    /// use uia_interaction::factory::UIAutomationFactory;
-    /// 
-    /// let automation = UIAutomationFactory::new()?;
-    /// let window = automation.get_active_window()?;
-    /// let root_element = window.get_focused_element()?;
+    /// Read the snapshot again from the live element
     /// 
-    /// // Find all buttons in the subtree
-    /// let query = UIQuery::ByType("Button".to_string());
-    /// let buttons = query.find_all(root_element.as_ref())?;
-    /// println!("Found {} buttons", buttons.len());
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    #[allow(dead_code)]
-    pub fn find_all(&self, root: &dyn UIElement) -> Result<Vec<Box<dyn UIElement>>, Box<dyn Error>> {
-        let mut results = Vec::new();
-        if self.matches(root)? {
-            results.push(root.to_tree_node()?);
-        }
-        
-        let children = root.get_children()?;
-        for child in children {
-            let child_results = self.find_all(child.as_ref())?;
-            results.extend(child_results);
-        }
-        
-        Ok(results)
+    /// The previous snapshot is kept if the element cannot be read.
+    pub fn refresh(&self) -> Result<(), UiError> {
+        let snapshot = ElementSnapshot::read(self.element.as_ref())?;
+        *self.snapshot.borrow_mut() = snapshot;
+        Ok(())
+    }
+
+    /// The cached automation id, if the element has one
+    pub fn automation_id(&self) -> Option<String> {
+        self.snapshot.borrow().properties.get("automation_id").cloned()
+    }
+
+    /// The cached class name, if the element has one
+    pub fn class_name(&self) -> Option<String> {
+        self.snapshot.borrow().properties.get("class_name").cloned()
+    }
+
+    /// The live element behind the snapshot
+    pub fn live(&self) -> &dyn UIElement {
+        self.element.as_ref()
     }
 }
 
-/// Main UI Automation interface for desktop applications
-/// 
-/// This trait provides the primary entry point for UI automation operations.
-/// It offers methods to access windows, elements, and perform high-level
-/// automation tasks across the desktop environment.
-/// 
-/// Implementations of this trait are platform-specific and handle the
-/// low-level details of interacting with the operating system's accessibility APIs.
-/// 
-/// # Thread Safety
+impl UIElement for CachedElement {
+    fn get_name(&self) -> Result<String, UiError> { Ok(self.snapshot.borrow().name.clone()) }
+    fn get_type(&self) -> Result<String, UiError> { Ok(self.snapshot.borrow().control_type.clone()) }
+    fn get_bounds(&self) -> Result<Option<Rect>, UiError> { Ok(self.snapshot.borrow().bounds.clone()) }
+    fn is_enabled(&self) -> Result<bool, UiError> { Ok(self.snapshot.borrow().enabled) }
+    fn get_properties(&self) -> Result<HashMap<String, String>, UiError> { Ok(self.snapshot.borrow().properties.clone()) }
+
+    fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError> {
+        Ok(Box::new(CachedElement {
+            element: self.element.to_tree_node()?,
+            snapshot: RefCell::new(self.snapshot.borrow().clone()),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn cached(&self) -> Result<CachedElement, UiError> {
+        CachedElement::new(self.element.to_tree_node()?)
+    }
+
+    // Everything else reads or acts on the live element
+    fn get_localized_control_type(&self) -> Result<String, UiError> { self.element.get_localized_control_type() }
+    fn get_text(&self) -> Result<String, UiError> { self.element.get_text() }
+    fn get_text_limited(&self, max_chars: usize) -> Result<(String, bool), UiError> { self.element.get_text_limited(max_chars) }
+    fn verify_text(&self, expected: &str) -> Result<TextVerification, UiError> { self.element.verify_text(expected) }
+    fn get_text_via_clipboard(&self) -> Result<String, UiError> { self.element.get_text_via_clipboard() }
+    fn set_text(&self, text: &str) -> Result<(), UiError> { self.element.set_text(text) }
+    fn set_text_with_options(&self, text: &str, options: &TextInputOptions) -> Result<(), UiError> { self.element.set_text_with_options(text, options) }
+    fn is_password(&self) -> Result<bool, UiError> { self.element.is_password() }
+    fn clear(&self) -> Result<(), UiError> { self.element.clear() }
+    fn select_all(&self) -> Result<(), UiError> { self.element.select_all() }
+    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), UiError> { self.element.append_text(text, position) }
+    fn insert_text_at(&self, offset: usize, text: &str) -> Result<(), UiError> { self.element.insert_text_at(offset, text) }
+    fn click(&self) -> Result<(), UiError> { self.element.click() }
+    fn invoke(&self) -> Result<(), UiError> { self.element.invoke() }
+    fn right_click(&self) -> Result<(), UiError> { self.element.right_click() }
+    fn double_click(&self) -> Result<(), UiError> { self.element.double_click() }
+    fn set_focus(&self) -> Result<(), UiError> { self.element.set_focus() }
+    fn is_offscreen(&self) -> Result<bool, UiError> { self.element.is_offscreen() }
+    fn is_editable(&self) -> Result<bool, UiError> { self.element.is_editable() }
+    fn is_focusable(&self) -> Result<bool, UiError> { self.element.is_focusable() }
+    fn get_orientation(&self) -> Result<Orientation, UiError> { self.element.get_orientation() }
+    fn is_content_element(&self) -> Result<bool, UiError> { self.element.is_content_element() }
+    fn is_control_element(&self) -> Result<bool, UiError> { self.element.is_control_element() }
+    fn get_access_key(&self) -> Result<Option<String>, UiError> { self.element.get_access_key() }
+    fn get_accelerator_key(&self) -> Result<Option<String>, UiError> { self.element.get_accelerator_key() }
+    fn get_properties_filtered(&self, keys: &[&str]) -> Result<HashMap<String, String>, UiError> { self.element.get_properties_filtered(keys) }
+    fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> { self.element.get_children() }
+    fn get_parent(&self) -> Result<Option<Box<dyn UIElement>>, UiError> { self.element.get_parent() }
+    fn index_in_parent(&self) -> Result<Option<usize>, UiError> { self.element.index_in_parent() }
+    fn runtime_id(&self) -> Result<Vec<i32>, UiError> { self.element.runtime_id() }
+    fn get_subtree(&self, max_depth: u32) -> Result<UITreeNode, UiError> { self.element.get_subtree(max_depth) }
+    fn get_text_element_info(&self) -> Result<TextElementInfo, UiError> { self.element.get_text_element_info() }
+    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, UiError> { self.element.get_selected_text() }
+    fn get_toggle_state(&self) -> Result<Option<bool>, UiError> { self.element.get_toggle_state() }
+    fn set_toggle_state(&self, checked: bool) -> Result<(), UiError> { self.element.set_toggle_state(checked) }
+    fn get_selection_options(&self) -> Result<Vec<String>, UiError> { self.element.get_selection_options() }
+    fn select_option(&self, value: &str) -> Result<(), UiError> { self.element.select_option(value) }
+    fn get_selected_items(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> { self.element.get_selected_items() }
+    fn select_item(&self, add_to_selection: bool) -> Result<(), UiError> { self.element.select_item(add_to_selection) }
+    fn deselect_item(&self) -> Result<(), UiError> { self.element.deselect_item() }
+    fn get_expand_state(&self) -> Result<Option<ExpandState>, UiError> { self.element.get_expand_state() }
+    fn expand(&self) -> Result<(), UiError> { self.element.expand() }
+    fn collapse(&self) -> Result<(), UiError> { self.element.collapse() }
+    fn get_value(&self) -> Result<String, UiError> { self.element.get_value() }
+    fn set_value(&self, value: &str) -> Result<(), UiError> { self.element.set_value(value) }
+    fn get_range_value(&self) -> Result<Option<RangeValue>, UiError> { self.element.get_range_value() }
+    fn set_range_value(&self, value: f64) -> Result<(), UiError> { self.element.set_range_value(value) }
+    fn hover(&self) -> Result<(), UiError> { self.element.hover() }
+    fn hover_for(&self, dwell: Duration, hover_restore: bool) -> Result<(), UiError> { self.element.hover_for(dwell, hover_restore) }
+    fn send_chord(&self, chord: &KeyChord) -> Result<(), UiError> { self.element.send_chord(chord) }
+    fn scroll(&self, direction: ScrollDirection, amount: f64) -> Result<(), UiError> { self.element.scroll(direction, amount) }
+    fn is_in_viewport(&self, window: &dyn Window) -> Result<bool, UiError> { self.element.is_in_viewport(window) }
+    fn visible_fraction(&self, window: &dyn Window) -> Result<f64, UiError> { self.element.visible_fraction(window) }
+    fn scroll_into_view(&self) -> Result<(), UiError> { self.element.scroll_into_view() }
+    fn supported_patterns(&self) -> Result<Vec<String>, UiError> { self.element.supported_patterns() }
+    fn capture_image(&self) -> Result<Vec<u8>, UiError> { self.element.capture_image() }
+    fn highlight(&self, duration: Duration) -> Result<(), UiError> { self.element.highlight(duration) }
+    fn save_screenshot(&self, path: &str) -> Result<(), UiError> { self.element.save_screenshot(path) }
+}
+
+/// Trait for interacting with application windows
 /// 
-/// This trait requires `Send + Sync`, making it safe to use across threads.
-/// However, be aware that some UI operations may need to be performed on
-/// specific threads (e.g., the main UI thread on some platforms).
+/// This trait provides methods to interact with windows, get window information,
+/// control window state, and find UI elements within the window. It represents
+/// a top-level window or dialog in a desktop application.
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use uia_interaction::factory::UIAutomationFactory;
-/// use uia_interaction::core::{UIQuery, AppendPosition};
+/// ```rust,ignore
+/// use ui_interaction::factory::UIAutomationFactory;
+/// use ui_interaction::core::UIQuery;
 /// 
-/// // Create automation instance
 /// let automation = UIAutomationFactory::new()?;
-/// 
-/// // Get the currently active window
 /// let window = automation.get_active_window()?;
-/// println!("Active window: {}", window.get_title()?);
 /// 
-/// // Get the focused element and interact with it
-/// let element = automation.get_focused_element()?;
-/// println!("Focused element: {} ({})", element.get_name()?, element.get_type()?);
+/// // Get window information
+/// println!("Window: {}", window.get_title()?);
+/// println!("Process: {} (PID: {})", window.get_process_name()?, window.get_process_id()?);
 /// 
-/// // Set text if it's a text input element
-/// if element.get_type()? == "Edit" {
-///     element.set_text("Hello, World!")?;
-/// }
+/// // Activate the window to ensure it has focus
+/// window.activate()?;
+/// 
+/// // Find all buttons in the window
+/// let button_query = UIQuery::ByType("Button".to_string());
+/// let buttons = window.find_elements(&button_query)?;
+/// println!("Found {} buttons", buttons.len());
+/// 
+/// // Get the UI tree for analysis
+/// let tree = window.get_ui_tree()?;
+/// println!("UI tree captured at: {}", tree.timestamp);
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 #[allow(dead_code)]
-pub trait UIAutomation: Send + Sync {
-    /// Get the currently active (foreground) window
+pub trait Window {
+    /// Get the window title text
     /// 
-    /// Returns the top-level application window that currently has focus.
-    /// This is typically the window the user is actively working with.
+    /// Returns the title bar text of the window, which is typically displayed
+    /// in the window's title bar and taskbar.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn Window>)` - The active window
-    /// * `Err(...)` - If no active window can be found or accessed
-    /// 
-    /// # Example
-    /// 
-    /// ```rust
-    /// let automation = UIAutomationFactory::new()?;
-    /// let active_window = automation.get_active_window()?;
-    /// println!("Current application: {}", active_window.get_title()?);
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    fn get_active_window(&self) -> Result<Box<dyn Window>, Box<dyn Error>>;
+    /// * `Ok(String)` - The window title
+    /// * `Err(...)` - If the title cannot be retrieved
+    fn get_title(&self) -> Result<String, UiError>;
     
-    /// Get the window that contains the currently focused element
+    /// Get the window class name
     /// 
-    /// This might return the same window as `get_active_window()`, but could
-    /// be different if focus is in a child window, dialog, or popup.
+    /// Returns the window class name, which is a Windows-specific identifier
+    /// that describes the type of window (e.g., "Notepad", "Chrome_WidgetWin_1").
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn Window>)` - The window containing the focused element
-    /// * `Err(...)` - If no window with focus can be found
-    fn get_window_containing_focus(&self) -> Result<Box<dyn Window>, Box<dyn Error>>;
+    /// * `Ok(String)` - The window class name
+    /// * `Err(...)` - If the class name cannot be retrieved
+    fn get_class_name(&self) -> Result<String, UiError>;
     
-    /// Get the currently focused element
+    /// Get the process ID of the window's owning process
     /// 
-    /// Returns the UI element that currently has keyboard focus across
-    /// the entire desktop. This element will receive keyboard input.
+    /// Returns the process identifier (PID) of the process that owns this window.
+    /// This can be used to identify which application created the window.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn UIElement>)` - The element with keyboard focus
-    /// * `Err(...)` - If no focused element can be found
+    /// * `Ok(u32)` - The process ID
+    /// * `Err(...)` - If the process ID cannot be retrieved
+    fn get_process_id(&self) -> Result<u32, UiError>;
+    
+    /// Get the thread ID of the window's owning thread
     /// 
-    /// # Example
+    /// Returns the thread identifier of the thread that created this window.
+    /// This is primarily useful for low-level Windows programming.
     /// 
-    /// ```rust
-    /// let automation = UIAutomationFactory::new()?;
-    /// let focused = automation.get_focused_element()?;
+    /// # Returns
     /// 
-    /// // Check what type of element has focus
-    /// match focused.get_type()?.as_str() {
-    ///     "Edit" => {
-    ///         println!("Text field has focus");
-    ///         focused.set_text("New text content")?;
-    ///     }
-    ///     "Button" => {
-    ///         println!("Button has focus: {}", focused.get_name()?);
-    ///     }
-    ///     _ => {
-    ///         println!("Other element type: {}", focused.get_type()?);
-    ///     }
-    /// }
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, Box<dyn Error>>;
+    /// * `Ok(u32)` - The thread ID
+    /// * `Err(...)` - If the thread ID cannot be retrieved
+    fn get_thread_id(&self) -> Result<u32, UiError>;
     
-    /// Find an element by its accessible name
-    /// 
-    /// Searches the entire desktop for a UI element with the specified name.
-    /// This is a global search that may be slower than window-specific searches.
-    /// 
-    /// # Arguments
+    /// Get the executable name of the window's owning process
     /// 
-    /// * `name` - The accessible name to search for
+    /// Returns the name of the executable file (e.g., "notepad.exe", "chrome.exe")
+    /// that owns this window.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn UIElement>)` - The first element found with this name
-    /// * `Err(...)` - If no element with this name is found
-    fn find_element_by_name(&self, name: &str) -> Result<Box<dyn UIElement>, Box<dyn Error>>;
+    /// * `Ok(String)` - The process executable name
+    /// * `Err(...)` - If the process name cannot be retrieved
+    fn get_process_name(&self) -> Result<String, UiError>;
     
-    /// Find an element by its control type
+    /// Get the full path to the window's owning process executable
     /// 
-    /// Searches the entire desktop for a UI element of the specified type.
-    /// This is a global search that may be slower than window-specific searches.
+    /// Returns the complete file system path to the executable that owns this window.
     /// 
-    /// # Arguments
+    /// # Returns
     /// 
-    /// * `element_type` - The control type to search for (e.g., "Button", "Edit")
+    /// * `Ok(String)` - The full path to the executable
+    /// * `Err(...)` - If the process path cannot be retrieved
+    fn get_process_path(&self) -> Result<String, UiError>;
+    
+    /// Check if the window is visible on screen
+    /// 
+    /// Returns whether the window is currently visible (not hidden).
+    /// Minimized windows are typically still considered visible.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn UIElement>)` - The first element found of this type
-    /// * `Err(...)` - If no element of this type is found
-    fn find_element_by_type(&self, element_type: &str) -> Result<Box<dyn UIElement>, Box<dyn Error>>;
+    /// * `Ok(true)` - Window is visible
+    /// * `Ok(false)` - Window is hidden
+    /// * `Err(...)` - If visibility state cannot be determined
+    fn is_visible(&self) -> Result<bool, UiError>;
     
-    /// DEPRECATED: Use get_active_window() instead
+    /// Check if the window is minimized
     /// 
-    /// This method is deprecated and will be removed in a future version.
-    /// Use `get_active_window()` for the same functionality.
-    #[deprecated(since = "0.1.0", note = "Use get_active_window() instead")]
-    fn get_focused_window(&self) -> Result<Box<dyn Window>, Box<dyn Error>> {
-        self.get_active_window()
+    /// Returns whether the window is currently minimized to the taskbar.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Window is minimized
+    /// * `Ok(false)` - Window is not minimized
+    /// * `Err(...)` - If minimized state cannot be determined
+    fn is_minimized(&self) -> Result<bool, UiError>;
+    
+    /// Check if the window is maximized
+    /// 
+    /// Returns whether the window is currently maximized to fill the screen
+    /// (or work area).
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Window is maximized
+    /// * `Ok(false)` - Window is not maximized
+    /// * `Err(...)` - If maximized state cannot be determined
+    fn is_maximized(&self) -> Result<bool, UiError>;
+    
+    /// Get the window's position and size
+    /// 
+    /// Returns a `Rect` containing the window's screen coordinates:
+    /// left, top, right, and bottom edges in pixels.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Rect)` - The window's screen rectangle
+    /// * `Err(...)` - If window geometry cannot be retrieved
+    fn get_rect(&self) -> Result<Rect, UiError>;
+
+    /// Get the window's current bounds, in the coordinates of `UIElement::get_bounds`
+    /// 
+    /// `get_rect` may report the rectangle seen when the window was found, and
+    /// on Windows it is in the caller's DPI-virtualised coordinates. These
+    /// bounds are read when called and can be compared with element bounds
+    /// directly; `UIElement::is_in_viewport` and `visible_fraction` use them.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Rect)` - The window's current screen bounds
+    /// * `Err(...)` - If window geometry cannot be retrieved
+    fn get_bounds(&self) -> Result<Rect, UiError> {
+        self.get_rect()
     }
-}
 
-/// Information about a running application on the system
-/// 
-/// This structure contains comprehensive information about an application process,
-/// including its main window details and process information. It's used for
-/// application discovery and management operations.
-/// 
-/// # Fields
-/// 
-/// * `process_id` - The unique process identifier (PID)
-/// * `process_name` - The executable filename (e.g., "notepad.exe")
-/// * `process_path` - Full path to the executable file
-/// * `main_window_title` - Title of the application's main window
-/// * `main_window_class` - Window class name of the main window
-/// * `is_visible` - Whether the main window is currently visible
-/// 
-/// # Example
-/// 
-/// ```rust
-/// use uia_interaction::factory::ApplicationManagerFactory;
-/// 
-/// let app_manager = ApplicationManagerFactory::new()?;
-/// let applications = app_manager.get_all_applications()?;
-/// 
-/// for app in applications {
-///     if app.is_visible {
-///         println!("App: {} (PID: {})", app.process_name, app.process_id);
-///         println!("  Window: '{}'", app.main_window_title);
-///         println!("  Path: {}", app.process_path);
-///     }
-/// }
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
-pub struct ApplicationInfo {
-    pub process_id: u32,
-    pub process_name: String,
-    pub process_path: String,
-    pub main_window_title: String,
-    pub main_window_class: String,
-    pub is_visible: bool,
-}
+    /// Move the window so its top-left corner is at the given screen position
+    /// 
+    /// Coordinates are physical screen pixels, the same space as element bounds,
+    /// regardless of the window's DPI scaling. The window keeps its size.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the window was moved
+    /// * `Err(UiError::InvalidArgument(...))` - If the window is minimized
+    /// * `Err(...)` - If the window cannot be repositioned
+    fn move_to(&self, _x: i32, _y: i32) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Moving windows is not supported on this platform".to_string()))
+    }
 
-/// Trait for discovering and managing applications running on the system
-/// 
-/// This trait provides methods to enumerate running applications, search for
-/// specific applications by various criteria, and obtain window handles for
-/// UI automation. It's the primary interface for application discovery and
-/// management in the automation system.
-/// 
-/// # Thread Safety
-/// 
-/// This trait requires `Send + Sync`, making it safe to use across threads.
-/// 
-/// # Example
-/// 
-/// ```rust
-/// use uia_interaction::factory::ApplicationManagerFactory;
-/// use uia_interaction::core::UIQuery;
-/// 
-/// let app_manager = ApplicationManagerFactory::new()?;
-/// 
-/// // Find all Notepad instances
-/// let notepad_apps = app_manager.find_applications_by_name("notepad.exe")?;
-/// 
-/// if let Some(notepad) = notepad_apps.first() {
-///     println!("Found Notepad: {} (PID: {})", notepad.main_window_title, notepad.process_id);
-///     
-///     // Get the window for interaction
-///     let window = app_manager.get_window_by_process_id(notepad.process_id)?;
-///     
-///     // Activate the window and interact with it
-///     window.activate()?;
-///     
-///     // Find text area and set content
-///     let text_areas = window.find_elements(&UIQuery::ByType("Edit".to_string()))?;
-///     if let Some(text_area) = text_areas.first() {
-///         text_area.set_text("Hello from automation!")?;
-///     }
-/// }
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
-#[allow(dead_code)]
-pub trait ApplicationManager: Send + Sync {
-    /// Get information about all running applications
+    /// Resize the window, keeping its top-left corner in place
     /// 
-    /// Enumerates all currently running applications on the system and returns
-    /// information about each one, including process details and main window information.
+    /// Sizes are physical pixels and must be positive.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Vec<ApplicationInfo>)` - List of all running applications
-    /// * `Err(...)` - If application enumeration fails
+    /// * `Ok(())` - If the window was resized
+    /// * `Err(UiError::InvalidArgument(...))` - If the window is minimized or the size is not positive
+    /// * `Err(...)` - If the window cannot be resized
+    fn resize(&self, _width: i32, _height: i32) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Resizing windows is not supported on this platform".to_string()))
+    }
+
+    /// Move and resize the window in one step
     /// 
     /// # Example
     /// 
-    /// ```rust
-    /// let app_manager = ApplicationManagerFactory::new()?;
-    /// let all_apps = app_manager.get_all_applications()?;
+    /// ```rust,ignore
+    /// use ui_interaction::core::Rect;
     /// 
-    /// println!("Found {} running applications:", all_apps.len());
-    /// for app in all_apps {
-    ///     if app.is_visible {
-    ///         println!("  {}: '{}' (PID: {})", 
-    ///                  app.process_name, 
-    ///                  app.main_window_title, 
-    ///                  app.process_id);
-    ///     }
-    /// }
+    /// // Reproducible layout for screenshot tests
+    /// window.set_rect(Rect::new(100, 100, 900, 700))?;
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    fn get_all_applications(&self) -> Result<Vec<ApplicationInfo>, Box<dyn Error>>;
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the window now covers `rect`
+    /// * `Err(UiError::InvalidArgument(...))` - If the window is minimized or `rect` is empty
+    /// * `Err(...)` - If the window cannot be repositioned
+    fn set_rect(&self, _rect: Rect) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Repositioning windows is not supported on this platform".to_string()))
+    }
     
-    /// Find applications by process name
+    /// Get the window's DPI (dots per inch) scaling
     /// 
-    /// Searches for applications whose process name matches the given name.
-    /// The search is case-insensitive and supports partial matching.
+    /// Returns the DPI scaling factor for this window, which is important
+    /// for high-DPI displays and coordinate calculations.
     /// 
-    /// # Arguments
+    /// # Returns
     /// 
-    /// * `name` - Process name to search for (e.g., "notepad", "notepad.exe", "chrome")
+    /// * `Ok(u32)` - The DPI value (typically 96, 120, 144, 192, etc.)
+    /// * `Err(...)` - If DPI cannot be retrieved
+    fn get_dpi(&self) -> Result<u32, UiError>;
+    
+    /// Activate the window (bring to foreground with focus)
+    /// 
+    /// Makes this window the active window, bringing it to the foreground
+    /// and giving it keyboard focus. This is the recommended way to ensure
+    /// a window is ready for interaction.
+    /// 
+    /// A minimized window is restored first. The foreground switch is then
+    /// requested and verified like [`Window::set_foreground`], so a successful
+    /// return means the window really is in front.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Vec<ApplicationInfo>)` - List of matching applications
-    /// * `Err(...)` - If the search fails
+    /// * `Ok(())` - Window was activated successfully
+    /// * `Err(UiError::Timeout)` - If the OS refused the foreground switch
+    /// * `Err(...)` - If the window cannot be activated
     /// 
     /// # Example
     /// 
-    /// ```rust
-    /// // Find all Chrome instances
-    /// let chrome_apps = app_manager.find_applications_by_name("chrome")?;
-    /// 
-    /// // Find specific executable
-    /// let calculator = app_manager.find_applications_by_name("calc.exe")?;
+    /// ```rust,ignore
+    /// // Ensure the window is active before interacting with it
+    /// window.activate()?;
     /// 
-    /// // Find by partial name
-    /// let text_editors = app_manager.find_applications_by_name("notepad")?; // Matches notepad.exe
+    /// // Now it's safe to send keyboard input or interact with elements
+    /// let element = window.get_focused_element()?;
+    /// element.set_text("Hello, World!")?;
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    fn find_applications_by_name(&self, name: &str) -> Result<Vec<ApplicationInfo>, Box<dyn Error>>;
+    fn activate(&self) -> Result<(), UiError>;
     
-    /// Find applications by window title
+    /// Bring the window to the top of the Z-order
     /// 
-    /// Searches for applications whose main window title contains the given text.
-    /// The search is case-insensitive and supports partial matching.
+    /// Moves the window to the top of the window stack without giving it
+    /// focus or activating it. This is less intrusive than `activate()`; the
+    /// previously active window keeps receiving keyboard input.
     /// 
-    /// # Arguments
+    /// # Returns
     /// 
-    /// * `title` - Window title text to search for
+    /// * `Ok(())` - Window was brought to top successfully
+    /// * `Err(...)` - If the window cannot be brought to top
+    fn bring_to_top(&self) -> Result<(), UiError>;
+    
+    /// Set the window as the foreground window
     /// 
-    /// # Returns
+    /// Attempts to make this window the foreground window, retrying a few
+    /// times and checking that the switch actually happened.
     /// 
-    /// * `Ok(Vec<ApplicationInfo>)` - List of matching applications
-    /// * `Err(...)` - If the search fails
+    /// # Limitations
     /// 
-    /// # Example
+    /// Operating systems guard the foreground against focus stealing. On
+    /// Windows the request is only honoured when the calling process owns the
+    /// current foreground window or received the last input event; the
+    /// Windows backend works around this by temporarily attaching to the
+    /// foreground thread's input queue, but it can still be refused, e.g. while
+    /// the user is typing in another window, while a full-screen application
+    /// or the lock screen is active, or when running in a non-interactive
+    /// session. In that case the taskbar button usually flashes instead.
     /// 
-    /// ```rust
-    /// // Find applications with "Discord" in the title
-    /// let discord_apps = app_manager.find_applications_by_title("Discord")?;
+    /// # Returns
     /// 
-    /// // Find document editing applications
-    /// let word_docs = app_manager.find_applications_by_title("Microsoft Word")?;
+    /// * `Ok(())` - Window is now the foreground window
+    /// * `Err(UiError::Timeout)` - If the window is still not in front after retrying
+    /// * `Err(...)` - If the window cannot be set as foreground
+    fn set_foreground(&self) -> Result<(), UiError>;
+
+    /// Minimize the window to the taskbar
     /// 
-    /// // Find any browser windows
-    /// let browsers = app_manager.find_applications_by_title("Mozilla Firefox")?;
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    fn find_applications_by_title(&self, title: &str) -> Result<Vec<ApplicationInfo>, Box<dyn Error>>;
-    
-    /// Get a window object from an application by process ID
+    /// # Returns
     /// 
-    /// Creates a Window object for the main window of the application with
-    /// the specified process ID. This window can then be used for UI automation.
+    /// * `Ok(())` - Window was minimized
+    /// * `Err(...)` - If the window cannot be minimized
+    fn minimize(&self) -> Result<(), UiError>;
+
+    /// Maximize the window to fill the screen (or work area)
     /// 
-    /// # Arguments
+    /// # Returns
     /// 
-    /// * `process_id` - The process ID of the target application
+    /// * `Ok(())` - Window was maximized
+    /// * `Err(...)` - If the window cannot be maximized
+    fn maximize(&self) -> Result<(), UiError>;
+
+    /// Restore the window from a minimized or maximized state
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn Window>)` - Window object for the application's main window
-    /// * `Err(...)` - If the window cannot be found or accessed
+    /// * `Ok(())` - Window was restored to its normal size and position
+    /// * `Err(...)` - If the window cannot be restored
+    fn restore(&self) -> Result<(), UiError>;
+
+    /// Close the window
     /// 
-    /// # Example
+    /// Asks the window to close the same way clicking its close button would,
+    /// without terminating the owning process. The call waits until the window
+    /// is gone or a timeout elapses.
     /// 
-    /// ```rust
-    /// // Find Notepad and get its window
-    /// let notepad_apps = app_manager.find_applications_by_name("notepad.exe")?;
-    /// if let Some(notepad) = notepad_apps.first() {
-    ///     let window = app_manager.get_window_by_process_id(notepad.process_id)?;
-    ///     
-    ///     // Now you can interact with the window
-    ///     window.activate()?;
-    ///     println!("Notepad window: {}", window.get_title()?);
-    /// }
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    fn get_window_by_process_id(&self, process_id: u32) -> Result<Box<dyn Window>, Box<dyn Error>>;
-    
-    /// Get the main window of an application by process name
+    /// # Returns
     /// 
-    /// Finds the first application with the given process name and returns
-    /// its main window. This is a convenience method that combines finding
-    /// an application by name and getting its window.
+    /// * `Ok(())` - Window was closed
+    /// * `Err(...)` - If the close request failed or the window is still open after the
+    ///   timeout (e.g. the application is showing a "save changes?" prompt)
+    fn close(&self) -> Result<(), UiError>;
+
+    /// Wait for the window to close
+    /// 
+    /// Pairs with clicking a dialog's OK button or calling `close` on a window
+    /// that may prompt first: scripts can tell the dialog is gone before
+    /// moving on. Windows checks the handle with `IsWindow`; elsewhere the
+    /// window counts as closed once reading its title reports it not found.
     /// 
     /// # Arguments
     /// 
-    /// * `name` - Process name to search for
+    /// * `timeout` - Maximum time to wait
     /// 
     /// # Returns
     /// 
-    /// * `Ok(Box<dyn Window>)` - Window object for the application's main window
-    /// * `Err(...)` - If no matching application is found or window cannot be accessed
+    /// * `Ok(true)` - The window closed, or was already gone
+    /// * `Ok(false)` - The window is still open after the timeout
+    /// * `Err(...)` - If the window's state cannot be read
     /// 
     /// # Example
     /// 
-    /// ```rust
-    /// // Get the first Calculator window directly
-    /// match app_manager.get_window_by_process_name("calc.exe") {
-    ///     Ok(calc_window) => {
-    ///         calc_window.activate()?;
-    ///         println!("Calculator: {}", calc_window.get_title()?);
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// let dialog = window.get_child_windows()?.remove(0);
+    /// dialog.find_first(&UIQuery::ByName("OK".to_string()))?.unwrap().click()?;
+    /// if !dialog.wait_for_close(Duration::from_secs(5))? {
+    ///     println!("The dialog is still open");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn wait_for_close(&self, timeout: Duration) -> Result<bool, UiError> {
+        let start = Instant::now();
+        loop {
+            match self.get_title() {
+                Err(UiError::ElementNotFound(_)) | Err(UiError::WindowNotFound(_)) => return Ok(true),
+                Err(e) => return Err(e),
+                Ok(_) => {}
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Ok(false);
+            }
+            thread::sleep(CLOSE_POLL_INTERVAL.min(timeout - elapsed));
+        }
+    }
+
+    /// Get the dialogs and tool windows that belong to this window
+    /// 
+    /// Windows has two kinds of subordinate windows:
+    /// 
+    /// * **Owned** windows are top-level windows (modal dialogs such as
+    ///   "Save As", message boxes, tool palettes) that stay above their owner
+    ///   and are hidden and destroyed with it. They are separate windows on
+    ///   screen and are not part of the owner's element tree.
+    /// * **Child** (parented) windows are drawn inside their parent's client
+    ///   area. Most are plain controls, which are reached with `find_elements`
+    ///   instead; only those UI Automation exposes as windows, such as MDI
+    ///   documents, are returned here.
+    /// 
+    /// Only visible windows are returned, owned windows first in Z-order
+    /// (topmost first), then child windows. Windows owned by those windows in
+    /// turn are not included; call this on them to go further down.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Box<dyn Window>>)` - The owned and child windows (may be empty)
+    /// * `Err(UiError::Unsupported)` - If the platform cannot list them
+    /// * `Err(...)` - If the windows cannot be enumerated
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// window.send_keys("{CTRL}{SHIFT}s")?;
+    /// std::thread::sleep(std::time::Duration::from_millis(500));
+    /// for dialog in window.get_child_windows()? {
+    ///     if dialog.get_title()? == "Save As" {
+    ///         dialog.activate()?;
     ///     }
-    ///     Err(_) => {
-    ///         println!("Calculator not found or not accessible");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_child_windows(&self) -> Result<Vec<Box<dyn Window>>, UiError> {
+        Err(UiError::Unsupported("Listing owned and child windows is not supported on this platform".to_string()))
+    }
+
+    /// Get an identifier for the native window behind this handle
+    /// 
+    /// Two handles to the same window report the same id, however they were
+    /// obtained, so ids can be compared across `get_all_windows` snapshots to
+    /// spot windows that opened or closed in between. On Windows this is the
+    /// HWND value; on Linux and macOS it is a hash of the accessibility
+    /// object reference. The platform may reuse an id once its window is
+    /// destroyed, so only compare ids of snapshots taken close together.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(u64)` - The window's id
+    /// * `Err(UiError::Unsupported)` - If the platform has no stable window id
+    /// * `Err(...)` - If the window no longer exists
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use std::collections::HashSet;
+    /// 
+    /// let before: HashSet<u64> = automation.get_all_windows()?
+    ///     .iter().filter_map(|w| w.window_id().ok()).collect();
+    /// window.send_keys("{CTRL}o")?;
+    /// std::thread::sleep(std::time::Duration::from_millis(500));
+    /// for opened in automation.get_all_windows()? {
+    ///     if !before.contains(&opened.window_id()?) {
+    ///         println!("New window: {}", opened.get_title()?);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn window_id(&self) -> Result<u64, UiError> {
+        Err(UiError::Unsupported("Window ids are not available on this platform".to_string()))
+    }
+
+    /// Send a key sequence to the window
+    /// 
+    /// Activates the window, then types the sequence as keyboard input. Special keys
+    /// are written in braces and act as modifiers for the key that follows:
+    /// 
+    /// * `{CTRL}s` - Ctrl+S
+    /// * `{F5}` - F5
+    /// * `{ALT}{F4}` - Alt+F4
+    /// * `{CTRL}{SHIFT}{ESC}` - Ctrl+Shift+Esc
+    /// * `{CTRL}(AB)` - Ctrl held while typing A and B
+    /// * `{ENTER}`, `{TAB}`, `{HOME}`, `{END}`, `{DELETE}`, `{BACK}` - Named keys
+    /// 
+    /// Literal braces and parentheses are quoted in braces, e.g. `{{}` types `{`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `keys` - The key sequence to send
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - Keys were sent
+    /// * `Err(...)` - If the sequence is empty or malformed, or the window cannot be activated
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // Save the document
+    /// window.send_keys("{CTRL}s")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn send_keys(&self, keys: &str) -> Result<(), UiError>;
+
+    /// Send a key chord to the window
+    ///
+    /// A typed alternative to [`Window::send_keys`]: the chord is converted
+    /// to the same key sequence syntax, and rejected before anything is typed
+    /// if one of its keys cannot be sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use ui_interaction::keyboard::{Key, KeyChord};
+    ///
+    /// // Save the document
+    /// window.send_chord(&KeyChord::new(Key::Char('s')).ctrl())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn send_chord(&self, chord: &KeyChord) -> Result<(), UiError> {
+        self.send_keys(&chord.to_key_string()?)
+    }
+    
+    /// Get the currently focused element within this window
+    /// 
+    /// Returns the UI element that currently has keyboard focus within this window.
+    /// This is useful for determining where text input will go.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn UIElement>)` - The focused element
+    /// * `Err(...)` - If no element has focus or focus cannot be determined
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError>;
+
+    /// Get a UI tree snapshot of the window
+    /// 
+    /// Captures the UI element hierarchy of this window at the current
+    /// moment, creating a tree structure that can be analyzed or traversed.
+    /// This is useful for debugging UI structure or finding elements.
+    /// 
+    /// Uses [`UITreeOptions::default`], which limits the depth and the number of
+    /// children per node; use [`Window::get_ui_tree_with_options`] for deep UIs.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(UITree)` - UI tree snapshot
+    /// * `Err(...)` - If the UI tree cannot be captured
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let tree = window.get_ui_tree()?;
+    /// println!("Window: {} captured at {}", tree.window_title, tree.timestamp);
+    /// 
+    /// // Recursively explore the tree
+    /// fn explore_node(node: &UITreeNode, depth: usize) {
+    ///     let indent = "  ".repeat(depth);
+    ///     println!("{}|- {} ({})", indent, node.name, node.control_type);
+    ///     for child in &node.children {
+    ///         explore_node(child, depth + 1);
     ///     }
     /// }
+    /// explore_node(&tree.root, 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_ui_tree(&self) -> Result<UITree, UiError> {
+        self.get_ui_tree_with_options(&UITreeOptions::default())
+    }
+
+    /// Capture the window's UI tree and write it to `path` as JSON
+    /// 
+    /// The file can be read back with [`UITree::load`] and searched with
+    /// [`UITree::find`], which lets queries be developed and debugged
+    /// without the application running. Properties are captured too, so
+    /// automation-id and class-name queries work on the saved copy.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - The tree was captured and written
+    /// * `Err(...)` - If the tree cannot be captured or the file cannot be written
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// window.save_ui_tree("notepad.json")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn save_ui_tree(&self, path: &str) -> Result<(), UiError> {
+        let options = UITreeOptions { include_properties: true, ..UITreeOptions::default() };
+        self.get_ui_tree_with_options(&options)?.save(path)
+    }
+
+    /// Get a UI tree snapshot of the window with custom limits
+    /// 
+    /// # Arguments
+    /// 
+    /// * `options` - Depth and width limits, which details to collect per node, and a cancellation token
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(UITree)` - UI tree snapshot
+    /// * `Err(UiError::Cancelled)` - If `options.cancel` was cancelled during the capture
+    /// * `Err(...)` - If the UI tree cannot be captured
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UITreeOptions;
+    /// 
+    /// // Full structure of a deep UI, skipping the bounds lookups
+    /// let options = UITreeOptions {
+    ///     max_depth: None,
+    ///     max_children_per_node: None,
+    ///     include_bounds: false,
+    ///     include_properties: false,
+    ///     max_nodes: Some(20_000),
+    ///     cancel: None,
+    /// };
+    /// let tree = window.get_ui_tree_with_options(&options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_ui_tree_with_options(&self, options: &UITreeOptions) -> Result<UITree, UiError>;
+    
+    /// Find UI elements matching a query
+    /// 
+    /// Searches for UI elements within this window that match the specified
+    /// query criteria. This is the primary method for locating specific
+    /// elements to interact with.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `query` - The search criteria specifying which elements to find
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Box<dyn UIElement>>)` - List of matching elements
+    /// * `Err(...)` - If the search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// // Find all buttons
+    /// let buttons = window.find_elements(&UIQuery::ByType("Button".to_string()))?;
+    /// 
+    /// // Find element by name
+    /// let save_button = window.find_elements(&UIQuery::ByName("Save".to_string()))?;
+    /// 
+    /// // Complex query - find enabled edit controls
+    /// let enabled_edits = window.find_elements(&UIQuery::And(vec![
+    ///     UIQuery::ByType("Edit".to_string()),
+    ///     UIQuery::ByProperty("IsEnabled".to_string(), "True".to_string())
+    /// ]))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, UiError>;
+
+    /// Find the first UI element matching a query
+    /// 
+    /// Like `find_elements`, but stops searching at the first match instead of
+    /// collecting every match in the window. Prefer this when only one element
+    /// is needed.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `query` - The search criteria specifying which element to find
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(Box<dyn UIElement>))` - The first matching element
+    /// * `Ok(None)` - If no element matches
+    /// * `Err(...)` - If the search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// if let Some(save) = window.find_first(&UIQuery::ByName("Save".to_string()))? {
+    ///     save.click()?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_first(&self, query: &UIQuery) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        Ok(self.find_elements(query)?.into_iter().next())
+    }
+
+    /// Run several labelled queries over a single walk of the window's descendants
+    /// 
+    /// Scripts that need a handful of named elements would otherwise walk the
+    /// tree once per `find_elements` call. Here each descendant is visited once
+    /// and every query is tested against it, sharing one property snapshot per
+    /// element, which matters on large UIs. Matches come back in document order.
+    /// 
+    /// Queries whose `find_elements` result depends on more than the element
+    /// itself (`ByText`, `Not` and the relational variants on Windows, which are
+    /// resolved relative to the window) are run through `find_elements`
+    /// separately, so they cost a walk of their own. Backends without a
+    /// single-pass implementation run every query separately.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `queries` - The queries to run, keyed by labels of the caller's choosing
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(HashMap)` - The matches of each query under its label, empty when nothing matched
+    /// * `Err(...)` - If the descendants cannot be enumerated
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use std::collections::HashMap;
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// let queries = HashMap::from([
+    ///     ("save".to_string(), UIQuery::ByName("Save".to_string())),
+    ///     ("cancel".to_string(), UIQuery::ByName("Cancel".to_string())),
+    /// ]);
+    /// let found = window.find_many(&queries)?;
+    /// if let Some(save) = found["save"].first() {
+    ///     save.click()?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_many(&self, queries: &HashMap<String, UIQuery>) -> Result<HashMap<String, Vec<Box<dyn UIElement>>>, UiError> {
+        queries.iter()
+            .map(|(label, query)| Ok((label.clone(), self.find_elements(query)?)))
+            .collect()
+    }
+
+    /// Find UI elements matching a query, along with each one's path from the window
+    /// 
+    /// Several elements often share a name, such as the "Delete" button of
+    /// every row in a list. The path tells them apart and can be stored to
+    /// find the same element again with [`Window::find_by_path`].
+    /// 
+    /// A path has one `ControlType[index]` segment per level below the
+    /// window, such as `["Pane[0]", "List[0]", "ListItem[2]", "Button[1]"]`.
+    /// The index is zero-based and counts only the siblings of the same
+    /// control type, so adding a control of another type next to an element
+    /// does not change its path. The window itself has the empty path.
+    /// 
+    /// Each element's own properties are tested, as with `UIQuery::matches`;
+    /// `Nth` and `Limit` apply to the matches in document order.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `query` - The search criteria specifying which elements to find
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<(Box<dyn UIElement>, Vec<String>)>)` - Matching elements and their paths, in document order
+    /// * `Err(UiError::Unsupported)` - If the backend cannot walk the window's tree
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// for (_, path) in window.find_elements_with_path(&UIQuery::ByName("Delete".to_string()))? {
+    ///     println!("{}", path.join("/"));
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn find_elements_with_path(&self, _query: &UIQuery) -> Result<Vec<(Box<dyn UIElement>, Vec<String>)>, UiError> {
+        Err(UiError::Unsupported("Element paths are not implemented for this platform".to_string()))
+    }
+
+    /// Find the element at a path returned by [`Window::find_elements_with_path`]
+    /// 
+    /// Each segment picks the child of the given control type and index, so
+    /// the lookup only reads the children along the path. The path goes stale
+    /// once controls of the same type are added or removed before the element.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - `ControlType[index]` segments from the window down; empty for the window itself
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(Box<dyn UIElement>))` - The element at the path
+    /// * `Ok(None)` - If some level has no such child
+    /// * `Err(UiError::InvalidArgument)` - If a segment is not of the form `ControlType[index]`
+    /// * `Err(UiError::Unsupported)` - If the backend cannot walk the window's tree
+    fn find_by_path(&self, _path: &[String]) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        Err(UiError::Unsupported("Element paths are not implemented for this platform".to_string()))
+    }
+
+    /// Fill several fields of the window in one go
+    /// 
+    /// Activates the window once, then for each pair sets the value of the
+    /// first element matching the query. The Value pattern is used when the
+    /// element has one, falling back to `set_text` otherwise. Each field gets
+    /// its own result, so a missing or read-only field does not stop the rest
+    /// of the form from being filled.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pairs` - The fields to fill, as (query, value) in filling order
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Result<(), UiError>>)` - One result per pair, in the same order;
+    ///   `ElementNotFound` for queries that matched nothing
+    /// * `Err(...)` - If the window cannot be activated
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// let results = window.fill_form(&[
+    ///     (UIQuery::ByAutomationId("username".to_string()), "alice".to_string()),
+    ///     (UIQuery::ByAutomationId("password".to_string()), "hunter2".to_string()),
+    /// ])?;
+    /// for result in results {
+    ///     result?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn fill_form(&self, pairs: &[(UIQuery, String)]) -> Result<Vec<Result<(), UiError>>, UiError> {
+        self.activate()?;
+        Ok(pairs.iter()
+            .map(|(query, value)| {
+                let element = self.find_first(query)?
+                    .ok_or_else(|| UiError::ElementNotFound(format!("No element matches {:?}", query)))?;
+                match element.set_value(value) {
+                    Err(UiError::PatternUnsupported(_) | UiError::Unsupported(_)) => element.set_text(value),
+                    result => result,
+                }
+            })
+            .collect())
+    }
+
+    /// Get the window's menu bar
+    /// 
+    /// Returns the first MenuBar control of the window, skipping the system
+    /// menu bar that classic windows keep in their title bar.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(Box<dyn UIElement>))` - The menu bar
+    /// * `Ok(None)` - If the window has no menu bar
+    /// * `Err(...)` - If the search fails
+    fn get_menu_bar(&self) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        for menu_bar in self.find_elements(&UIQuery::ByType("MenuBar".to_string()))? {
+            let in_title_bar = menu_bar.get_parent().ok().flatten()
+                .is_some_and(|parent| parent.get_type().is_ok_and(|control_type| control_type == "TitleBar"));
+            if !in_title_bar {
+                return Ok(Some(menu_bar));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Open a chain of menus and invoke the last item, such as `["File", "New", "Tab"]`
+    /// 
+    /// Each item but the last is opened with the ExpandCollapse pattern (or
+    /// Invoke, for menus that open that way) and the last one is invoked, so no
+    /// mouse input is synthesized and the window does not need to be in front.
+    /// Names must match exactly. Each item is looked for among the children of
+    /// the previous one and then in the open popup menus, waiting briefly for
+    /// a submenu to appear. If a step fails, the menus opened so far are
+    /// collapsed again.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Item names from the menu bar down to the item to invoke
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - The last item was invoked
+    /// * `Err(UiError::ElementNotFound)` - If the window has no menu bar, or an item
+    ///   is missing; the message names the step that failed
+    /// * `Err(UiError::InvalidArgument)` - If `path` is empty
+    /// * `Err(...)` - If an item cannot be opened or invoked
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// window.invoke_menu_path(&["File", "Save As..."])?;
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    fn get_window_by_process_name(&self, name: &str) -> Result<Box<dyn Window>, Box<dyn Error>>;
-} 
\ No newline at end of file
+    fn invoke_menu_path(&self, path: &[&str]) -> Result<(), UiError> {
+        let menu_bar = self.get_menu_bar()?
+            .ok_or_else(|| UiError::ElementNotFound("The window has no menu bar".to_string()))?;
+        walk_menu_path(menu_bar, path, |name| self.find_first(&menu_item_query(name)))
+    }
+
+    /// Find UI elements matching a query, polling until they appear
+    /// 
+    /// Many applications build their UI lazily, so a single `find_elements` call
+    /// right after activating a window can come back empty. This repeatedly runs
+    /// `find_elements` until at least one element matches or the timeout elapses.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `query` - The query to match elements against
+    /// * `timeout` - Maximum time to keep searching
+    /// * `poll_interval` - Delay between consecutive searches
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Box<dyn UIElement>>)` - Matching elements, or an empty list if none
+    ///   appeared before the timeout
+    /// * `Err(...)` - If a search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// window.activate()?;
+    /// let buttons = window.find_elements_wait(
+    ///     &UIQuery::ByName("Save".to_string()),
+    ///     Duration::from_secs(5),
+    ///     Duration::from_millis(200),
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_elements_wait(&self, query: &UIQuery, timeout: Duration, poll_interval: Duration) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        self.find_elements_wait_cancellable(query, timeout, poll_interval, &CancellationToken::new())
+    }
+
+    /// `find_elements_wait` that a caller on another thread can stop early
+    /// 
+    /// The token is checked before each search, so cancelling takes effect
+    /// within one `poll_interval` plus the search in progress.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Box<dyn UIElement>>)` - Matching elements, or an empty list if none
+    ///   appeared before the timeout
+    /// * `Err(UiError::Cancelled)` - If `cancel` was cancelled before anything matched
+    /// * `Err(...)` - If a search fails
+    fn find_elements_wait_cancellable(&self, query: &UIQuery, timeout: Duration, poll_interval: Duration, cancel: &CancellationToken) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        let start = Instant::now();
+        loop {
+            cancel.check("find_elements_wait")?;
+            let elements = self.find_elements(query)?;
+            if !elements.is_empty() {
+                return Ok(elements);
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Ok(Vec::new());
+            }
+            thread::sleep(poll_interval.min(timeout - elapsed));
+        }
+    }
+
+    /// Wait until the window's UI stops changing, using the default [`ReadinessOptions`]
+    /// 
+    /// See [`Window::wait_until_ready_with_options`] for the heuristic.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// 
+    /// window.activate()?;
+    /// window.wait_until_ready(Duration::from_secs(3))?;
+    /// let tree = window.get_ui_tree()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn wait_until_ready(&self, timeout: Duration) -> Result<(), UiError> {
+        self.wait_until_ready_with_options(timeout, &ReadinessOptions::default())
+    }
+
+    /// Wait until the window's UI stops changing
+    /// 
+    /// Applications keep building their UI for a moment after being launched or
+    /// activated. This samples the window every `poll_interval` and considers it
+    /// ready once `stable_samples` consecutive samples are identical. A sample
+    /// is the number of nodes in the top two levels of the UI tree together with
+    /// the runtime id of the focused element; values that cannot be read are
+    /// compared as missing.
+    /// 
+    /// The wait is best-effort: it returns `Ok(())` once the timeout elapses
+    /// even if the UI never settled, so callers can proceed as they would after
+    /// a fixed sleep.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `timeout` - Maximum time to wait
+    /// * `options` - Sampling interval and stabilization threshold
+    fn wait_until_ready_with_options(&self, timeout: Duration, options: &ReadinessOptions) -> Result<(), UiError> {
+        let start = Instant::now();
+        let mut tracker = StabilityTracker::new(options.stable_samples);
+        loop {
+            if tracker.observe(ReadinessSample::take(self)) {
+                debug!("Window UI settled after {:?}", start.elapsed());
+                return Ok(());
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                debug!("Window UI still changing after {:?}, continuing anyway", timeout);
+                return Ok(());
+            }
+            thread::sleep(options.poll_interval.min(timeout - elapsed));
+        }
+    }
+
+    /// Stream the text-containing elements of the window to a callback
+    ///
+    /// Walks the window's UI tree depth-first, in document order, and calls `f`
+    /// with each element that passes `options`. Returning `false` from `f`
+    /// stops the walk, so callers can look for the first match or cap memory
+    /// without materializing the whole tree as `get_text_elements` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Options controlling what elements to include
+    /// * `f` - Called for each element; return `false` to stop
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The walk finished or was stopped by `f`
+    /// * `Err(UiError::Cancelled)` - If `options.cancel` was cancelled during the walk
+    /// * `Err(...)` - If the walk could not be started
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let options = TextExtractionOptions::default();
+    /// let mut error = None;
+    /// window.for_each_text_element(&options, &mut |elem| {
+    ///     if elem.text.starts_with("Error:") {
+    ///         error = Some(elem.text.clone());
+    ///         return false;
+    ///     }
+    ///     true
+    /// })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn for_each_text_element(&self, options: &TextExtractionOptions, f: &mut dyn FnMut(&TextElementInfo) -> bool) -> Result<(), UiError>;
+
+    /// Get all text-containing elements in the window
+    ///
+    /// Scans the window's UI tree and returns structured information about all
+    /// elements that contain text. This is useful for extracting readable content
+    /// from applications. Built on [`Window::for_each_text_element`].
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Options controlling what elements to include
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<TextElementInfo>)` - List of text element information
+    /// * `Err(...)` - If elements cannot be retrieved
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let options = TextExtractionOptions::default();
+    /// let text_elements = window.get_text_elements(&options)?;
+    ///
+    /// for elem in text_elements {
+    ///     if elem.has_text() {
+    ///         println!("[{}] {}: '{}'", elem.control_type, elem.name, elem.text);
+    ///     }
+    /// }
+    /// ```
+    fn get_text_elements(&self, options: &TextExtractionOptions) -> Result<Vec<TextElementInfo>, UiError> {
+        let mut results = Vec::new();
+        self.for_each_text_element(options, &mut |info| {
+            results.push(info.clone());
+            true
+        })?;
+        if options.sort_by_position {
+            sort_by_reading_order(&mut results, options.row_tolerance);
+        }
+        Ok(results)
+    }
+
+    /// Get the selected text from the currently focused element in this window
+    ///
+    /// Retrieves any text that is currently selected (highlighted) within
+    /// the focused element of this window.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(SelectedTextInfo))` - Selected text information
+    /// * `Ok(None)` - No text is currently selected
+    /// * `Err(...)` - If selection cannot be retrieved
+    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, UiError> {
+        let focused = self.get_focused_element()?;
+        focused.get_selected_text()
+    }
+
+    /// Capture the pixels of the window as a PNG image
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` - PNG-encoded image bytes
+    /// * `Err(...)` - If the window has zero-area bounds, is entirely offscreen,
+    ///   or capturing is not supported on this platform
+    fn capture_image(&self) -> Result<Vec<u8>, UiError> {
+        Err(UiError::Unsupported("Screen capture is not available on this platform".to_string()))
+    }
+
+    /// Capture the window and save it as a PNG file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination file path
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// window.save_screenshot("window.png")?;
+    /// ```
+    fn save_screenshot(&self, path: &str) -> Result<(), UiError> {
+        std::fs::write(path, self.capture_image()?)?;
+        Ok(())
+    }
+}
+
+/// A flag for stopping a long-running operation from another thread
+///
+/// Clones share the flag: keep one, pass another in `UITreeOptions::cancel`,
+/// `TextExtractionOptions::cancel` or to `Window::find_elements_wait_cancellable`,
+/// and call `cancel` to stop the operation. The flag is checked between nodes
+/// and between polls, so the operation fails with `UiError::Cancelled` shortly
+/// after; a single platform call already in flight still runs to completion.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use ui_interaction::core::{CancellationToken, UITreeOptions};
+///
+/// let cancel = CancellationToken::new();
+/// let stop = cancel.clone();
+/// std::thread::spawn(move || {
+///     std::thread::sleep(Duration::from_secs(2));
+///     stop.cancel();
+/// });
+/// let options = UITreeOptions { cancel: Some(cancel), ..UITreeOptions::unlimited() };
+/// match window.get_ui_tree_with_options(&options) {
+///     Err(UiError::Cancelled(_)) => println!("Gave up on the capture"),
+///     result => println!("{} top-level nodes", result?.root.children.len()),
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every operation holding a clone of this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel` has been called on this token or one of its clones
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail with `UiError::Cancelled` if the token was cancelled, naming the interrupted operation
+    pub fn check(&self, operation: &str) -> Result<(), UiError> {
+        if self.is_cancelled() {
+            return Err(UiError::Cancelled(format!("{} was cancelled", operation)));
+        }
+        Ok(())
+    }
+}
+
+/// `CancellationToken::check` for an optional token
+pub(crate) fn check_cancelled(cancel: &Option<CancellationToken>, operation: &str) -> Result<(), UiError> {
+    cancel.as_ref().map_or(Ok(()), |cancel| cancel.check(operation))
+}
+
+/// Options controlling how much of the UI hierarchy `get_ui_tree_with_options` captures
+///
+/// Deep UIs such as IDEs and browsers can contain thousands of elements; the
+/// limits keep snapshots fast, and the `include_*` flags skip per-node lookups
+/// that are not needed when only the structure matters.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = UITreeOptions {
+///     max_depth: Some(10),
+///     max_children_per_node: Some(200),
+///     include_bounds: true,
+///     include_properties: false,
+///     max_nodes: Some(5_000),
+///     cancel: None,
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct UITreeOptions {
+    /// Maximum depth below the window to capture (None = unlimited)
+    pub max_depth: Option<u32>,
+    /// Maximum number of children captured for each node (None = unlimited)
+    pub max_children_per_node: Option<usize>,
+    /// Look up the bounding rectangle of every node
+    pub include_bounds: bool,
+    /// Collect the full property map of every node instead of just name and control type
+    pub include_properties: bool,
+    /// Maximum number of nodes captured in total (None = unlimited)
+    pub max_nodes: Option<usize>,
+    /// Stops the capture with `UiError::Cancelled` once cancelled
+    pub cancel: Option<CancellationToken>,
+}
+
+impl Default for UITreeOptions {
+    fn default() -> Self {
+        UITreeOptions {
+            max_depth: Some(3),
+            max_children_per_node: Some(50),
+            include_bounds: true,
+            include_properties: false,
+            max_nodes: Some(DEFAULT_MAX_NODES),
+            cancel: None,
+        }
+    }
+}
+
+impl UITreeOptions {
+    /// Create options that capture the whole tree without limits
+    pub fn unlimited() -> Self {
+        UITreeOptions {
+            max_depth: None,
+            max_children_per_node: None,
+            max_nodes: None,
+            ..Default::default()
+        }
+    }
+
+    /// Whether children of a node at `depth` should be captured
+    pub fn should_descend(&self, depth: u32) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth < max_depth)
+    }
+
+    /// Maximum number of children to capture per node
+    pub fn child_limit(&self) -> usize {
+        self.max_children_per_node.unwrap_or(usize::MAX)
+    }
+
+    /// Maximum number of nodes to capture in total
+    pub fn node_limit(&self) -> usize {
+        self.max_nodes.unwrap_or(usize::MAX)
+    }
+}
+
+/// Options for [`Window::wait_until_ready_with_options`]
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+///
+/// // Require three identical samples taken 250 ms apart
+/// let options = ReadinessOptions {
+///     poll_interval: Duration::from_millis(250),
+///     stable_samples: 3,
+/// };
+/// window.wait_until_ready_with_options(Duration::from_secs(5), &options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReadinessOptions {
+    /// Delay between consecutive samples
+    pub poll_interval: Duration,
+    /// Number of consecutive identical samples after which the UI counts as settled;
+    /// values below 2 are treated as 2
+    pub stable_samples: u32,
+}
+
+impl Default for ReadinessOptions {
+    fn default() -> Self {
+        ReadinessOptions {
+            poll_interval: Duration::from_millis(100),
+            stable_samples: 2,
+        }
+    }
+}
+
+/// Node budget for each readiness sample, so sampling a huge UI stays cheap
+const READINESS_MAX_NODES: usize = 500;
+
+/// What `wait_until_ready` compares between consecutive polls
+#[derive(Clone, Debug, PartialEq)]
+struct ReadinessSample {
+    node_count: Option<usize>,
+    focused_runtime_id: Option<Vec<i32>>,
+}
+
+impl ReadinessSample {
+    fn take<W: Window + ?Sized>(window: &W) -> Self {
+        let options = UITreeOptions {
+            max_depth: Some(2),
+            max_children_per_node: None,
+            include_bounds: false,
+            include_properties: false,
+            max_nodes: Some(READINESS_MAX_NODES),
+            cancel: None,
+        };
+        let node_count = window.get_ui_tree_with_options(&options).ok().map(|tree| {
+            let mut count = 0;
+            let mut stack = vec![&tree.root];
+            while let Some(node) = stack.pop() {
+                count += 1;
+                stack.extend(&node.children);
+            }
+            count
+        });
+        let focused_runtime_id = window.get_focused_element().ok()
+            .and_then(|element| element.runtime_id().ok());
+
+        ReadinessSample { node_count, focused_runtime_id }
+    }
+}
+
+/// Counts how many consecutive observations were identical
+struct StabilityTracker<T> {
+    last: Option<T>,
+    matching: u32,
+    required: u32,
+}
+
+impl<T: PartialEq> StabilityTracker<T> {
+    /// Track stability over `required` samples, at least two since a single sample shows no trend
+    fn new(required: u32) -> Self {
+        StabilityTracker { last: None, matching: 0, required: required.max(2) }
+    }
+
+    /// Record an observation; returns true once `required` identical ones were seen in a row
+    fn observe(&mut self, sample: T) -> bool {
+        if self.last.as_ref() == Some(&sample) {
+            self.matching += 1;
+        } else {
+            self.last = Some(sample);
+            self.matching = 1;
+        }
+        self.matching >= self.required
+    }
+}
+
+/// Options for text extraction from UI elements
+///
+/// Controls what elements are included when extracting text from a window.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = TextExtractionOptions {
+///     include_hidden: false,
+///     include_disabled: true,
+///     min_text_length: 1,
+///     control_types: Some(vec!["Text".to_string(), "Edit".to_string()]),
+///     max_depth: Some(10),
+///     include_names_as_text: true,
+///     sort_by_position: true,
+///     row_tolerance: 8,
+///     cancel: None,
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct TextExtractionOptions {
+    /// Include elements that are not visible on screen
+    pub include_hidden: bool,
+    /// Include elements that are disabled
+    pub include_disabled: bool,
+    /// Minimum text length to include (0 = include empty)
+    pub min_text_length: usize,
+    /// Only include specific control types (None = all)
+    pub control_types: Option<Vec<String>>,
+    /// Maximum depth in UI tree to traverse (None = unlimited)
+    pub max_depth: Option<u32>,
+    /// Include element names as text if actual text is empty
+    pub include_names_as_text: bool,
+    /// Return elements in reading order (top to bottom, then left to right)
+    /// instead of tree order; see [`sort_by_reading_order`]
+    pub sort_by_position: bool,
+    /// How far apart, in pixels, the tops of two elements can be for them to
+    /// count as one row when sorting by position
+    pub row_tolerance: i32,
+    /// Stops the walk with `UiError::Cancelled` once cancelled
+    pub cancel: Option<CancellationToken>,
+}
+
+/// Default `TextExtractionOptions::row_tolerance`, in pixels
+pub const DEFAULT_ROW_TOLERANCE: i32 = 8;
+
+/// Sort text elements into reading order: rows top to bottom, each row left to right
+///
+/// Elements are grouped into rows by the top of their bounds: an element
+/// starts a new row unless its top is within `row_tolerance` pixels of the
+/// top of the row's first element. This keeps labels and fields that are a
+/// few pixels out of line on the same row. Elements without bounds go last,
+/// in their original order, and the sort is stable throughout.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ui_interaction::core::{sort_by_reading_order, DEFAULT_ROW_TOLERANCE};
+///
+/// let mut elements = window.get_text_elements(&TextExtractionOptions::default())?;
+/// sort_by_reading_order(&mut elements, DEFAULT_ROW_TOLERANCE);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn sort_by_reading_order(elements: &mut Vec<TextElementInfo>, row_tolerance: i32) {
+    let (mut placed, unplaced): (Vec<TextElementInfo>, Vec<TextElementInfo>) = elements.drain(..)
+        .partition(|element| element.bounds.is_some());
+    let top = |element: &TextElementInfo| element.bounds.as_ref().map_or(0, |bounds| bounds.top);
+    let left = |element: &TextElementInfo| element.bounds.as_ref().map_or(0, |bounds| bounds.left);
+    placed.sort_by_key(top);
+
+    let mut rows: Vec<Vec<TextElementInfo>> = Vec::new();
+    let mut row_top = 0;
+    for element in placed {
+        match rows.last_mut() {
+            Some(row) if top(&element) - row_top <= row_tolerance => row.push(element),
+            _ => {
+                row_top = top(&element);
+                rows.push(vec![element]);
+            }
+        }
+    }
+
+    for mut row in rows {
+        row.sort_by_key(left);
+        elements.extend(row);
+    }
+    elements.extend(unplaced);
+}
+
+impl Default for TextExtractionOptions {
+    fn default() -> Self {
+        TextExtractionOptions {
+            include_hidden: false,
+            include_disabled: true,
+            min_text_length: 1,
+            control_types: None,
+            max_depth: Some(20),
+            include_names_as_text: true,
+            sort_by_position: false,
+            row_tolerance: DEFAULT_ROW_TOLERANCE,
+            cancel: None,
+        }
+    }
+}
+
+impl TextExtractionOptions {
+    /// Create options that extract all text elements
+    pub fn all() -> Self {
+        TextExtractionOptions {
+            include_hidden: true,
+            include_disabled: true,
+            min_text_length: 0,
+            control_types: None,
+            max_depth: None,
+            include_names_as_text: true,
+            sort_by_position: false,
+            row_tolerance: DEFAULT_ROW_TOLERANCE,
+            cancel: None,
+        }
+    }
+
+    /// Create options for extracting only visible, non-empty text
+    pub fn visible_text_only() -> Self {
+        TextExtractionOptions {
+            include_hidden: false,
+            include_disabled: true,
+            min_text_length: 1,
+            control_types: Some(vec![
+                "Text".to_string(),
+                "Edit".to_string(),
+                "Document".to_string(),
+            ]),
+            max_depth: Some(20),
+            include_names_as_text: false,
+            sort_by_position: false,
+            row_tolerance: DEFAULT_ROW_TOLERANCE,
+            cancel: None,
+        }
+    }
+
+    /// Create options for extracting editable text fields
+    pub fn editable_only() -> Self {
+        TextExtractionOptions {
+            include_hidden: false,
+            include_disabled: false,
+            min_text_length: 0,
+            control_types: Some(vec![
+                "Edit".to_string(),
+                "Document".to_string(),
+                "ComboBox".to_string(),
+            ]),
+            max_depth: Some(20),
+            include_names_as_text: false,
+            sort_by_position: false,
+            row_tolerance: DEFAULT_ROW_TOLERANCE,
+            cancel: None,
+        }
+    }
+
+    /// Whether elements at `depth` are within `max_depth` (the root is depth 0)
+    pub fn includes_depth(&self, depth: u32) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth <= max_depth)
+    }
+
+    /// Whether elements of `control_type` pass the `control_types` filter
+    pub fn accepts_control_type(&self, control_type: &str) -> bool {
+        self.control_types
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|t| t == control_type))
+    }
+
+    /// Whether `text` is long enough to be reported
+    ///
+    /// Length is counted in characters after trimming surrounding whitespace.
+    pub fn accepts_text(&self, text: &str) -> bool {
+        text.trim().chars().count() >= self.min_text_length
+    }
+}
+
+/// The element's property map in `properties`, fetching it on first use
+fn property_snapshot<'a>(element: &dyn UIElement, properties: &'a mut Option<HashMap<String, String>>) -> Result<&'a HashMap<String, String>, UiError> {
+    if properties.is_none() {
+        *properties = Some(element.get_properties()?);
+    }
+    Ok(properties.get_or_insert_with(HashMap::new))
+}
+
+/// Maximum number of levels `UIQuery::Ancestor` walks up before giving up
+pub(crate) const MAX_ANCESTOR_DEPTH: usize = 256;
+
+/// Number of elements a search or tree snapshot visits before giving up by default
+pub const DEFAULT_MAX_NODES: usize = 10_000;
+
+/// Number of characters `get_text_element_info` and text extraction read from one element
+pub const DEFAULT_TEXT_LIMIT: usize = 64 * 1024;
+
+/// Cut `text` after `max_chars` characters, reporting whether anything was removed
+pub(crate) fn truncate_chars(mut text: String, max_chars: usize) -> (String, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => {
+            text.truncate(end);
+            (text, true)
+        }
+        None => (text, false),
+    }
+}
+
+/// How `UIQuery::ByNameMatch` compares an element's name with its pattern
+/// 
+/// All modes except `CaseInsensitive` are case-sensitive. `CaseInsensitive`
+/// is a whole-name match that folds case with Unicode rules, so "RÉSUMÉ"
+/// matches "résumé".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameMatch {
+    /// The whole name equals the pattern, like `ByName`
+    Exact,
+    /// The pattern occurs anywhere in the name
+    Contains,
+    /// The name begins with the pattern
+    StartsWith,
+    /// The name ends with the pattern
+    EndsWith,
+    /// The whole name equals the pattern, ignoring case
+    CaseInsensitive,
+}
+
+impl NameMatch {
+    /// Check whether `name` matches `pattern` in this mode
+    pub fn matches(&self, pattern: &str, name: &str) -> bool {
+        match self {
+            NameMatch::Exact => name == pattern,
+            NameMatch::Contains => name.contains(pattern),
+            NameMatch::StartsWith => name.starts_with(pattern),
+            NameMatch::EndsWith => name.ends_with(pattern),
+            NameMatch::CaseInsensitive => name.to_lowercase() == pattern.to_lowercase(),
+        }
+    }
+}
+
+/// Query system for finding UI elements with various criteria
+/// 
+/// This enum provides a flexible query system for finding UI elements based on
+/// different criteria such as name, type, properties, or complex combinations.
+/// It supports logical operations and hierarchical relationships.
+/// 
+/// # Variants
+/// 
+/// * `ByName(String)` - Find elements with a specific accessible name
+/// * `ByType(String)` - Find elements of a specific control type
+/// * `ByTypes(Vec<String>)` - Find elements of any of several control types. Unlike `Or` of
+///   `ByType` queries this is a single search, so matches come in document order without duplicates
+/// * `ByProperty(String, String)` - Find elements with a specific property value
+/// * `ByAutomationId(String)` - Find elements with a specific automation ID (case-sensitive exact match)
+/// * `ByClassName(String)` - Find elements with a specific class name (case-sensitive exact match, like `ByType`)
+/// * `ByControlTypeId(i32)` - Find elements of the control type with this UI Automation identifier (see [`crate::control_type`])
+/// * `ByNameMatch(String, NameMatch)` - Find elements whose name matches the string in the given way,
+///   such as a substring or ignoring case (see [`NameMatch`])
+/// * `ByNameRegex(Regex)` - Find elements whose name matches a regular expression (see [`UIQuery::by_name_regex`])
+/// * `ByText(String)` - Find elements whose text content (see [`UIElement::get_text`]) contains the given
+///   substring, case-sensitively. Documents and read-only text often carry their content here rather
+///   than in the name. There is no native condition on text, so every text-bearing element is read:
+///   this is much slower than name or automation-id queries, and searches from a window only descend
+///   as deep as [`TextExtractionOptions::default`]'s `max_depth`
+/// * `And(Vec<UIQuery>)` - Find elements matching ALL of the given queries
+/// * `Or(Vec<UIQuery>)` - Find elements matching ANY of the given queries
+/// * `Not(Box<UIQuery>)` - Find elements NOT matching the given query
+/// * `Child(Box<UIQuery>)` - Find elements that have children matching the query
+/// * `Descendant(Box<UIQuery>)` - Find elements that have descendants matching the query
+/// * `Parent(Box<UIQuery>)` - Find elements whose parent matches the query
+/// * `Ancestor(Box<UIQuery>)` - Find elements with an ancestor matching the query
+/// * `Visible(Box<UIQuery>)` - Matches of the inner query that are on screen (see [`UIElement::is_offscreen`])
+/// * `Enabled(Box<UIQuery>)` - Matches of the inner query that are enabled (see [`UIElement::is_enabled`])
+/// * `Nth(Box<UIQuery>, usize)` - Only the match at a zero-based index among the inner query's matches
+/// * `Limit(Box<UIQuery>, usize)` - At most the first N matches of the inner query
+/// 
+/// # Ordering
+/// 
+/// Searches return matches in document order: a pre-order depth-first walk
+/// of the tree, visiting children in the order the tree walker reports them.
+/// `Nth` and `Limit` index into that order. `Or` concatenates the results of
+/// its queries in turn, so its matches are grouped by sub-query instead; use
+/// `ByTypes` rather than `Or` of `ByType` queries to keep document order.
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use ui_interaction::core::UIQuery;
+/// use ui_interaction::factory::UIAutomationFactory;
+/// 
+/// let automation = UIAutomationFactory::new()?;
+/// let window = automation.get_active_window()?;
+/// 
+/// // Simple queries
+/// let buttons = window.find_elements(&UIQuery::ByType("Button".to_string()))?;
+/// let save_btn = window.find_elements(&UIQuery::ByName("Save".to_string()))?;
+/// 
+/// // Complex query - find enabled buttons that contain "OK"
+/// let ok_buttons = window.find_elements(&UIQuery::And(vec![
+///     UIQuery::ByType("Button".to_string()),
+///     UIQuery::ByName("OK".to_string()),
+///     UIQuery::ByProperty("IsEnabled".to_string(), "True".to_string())
+/// ]))?;
+/// 
+/// // Find any text input control (Edit or Document)
+/// let text_inputs = window.find_elements(&UIQuery::ByTypes(vec![
+///     "Edit".to_string(),
+///     "Document".to_string()
+/// ]))?;
+/// 
+/// // Find elements that are NOT buttons
+/// let non_buttons = window.find_elements(&UIQuery::Not(
+///     Box::new(UIQuery::ByType("Button".to_string()))
+/// ))?;
+/// 
+/// // Find buttons that can be clicked right now
+/// let clickable = window.find_elements(&UIQuery::Visible(Box::new(
+///     UIQuery::Enabled(Box::new(UIQuery::ByType("Button".to_string())))
+/// )))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum UIQuery {
+    ByName(String),
+    ByType(String),
+    ByTypes(Vec<String>),
+    ByProperty(String, String),
+    ByAutomationId(String),
+    ByClassName(String),
+    ByControlTypeId(i32),
+    ByNameMatch(String, NameMatch),
+    ByNameRegex(Regex),
+    ByText(String),
+    And(Vec<UIQuery>),
+    Or(Vec<UIQuery>),
+    Not(Box<UIQuery>),
+    Child(Box<UIQuery>),
+    Descendant(Box<UIQuery>),
+    Parent(Box<UIQuery>),
+    Ancestor(Box<UIQuery>),
+    Visible(Box<UIQuery>),
+    Enabled(Box<UIQuery>),
+    Nth(Box<UIQuery>, usize),
+    Limit(Box<UIQuery>, usize),
+}
+
+impl UIQuery {
+    /// Create a query matching element names against a regular expression
+    /// 
+    /// The pattern is compiled once here. Like any regex it is unanchored, so
+    /// `"Notepad"` matches "Document1 — Notepad"; use `^...$` for a full match.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pattern` - Regular expression in `regex` crate syntax
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(UIQuery)` - A `ByNameRegex` query
+    /// * `Err(...)` - If the pattern is not a valid regular expression
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// let inbox = UIQuery::by_name_regex(r"^Inbox \(\d+\)$")?;
+    /// let folders = window.find_elements(&inbox)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn by_name_regex(pattern: &str) -> Result<UIQuery, UiError> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| UiError::InvalidArgument(format!("Invalid name pattern '{}': {}", pattern, e)))?;
+        Ok(UIQuery::ByNameRegex(regex))
+    }
+
+    /// Check if an element matches this query
+    /// 
+    /// Tests whether the given UI element satisfies the conditions of this query.
+    /// This method is used internally by the query system to filter elements.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `element` - The UI element to test against this query
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Element matches the query
+    /// * `Ok(false)` - Element does not match
+    /// * `Err(...)` - If the matching process fails
+    #[allow(dead_code)]
+    pub fn matches(&self, element: &dyn UIElement) -> Result<bool, UiError> {
+        self.matches_with_properties(element, &mut None)
+    }
+
+    /// Check if an element matches this query, sharing one property snapshot
+    /// 
+    /// `properties` holds the element's `get_properties` map. Pass `Some` to
+    /// reuse a snapshot fetched beforehand, or `None` to have it fetched the
+    /// first time a variant needs it; either way composite queries such as
+    /// `And` read the properties of `element` at most once. Related elements
+    /// reached through `Child`, `Parent` and the like get their own snapshot.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `element` - The UI element to test against this query
+    /// * `properties` - The element's property map, filled in on first use
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - Element matches the query
+    /// * `Ok(false)` - Element does not match
+    /// * `Err(...)` - If the matching process fails
+    pub fn matches_with_properties(&self, element: &dyn UIElement, properties: &mut Option<HashMap<String, String>>) -> Result<bool, UiError> {
+        match self {
+            UIQuery::ByName(name) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("name") == Some(name))
+            }
+            UIQuery::ByType(control_type) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("control_type") == Some(control_type))
+            }
+            UIQuery::ByTypes(control_types) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("control_type").is_some_and(|t| control_types.contains(t)))
+            }
+            UIQuery::ByProperty(key, value) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get(key) == Some(value))
+            }
+            UIQuery::ByAutomationId(automation_id) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("automation_id") == Some(automation_id))
+            }
+            UIQuery::ByClassName(class_name) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("class_name") == Some(class_name))
+            }
+            UIQuery::ByControlTypeId(control_type_id) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("control_type")
+                    .and_then(|t| control_type::control_type_id(t)) == Some(*control_type_id))
+            }
+            UIQuery::ByNameMatch(pattern, mode) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("name").is_some_and(|n| mode.matches(pattern, n)))
+            }
+            UIQuery::ByNameRegex(pattern) => {
+                let props = property_snapshot(element, properties)?;
+                Ok(props.get("name").is_some_and(|n| pattern.is_match(n)))
+            }
+            UIQuery::ByText(text) => {
+                // Most elements carry no text at all, which is simply not a match
+                Ok(element.get_text().is_ok_and(|content| content.contains(text.as_str())))
+            }
+            UIQuery::And(queries) => {
+                for query in queries {
+                    if !query.matches_with_properties(element, properties)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            UIQuery::Or(queries) => {
+                for query in queries {
+                    if query.matches_with_properties(element, properties)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            UIQuery::Not(query) => {
+                Ok(!query.matches_with_properties(element, properties)?)
+            }
+            UIQuery::Child(query) => {
+                let children = element.get_children()?;
+                for child in children {
+                    if query.matches(child.as_ref())? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            UIQuery::Descendant(query) => {
+                let mut found = false;
+                let truncated = walk_bounded(element, DEFAULT_MAX_NODES, false, |descendant| {
+                    found = query.matches(descendant)?;
+                    Ok(!found)
+                })?;
+                if truncated && !found {
+                    debug!("UIQuery::Descendant - gave up after visiting {} nodes", DEFAULT_MAX_NODES);
+                }
+                Ok(found)
+            }
+            UIQuery::Parent(query) => {
+                match element.get_parent()? {
+                    Some(parent) => query.matches(parent.as_ref()),
+                    None => Ok(false),
+                }
+            },
+            UIQuery::Ancestor(query) => {
+                // Walk up until a match or the root; the depth cap guards against
+                // platforms that report an element as its own parent
+                let mut current = element.get_parent()?;
+                let mut depth = 0;
+                while let Some(ancestor) = current {
+                    if depth >= MAX_ANCESTOR_DEPTH {
+                        return Ok(false);
+                    }
+                    if query.matches(ancestor.as_ref())? {
+                        return Ok(true);
+                    }
+                    current = ancestor.get_parent()?;
+                    depth += 1;
+                }
+                Ok(false)
+            }
+            // The flag is a single property read, so it is checked before the inner query
+            UIQuery::Visible(query) => {
+                Ok(!element.is_offscreen()? && query.matches_with_properties(element, properties)?)
+            }
+            UIQuery::Enabled(query) => {
+                Ok(element.is_enabled()? && query.matches_with_properties(element, properties)?)
+            }
+            // Position only has meaning within a search, so a single element
+            // is tested against the inner query alone
+            UIQuery::Nth(query, _) | UIQuery::Limit(query, _) => query.matches_with_properties(element, properties),
+        }
+    }
+
+    /// Find the first element matching this query in a tree rooted at the given element
+    /// 
+    /// Performs the same depth-first, pre-order search as [`UIQuery::find_all`]
+    /// but returns as soon as an element matches, so the rest of the tree is
+    /// never visited. Elements whose children cannot be read are treated as leaves.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `root` - The root element to start the search from
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Some(Box<dyn UIElement>))` - The first matching element
+    /// * `Ok(None)` - If nothing in the subtree matches
+    /// * `Err(...)` - If matching an element fails
+    pub fn find_first(&self, root: &dyn UIElement) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        match self {
+            UIQuery::Nth(_, _) => return Ok(self.find_all(root)?.into_iter().next()),
+            UIQuery::Limit(_, 0) => return Ok(None),
+            UIQuery::Limit(query, _) => return query.find_first(root),
+            _ => {}
+        }
+
+        let mut found = None;
+        walk_bounded(root, DEFAULT_MAX_NODES, true, |element| {
+            if self.matches(element)? {
+                found = Some(element.to_tree_node()?);
+                return Ok(false);
+            }
+            Ok(true)
+        })?;
+        Ok(found)
+    }
+
+    /// Find all elements matching this query in a tree rooted at the given element
+    /// 
+    /// Searches the UI element tree starting from the root element, depth-first and
+    /// in pre-order, and returns all elements that match this query. At most
+    /// [`DEFAULT_MAX_NODES`] elements are visited; use [`UIQuery::find_all_bounded`]
+    /// to choose the budget and to learn whether the search was cut short.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `root` - The root element to start the search from
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Box<dyn UIElement>>)` - List of all matching elements
+    /// * `Err(...)` - If the search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UIQuery;
+    /// use ui_interaction::factory::UIAutomationFactory;
+    /// 
+    /// let automation = UIAutomationFactory::new()?;
+    /// let window = automation.get_active_window()?;
+    /// let root_element = window.get_focused_element()?;
+    /// 
+    /// // Find all buttons in the subtree
+    /// let query = UIQuery::ByType("Button".to_string());
+    /// let buttons = query.find_all(root_element.as_ref())?;
+    /// println!("Found {} buttons", buttons.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[allow(dead_code)]
+    pub fn find_all(&self, root: &dyn UIElement) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        let result = self.find_all_bounded(root, DEFAULT_MAX_NODES)?;
+        if result.truncated {
+            warn!("UIQuery::find_all - stopped after visiting {} nodes; results are partial", DEFAULT_MAX_NODES);
+        }
+        Ok(result.elements)
+    }
+
+    /// Find all elements matching this query, visiting at most `max_nodes` elements
+    /// 
+    /// The walk uses an explicit work stack rather than recursion, so very deep
+    /// trees cannot overflow the call stack. When the budget runs out the matches
+    /// found so far are returned with `truncated` set. Elements whose children
+    /// cannot be read are treated as leaves.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `root` - The root element to start the search from
+    /// * `max_nodes` - Maximum number of elements to visit, including the root
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(SearchResult)` - The matching elements and whether the budget was exhausted
+    /// * `Err(...)` - If matching an element fails
+    pub fn find_all_bounded(&self, root: &dyn UIElement, max_nodes: usize) -> Result<SearchResult, UiError> {
+        match self {
+            UIQuery::Nth(query, index) => {
+                let result = query.find_all_bounded(root, max_nodes)?;
+                return Ok(SearchResult {
+                    elements: result.elements.into_iter().nth(*index).into_iter().collect(),
+                    truncated: result.truncated,
+                });
+            }
+            UIQuery::Limit(query, limit) => {
+                let result = query.find_all_bounded(root, max_nodes)?;
+                return Ok(SearchResult {
+                    elements: result.elements.into_iter().take(*limit).collect(),
+                    truncated: result.truncated,
+                });
+            }
+            _ => {}
+        }
+
+        let mut elements = Vec::new();
+        let truncated = walk_bounded(root, max_nodes, true, |element| {
+            if self.matches(element)? {
+                elements.push(element.to_tree_node()?);
+            }
+            Ok(true)
+        })?;
+        Ok(SearchResult { elements, truncated })
+    }
+}
+
+/// Matches from a node-capped search, see [`UIQuery::find_all_bounded`]
+pub struct SearchResult {
+    /// Matching elements in pre-order
+    pub elements: Vec<Box<dyn UIElement>>,
+    /// Whether the node budget ran out before the whole tree was visited
+    pub truncated: bool,
+}
+
+/// Visit `root`'s subtree depth-first, in pre-order, without recursing
+/// 
+/// `visit` returns `Ok(false)` to stop early. At most `max_nodes` elements are
+/// visited (the root counts only when `include_root` is set); the return value
+/// tells whether the walk stopped because that budget ran out.
+fn walk_bounded<F>(root: &dyn UIElement, max_nodes: usize, include_root: bool, mut visit: F) -> Result<bool, UiError>
+where
+    F: FnMut(&dyn UIElement) -> Result<bool, UiError>,
+{
+    let mut budget = max_nodes;
+    if include_root {
+        if max_nodes == 0 {
+            return Ok(true);
+        }
+        budget -= 1;
+        if !visit(root)? {
+            return Ok(false);
+        }
+    }
+
+    walk_nodes_bounded(
+        readable_children(root),
+        budget,
+        |element| readable_children(element.as_ref()),
+        |element| visit(element.as_ref()),
+    )
+}
+
+/// Visit the subtrees of `roots` depth-first, in pre-order, without recursing
+/// 
+/// The walk behind [`walk_bounded`], for nodes that carry more than the
+/// element itself. `children_of` lists a node's children and `visit` returns
+/// `Ok(false)` to stop early. At most `max_nodes` nodes are visited; the
+/// return value tells whether the walk stopped because that budget ran out.
+pub(crate) fn walk_nodes_bounded<T, C, F>(roots: Vec<T>, max_nodes: usize, mut children_of: C, mut visit: F) -> Result<bool, UiError>
+where
+    C: FnMut(&T) -> Vec<T>,
+    F: FnMut(&T) -> Result<bool, UiError>,
+{
+    let mut stack = roots;
+    stack.reverse();
+    let mut visited = 0;
+    while let Some(node) = stack.pop() {
+        if visited >= max_nodes {
+            return Ok(true);
+        }
+        visited += 1;
+        if !visit(&node)? {
+            return Ok(false);
+        }
+        stack.extend(children_of(&node).into_iter().rev());
+    }
+    Ok(false)
+}
+
+/// Visit the descendants of `root` depth-first, in pre-order, for backends that test elements one at a time
+/// 
+/// `children_of` lists an element's children (none when they cannot be
+/// read) and `visit` returns `false` to stop early. At most
+/// [`DEFAULT_MAX_NODES`] elements are visited, so a huge or cyclic tree
+/// cannot stall the caller.
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+pub(crate) fn for_each_descendant<E, C, F>(root: &E, mut children_of: C, mut visit: F)
+where
+    C: FnMut(&E) -> Vec<E>,
+    F: FnMut(&E) -> bool,
+{
+    let roots = children_of(root);
+    if let Ok(true) = walk_nodes_bounded(roots, DEFAULT_MAX_NODES, children_of, |element| Ok(visit(element))) {
+        warn!("Stopped after visiting {} descendants; results are partial", DEFAULT_MAX_NODES);
+    }
+}
+
+/// The descendants of `root` matching `query`, in document order, for `Window::find_elements`
+/// 
+/// Walks like [`for_each_descendant`] and tests each element with
+/// `UIQuery::matches`; `Nth` and `Limit` are applied once the walk is over.
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+pub(crate) fn find_descendants<E, C>(root: &E, children_of: C, query: &UIQuery) -> Vec<Box<dyn UIElement>>
+where
+    E: UIElement,
+    C: FnMut(&E) -> Vec<E>,
+{
+    let base = without_position(query);
+    let mut matches = Vec::new();
+    for_each_descendant(root, children_of, |element| {
+        if base.matches(element).unwrap_or(false) {
+            if let Ok(node) = element.to_tree_node() {
+                matches.push(node);
+            }
+        }
+        true
+    });
+    apply_position(query, matches)
+}
+
+/// The children of `element`, or none when they cannot be read
+/// 
+/// Some platforms report a leaf with a generic error, so only failures that
+/// mean the subtree itself could not be read are logged.
+fn readable_children(element: &dyn UIElement) -> Vec<Box<dyn UIElement>> {
+    match element.get_children() {
+        Ok(children) => children,
+        Err(UiError::PlatformError(_)) => Vec::new(),
+        Err(e) => {
+            warn!("Skipping a subtree whose children could not be read: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Test labelled queries against every element `walk` visits, for `Window::find_many`
+/// 
+/// `walk` calls the visitor once per element, in document order, with the
+/// element's `get_properties` map when it was fetched in bulk. `Nth` and
+/// `Limit` are applied to each query's matches once the walk is over. An
+/// element that cannot be read is not a match, as in the backends' own
+/// descendant filtering.
+pub(crate) fn find_many_in_one_pass<W>(queries: &[(&str, &UIQuery)], walk: W) -> HashMap<String, Vec<Box<dyn UIElement>>>
+where
+    W: FnOnce(&mut dyn FnMut(&dyn UIElement, Option<HashMap<String, String>>)),
+{
+    let bases: Vec<&UIQuery> = queries.iter().map(|(_, query)| without_position(query)).collect();
+    let mut found: Vec<Vec<Box<dyn UIElement>>> = queries.iter().map(|_| Vec::new()).collect();
+
+    walk(&mut |element: &dyn UIElement, mut properties: Option<HashMap<String, String>>| {
+        for (query, matches) in bases.iter().zip(found.iter_mut()) {
+            if query.matches_with_properties(element, &mut properties).unwrap_or(false) {
+                if let Ok(node) = element.to_tree_node() {
+                    matches.push(node);
+                }
+            }
+        }
+    });
+
+    queries.iter()
+        .zip(found)
+        .map(|((label, query), matches)| (label.to_string(), apply_position(query, matches)))
+        .collect()
+}
+
+/// The query under any `Nth` and `Limit` wrappers
+fn without_position(query: &UIQuery) -> &UIQuery {
+    match query {
+        UIQuery::Nth(query, _) | UIQuery::Limit(query, _) => without_position(query),
+        query => query,
+    }
+}
+
+/// Apply the `Nth` and `Limit` wrappers of `query` to matches of the query under them, innermost first
+fn apply_position<T>(query: &UIQuery, matches: Vec<T>) -> Vec<T> {
+    match query {
+        UIQuery::Nth(inner, index) => apply_position(inner, matches).into_iter().nth(*index).into_iter().collect(),
+        UIQuery::Limit(inner, limit) => apply_position(inner, matches).into_iter().take(*limit).collect(),
+        _ => matches,
+    }
+}
+
+/// The control type of an element as used in path segments
+fn path_control_type(element: &dyn UIElement) -> String {
+    element.get_type().unwrap_or_else(|_| "Unknown".to_string())
+}
+
+/// Walk `root`'s subtree for `Window::find_elements_with_path`, building each element's path as it goes
+/// 
+/// `properties_of` returns an element's `get_properties` map when the backend
+/// can fetch it in bulk, or `None` to read the control type and properties on
+/// demand. At most [`DEFAULT_MAX_NODES`] elements are visited.
+pub(crate) fn find_with_paths<P>(root: &dyn UIElement, query: &UIQuery, mut properties_of: P) -> Vec<(Box<dyn UIElement>, Vec<String>)>
+where
+    P: FnMut(&dyn UIElement) -> Option<HashMap<String, String>>,
+{
+    type Node = (Box<dyn UIElement>, Vec<String>, Option<HashMap<String, String>>);
+
+    let base = without_position(query);
+    let mut children_of = |parent: &dyn UIElement, path: &[String]| -> Vec<Node> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        readable_children(parent).into_iter()
+            .map(|child| {
+                let properties = properties_of(child.as_ref());
+                let control_type = properties.as_ref()
+                    .and_then(|properties| properties.get("control_type").cloned())
+                    .unwrap_or_else(|| path_control_type(child.as_ref()));
+                let index = counts.entry(control_type.clone()).or_insert(0);
+                let mut child_path = path.to_vec();
+                child_path.push(format!("{}[{}]", control_type, index));
+                *index += 1;
+                (child, child_path, properties)
+            })
+            .collect()
+    };
+
+    let mut matches = Vec::new();
+    let roots = children_of(root, &[]);
+    let truncated = walk_nodes_bounded(
+        roots,
+        DEFAULT_MAX_NODES,
+        |(element, path, _)| children_of(element.as_ref(), path),
+        |(element, path, properties)| {
+            let mut properties = properties.clone();
+            if base.matches_with_properties(element.as_ref(), &mut properties).unwrap_or(false) {
+                matches.push((element.to_tree_node()?, path.clone()));
+            }
+            Ok(true)
+        },
+    );
+    match truncated {
+        Ok(true) => warn!("find_with_paths - stopped after visiting {} nodes; results are partial", DEFAULT_MAX_NODES),
+        Ok(false) => {}
+        Err(e) => warn!("find_with_paths - walk failed, results are partial: {}", e),
+    }
+    apply_position(query, matches)
+}
+
+/// Follow `path` down from `root`, for `Window::find_by_path`
+pub(crate) fn resolve_path(root: Box<dyn UIElement>, path: &[String]) -> Result<Option<Box<dyn UIElement>>, UiError> {
+    let mut current = root;
+    for segment in path {
+        let (control_type, index) = segment.strip_suffix(']')
+            .and_then(|segment| segment.rsplit_once('['))
+            .and_then(|(control_type, index)| Some((control_type, index.parse::<usize>().ok()?)))
+            .ok_or_else(|| UiError::InvalidArgument(format!("Malformed path segment '{}', expected ControlType[index]", segment)))?;
+
+        let child = current.get_children()?.into_iter()
+            .filter(|child| path_control_type(child.as_ref()) == control_type)
+            .nth(index);
+        match child {
+            Some(child) => current = child,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current))
+}
+
+/// How long `walk_menu_path` waits for the items of a submenu to appear
+const MENU_OPEN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A menu item with exactly this name
+pub(crate) fn menu_item_query(name: &str) -> UIQuery {
+    UIQuery::And(vec![UIQuery::ByType("MenuItem".to_string()), UIQuery::ByName(name.to_string())])
+}
+
+/// Open the items of `path` one by one from `menu_bar` and invoke the last, for `Window::invoke_menu_path`
+/// 
+/// An item is looked for among the children of the previous one, then with
+/// `find_elsewhere`, which covers submenus that open as separate popups.
+pub(crate) fn walk_menu_path<F>(menu_bar: Box<dyn UIElement>, path: &[&str], mut find_elsewhere: F) -> Result<(), UiError>
+where
+    F: FnMut(&str) -> Result<Option<Box<dyn UIElement>>, UiError>,
+{
+    if path.is_empty() {
+        return Err(UiError::InvalidArgument("The menu path is empty".to_string()));
+    }
+
+    let mut opened: Vec<Box<dyn UIElement>> = Vec::new();
+    let result = (|| {
+        let mut parent = menu_bar;
+        for (index, &name) in path.iter().enumerate() {
+            let start = Instant::now();
+            let item = loop {
+                let child = parent.get_children().unwrap_or_default().into_iter()
+                    .find(|child| menu_item_query(name).matches(child.as_ref()).unwrap_or(false));
+                if let Some(item) = child.map_or_else(|| find_elsewhere(name), |child| Ok(Some(child)))? {
+                    break item;
+                }
+                if start.elapsed() >= MENU_OPEN_TIMEOUT {
+                    return Err(UiError::ElementNotFound(format!(
+                        "Menu path {:?} failed at step {} of {}: no menu item named '{}'",
+                        path, index + 1, path.len(), name
+                    )));
+                }
+                thread::sleep(CLOSE_POLL_INTERVAL);
+            };
+
+            if index + 1 == path.len() {
+                return item.invoke();
+            }
+            match item.expand() {
+                Err(UiError::PatternUnsupported(_) | UiError::Unsupported(_)) => item.invoke()?,
+                result => result?,
+            }
+            parent = item.to_tree_node()?;
+            opened.push(item);
+        }
+        Ok(())
+    })();
+
+    if result.is_err() {
+        for item in opened.iter().rev() {
+            let _ = item.collapse();
+        }
+    }
+    result
+}
+
+/// Build a `UITreeNode` hierarchy without recursion, honouring every limit in `options`
+/// 
+/// `describe` fills in a node without its children and `children_of` lists at
+/// most the given number of children of an element. Nodes are captured in
+/// pre-order into a flat arena and assembled bottom-up afterwards, so the
+/// depth of the UI never reaches the call stack. The returned flag is set when
+/// `options.max_nodes` stopped the capture early; `options.cancel` is checked
+/// before each node.
+pub(crate) fn build_tree_bounded<E, D, C>(root: E, options: &UITreeOptions, mut describe: D, mut children_of: C) -> Result<(UITreeNode, bool), UiError>
+where
+    D: FnMut(&E) -> UITreeNode,
+    C: FnMut(&E, usize) -> Vec<E>,
+{
+    // The root is always captured, whatever the budget
+    let node_limit = options.node_limit().max(1);
+    let mut arena: Vec<(UITreeNode, Option<usize>)> = Vec::new();
+    let mut stack = vec![(root, 0u32, None)];
+    let mut truncated = false;
+
+    while let Some((element, depth, parent)) = stack.pop() {
+        check_cancelled(&options.cancel, "UI tree capture")?;
+        if arena.len() >= node_limit {
+            truncated = true;
+            break;
+        }
+        let index = arena.len();
+        arena.push((describe(&element), parent));
+        if options.should_descend(depth) {
+            let children = children_of(&element, options.child_limit());
+            stack.extend(children.into_iter().rev().map(|child| (child, depth + 1, Some(index))));
+        }
+    }
+
+    // Children always sit after their parent in the arena, so draining it from
+    // the back completes every node before it is attached
+    let mut root_node = None;
+    while let Some((mut node, parent)) = arena.pop() {
+        node.children.reverse();
+        match parent {
+            Some(parent) => arena[parent].0.children.push(node),
+            None => root_node = Some(node),
+        }
+    }
+    Ok((root_node.expect("the root node is always captured"), truncated))
+}
+
+/// Describe an element as a childless tree node through the portable `UIElement` API
+/// 
+/// Elements without on-screen bounds are reported as not visible.
+pub(crate) fn describe_element(element: &dyn UIElement) -> UITreeNode {
+    let name = element.get_name().unwrap_or_default();
+    let control_type = element.get_type().unwrap_or_else(|_| "Unknown".to_string());
+    let bounds = element.get_bounds().ok().flatten();
+
+    let mut properties = HashMap::new();
+    properties.insert("name".to_string(), name.clone());
+    properties.insert("control_type".to_string(), control_type.clone());
+
+    UITreeNode {
+        name,
+        control_type,
+        properties,
+        children: Vec::new(),
+        is_visible: bounds.as_ref().is_some_and(|b| b.width() > 0 && b.height() > 0),
+        bounds,
+        is_enabled: element.is_enabled().unwrap_or(false),
+    }
+}
+
+/// Describe an element as a childless node for `Window::get_ui_tree_with_options`
+/// 
+/// Like [`describe_element`], but reads properties and bounds only when
+/// `options` asks for them. Platforms report visibility differently, so the
+/// backend passes `is_visible` in.
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+pub(crate) fn describe_tree_node(element: &dyn UIElement, options: &UITreeOptions, is_visible: bool) -> UITreeNode {
+    let name = element.get_name().unwrap_or_default();
+    let control_type = element.get_type().unwrap_or_else(|_| "Unknown".to_string());
+
+    let mut properties = if options.include_properties {
+        element.get_properties().unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
+    properties.insert("name".to_string(), name.clone());
+    properties.insert("control_type".to_string(), control_type.clone());
+
+    UITreeNode {
+        name,
+        control_type,
+        properties,
+        children: Vec::new(),
+        bounds: if options.include_bounds { element.get_bounds().ok().flatten() } else { None },
+        is_enabled: element.is_enabled().unwrap_or(false),
+        is_visible,
+    }
+}
+
+/// Walk the subtrees of `children` in pre-order, passing the text elements that satisfy `options` to `visit`
+/// 
+/// `describe` reads an element's text info (`None` skips the element and its
+/// subtree) and `children_of` lists its children. `children` sit at depth 1
+/// below a root named `root_name`. Elements rejected by the filters are not
+/// reported, but their children are still visited since they may qualify on
+/// their own. Returns `false` when `visit` stopped the walk, and fails once
+/// `options.cancel` is cancelled.
+pub(crate) fn visit_text_elements<E, D, C>(
+    children: Vec<E>,
+    root_name: Option<String>,
+    options: &TextExtractionOptions,
+    mut describe: D,
+    mut children_of: C,
+    visit: &mut dyn FnMut(&TextElementInfo) -> bool,
+) -> Result<bool, UiError>
+where
+    D: FnMut(&E) -> Option<TextElementInfo>,
+    C: FnMut(&E) -> Vec<E>,
+{
+    let mut stack: Vec<(E, u32, Option<String>)> = children.into_iter()
+        .rev()
+        .map(|child| (child, 1, root_name.clone()))
+        .collect();
+
+    while let Some((element, depth, parent_name)) = stack.pop() {
+        check_cancelled(&options.cancel, "Text extraction")?;
+        if !options.includes_depth(depth) {
+            continue;
+        }
+
+        let mut info = match describe(&element) {
+            Some(info) => info,
+            None => continue,
+        };
+        if options.includes_depth(depth + 1) {
+            let name = Some(info.name.clone());
+            stack.extend(children_of(&element).into_iter().rev().map(|child| (child, depth + 1, name.clone())));
+        }
+        if !options.accepts_control_type(&info.control_type) {
+            continue;
+        }
+
+        info.depth = depth;
+        info.parent_name = parent_name;
+        if info.text.is_empty() && options.include_names_as_text {
+            info.text = info.name.clone();
+        }
+
+        if (options.include_hidden || info.is_visible)
+            && (options.include_disabled || info.is_enabled)
+            && options.accepts_text(&info.text)
+            && !visit(&info)
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The fraction of `bounds` that lies inside `viewport`, 0.0 for empty bounds
+fn fraction_inside(bounds: &Rect, viewport: &Rect) -> f64 {
+    let area = bounds.area();
+    if area == 0 {
+        return 0.0;
+    }
+    let inside = bounds.intersection(viewport).map_or(0, |overlap| overlap.area());
+    inside as f64 / area as f64
+}
+
+/// Check whether two element handles refer to the same on-screen control
+/// 
+/// Elements are compared by runtime ID. The platform may reuse a runtime ID
+/// once its element is destroyed, so the result is only meaningful for live
+/// elements captured close together in time.
+/// 
+/// # Returns
+/// 
+/// * `Ok(true)` - Both handles refer to the same element
+/// * `Ok(false)` - They refer to different elements
+/// * `Err(...)` - If either element has no runtime ID
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use ui_interaction::core::same_element;
+/// 
+/// let focused = window.get_focused_element()?;
+/// if same_element(focused.as_ref(), clicked.as_ref())? {
+///     println!("Focus stayed on the clicked element");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn same_element(a: &dyn UIElement, b: &dyn UIElement) -> Result<bool, UiError> {
+    Ok(a.runtime_id()? == b.runtime_id()?)
+}
+
+/// Check whether two window handles refer to the same native window
+/// 
+/// Windows are compared by [`Window::window_id`], with the same caveat about
+/// ids being reused once a window is destroyed.
+/// 
+/// # Returns
+/// 
+/// * `Ok(true)` - Both handles refer to the same window
+/// * `Ok(false)` - They refer to different windows
+/// * `Err(...)` - If either window has no id
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use ui_interaction::core::same_window;
+/// 
+/// let active = automation.get_active_window()?;
+/// if same_window(active.as_ref(), editor.as_ref())? {
+///     println!("The editor still has focus");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn same_window(a: &dyn Window, b: &dyn Window) -> Result<bool, UiError> {
+    Ok(a.window_id()? == b.window_id()?)
+}
+
+/// Number of attempts `find_elements` and `get_children` make before giving up on transient failures
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+
+/// Delay between those attempts
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// How often `Window::wait_for_close` checks whether the window is gone
+pub(crate) const CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Run `f`, repeating it after `delay` while it fails with a transient error
+/// 
+/// Only errors for which [`UiError::is_retryable`] holds are retried, that is
+/// `ElementNotFound`, which UI Automation reports when the UI changes while a
+/// call is in flight (`UIA_E_ELEMENTNOTAVAILABLE`). A `Timeout` from a busy
+/// application (`UIA_E_TIMEOUT`, `RPC_E_CALL_REJECTED`) has already waited and
+/// is returned at once, as are unsupported patterns, invalid arguments, access
+/// denied and other platform errors, since repeating the call cannot fix them.
+/// 
+/// Do not wrap calls for which "not found" is an expected answer, such as
+/// waiting for an element to appear: the retries would only delay it.
+/// 
+/// # Arguments
+/// 
+/// * `attempts` - Total number of calls, at least one is always made
+/// * `delay` - Pause between calls
+/// * `f` - The operation to run
+/// 
+/// # Returns
+/// 
+/// The first success, the first non-retryable error, or the error of the last attempt
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use ui_interaction::core::with_retry;
+/// 
+/// let items = with_retry(5, Duration::from_millis(100), || list.get_children())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn with_retry<T>(attempts: u32, delay: Duration, mut f: impl FnMut() -> Result<T, UiError>) -> Result<T, UiError> {
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Err(e) if e.is_retryable() && attempt < attempts => {
+                debug!("with_retry - attempt {} of {} failed, retrying: {}", attempt, attempts, e);
+                attempt += 1;
+                thread::sleep(delay);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Run `f` on a worker thread, giving up on it after `timeout`
+/// 
+/// Use this to bound calls that may hang inside a misbehaving application,
+/// such as `find_elements` or `get_text` on an unresponsive window. If `f`
+/// does not finish in time the caller gets `UiError::Timeout` at once and the
+/// worker is abandoned: it keeps waiting on the stuck call, then drops the
+/// result, leaves its COM apartment and exits. Nothing it holds outlives the
+/// call that hung.
+/// 
+/// `f` must own everything it uses, so it suits operations that start from
+/// `Send` data. On Windows, prefer [`UIAutomation::set_call_timeout`] or
+/// [`with_call_timeout`], which make UI Automation itself fail the call
+/// without an extra thread.
+/// 
+/// # Returns
+/// 
+/// * `Ok(T)` - The value returned by `f` in time
+/// * `Err(UiError::Timeout)` - If `f` is still running after `timeout`
+/// * `Err(...)` - The error returned by `f`, or `PlatformError` if the worker
+///   could not be started or panicked
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use ui_interaction::core::run_with_timeout;
+/// use ui_interaction::factory::UIAutomationFactory;
+/// 
+/// let title = run_with_timeout(Duration::from_secs(2), || {
+///     let automation = UIAutomationFactory::new()?;
+///     automation.get_active_window()?.get_title()
+/// })?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn run_with_timeout<T, F>(timeout: Duration, f: F) -> Result<T, UiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, UiError> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("uia-call".to_string())
+        .spawn(move || {
+            let _apartment = WorkerApartment::join();
+            // Fails only if the caller gave up; the late result is dropped here,
+            // before the apartment is left
+            let _ = sender.send(f());
+        })
+        .map_err(|e| UiError::PlatformError(format!("Failed to start a worker thread: {}", e)))?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            warn!("run_with_timeout - call did not complete within {:?}, abandoning it", timeout);
+            Err(UiError::Timeout(format!("Call did not complete within {:?}", timeout)))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(UiError::PlatformError("The worker thread panicked".to_string()))
+        }
+    }
+}
+
+/// Membership of the multithreaded COM apartment for the lifetime of a worker thread
+pub(crate) struct WorkerApartment {
+    #[cfg(target_os = "windows")]
+    joined: bool,
+}
+
+impl WorkerApartment {
+    #[cfg(target_os = "windows")]
+    pub(crate) fn join() -> Self {
+        use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+        let joined = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok();
+        WorkerApartment { joined }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub(crate) fn join() -> Self {
+        WorkerApartment {}
+    }
+}
+
+impl Drop for WorkerApartment {
+    fn drop(&mut self) {
+        #[cfg(target_os = "windows")]
+        if self.joined {
+            unsafe { windows::Win32::System::Com::CoUninitialize() };
+        }
+    }
+}
+
+/// Serialises [`with_call_timeout`]'s set/call/restore sequences across threads
+static CALL_TIMEOUT_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Whether this thread is inside `with_call_timeout`, so nested calls do not re-lock
+    static IN_CALL_TIMEOUT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with the call timeout of `automation` temporarily set to `timeout`
+/// 
+/// This is the per-call override of [`UIAutomation::set_call_timeout`]: `f`
+/// runs on the calling thread and the previous timeout is restored afterwards,
+/// whether `f` succeeds or not. Calls that exceed the budget fail with
+/// `UiError::Timeout`.
+/// 
+/// The timeout belongs to the automation client, which every handle obtained
+/// from it shares, so this is not a per-thread setting. Overlapping
+/// `with_call_timeout` calls are serialised so that one never restores over
+/// another, but a call made meanwhile on another thread without going through
+/// this function still runs under the override. When that matters, bound the
+/// call with [`run_with_timeout`] instead.
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use ui_interaction::core::{with_call_timeout, UIQuery};
+/// 
+/// let buttons = with_call_timeout(automation.as_ref(), Duration::from_millis(500), || {
+///     window.find_elements(&UIQuery::ByType("Button".to_string()))
+/// })?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn with_call_timeout<T>(automation: &dyn UIAutomation, timeout: Duration, f: impl FnOnce() -> Result<T, UiError>) -> Result<T, UiError> {
+    // A nested call already holds the lock on this thread
+    let _scope = if IN_CALL_TIMEOUT.with(Cell::get) {
+        None
+    } else {
+        // The lock guards no data, so a panic while it was held leaves nothing inconsistent
+        let guard = CALL_TIMEOUT_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        IN_CALL_TIMEOUT.with(|flag| flag.set(true));
+        Some(CallTimeoutScope { _guard: guard })
+    };
+
+    let previous = automation.call_timeout();
+    automation.set_call_timeout(Some(timeout))?;
+    let result = f();
+    if let Err(e) = automation.set_call_timeout(previous) {
+        warn!("with_call_timeout - failed to restore the previous timeout: {}", e);
+    }
+    result
+}
+
+/// The outermost `with_call_timeout` on a thread; clears the nesting flag before unlocking
+struct CallTimeoutScope {
+    _guard: std::sync::MutexGuard<'static, ()>,
+}
+
+impl Drop for CallTimeoutScope {
+    fn drop(&mut self) {
+        IN_CALL_TIMEOUT.with(|flag| flag.set(false));
+    }
+}
+
+/// Run `query` in each window, keeping the windows with matches in their original order
+/// 
+/// A window that disappears mid-search reports `ElementNotFound` or
+/// `WindowNotFound` and is skipped; other errors end the search.
+pub(crate) fn search_windows(windows: Vec<Box<dyn Window>>, query: &UIQuery) -> Result<Vec<WindowMatches>, UiError> {
+    let mut found = Vec::new();
+    for window in windows {
+        match window.find_elements(query) {
+            Ok(elements) if elements.is_empty() => {}
+            Ok(elements) => found.push(WindowMatches { window, elements }),
+            Err(UiError::ElementNotFound(e)) | Err(UiError::WindowNotFound(e)) => {
+                debug!("search_windows - skipping a window that went away: {}", e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(found)
+}
+
+/// Keep only the elements that also appear in `others`, compared by runtime ID
+/// 
+/// Elements without a runtime ID cannot be matched reliably and are dropped.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn intersect_by_runtime_id(elements: Vec<Box<dyn UIElement>>, others: &[Box<dyn UIElement>]) -> Vec<Box<dyn UIElement>> {
+    let other_ids: HashSet<Vec<i32>> = others.iter()
+        .filter_map(|element| element.runtime_id().ok())
+        .collect();
+
+    elements.into_iter()
+        .filter(|element| element.runtime_id()
+            .map(|id| other_ids.contains(&id))
+            .unwrap_or(false))
+        .collect()
+}
+
+/// Main UI Automation interface for desktop applications
+/// 
+/// This trait provides the primary entry point for UI automation operations.
+/// It offers methods to access windows, elements, and perform high-level
+/// automation tasks across the desktop environment.
+/// 
+/// Implementations of this trait are platform-specific and handle the
+/// low-level details of interacting with the operating system's accessibility APIs.
+/// 
+/// # Thread Safety
+/// 
+/// This trait requires `Send + Sync`, making it safe to use across threads.
+/// However, be aware that some UI operations may need to be performed on
+/// specific threads (e.g., the main UI thread on some platforms).
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use ui_interaction::factory::UIAutomationFactory;
+/// use ui_interaction::core::{UIQuery, AppendPosition};
+/// 
+/// // Create automation instance
+/// let automation = UIAutomationFactory::new()?;
+/// 
+/// // Get the currently active window
+/// let window = automation.get_active_window()?;
+/// println!("Active window: {}", window.get_title()?);
+/// 
+/// // Get the focused element and interact with it
+/// let element = automation.get_focused_element()?;
+/// println!("Focused element: {} ({})", element.get_name()?, element.get_type()?);
+/// 
+/// // Set text if it's a text input element
+/// if element.get_type()? == "Edit" {
+///     element.set_text("Hello, World!")?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[allow(dead_code)]
+pub trait UIAutomation: Send + Sync {
+    /// Get the currently active (foreground) window
+    /// 
+    /// Returns the top-level application window that currently has focus.
+    /// This is typically the window the user is actively working with.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn Window>)` - The active window
+    /// * `Err(...)` - If no active window can be found or accessed
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let automation = UIAutomationFactory::new()?;
+    /// let active_window = automation.get_active_window()?;
+    /// println!("Current application: {}", active_window.get_title()?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_active_window(&self) -> Result<Box<dyn Window>, UiError>;
+    
+    /// Get the window that contains the currently focused element
+    /// 
+    /// This might return the same window as `get_active_window()`, but could
+    /// be different if focus is in a child window, dialog, or popup.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn Window>)` - The window containing the focused element
+    /// * `Err(...)` - If no window with focus can be found
+    fn get_window_containing_focus(&self) -> Result<Box<dyn Window>, UiError>;
+    
+    /// Get the currently focused element
+    /// 
+    /// Returns the UI element that currently has keyboard focus across
+    /// the entire desktop. This element will receive keyboard input.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn UIElement>)` - The element with keyboard focus
+    /// * `Err(...)` - If no focused element can be found
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let automation = UIAutomationFactory::new()?;
+    /// let focused = automation.get_focused_element()?;
+    /// 
+    /// // Check what type of element has focus
+    /// match focused.get_type()?.as_str() {
+    ///     "Edit" => {
+    ///         println!("Text field has focus");
+    ///         focused.set_text("New text content")?;
+    ///     }
+    ///     "Button" => {
+    ///         println!("Button has focus: {}", focused.get_name()?);
+    ///     }
+    ///     _ => {
+    ///         println!("Other element type: {}", focused.get_type()?);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError>;
+    
+    /// Find an element by its accessible name
+    /// 
+    /// Searches the entire desktop for a UI element with the specified name.
+    /// This is a global search that may be slower than window-specific searches.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The accessible name to search for
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn UIElement>)` - The first element found with this name
+    /// * `Err(...)` - If no element with this name is found
+    fn find_element_by_name(&self, name: &str) -> Result<Box<dyn UIElement>, UiError>;
+    
+    /// Find an element by its control type
+    /// 
+    /// Searches the entire desktop for a UI element of the specified type.
+    /// This is a global search that may be slower than window-specific searches.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `element_type` - The control type to search for (e.g., "Button", "Edit")
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn UIElement>)` - The first element found of this type
+    /// * `Err(...)` - If no element of this type is found
+    fn find_element_by_type(&self, element_type: &str) -> Result<Box<dyn UIElement>, UiError>;
+
+    /// Call `callback` every time keyboard focus moves to another element
+    /// 
+    /// The callback runs on a platform event thread; see the [`crate::events`]
+    /// module for the thread-safety constraints.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(EventSubscription)` - Handle that unsubscribes when dropped
+    /// * `Err(...)` - If the handler cannot be registered or events are not supported
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let subscription = automation.subscribe_focus_changed(Box::new(|element| {
+    ///     println!("Focus: {}", element.get_name().unwrap_or_default());
+    /// }))?;
+    /// std::thread::sleep(std::time::Duration::from_secs(10));
+    /// drop(subscription);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn subscribe_focus_changed(&self, _callback: FocusChangedCallback) -> Result<EventSubscription, UiError> {
+        Err(UiError::Unsupported("Focus-changed events are not available on this platform".to_string()))
+    }
+
+    /// Call `callback` every time a new window opens anywhere on the desktop
+    /// 
+    /// The callback runs on a platform event thread; see the [`crate::events`]
+    /// module for the thread-safety constraints.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(EventSubscription)` - Handle that unsubscribes when dropped
+    /// * `Err(...)` - If the handler cannot be registered or events are not supported
+    fn subscribe_window_opened(&self, _callback: WindowOpenedCallback) -> Result<EventSubscription, UiError> {
+        Err(UiError::Unsupported("Window-opened events are not available on this platform".to_string()))
+    }
+
+    /// Get the element under a point on the screen
+    /// 
+    /// Coordinates are physical screen pixels, the same space as
+    /// [`UIElement::get_bounds`] and [`UIElement::click`], regardless of the
+    /// calling process's DPI awareness. On a scaled monitor, a cursor position
+    /// read by a DPI-unaware process must be scaled up before it is passed in.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn UIElement>)` - The deepest element at the point
+    /// * `Err(UiError::ElementNotFound)` - If the point is not over any window
+    /// * `Err(...)` - If the platform cannot hit-test the screen
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let element = automation.element_from_point(640, 360)?;
+    /// println!("Under the point: {} ({})", element.get_name()?, element.get_type()?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn element_from_point(&self, _x: i32, _y: i32) -> Result<Box<dyn UIElement>, UiError> {
+        Err(UiError::Unsupported("Hit-testing the screen is not available on this platform".to_string()))
+    }
+
+    /// Get the top-level window under a point on the screen
+    /// 
+    /// Coordinates are physical screen pixels, as for [`UIAutomation::element_from_point`].
+    /// Child controls are resolved to the top-level window that contains them.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn Window>)` - The top-level window at the point
+    /// * `Err(UiError::WindowNotFound)` - If the point is over the desktop or outside every screen
+    /// * `Err(...)` - If the platform cannot hit-test the screen
+    fn window_from_point(&self, _x: i32, _y: i32) -> Result<Box<dyn Window>, UiError> {
+        Err(UiError::Unsupported("Hit-testing the screen is not available on this platform".to_string()))
+    }
+
+    /// The longest a single call into the target application may block, if limited
+    fn call_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Limit how long a single call into the target application may block
+    /// 
+    /// Applies to every element and window obtained from this automation,
+    /// including those already retrieved. A call that exceeds the budget, for
+    /// example `find_elements` or `get_text` on a hung application, fails with
+    /// `UiError::Timeout` instead of blocking. `None` restores the platform
+    /// defaults. Use [`with_call_timeout`] to override it for a single call.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - The timeout applies from now on
+    /// * `Err(UiError::Unsupported)` - If the platform cannot bound its calls
+    fn set_call_timeout(&self, _timeout: Option<Duration>) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Call timeouts are not available on this platform".to_string()))
+    }
+
+    /// The interaction mode used by elements obtained from this automation
+    fn interaction_mode(&self) -> InteractionMode {
+        InteractionMode::Physical
+    }
+
+    /// Change how elements obtained from now on interact with the application
+    /// 
+    /// Elements and windows that were already retrieved keep the mode they were
+    /// created with; look them up again to pick up the change.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - The mode applies to elements retrieved from now on
+    /// * `Err(UiError::Unsupported)` - If the platform cannot honour the mode
+    fn set_interaction_mode(&self, mode: InteractionMode) -> Result<(), UiError> {
+        match mode {
+            InteractionMode::Physical => Ok(()),
+            InteractionMode::PatternsOnly => Err(UiError::Unsupported(
+                "Pattern-only interaction is not available on this platform".to_string()
+            )),
+        }
+    }
+    
+    /// DEPRECATED: Use get_active_window() instead
+    /// 
+    /// This method is deprecated and will be removed in a future version.
+    /// Use `get_active_window()` for the same functionality.
+    #[deprecated(since = "0.1.0", note = "Use get_active_window() instead")]
+    fn get_focused_window(&self) -> Result<Box<dyn Window>, UiError> {
+        self.get_active_window()
+    }
+}
+
+/// Information about a running application on the system
+/// 
+/// This structure contains comprehensive information about an application process,
+/// including its main window details and process information. It's used for
+/// application discovery and management operations.
+/// 
+/// # Fields
+/// 
+/// * `process_id` - The unique process identifier (PID)
+/// * `process_name` - The executable filename (e.g., "notepad.exe")
+/// * `process_path` - Full path to the executable file
+/// * `main_window_title` - Title of the application's main window
+/// * `main_window_class` - Window class name of the main window
+/// * `is_visible` - Whether the main window is currently visible
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use ui_interaction::factory::ApplicationManagerFactory;
+/// 
+/// let app_manager = ApplicationManagerFactory::new()?;
+/// let applications = app_manager.get_all_applications()?;
+/// 
+/// for app in applications {
+///     if app.is_visible {
+///         println!("App: {} (PID: {})", app.process_name, app.process_id);
+///         println!("  Window: '{}'", app.main_window_title);
+///         println!("  Path: {}", app.process_path);
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct ApplicationInfo {
+    pub process_id: u32,
+    pub process_name: String,
+    pub process_path: String,
+    pub main_window_title: String,
+    pub main_window_class: String,
+    pub is_visible: bool,
+}
+
+/// The elements matching a query in one window of an application
+/// 
+/// Returned by [`ApplicationManager::find_elements_in_application`], so each
+/// match can be traced back to the window (or popup) it was found in.
+pub struct WindowMatches {
+    pub window: Box<dyn Window>,
+    pub elements: Vec<Box<dyn UIElement>>,
+}
+
+impl ApplicationInfo {
+    /// Whether the process name contains `name`, ignoring case
+    pub fn name_contains(&self, name: &str) -> bool {
+        self.process_name.to_lowercase().contains(&name.to_lowercase())
+    }
+
+    /// Whether the full executable path contains `path`, ignoring case
+    pub fn path_contains(&self, path: &str) -> bool {
+        self.process_path.to_lowercase().contains(&path.to_lowercase())
+    }
+}
+
+/// Trait for discovering and managing applications running on the system
+/// 
+/// This trait provides methods to enumerate running applications, search for
+/// specific applications by various criteria, and obtain window handles for
+/// UI automation. It's the primary interface for application discovery and
+/// management in the automation system.
+/// 
+/// # Thread Safety
+/// 
+/// This trait requires `Send + Sync`, making it safe to use across threads.
+/// 
+/// # Example
+/// 
+/// ```rust,ignore
+/// use ui_interaction::factory::ApplicationManagerFactory;
+/// use ui_interaction::core::UIQuery;
+/// 
+/// let app_manager = ApplicationManagerFactory::new()?;
+/// 
+/// // Find all Notepad instances
+/// let notepad_apps = app_manager.find_applications_by_name("notepad.exe")?;
+/// 
+/// if let Some(notepad) = notepad_apps.first() {
+///     println!("Found Notepad: {} (PID: {})", notepad.main_window_title, notepad.process_id);
+///     
+///     // Get the window for interaction
+///     let window = app_manager.get_window_by_process_id(notepad.process_id)?;
+///     
+///     // Activate the window and interact with it
+///     window.activate()?;
+///     
+///     // Find text area and set content
+///     let text_areas = window.find_elements(&UIQuery::ByType("Edit".to_string()))?;
+///     if let Some(text_area) = text_areas.first() {
+///         text_area.set_text("Hello from automation!")?;
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[allow(dead_code)]
+pub trait ApplicationManager: Send + Sync {
+    /// Get information about all running applications
+    /// 
+    /// Enumerates all currently running applications on the system and returns
+    /// information about each one, including process details and main window information.
+    /// 
+    /// Each process appears once, however many windows it has, and the list is
+    /// sorted by process ID so repeated calls return running processes in the
+    /// same order. The `find_applications_by_*` methods keep this order.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<ApplicationInfo>)` - List of all running applications, sorted by process ID
+    /// * `Err(...)` - If application enumeration fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let app_manager = ApplicationManagerFactory::new()?;
+    /// let all_apps = app_manager.get_all_applications()?;
+    /// 
+    /// println!("Found {} running applications:", all_apps.len());
+    /// for app in all_apps {
+    ///     if app.is_visible {
+    ///         println!("  {}: '{}' (PID: {})", 
+    ///                  app.process_name, 
+    ///                  app.main_window_title, 
+    ///                  app.process_id);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_all_applications(&self) -> Result<Vec<ApplicationInfo>, UiError>;
+    
+    /// Find applications by process name
+    /// 
+    /// Searches for applications whose process name matches the given name.
+    /// The search is case-insensitive and supports partial matching.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Process name to search for (e.g., "notepad", "notepad.exe", "chrome")
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<ApplicationInfo>)` - List of matching applications
+    /// * `Err(...)` - If the search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // Find all Chrome instances
+    /// let chrome_apps = app_manager.find_applications_by_name("chrome")?;
+    /// 
+    /// // Find specific executable
+    /// let calculator = app_manager.find_applications_by_name("calc.exe")?;
+    /// 
+    /// // Find by partial name
+    /// let text_editors = app_manager.find_applications_by_name("notepad")?; // Matches notepad.exe
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_applications_by_name(&self, name: &str) -> Result<Vec<ApplicationInfo>, UiError>;
+    
+    /// Find applications by window title
+    /// 
+    /// Searches for applications whose main window title contains the given text.
+    /// The search is case-insensitive and supports partial matching.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `title` - Window title text to search for
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<ApplicationInfo>)` - List of matching applications
+    /// * `Err(...)` - If the search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // Find applications with "Discord" in the title
+    /// let discord_apps = app_manager.find_applications_by_title("Discord")?;
+    /// 
+    /// // Find document editing applications
+    /// let word_docs = app_manager.find_applications_by_title("Microsoft Word")?;
+    /// 
+    /// // Find any browser windows
+    /// let browsers = app_manager.find_applications_by_title("Mozilla Firefox")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_applications_by_title(&self, title: &str) -> Result<Vec<ApplicationInfo>, UiError>;
+
+    /// Find applications by executable path
+    /// 
+    /// Searches for applications whose full executable path contains the given
+    /// text, ignoring case. Unlike [`ApplicationManager::find_applications_by_name`]
+    /// this tells apart executables that share a file name, such as the
+    /// `python.exe` of two different installs.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path_substring` - Part of the executable path to search for
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<ApplicationInfo>)` - List of matching applications
+    /// * `Err(...)` - If the search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let apps = app_manager.find_applications_by_path(r"C:\Program Files\MyApp\")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_applications_by_path(&self, path_substring: &str) -> Result<Vec<ApplicationInfo>, UiError>;
+    
+    /// Get a window object from an application by process ID
+    /// 
+    /// Creates a Window object for the main window of the application with
+    /// the specified process ID. This window can then be used for UI automation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `process_id` - The process ID of the target application
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn Window>)` - Window object for the application's main window
+    /// * `Err(...)` - If the window cannot be found or accessed
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // Find Notepad and get its window
+    /// let notepad_apps = app_manager.find_applications_by_name("notepad.exe")?;
+    /// if let Some(notepad) = notepad_apps.first() {
+    ///     let window = app_manager.get_window_by_process_id(notepad.process_id)?;
+    ///     
+    ///     // Now you can interact with the window
+    ///     window.activate()?;
+    ///     println!("Notepad window: {}", window.get_title()?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_window_by_process_id(&self, process_id: u32) -> Result<Box<dyn Window>, UiError>;
+
+    /// Get every main window of an application by process ID
+    /// 
+    /// Applications such as browsers or Office can own several top-level windows
+    /// under a single process. This returns all visible, titled, top-level windows
+    /// owned by the process, in Z-order (topmost first).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `process_id` - The process ID of the application
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<Box<dyn Window>>)` - All main windows of the process (may be empty)
+    /// * `Err(...)` - If a window cannot be accessed
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// for window in app_manager.get_windows_by_process_id(1234)? {
+    ///     println!("Window: {}", window.get_title()?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_windows_by_process_id(&self, process_id: u32) -> Result<Vec<Box<dyn Window>>, UiError>;
+    
+    /// Get the main window of an application by process name
+    /// 
+    /// Finds the first application with the given process name and returns
+    /// its main window. This is a convenience method that combines finding
+    /// an application by name and getting its window.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Process name to search for
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn Window>)` - Window object for the application's main window
+    /// * `Err(...)` - If no matching application is found or window cannot be accessed
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// // Get the first Calculator window directly
+    /// match app_manager.get_window_by_process_name("calc.exe") {
+    ///     Ok(calc_window) => {
+    ///         calc_window.activate()?;
+    ///         println!("Calculator: {}", calc_window.get_title()?);
+    ///     }
+    ///     Err(_) => {
+    ///         println!("Calculator not found or not accessible");
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn get_window_by_process_name(&self, name: &str) -> Result<Box<dyn Window>, UiError>;
+
+    /// Launch an application and wait for its main window
+    /// 
+    /// Spawns the process and polls until it shows its first visible, titled,
+    /// top-level window, then returns information about the running application.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Executable path or name (resolved through `PATH`)
+    /// * `args` - Command-line arguments to pass to the process
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(ApplicationInfo)` - Information about the launched application
+    /// * `Err(...)` - If the process cannot be started, exits early, or shows no
+    ///   window before the timeout
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let app = app_manager.launch_application("notepad.exe", &[])?;
+    /// let window = app_manager.get_window_by_process_id(app.process_id)?;
+    /// window.activate()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn launch_application(&self, path: &str, args: &[String]) -> Result<ApplicationInfo, UiError>;
+
+    /// Launch an application and wait for a matching top-level window
+    /// 
+    /// Spawns the process and polls its visible, titled, top-level windows until
+    /// one whose title contains `title_contains` appears (any window if `None`).
+    /// If no window matches before `timeout`, the process is terminated so
+    /// failed launches do not leave orphans behind.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Executable path or name (resolved through `PATH`)
+    /// * `args` - Command-line arguments to pass to the process
+    /// * `title_contains` - Case-sensitive substring the window title must contain
+    /// * `timeout` - How long to wait for the window
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Box<dyn Window>)` - The first matching window
+    /// * `Err(UiError::Timeout(...))` - If no matching window appeared in time
+    /// * `Err(...)` - If the process cannot be started or exits before showing
+    ///   a matching window
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// 
+    /// let window = app_manager.launch_and_wait("notepad.exe", &[], Some("Notepad"), Duration::from_secs(10))?;
+    /// window.activate()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn launch_and_wait(
+        &self,
+        path: &str,
+        args: &[String],
+        title_contains: Option<&str>,
+        timeout: Duration,
+    ) -> Result<Box<dyn Window>, UiError>;
+
+    /// Terminate a running process
+    /// 
+    /// Intended for tearing down applications started with `launch_application`
+    /// or `launch_and_wait`. The process is killed immediately without being
+    /// asked to save or close its windows.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pid` - The process ID to terminate
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - If the process was terminated
+    /// * `Err(UiError::AccessDenied(...))` - If the process is protected or owned by another user
+    /// * `Err(UiError::InvalidArgument(...))` - If no process has this ID
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let app = app_manager.launch_application("notepad.exe", &[])?;
+    /// // ... run the test ...
+    /// app_manager.kill_application(app.process_id)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn kill_application(&self, pid: u32) -> Result<(), UiError>;
+
+    /// Check whether a process is still running
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pid` - The process ID to check
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` - If the process is running
+    /// * `Ok(false)` - If the process has exited or never existed
+    /// * `Err(UiError::AccessDenied(...))` - If the process exists but may not be queried,
+    ///   so its state is unknown
+    fn is_process_running(&self, pid: u32) -> Result<bool, UiError>;
+
+    /// Re-read the main window details of one process
+    /// 
+    /// `ApplicationInfo` is a snapshot: right after a launch the main window
+    /// title is often empty or a splash title. This refreshes the title, class
+    /// and visibility for `pid` without enumerating every application.
+    /// 
+    /// The default implementation enumerates all applications; platforms
+    /// override it with a cheaper per-process lookup.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pid` - The process ID to look up
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(ApplicationInfo)` - The current details of the process
+    /// * `Err(UiError::ElementNotFound(...))` - If the process has exited
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// let app = app_manager.launch_application("notepad.exe", &[])?;
+    /// // ... open a file ...
+    /// let app = app_manager.refresh_application(app.process_id)?;
+    /// println!("Now showing: {}", app.main_window_title);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn refresh_application(&self, pid: u32) -> Result<ApplicationInfo, UiError> {
+        self.get_all_applications()?
+            .into_iter()
+            .find(|app| app.process_id == pid)
+            .ok_or_else(|| UiError::ElementNotFound(format!("No running application with PID {}", pid)))
+    }
+
+    /// Search every window of an application for elements matching a query
+    /// 
+    /// Applications such as browsers spread their UI across several top-level
+    /// windows and popups; this runs `find_elements` in each of them and groups
+    /// the matches by window. Windows come in Z-order (topmost first) and the
+    /// elements of each window in the order `find_elements` returns them.
+    /// Windows without matches are left out.
+    /// 
+    /// This is as expensive as one search per window, so prefer
+    /// `Window::find_elements` when the window is known. Windows that close
+    /// during the search are skipped.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pid` - The process ID of the application
+    /// * `query` - The search criteria
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<WindowMatches>)` - The matches, grouped by window (may be empty)
+    /// * `Err(...)` - If the windows cannot be listed or a search fails
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,ignore
+    /// use ui_interaction::core::UIQuery;
+    /// 
+    /// for found in app_manager.find_elements_in_application(pid, &UIQuery::ByName("OK".to_string()))? {
+    ///     println!("{} match(es) in '{}'", found.elements.len(), found.window.get_title()?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn find_elements_in_application(&self, pid: u32, query: &UIQuery) -> Result<Vec<WindowMatches>, UiError> {
+        search_windows(self.get_windows_by_process_id(pid)?, query)
+    }
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Minimal in-memory element used to drive query matching without a platform backend
+    #[derive(Clone)]
+    struct FakeElement {
+        name: String,
+        control_type: String,
+        parent: Option<Rc<FakeElement>>,
+        children: Vec<FakeElement>,
+        automation_id: Option<String>,
+        class_name: Option<String>,
+        runtime_id: Vec<i32>,
+        is_own_parent: bool,
+        /// Shared count of `get_properties` calls, to observe how much of a tree a search visits
+        inspected: Rc<Cell<usize>>,
+        text: String,
+        enabled: bool,
+        offscreen: bool,
+    }
+
+    impl FakeElement {
+        fn new(name: &str, control_type: &str) -> Self {
+            FakeElement {
+                name: name.to_string(),
+                control_type: control_type.to_string(),
+                parent: None,
+                children: Vec::new(),
+                automation_id: None,
+                class_name: None,
+                runtime_id: Vec::new(),
+                is_own_parent: false,
+                inspected: Rc::new(Cell::new(0)),
+                text: String::new(),
+                enabled: true,
+                offscreen: false,
+            }
+        }
+
+        fn with_parent(mut self, parent: &FakeElement) -> Self {
+            self.parent = Some(Rc::new(parent.clone()));
+            self
+        }
+    }
+
+    impl UIElement for FakeElement {
+        fn get_name(&self) -> Result<String, UiError> {
+            Ok(self.name.clone())
+        }
+
+        fn get_type(&self) -> Result<String, UiError> {
+            Ok(self.control_type.clone())
+        }
+
+        fn get_text(&self) -> Result<String, UiError> {
+            Ok(self.text.clone())
+        }
+
+        fn set_text(&self, _text: &str) -> Result<(), UiError> {
+            Ok(())
+        }
+
+        fn append_text(&self, _text: &str, _position: AppendPosition) -> Result<(), UiError> {
+            Ok(())
+        }
+
+        fn click(&self) -> Result<(), UiError> {
+            Ok(())
+        }
+
+        fn right_click(&self) -> Result<(), UiError> {
+            Ok(())
+        }
+
+        fn double_click(&self) -> Result<(), UiError> {
+            Ok(())
+        }
+
+        fn set_focus(&self) -> Result<(), UiError> {
+            Ok(())
+        }
+
+        fn is_enabled(&self) -> Result<bool, UiError> {
+            Ok(self.enabled)
+        }
+
+        fn is_offscreen(&self) -> Result<bool, UiError> {
+            Ok(self.offscreen)
+        }
+
+        fn get_properties(&self) -> Result<HashMap<String, String>, UiError> {
+            self.inspected.set(self.inspected.get() + 1);
+            let mut properties = HashMap::new();
+            properties.insert("name".to_string(), self.name.clone());
+            properties.insert("control_type".to_string(), self.control_type.clone());
+            if let Some(automation_id) = &self.automation_id {
+                properties.insert("automation_id".to_string(), automation_id.clone());
+            }
+            if let Some(class_name) = &self.class_name {
+                properties.insert("class_name".to_string(), class_name.clone());
+            }
+            Ok(properties)
+        }
+
+        fn get_bounds(&self) -> Result<Option<Rect>, UiError> {
+            Ok(None)
+        }
+
+        fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+            Ok(self.children.iter()
+                .map(|child| Box::new(child.clone()) as Box<dyn UIElement>)
+                .collect())
+        }
+
+        fn get_parent(&self) -> Result<Option<Box<dyn UIElement>>, UiError> {
+            if self.is_own_parent {
+                return Ok(Some(Box::new(self.clone())));
+            }
+            Ok(self.parent.as_ref().map(|p| Box::new((**p).clone()) as Box<dyn UIElement>))
+        }
+
+        fn runtime_id(&self) -> Result<Vec<i32>, UiError> {
+            Ok(self.runtime_id.clone())
+        }
+
+        fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError> {
+            Ok(Box::new(self.clone()))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    /// Window whose `find_elements` is driven by a closure; every other method is unsupported
+    struct FakeWindow<F: Fn(&UIQuery) -> Vec<Box<dyn UIElement>>> {
+        find: F,
+    }
+
+    impl<F: Fn(&UIQuery) -> Vec<Box<dyn UIElement>>> Window for FakeWindow<F> {
+        fn get_title(&self) -> Result<String, UiError> { Ok("Fake".to_string()) }
+        fn get_class_name(&self) -> Result<String, UiError> { Ok("Fake".to_string()) }
+        fn get_process_id(&self) -> Result<u32, UiError> { Ok(0) }
+        fn get_thread_id(&self) -> Result<u32, UiError> { Ok(0) }
+        fn get_process_name(&self) -> Result<String, UiError> { Ok(String::new()) }
+        fn get_process_path(&self) -> Result<String, UiError> { Ok(String::new()) }
+        fn is_visible(&self) -> Result<bool, UiError> { Ok(true) }
+        fn is_minimized(&self) -> Result<bool, UiError> { Ok(false) }
+        fn is_maximized(&self) -> Result<bool, UiError> { Ok(false) }
+        fn get_rect(&self) -> Result<Rect, UiError> { Ok(Rect::new(0, 0, 0, 0)) }
+        fn get_dpi(&self) -> Result<u32, UiError> { Ok(96) }
+        fn activate(&self) -> Result<(), UiError> { Ok(()) }
+        fn bring_to_top(&self) -> Result<(), UiError> { Ok(()) }
+        fn set_foreground(&self) -> Result<(), UiError> { Ok(()) }
+        fn minimize(&self) -> Result<(), UiError> { Ok(()) }
+        fn maximize(&self) -> Result<(), UiError> { Ok(()) }
+        fn restore(&self) -> Result<(), UiError> { Ok(()) }
+        fn close(&self) -> Result<(), UiError> { Ok(()) }
+        fn send_keys(&self, _keys: &str) -> Result<(), UiError> { Ok(()) }
+
+        fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError> {
+            Err("not supported".into())
+        }
+
+        fn get_ui_tree_with_options(&self, _options: &UITreeOptions) -> Result<UITree, UiError> {
+            Err("not supported".into())
+        }
+
+        fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+            Ok((self.find)(query))
+        }
+
+        fn for_each_text_element(&self, _options: &TextExtractionOptions, _f: &mut dyn FnMut(&TextElementInfo) -> bool) -> Result<(), UiError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_highlight_offscreen_element_succeeds_without_drawing() {
+        let mut hidden = FakeElement::new("Row 40", "ListItem");
+        hidden.offscreen = true;
+        assert_eq!(hidden.highlight(Duration::from_secs(1)), Ok(()));
+
+        let shown = FakeElement::new("Row 1", "ListItem");
+        assert!(matches!(shown.highlight(Duration::from_secs(1)), Err(UiError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_search_windows_groups_matches_by_window() {
+        let ok_button = |query: &UIQuery| -> Vec<Box<dyn UIElement>> {
+            let button = FakeElement::new("OK", "Button");
+            if query.matches(&button).unwrap() { vec![Box::new(button) as Box<dyn UIElement>] } else { Vec::new() }
+        };
+        let windows: Vec<Box<dyn Window>> = vec![
+            Box::new(FakeWindow { find: ok_button }),
+            Box::new(FakeWindow { find: |_: &UIQuery| Vec::new() }),
+            Box::new(FakeWindow { find: |_: &UIQuery| vec![
+                Box::new(FakeElement::new("OK", "Button")) as Box<dyn UIElement>,
+                Box::new(FakeElement::new("OK", "Button")) as Box<dyn UIElement>,
+            ] }),
+        ];
+
+        let found = search_windows(windows, &UIQuery::ByName("OK".to_string())).unwrap();
+        let counts: Vec<usize> = found.iter().map(|matches| matches.elements.len()).collect();
+        assert_eq!(counts, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_fill_form_reports_each_field() {
+        let window = FakeWindow {
+            find: |query: &UIQuery| -> Vec<Box<dyn UIElement>> {
+                match query {
+                    UIQuery::ByName(name) if name != "Missing" => vec![Box::new(FakeElement::new(name, "Edit")) as Box<dyn UIElement>],
+                    _ => Vec::new(),
+                }
+            },
+        };
+
+        let results = window.fill_form(&[
+            (UIQuery::ByName("User".to_string()), "alice".to_string()),
+            (UIQuery::ByName("Missing".to_string()), "ignored".to_string()),
+            (UIQuery::ByName("Password".to_string()), "hunter2".to_string()),
+        ]).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(()));
+        assert!(matches!(results[1], Err(UiError::ElementNotFound(_))));
+        assert_eq!(results[2], Ok(()));
+    }
+
+    #[test]
+    fn test_same_named_buttons_get_distinct_paths() {
+        let mut toolbar = FakeElement::new("toolbar", "ToolBar");
+        toolbar.children = vec![
+            FakeElement::new("Delete", "Button"),
+            FakeElement::new("separator", "Separator"),
+            FakeElement::new("Delete", "Button"),
+        ];
+        let mut root = FakeElement::new("Main", "Window");
+        root.children = vec![FakeElement::new("Delete", "Button"), toolbar];
+
+        let found = find_with_paths(&root, &UIQuery::ByName("Delete".to_string()), |_| None);
+        let paths: Vec<String> = found.iter().map(|(_, path)| path.join("/")).collect();
+        assert_eq!(paths, vec!["Button[0]", "ToolBar[0]/Button[0]", "ToolBar[0]/Button[1]"]);
+
+        let last = found.last().unwrap().1.clone();
+        let relocated = resolve_path(Box::new(root.clone()), &last).unwrap().unwrap();
+        assert_eq!(relocated.get_name().unwrap(), "Delete");
+        assert!(resolve_path(Box::new(root.clone()), &["ToolBar[0]".to_string(), "Button[2]".to_string()]).unwrap().is_none());
+        assert!(matches!(resolve_path(Box::new(root), &["Button".to_string()]), Err(UiError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_walk_menu_path_names_the_missing_step() {
+        let mut menu_bar = FakeElement::new("Application", "MenuBar");
+        menu_bar.children = vec![FakeElement::new("File", "MenuItem"), FakeElement::new("Edit", "MenuItem")];
+
+        let err = walk_menu_path(Box::new(menu_bar.clone()), &["View", "Zoom"], |_| Ok(None)).unwrap_err();
+        match err {
+            UiError::ElementNotFound(message) => assert!(message.contains("step 1 of 2") && message.contains("'View'"), "{}", message),
+            other => panic!("Expected ElementNotFound, got {:?}", other),
+        }
+
+        assert!(matches!(walk_menu_path(Box::new(menu_bar), &[], |_| Ok(None)), Err(UiError::InvalidArgument(_))));
+    }
+
+    /// Window > Pane "toolbar" > Button "Save"
+    fn build_tree() -> FakeElement {
+        let window = FakeElement::new("Main", "Window");
+        let toolbar = FakeElement::new("toolbar", "Pane").with_parent(&window);
+        FakeElement::new("Save", "Button").with_parent(&toolbar)
+    }
+
+    #[test]
+    fn test_parent_matches_immediate_parent_only() {
+        let button = build_tree();
+
+        let pane = UIQuery::Parent(Box::new(UIQuery::ByType("Pane".to_string())));
+        assert!(pane.matches(&button).unwrap());
+
+        let window = UIQuery::Parent(Box::new(UIQuery::ByType("Window".to_string())));
+        assert!(!window.matches(&button).unwrap());
+    }
+
+    #[test]
+    fn test_and_of_property_queries_fetches_properties_once() {
+        let mut field = FakeElement::new("User", "Edit");
+        field.automation_id = Some("username".to_string());
+        let query = UIQuery::And(vec![
+            UIQuery::ByName("User".to_string()),
+            UIQuery::ByType("Edit".to_string()),
+            UIQuery::ByProperty("automation_id".to_string(), "username".to_string()),
+        ]);
+
+        assert!(query.matches(&field).unwrap());
+        assert_eq!(field.inspected.get(), 1);
+
+        // A snapshot fetched beforehand is used as is
+        let mut properties = Some(field.get_properties().unwrap());
+        let nested = UIQuery::Not(Box::new(UIQuery::Or(vec![
+            UIQuery::ByName("Password".to_string()),
+            UIQuery::ByClassName("Secret".to_string()),
+        ])));
+        assert!(nested.matches_with_properties(&field, &mut properties).unwrap());
+        assert_eq!(field.inspected.get(), 2);
+    }
+
+    #[test]
+    fn test_parent_without_parent_does_not_match() {
+        let root = FakeElement::new("Main", "Window");
+        let query = UIQuery::Parent(Box::new(UIQuery::ByType("Window".to_string())));
+        assert!(!query.matches(&root).unwrap());
+    }
+
+    #[test]
+    fn test_ancestor_walks_up_to_root() {
+        let button = build_tree();
+
+        let window = UIQuery::Ancestor(Box::new(UIQuery::ByName("Main".to_string())));
+        assert!(window.matches(&button).unwrap());
+
+        let pane = UIQuery::Ancestor(Box::new(UIQuery::ByName("toolbar".to_string())));
+        assert!(pane.matches(&button).unwrap());
+
+        let missing = UIQuery::Ancestor(Box::new(UIQuery::ByName("Missing".to_string())));
+        assert!(!missing.matches(&button).unwrap());
+    }
+
+    #[test]
+    fn test_ancestor_terminates_on_self_parent() {
+        let mut element = FakeElement::new("Loop", "Pane");
+        element.is_own_parent = true;
+
+        let query = UIQuery::Ancestor(Box::new(UIQuery::ByType("Window".to_string())));
+        assert!(!query.matches(&element).unwrap());
+    }
+
+    #[test]
+    fn test_by_automation_id_is_case_sensitive_exact_match() {
+        let mut button = FakeElement::new("Save", "Button");
+        button.automation_id = Some("SaveButton".to_string());
+
+        assert!(UIQuery::ByAutomationId("SaveButton".to_string()).matches(&button).unwrap());
+        assert!(!UIQuery::ByAutomationId("savebutton".to_string()).matches(&button).unwrap());
+        assert!(!UIQuery::ByAutomationId("Save".to_string()).matches(&button).unwrap());
+
+        let unnamed = FakeElement::new("Save", "Button");
+        assert!(!UIQuery::ByAutomationId("SaveButton".to_string()).matches(&unnamed).unwrap());
+    }
+
+    #[test]
+    fn test_find_elements_wait_returns_once_elements_appear() {
+        let start = Instant::now();
+        let window = FakeWindow {
+            find: move |_: &UIQuery| {
+                if start.elapsed() >= Duration::from_millis(100) {
+                    vec![Box::new(FakeElement::new("Save", "Button")) as Box<dyn UIElement>]
+                } else {
+                    Vec::new()
+                }
+            },
+        };
+
+        let query = UIQuery::ByName("Save".to_string());
+        let found = window.find_elements_wait(&query, Duration::from_secs(5), Duration::from_millis(10)).unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_find_elements_wait_returns_empty_after_timeout() {
+        let calls = std::cell::Cell::new(0);
+        let window = FakeWindow {
+            find: |_: &UIQuery| {
+                calls.set(calls.get() + 1);
+                Vec::new()
+            },
+        };
+
+        let start = Instant::now();
+        let query = UIQuery::ByName("Missing".to_string());
+        let found = window.find_elements_wait(&query, Duration::from_millis(150), Duration::from_millis(20)).unwrap();
+
+        assert!(found.is_empty());
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(calls.get() > 1);
+    }
+
+    #[test]
+    fn test_range_value_clamps_to_bounds() {
+        let range = RangeValue { value: 25.0, min: 0.0, max: 50.0, small_change: 1.0, large_change: 10.0 };
+        assert_eq!(range.clamp(-5.0), 0.0);
+        assert_eq!(range.clamp(30.0), 30.0);
+        assert_eq!(range.clamp(80.0), 50.0);
+        assert_eq!(range.fraction(), 0.5);
+
+        let empty = RangeValue { value: 3.0, min: 3.0, max: 3.0, small_change: 0.0, large_change: 0.0 };
+        assert_eq!(empty.fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_stability_tracker_requires_consecutive_identical_samples() {
+        let mut tracker = StabilityTracker::new(3);
+        assert!(!tracker.observe(1));
+        assert!(!tracker.observe(1));
+        // A change restarts the count
+        assert!(!tracker.observe(2));
+        assert!(!tracker.observe(2));
+        assert!(tracker.observe(2));
+
+        // Fewer than two samples would report the very first one as stable
+        for required in [0, 1] {
+            let mut clamped = StabilityTracker::new(required);
+            assert!(!clamped.observe("first"));
+            assert!(clamped.observe("first"));
+        }
+    }
+
+    #[test]
+    fn test_wait_until_ready_returns_ok_on_timeout() {
+        let window = FakeWindow { find: |_: &UIQuery| Vec::new() };
+        let options = ReadinessOptions {
+            poll_interval: Duration::from_millis(10),
+            stable_samples: u32::MAX,
+        };
+
+        let start = Instant::now();
+        window.wait_until_ready_with_options(Duration::from_millis(100), &options).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_by_control_type_id_matches_known_names_only() {
+        let slider = FakeElement::new("Volume", "Slider");
+        assert!(UIQuery::ByControlTypeId(50015).matches(&slider).unwrap());
+        assert!(!UIQuery::ByControlTypeId(50025).matches(&slider).unwrap());
+
+        // A name outside the table never matches, not even Custom
+        let unknown = FakeElement::new("Widget", "Unknown");
+        assert!(!UIQuery::ByControlTypeId(50025).matches(&unknown).unwrap());
+    }
+
+    #[test]
+    fn test_application_path_matching_is_case_insensitive_substring() {
+        let app = |name: &str, path: &str| ApplicationInfo {
+            process_id: 1,
+            process_name: name.to_string(),
+            process_path: path.to_string(),
+            main_window_title: String::new(),
+            main_window_class: String::new(),
+            is_visible: true,
+        };
+        let tool = app("python.exe", r"C:\Program Files\MyApp\python.exe");
+        let system = app("python.exe", r"C:\Python312\python.exe");
+
+        assert!(tool.path_contains(r"program files\myapp"));
+        assert!(!system.path_contains(r"Program Files\MyApp"));
+        assert!(system.path_contains("PYTHON312"));
+
+        // Name matching still only looks at the executable name
+        assert!(tool.name_contains("Python") && system.name_contains("Python"));
+        assert!(!tool.name_contains("MyApp"));
+    }
+
+    #[test]
+    fn test_by_class_name_is_exact_match() {
+        let mut pane = FakeElement::new("", "Pane");
+        pane.class_name = Some("Chrome_RenderWidgetHostHWND".to_string());
+
+        assert!(UIQuery::ByClassName("Chrome_RenderWidgetHostHWND".to_string()).matches(&pane).unwrap());
+        assert!(!UIQuery::ByClassName("Chrome_RenderWidget".to_string()).matches(&pane).unwrap());
+        assert!(!UIQuery::ByClassName("chrome_renderwidgethosthwnd".to_string()).matches(&pane).unwrap());
+    }
+
+    #[test]
+    fn test_text_elements_json_schema_is_stable() {
+        let mut located = TextElementInfo::new("Hello".to_string());
+        located.bounds = Some(Rect::new(1, 2, 30, 40));
+        located.automation_id = Some("greeting".to_string());
+        let unlocated = TextElementInfo::new("World".to_string());
+
+        let json = text_elements_to_json(&[located, unlocated]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let items = value.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+
+        let mut keys: Vec<&str> = items[0].as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, [
+            "automation_id", "bounds", "class_name", "control_type", "depth", "is_editable",
+            "is_enabled", "is_selected", "is_visible", "localized_control_type", "name",
+            "parent_name", "text",
+        ]);
+        assert_eq!(items[0]["bounds"], serde_json::json!({"left": 1, "top": 2, "right": 30, "bottom": 40}));
+        assert_eq!(items[0]["automation_id"], "greeting");
+
+        // Missing values stay in the object as null
+        assert!(items[1]["bounds"].is_null());
+        assert!(items[1]["automation_id"].is_null());
+        assert_eq!(items[1].as_object().unwrap().len(), keys.len());
+    }
+
+    #[test]
+    fn test_clear_refuses_non_editable_elements() {
+        assert!(FakeElement::new("Name", "Edit").clear().is_ok());
+        assert!(matches!(FakeElement::new("OK", "Button").clear(), Err(UiError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_by_text_matches_content_not_name() {
+        let mut paragraph = FakeElement::new("Paragraph", "Text");
+        paragraph.text = "The quick brown fox".to_string();
+        let mut root = FakeElement::new("Document", "Document");
+        root.children = vec![FakeElement::new("brown", "Button"), paragraph];
+
+        let query = UIQuery::ByText("brown".to_string());
+        let found = query.find_all(&root).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].get_name().unwrap(), "Paragraph");
+
+        // Case-sensitive, like the other string queries
+        assert!(!UIQuery::ByText("Brown".to_string()).matches(found[0].as_ref()).unwrap());
+    }
+
+    #[test]
+    fn test_key_hints_default_to_none() {
+        let label = FakeElement::new("Status", "Text");
+        assert_eq!(label.get_access_key(), Ok(None));
+        assert_eq!(label.get_accelerator_key(), Ok(None));
+    }
+
+    #[test]
+    fn test_with_retry_retries_only_transient_errors() {
+        let mut calls = 0;
+        let result = with_retry(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 { Err(UiError::ElementNotFound("changed".into())) } else { Ok(calls) }
+        });
+        assert_eq!(result, Ok(3));
+
+        let mut calls = 0;
+        let result: Result<(), UiError> = with_retry(3, Duration::ZERO, || {
+            calls += 1;
+            Err(UiError::pattern_unsupported("Value"))
+        });
+        assert_eq!(result, Err(UiError::pattern_unsupported("Value")));
+        assert_eq!(calls, 1);
+
+        // A timeout already waited, so it is not waited for again
+        let mut calls = 0;
+        let result: Result<(), UiError> = with_retry(3, Duration::ZERO, || {
+            calls += 1;
+            Err(UiError::Timeout("busy".into()))
+        });
+        assert_eq!(result, Err(UiError::Timeout("busy".into())));
+        assert_eq!(calls, 1);
+
+        // The last error is returned once the attempts run out
+        let mut calls = 0;
+        let result: Result<(), UiError> = with_retry(2, Duration::ZERO, || {
+            calls += 1;
+            Err(UiError::ElementNotFound(format!("changed {}", calls)))
+        });
+        assert_eq!(result, Err(UiError::ElementNotFound("changed 2".into())));
+    }
+
+    fn edges(rect: &Rect) -> (i32, i32, i32, i32) {
+        (rect.left, rect.top, rect.right, rect.bottom)
+    }
+
+    #[test]
+    fn test_logical_to_physical_at_common_dpis() {
+        let logical = Rect::new(100, 40, 300, 90);
+        assert_eq!(edges(&logical_to_physical(&logical, 96)), (100, 40, 300, 90));
+        assert_eq!(edges(&logical_to_physical(&logical, 120)), (125, 50, 375, 113));
+        assert_eq!(edges(&logical_to_physical(&logical, 144)), (150, 60, 450, 135));
+        assert_eq!(edges(&logical_to_physical(&logical, 192)), (200, 80, 600, 180));
+        assert_eq!(edges(&logical_to_physical(&logical, 0)), (100, 40, 300, 90));
+    }
+
+    #[test]
+    fn test_physical_to_logical_at_common_dpis() {
+        let physical = Rect::new(300, 150, 600, 300);
+        assert_eq!(edges(&physical_to_logical(&physical, 96)), (300, 150, 600, 300));
+        assert_eq!(edges(&physical_to_logical(&physical, 120)), (240, 120, 480, 240));
+        assert_eq!(edges(&physical_to_logical(&physical, 144)), (200, 100, 400, 200));
+        assert_eq!(edges(&physical_to_logical(&physical, 192)), (150, 75, 300, 150));
+    }
+
+    #[test]
+    fn test_dpi_conversion_keeps_negative_coordinates() {
+        // A monitor to the left of and above the primary one
+        let logical = Rect::new(-1280, -200, -1180, -150);
+        let physical = logical_to_physical(&logical, 144);
+        assert_eq!(edges(&physical), (-1920, -300, -1770, -225));
+        assert_eq!(edges(&physical_to_logical(&physical, 144)), edges(&logical));
+
+        assert_eq!(edges(&Rect::new(-5, -3, 5, 3).scale(1.25)), (-6, -4, 6, 4));
+    }
+
+    #[test]
+    fn test_truncate_chars_counts_characters() {
+        assert_eq!(truncate_chars("réunion".to_string(), 3), ("réu".to_string(), true));
+        assert_eq!(truncate_chars("été".to_string(), 3), ("été".to_string(), false));
+        assert_eq!(truncate_chars(String::new(), 0), (String::new(), false));
+    }
+
+    #[test]
+    fn test_get_text_limited_defaults_to_cutting_get_text() {
+        let mut editor = FakeElement::new("Editor", "Document");
+        editor.text = "Hello, world".to_string();
+        assert_eq!(editor.get_text_limited(5), Ok(("Hello".to_string(), true)));
+        assert_eq!(editor.get_text_limited(100), Ok(("Hello, world".to_string(), false)));
+    }
+
+    /// Stand-in for a provider whose calls hang inside the target application
+    struct HungBackend {
+        delay: Duration,
+    }
+
+    impl HungBackend {
+        fn get_text(&self) -> Result<String, UiError> {
+            thread::sleep(self.delay);
+            Ok("late".to_string())
+        }
+    }
+
+    #[test]
+    fn test_run_with_timeout_abandons_hung_call() {
+        let backend = HungBackend { delay: Duration::from_secs(2) };
+        let start = Instant::now();
+        let result = run_with_timeout(Duration::from_millis(100), move || backend.get_text());
+        let elapsed = start.elapsed();
+
+        assert!(matches!(result, Err(UiError::Timeout(_))), "got {:?}", result);
+        assert!(elapsed < Duration::from_millis(1000), "timed out after {:?}", elapsed);
+
+        let backend = HungBackend { delay: Duration::ZERO };
+        let result = run_with_timeout(Duration::from_secs(5), move || backend.get_text());
+        assert_eq!(result, Ok("late".to_string()));
+    }
+
+    #[test]
+    fn test_run_with_timeout_returns_the_call_error() {
+        let result: Result<(), UiError> = run_with_timeout(Duration::from_secs(5), || {
+            Err(UiError::pattern_unsupported("Text"))
+        });
+        assert_eq!(result, Err(UiError::pattern_unsupported("Text")));
+    }
+
+    #[test]
+    fn test_enabled_query_excludes_disabled_button() {
+        let mut disabled = FakeElement::new("Apply", "Button");
+        disabled.enabled = false;
+        let mut root = FakeElement::new("Dialog", "Window");
+        root.children = vec![FakeElement::new("OK", "Button"), disabled];
+
+        let buttons = UIQuery::ByType("Button".to_string());
+        assert_eq!(buttons.find_all(&root).unwrap().len(), 2);
+
+        let found = UIQuery::Enabled(Box::new(buttons)).find_all(&root).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].get_name().unwrap(), "OK");
+    }
+
+    #[test]
+    fn test_visible_query_excludes_offscreen_elements() {
+        let mut scrolled_away = FakeElement::new("Row 40", "ListItem");
+        scrolled_away.offscreen = true;
+        let mut root = FakeElement::new("List", "List");
+        root.children = vec![FakeElement::new("Row 1", "ListItem"), scrolled_away];
+
+        let query = UIQuery::Visible(Box::new(UIQuery::ByType("ListItem".to_string())));
+        let found = query.find_all(&root).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].get_name().unwrap(), "Row 1");
+
+        // The flags combine, and the cheap flag check rejects before the inner query runs
+        let mut hidden_disabled = FakeElement::new("Row 41", "ListItem");
+        hidden_disabled.offscreen = true;
+        hidden_disabled.enabled = false;
+        let both = UIQuery::Visible(Box::new(UIQuery::Enabled(Box::new(UIQuery::ByType("ListItem".to_string())))));
+        assert!(!both.matches(&hidden_disabled).unwrap());
+        assert_eq!(hidden_disabled.inspected.get(), 0);
+    }
+
+    #[test]
+    fn test_by_name_regex_unanchored_matches_substring() {
+        let title = FakeElement::new("Document1 — Notepad", "TitleBar");
+        assert!(UIQuery::by_name_regex("Notepad").unwrap().matches(&title).unwrap());
+        assert!(UIQuery::by_name_regex(r"Document\d+").unwrap().matches(&title).unwrap());
+    }
+
+    #[test]
+    fn test_by_name_regex_anchored_requires_full_match() {
+        let inbox = FakeElement::new("Inbox (12)", "TreeItem");
+        assert!(UIQuery::by_name_regex(r"^Inbox \(\d+\)$").unwrap().matches(&inbox).unwrap());
+        assert!(!UIQuery::by_name_regex(r"^Inbox$").unwrap().matches(&inbox).unwrap());
+        assert!(!UIQuery::by_name_regex(r"^\(12\)").unwrap().matches(&inbox).unwrap());
+    }
+
+    #[test]
+    fn test_by_name_regex_matches_unicode_names() {
+        let meeting = FakeElement::new("Réunion à 14h — Côte d'Azur", "Text");
+        assert!(UIQuery::by_name_regex("^Réunion").unwrap().matches(&meeting).unwrap());
+        assert!(UIQuery::by_name_regex(r"C\w+te").unwrap().matches(&meeting).unwrap());
+        assert!(!UIQuery::by_name_regex("^reunion").unwrap().matches(&meeting).unwrap());
+    }
+
+    #[test]
+    fn test_by_name_regex_rejects_invalid_pattern() {
+        let err = UIQuery::by_name_regex("Inbox (").unwrap_err();
+        assert!(err.to_string().contains("Invalid name pattern"));
+    }
+
+    fn button_with_id(name: &str, runtime_id: i32) -> Box<dyn UIElement> {
+        let mut button = FakeElement::new(name, "Button");
+        button.runtime_id = vec![42, runtime_id];
+        Box::new(button)
+    }
+
+    #[test]
+    fn test_and_intersection_keeps_same_named_elements_distinct() {
+        let by_type = vec![button_with_id("OK", 1), button_with_id("OK", 2)];
+        let by_name = vec![button_with_id("OK", 1), button_with_id("OK", 2)];
+
+        let both = intersect_by_runtime_id(by_type, &by_name);
+        let ids: Vec<Vec<i32>> = both.iter().map(|e| e.runtime_id().unwrap()).collect();
+        assert_eq!(ids, vec![vec![42, 1], vec![42, 2]]);
+    }
+
+    #[test]
+    fn test_and_intersection_drops_same_named_element_missing_from_other_query() {
+        let by_type = vec![button_with_id("OK", 1), button_with_id("OK", 2)];
+        let enabled = vec![button_with_id("OK", 2)];
+
+        let both = intersect_by_runtime_id(by_type, &enabled);
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].runtime_id().unwrap(), vec![42, 2]);
+    }
+
+    #[test]
+    fn test_ui_tree_options_limits() {
+        let defaults = UITreeOptions::default();
+        assert!(defaults.should_descend(2));
+        assert!(!defaults.should_descend(3));
+        assert_eq!(defaults.child_limit(), 50);
+
+        let unlimited = UITreeOptions::unlimited();
+        assert!(unlimited.should_descend(1000));
+        assert_eq!(unlimited.child_limit(), usize::MAX);
+        assert!(unlimited.include_bounds);
+    }
+
+    #[test]
+    fn test_editable_only_accepts_only_editable_control_types() {
+        let options = TextExtractionOptions::editable_only();
+        for control_type in ["Edit", "Document", "ComboBox"] {
+            assert!(options.accepts_control_type(control_type), "{} should be accepted", control_type);
+        }
+        for control_type in ["Text", "Button", "Window", "edit"] {
+            assert!(!options.accepts_control_type(control_type), "{} should be rejected", control_type);
+        }
+        assert!(TextExtractionOptions::default().accepts_control_type("Button"));
+    }
+
+    #[test]
+    fn test_min_text_length_filters_short_strings() {
+        let options = TextExtractionOptions { min_text_length: 3, ..TextExtractionOptions::default() };
+        assert!(!options.accepts_text("ab"));
+        assert!(!options.accepts_text("  ab  "));
+        assert!(options.accepts_text("abc"));
+        // Counted in characters, not bytes
+        assert!(!options.accepts_text("éé"));
+        assert!(options.accepts_text("héé"));
+
+        assert!(TextExtractionOptions::all().accepts_text(""));
+    }
+
+    /// A captured node with the given children, visible and enabled
+    fn tree_node(name: &str, control_type: &str, children: Vec<UITreeNode>) -> UITreeNode {
+        UITreeNode {
+            name: name.to_string(),
+            control_type: control_type.to_string(),
+            properties: HashMap::new(),
+            children,
+            bounds: Some(Rect::new(0, 0, 10, 10)),
+            is_enabled: true,
+            is_visible: true,
+        }
+    }
+
+    #[test]
+    fn test_saved_ui_tree_round_trips_and_answers_queries() {
+        let mut save = tree_node("Save", "Button", Vec::new());
+        save.properties.insert("automation_id".to_string(), "SaveButton".to_string());
+        let mut cancel = tree_node("Cancel", "Button", Vec::new());
+        cancel.is_enabled = false;
+        let tree = UITree {
+            root: tree_node("Editor", "Window", vec![
+                tree_node("toolbar", "ToolBar", vec![save, cancel]),
+                tree_node("Body", "Document", Vec::new()),
+                tree_node("Save As", "Button", Vec::new()),
+            ]),
+            timestamp: Utc::now(),
+            window_title: "Editor".to_string(),
+            window_class: "EditorClass".to_string(),
+            truncated: false,
+        };
+
+        let path = std::env::temp_dir().join(format!("ui_tree_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        tree.save(path).unwrap();
+        let loaded = UITree::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.window_title, "Editor");
+        assert_eq!(loaded.timestamp, tree.timestamp);
+        let names = |query: UIQuery| -> Vec<String> {
+            loaded.find(&query).iter().map(|node| node.name.clone()).collect()
+        };
+        let buttons = || Box::new(UIQuery::ByType("Button".to_string()));
+
+        assert_eq!(names(*buttons()), vec!["Save", "Cancel", "Save As"]);
+        assert_eq!(names(UIQuery::ByAutomationId("SaveButton".to_string())), vec!["Save"]);
+        assert_eq!(names(UIQuery::ByNameMatch("Save".to_string(), NameMatch::StartsWith)), vec!["Save", "Save As"]);
+        assert_eq!(names(UIQuery::Enabled(buttons())), vec!["Save", "Save As"]);
+        assert_eq!(names(UIQuery::Parent(Box::new(UIQuery::ByType("ToolBar".to_string())))), vec!["Save", "Cancel"]);
+        assert_eq!(names(UIQuery::Ancestor(Box::new(UIQuery::ByName("Editor".to_string())))).len(), 5);
+        assert_eq!(names(UIQuery::Descendant(Box::new(UIQuery::ByName("Cancel".to_string())))), vec!["Editor", "toolbar"]);
+        assert_eq!(names(UIQuery::Child(Box::new(UIQuery::ByType("Document".to_string())))), vec!["Editor"]);
+        assert_eq!(names(UIQuery::Nth(buttons(), 2)), vec!["Save As"]);
+        assert_eq!(names(UIQuery::Limit(buttons(), 1)), vec!["Save"]);
+        assert!(names(UIQuery::ByClassName("Button".to_string())).is_empty());
+    }
+
+    #[test]
+    fn test_loading_a_file_that_is_not_a_tree_is_an_invalid_argument() {
+        let path = std::env::temp_dir().join(format!("not_a_tree_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "{\"name\": \"Save\"}").unwrap();
+        let loaded = UITree::load(path);
+        std::fs::remove_file(path).unwrap();
+
+        assert!(matches!(loaded, Err(UiError::InvalidArgument(_))));
+        assert!(UITree::load("/nonexistent/ui_tree.json").is_err());
+    }
+
+    #[test]
+    fn test_rect_intersection_and_visible_fraction() {
+        let viewport = Rect::new(0, 0, 100, 100);
+
+        assert_eq!(Rect::new(10, 10, 30, 30).intersection(&viewport).unwrap().area(), 400);
+        assert_eq!(fraction_inside(&Rect::new(10, 10, 30, 30), &viewport), 1.0);
+        // Scrolled half out of the bottom: still not offscreen, but only half visible
+        let half = Rect::new(0, 90, 10, 110);
+        assert_eq!(half.intersection(&viewport).unwrap(), Rect::new(0, 90, 10, 100));
+        assert_eq!(fraction_inside(&half, &viewport), 0.5);
+        // Touching edges do not overlap
+        assert!(Rect::new(100, 0, 120, 10).intersection(&viewport).is_none());
+        assert_eq!(fraction_inside(&Rect::new(100, 0, 120, 10), &viewport), 0.0);
+        assert_eq!(fraction_inside(&Rect::new(5, 5, 5, 50), &viewport), 0.0);
+        assert_eq!(Rect::new(10, 10, 0, 0).area(), 0);
+    }
+
+    #[test]
+    fn test_find_many_in_one_pass_visits_each_element_once() {
+        let elements = vec![
+            FakeElement::new("Save", "Button"),
+            FakeElement::new("Name", "Edit"),
+            FakeElement::new("Cancel", "Button"),
+            FakeElement::new("Help", "Button"),
+        ];
+        let buttons = UIQuery::ByType("Button".to_string());
+        let second_button = UIQuery::Nth(Box::new(buttons.clone()), 1);
+        let first_two = UIQuery::Limit(Box::new(buttons.clone()), 2);
+        let missing = UIQuery::ByName("Close".to_string());
+
+        let mut visits = 0;
+        let found = find_many_in_one_pass(
+            &[("buttons", &buttons), ("second", &second_button), ("first_two", &first_two), ("close", &missing)],
+            |visit| {
+                for element in &elements {
+                    visits += 1;
+                    visit(element, None);
+                }
+            },
+        );
+
+        let names = |label: &str| -> Vec<String> {
+            found[label].iter().map(|element| element.get_name().unwrap()).collect()
+        };
+        assert_eq!(visits, elements.len());
+        assert_eq!(names("buttons"), ["Save", "Cancel", "Help"]);
+        assert_eq!(names("second"), ["Cancel"]);
+        assert_eq!(names("first_two"), ["Save", "Cancel"]);
+        assert!(found["close"].is_empty());
+    }
+
+    #[test]
+    fn test_sort_by_reading_order_on_a_grid() {
+        let cell = |text: &str, left: i32, top: i32| TextElementInfo {
+            bounds: Some(Rect::new(left, top, left + 80, top + 20)),
+            ..TextElementInfo::new(text.to_string())
+        };
+        // A 3x3 grid in scrambled tree order, with rows slightly out of line
+        let mut elements = vec![
+            cell("C3", 200, 103),
+            TextElementInfo::new("footer".to_string()),
+            cell("A2", 0, 52),
+            cell("B1", 100, 2),
+            cell("A1", 0, 0),
+            cell("B3", 100, 100),
+            TextElementInfo::new("status".to_string()),
+            cell("C1", 200, 4),
+            cell("A3", 0, 101),
+            cell("C2", 200, 50),
+            cell("B2", 100, 55),
+        ];
+
+        sort_by_reading_order(&mut elements, DEFAULT_ROW_TOLERANCE);
+        let order: Vec<&str> = elements.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(order, vec!["A1", "B1", "C1", "A2", "B2", "C2", "A3", "B3", "C3", "footer", "status"]);
+
+        // Without tolerance each distinct top is its own row
+        sort_by_reading_order(&mut elements, 0);
+        let order: Vec<&str> = elements.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(order, vec!["A1", "B1", "C1", "C2", "A2", "B2", "B3", "A3", "C3", "footer", "status"]);
+    }
+
+    #[test]
+    fn test_text_extraction_max_depth_is_inclusive() {
+        let options = TextExtractionOptions { max_depth: Some(2), ..TextExtractionOptions::default() };
+        assert!(options.includes_depth(2));
+        assert!(!options.includes_depth(3));
+        assert!(TextExtractionOptions::all().includes_depth(u32::MAX));
+    }
+
+    #[test]
+    fn test_find_first_by_type_stops_at_first_match() {
+        let inspected = Rc::new(Cell::new(0));
+        let element = |name: &str, control_type: &str| FakeElement {
+            inspected: inspected.clone(),
+            ..FakeElement::new(name, control_type)
+        };
+
+        let mut pane = element("Log", "Pane");
+        pane.children = (0..50).map(|i| element(&format!("Line {}", i), "Text")).collect();
+        let mut root = element("Main", "Window");
+        root.children = vec![element("OK", "Button"), pane, element("Cancel", "Button")];
+
+        let query = UIQuery::ByType("Button".to_string());
+        let found = query.find_first(&root).unwrap().expect("a button should be found");
+        assert_eq!(found.get_name().unwrap(), "OK");
+        // Only the root and its first child were inspected
+        assert_eq!(inspected.get(), 2);
+
+        inspected.set(0);
+        assert_eq!(query.find_all(&root).unwrap().len(), 2);
+        assert_eq!(inspected.get(), 54);
+
+        inspected.set(0);
+        assert!(UIQuery::ByType("Slider".to_string()).find_first(&root).unwrap().is_none());
+        assert_eq!(inspected.get(), 54);
+    }
+
+    #[test]
+    fn test_window_find_first_defaults_to_first_find_elements_match() {
+        let window = FakeWindow {
+            find: |_query: &UIQuery| vec![button_with_id("OK", 1), button_with_id("OK", 2)],
+        };
+        let found = window.find_first(&UIQuery::ByName("OK".to_string())).unwrap().unwrap();
+        assert_eq!(found.runtime_id().unwrap(), vec![42, 1]);
+
+        let empty = FakeWindow { find: |_query: &UIQuery| Vec::new() };
+        assert!(empty.find_first(&UIQuery::ByName("OK".to_string())).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_nth_and_limit_index_matches_in_pre_order() {
+        let mut toolbar = FakeElement::new("toolbar", "Pane");
+        toolbar.children = vec![FakeElement::new("Back", "Button"), FakeElement::new("Forward", "Button")];
+        let mut root = FakeElement::new("Main", "Window");
+        root.children = vec![toolbar, FakeElement::new("Go", "Button")];
+
+        let buttons = || Box::new(UIQuery::ByType("Button".to_string()));
+        let names = |elements: Vec<Box<dyn UIElement>>| -> Vec<String> {
+            elements.iter().map(|e| e.get_name().unwrap()).collect()
+        };
+
+        assert_eq!(names(UIQuery::Nth(buttons(), 0).find_all(&root).unwrap()), vec!["Back"]);
+        assert_eq!(names(UIQuery::Nth(buttons(), 2).find_all(&root).unwrap()), vec!["Go"]);
+        assert_eq!(names(UIQuery::Limit(buttons(), 2).find_all(&root).unwrap()), vec!["Back", "Forward"]);
+        assert_eq!(names(UIQuery::Limit(buttons(), 10).find_all(&root).unwrap()).len(), 3);
+
+        let second = UIQuery::Nth(buttons(), 1).find_first(&root).unwrap().unwrap();
+        assert_eq!(second.get_name().unwrap(), "Forward");
+        assert!(UIQuery::Limit(buttons(), 0).find_first(&root).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_properties_filtered_skips_unknown_keys() {
+        let element = FakeElement::new("Subject", "Edit");
+
+        let props = element.get_properties_filtered(&["name", "help_text", "control_type"]).unwrap();
+        assert_eq!(props.len(), 2);
+        assert_eq!(props.get("name").map(String::as_str), Some("Subject"));
+        assert_eq!(props.get("control_type").map(String::as_str), Some("Edit"));
+        assert!(element.get_properties_filtered(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_by_types_matches_the_same_elements_as_or_in_document_order() {
+        let mut form = FakeElement::new("form", "Pane");
+        form.children = vec![FakeElement::new("Subject", "Edit"), FakeElement::new("Send", "Button")];
+        let mut root = FakeElement::new("Main", "Window");
+        root.children = vec![FakeElement::new("Body", "Document"), form, FakeElement::new("To", "ComboBox")];
+
+        let types = ["Edit", "Document", "ComboBox"];
+        let by_types = UIQuery::ByTypes(types.iter().map(|t| t.to_string()).collect());
+        let or = UIQuery::Or(types.iter().map(|t| UIQuery::ByType(t.to_string())).collect());
+        let names = |elements: Vec<Box<dyn UIElement>>| -> Vec<String> {
+            elements.iter().map(|e| e.get_name().unwrap()).collect()
+        };
+
+        let found = names(by_types.find_all(&root).unwrap());
+        assert_eq!(found, vec!["Body", "Subject", "To"]);
+        let mut expected = names(or.find_all(&root).unwrap());
+        let mut sorted = found.clone();
+        expected.sort();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+        assert!(UIQuery::ByTypes(Vec::new()).find_all(&root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_by_name_match_modes() {
+        let mut root = FakeElement::new("Main", "Window");
+        root.children = vec![
+            FakeElement::new("Save As", "Button"),
+            FakeElement::new("Save", "Button"),
+            FakeElement::new("Auto save", "CheckBox"),
+            FakeElement::new("Réunion d'équipe", "ListItem"),
+        ];
+        let find = |pattern: &str, mode: NameMatch| -> Vec<String> {
+            UIQuery::ByNameMatch(pattern.to_string(), mode).find_all(&root).unwrap()
+                .iter().map(|e| e.get_name().unwrap()).collect()
+        };
+
+        assert_eq!(find("Save", NameMatch::Exact), vec!["Save"]);
+        assert_eq!(find("ave", NameMatch::Contains), vec!["Save As", "Save", "Auto save"]);
+        assert_eq!(find("Save", NameMatch::StartsWith), vec!["Save As", "Save"]);
+        assert_eq!(find("save", NameMatch::EndsWith), vec!["Auto save"]);
+        assert!(find("save as", NameMatch::Exact).is_empty());
+        assert_eq!(find("save as", NameMatch::CaseInsensitive), vec!["Save As"]);
+        // Case folding covers accented letters, not just ASCII
+        assert_eq!(find("RÉUNION D'ÉQUIPE", NameMatch::CaseInsensitive), vec!["Réunion d'équipe"]);
+        assert!(find("Reunion d'equipe", NameMatch::CaseInsensitive).is_empty());
+    }
+
+    #[test]
+    fn test_nth_out_of_range_returns_empty_instead_of_error() {
+        let mut root = FakeElement::new("Main", "Window");
+        root.children = vec![FakeElement::new("OK", "Button")];
+
+        let query = UIQuery::Nth(Box::new(UIQuery::ByType("Button".to_string())), 5);
+        assert!(query.find_all(&root).unwrap().is_empty());
+        assert!(query.find_first(&root).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_set_text_with_options_verify_reports_mismatch() {
+        // FakeElement accepts any text but always reads back empty
+        let element = FakeElement::new("Input", "Edit");
+
+        let trusted = TextInputOptions { correction: CorrectionMode::None, ..Default::default() };
+        assert!(element.set_text_with_options("hello", &trusted).is_ok());
+
+        let verified = TextInputOptions { verify: true, ..trusted };
+        assert!(element.set_text_with_options("hello", &verified).is_err());
+        assert!(element.set_text_with_options("", &verified).is_ok());
+    }
+
+    #[test]
+    fn test_secret_text_is_not_read_back_or_logged() {
+        let element = FakeElement::new("Password", "Edit");
+
+        // Verification would fail on a read-back, so passing means it was skipped
+        let secret = TextInputOptions { verify: true, secret: true, ..Default::default() };
+        assert!(element.set_text_with_options("hunter2", &secret).is_ok());
+        assert!(!element.is_password().unwrap());
+
+        assert_eq!(loggable_text("hunter2", true), "<redacted>");
+        assert_eq!(loggable_text("hello", false), "hello");
+    }
+
+    #[test]
+    fn test_compare_text_lists_character_mismatches() {
+        let result = compare_text("ça va être", "ca va etre");
+        assert!(!result.matches);
+        assert_eq!(result.mismatches, vec![(0, 'ç', 'c'), (6, 'ê', 'e')]);
+
+        let crlf = compare_text("line one\nline two", "line one\r\nline two");
+        assert!(crlf.matches);
+        assert!(crlf.mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_compare_text_reports_length_differences() {
+        let short = compare_text("réunion", "réu");
+        assert!(!short.matches);
+        assert!(short.mismatches.is_empty());
+        assert_eq!((short.expected_len, short.actual_len), (7, 3));
+    }
+
+    #[test]
+    fn test_index_in_parent_finds_element_among_siblings() {
+        let with_id = |name: &str, id: i32| {
+            let mut element = FakeElement::new(name, "Button");
+            element.runtime_id = vec![42, id];
+            element
+        };
+        let mut toolbar = FakeElement::new("toolbar", "Pane");
+        toolbar.children = vec![with_id("Back", 1), with_id("Forward", 2), with_id("Reload", 3)];
+
+        let forward = with_id("Forward", 2).with_parent(&toolbar);
+        assert_eq!(forward.index_in_parent().unwrap(), Some(1));
+
+        let detached = with_id("Stop", 4).with_parent(&toolbar);
+        assert_eq!(detached.index_in_parent().unwrap(), None);
+
+        assert_eq!(toolbar.index_in_parent().unwrap(), None);
+    }
+
+    #[test]
+    fn test_same_element_compares_runtime_ids() {
+        let mut first = FakeElement::new("OK", "Button");
+        first.runtime_id = vec![42, 7];
+        let mut renamed = FakeElement::new("Done", "Button");
+        renamed.runtime_id = vec![42, 7];
+        let mut twin = FakeElement::new("OK", "Button");
+        twin.runtime_id = vec![42, 8];
+
+        assert!(same_element(&first, &renamed).unwrap());
+        assert!(!same_element(&first, &twin).unwrap());
+    }
+
+    /// Chain of `total` panes ending in a button, built lazily one level at a time
+    ///
+    /// Deep enough trees would overflow the stack if they were stored nested.
+    #[derive(Clone)]
+    struct SyntheticElement {
+        index: usize,
+        total: usize,
+    }
+
+    impl SyntheticElement {
+        fn chain(total: usize) -> Self {
+            SyntheticElement { index: 0, total }
+        }
+
+        fn control_type(&self) -> &'static str {
+            if self.index + 1 == self.total { "Button" } else { "Pane" }
+        }
+    }
+
+    impl UIElement for SyntheticElement {
+        fn get_name(&self) -> Result<String, UiError> { Ok(format!("Node {}", self.index)) }
+        fn get_type(&self) -> Result<String, UiError> { Ok(self.control_type().to_string()) }
+        fn get_text(&self) -> Result<String, UiError> { Ok(String::new()) }
+        fn set_text(&self, _text: &str) -> Result<(), UiError> { Ok(()) }
+        fn append_text(&self, _text: &str, _position: AppendPosition) -> Result<(), UiError> { Ok(()) }
+        fn click(&self) -> Result<(), UiError> { Ok(()) }
+        fn right_click(&self) -> Result<(), UiError> { Ok(()) }
+        fn double_click(&self) -> Result<(), UiError> { Ok(()) }
+        fn set_focus(&self) -> Result<(), UiError> { Ok(()) }
+        fn is_enabled(&self) -> Result<bool, UiError> { Ok(true) }
+
+        fn get_properties(&self) -> Result<HashMap<String, String>, UiError> {
+            let mut properties = HashMap::new();
+            properties.insert("name".to_string(), self.get_name()?);
+            properties.insert("control_type".to_string(), self.control_type().to_string());
+            Ok(properties)
+        }
+
+        fn get_bounds(&self) -> Result<Option<Rect>, UiError> { Ok(None) }
+
+        fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+            if self.index + 1 >= self.total {
+                return Ok(Vec::new());
+            }
+            Ok(vec![Box::new(SyntheticElement { index: self.index + 1, total: self.total })])
+        }
+
+        fn get_parent(&self) -> Result<Option<Box<dyn UIElement>>, UiError> { Ok(None) }
+        fn runtime_id(&self) -> Result<Vec<i32>, UiError> { Ok(vec![self.index as i32]) }
+        fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError> { Ok(Box::new(self.clone())) }
+        fn as_any(&self) -> &dyn Any { self }
+    }
+
+    /// An element whose name and enabled state can be changed after it is handed out, like live UI
+    #[derive(Clone)]
+    struct MutableElement {
+        name: Rc<RefCell<String>>,
+        enabled: Rc<Cell<bool>>,
+    }
+
+    impl UIElement for MutableElement {
+        fn get_name(&self) -> Result<String, UiError> { Ok(self.name.borrow().clone()) }
+        fn get_type(&self) -> Result<String, UiError> { Ok("Button".to_string()) }
+        fn get_text(&self) -> Result<String, UiError> { Ok(self.name.borrow().clone()) }
+        fn set_text(&self, _text: &str) -> Result<(), UiError> { Ok(()) }
+        fn append_text(&self, _text: &str, _position: AppendPosition) -> Result<(), UiError> { Ok(()) }
+        fn click(&self) -> Result<(), UiError> { Ok(()) }
+        fn right_click(&self) -> Result<(), UiError> { Ok(()) }
+        fn double_click(&self) -> Result<(), UiError> { Ok(()) }
+        fn set_focus(&self) -> Result<(), UiError> { Ok(()) }
+        fn is_enabled(&self) -> Result<bool, UiError> { Ok(self.enabled.get()) }
+
+        fn get_properties(&self) -> Result<HashMap<String, String>, UiError> {
+            let mut properties = HashMap::new();
+            properties.insert("name".to_string(), self.get_name()?);
+            properties.insert("control_type".to_string(), "Button".to_string());
+            properties.insert("automation_id".to_string(), "save".to_string());
+            Ok(properties)
+        }
+
+        fn get_bounds(&self) -> Result<Option<Rect>, UiError> { Ok(None) }
+        fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> { Ok(Vec::new()) }
+        fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError> { Ok(Box::new(self.clone())) }
+        fn as_any(&self) -> &dyn Any { self }
+    }
+
+    #[test]
+    fn test_cached_element_keeps_its_snapshot_until_refresh() {
+        let live = MutableElement { name: Rc::new(RefCell::new("Save".to_string())), enabled: Rc::new(Cell::new(true)) };
+        let cached = live.cached().unwrap();
+
+        *live.name.borrow_mut() = "Saving...".to_string();
+        live.enabled.set(false);
+
+        assert_eq!(cached.get_name().unwrap(), "Save");
+        assert!(cached.is_enabled().unwrap());
+        assert!(UIQuery::ByName("Save".to_string()).matches(&cached).unwrap());
+        assert_eq!(cached.automation_id().as_deref(), Some("save"));
+        // Reads outside the snapshot are live
+        assert_eq!(cached.get_text().unwrap(), "Saving...");
+
+        cached.refresh().unwrap();
+        assert_eq!(cached.get_name().unwrap(), "Saving...");
+        assert!(!cached.is_enabled().unwrap());
+        assert!(!UIQuery::ByName("Save".to_string()).matches(&cached).unwrap());
+    }
+
+    #[test]
+    fn test_find_all_bounded_stops_at_node_cap_on_deep_tree() {
+        let root = SyntheticElement::chain(10_000);
+        let panes = UIQuery::ByType("Pane".to_string());
+
+        let partial = panes.find_all_bounded(&root, 1_000).unwrap();
+        assert!(partial.truncated);
+        assert_eq!(partial.elements.len(), 1_000);
+
+        let complete = panes.find_all_bounded(&root, 10_000).unwrap();
+        assert!(!complete.truncated);
+        assert_eq!(complete.elements.len(), 9_999);
+    }
+
+    #[test]
+    fn test_descendant_terminates_on_deep_tree() {
+        let root = SyntheticElement::chain(10_000);
+
+        // The button sits 9,999 levels down, right at the edge of the default budget
+        let button = UIQuery::Descendant(Box::new(UIQuery::ByType("Button".to_string())));
+        assert!(button.matches(&root).unwrap());
+
+        let deeper = SyntheticElement::chain(50_000);
+        assert!(!button.matches(&deeper).unwrap());
+        assert!(UIQuery::ByType("Button".to_string()).find_first(&deeper).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_visit_text_elements_halts_when_callback_returns_false() {
+        let root = SyntheticElement::chain(1_000);
+        let expanded = std::cell::Cell::new(0);
+        let children = |element: &SyntheticElement| -> Vec<SyntheticElement> {
+            expanded.set(expanded.get() + 1);
+            (element.index + 1 < element.total)
+                .then(|| SyntheticElement { index: element.index + 1, total: element.total })
+                .into_iter()
+                .collect()
+        };
+
+        let mut names = Vec::new();
+        let completed = visit_text_elements(
+            children(&root),
+            Some("Node 0".to_string()),
+            &TextExtractionOptions::all(),
+            |element| element.get_text_element_info().ok(),
+            children,
+            &mut |info| {
+                names.push(info.text.clone());
+                names.len() < 5
+            },
+        ).unwrap();
+
+        assert!(!completed);
+        assert_eq!(names, ["Node 1", "Node 2", "Node 3", "Node 4", "Node 5"]);
+        // One expansion for the root plus one per visited element, nothing beyond
+        assert_eq!(expanded.get(), 6);
+    }
+
+    #[test]
+    fn test_get_subtree_stops_at_max_depth() {
+        let root = SyntheticElement::chain(100);
+
+        let leaf_only = root.get_subtree(0).unwrap();
+        assert_eq!(leaf_only.name, "Node 0");
+        assert!(leaf_only.children.is_empty());
+
+        let tree = root.get_subtree(3).unwrap();
+        let mut names = vec![tree.name.clone()];
+        let mut node = &tree;
+        while let Some(child) = node.children.first() {
+            names.push(child.name.clone());
+            node = child;
+        }
+        assert_eq!(names, ["Node 0", "Node 1", "Node 2", "Node 3"]);
+        assert_eq!(tree.properties.get("control_type").map(String::as_str), Some("Pane"));
+    }
+
+    #[test]
+    fn test_build_tree_bounded_respects_node_cap() {
+        let options = UITreeOptions { max_nodes: Some(500), ..UITreeOptions::unlimited() };
+        let describe = |element: &SyntheticElement| UITreeNode {
+            name: element.get_name().unwrap(),
+            control_type: element.control_type().to_string(),
+            properties: HashMap::new(),
+            children: Vec::new(),
+            bounds: None,
+            is_enabled: true,
+            is_visible: true,
+        };
+        let children = |element: &SyntheticElement, limit: usize| -> Vec<SyntheticElement> {
+            (element.index + 1 < element.total)
+                .then(|| SyntheticElement { index: element.index + 1, total: element.total })
+                .into_iter()
+                .take(limit)
+                .collect()
+        };
+
+        let (root, truncated) = build_tree_bounded(SyntheticElement::chain(10_000), &options, describe, children).unwrap();
+        assert!(truncated);
+        assert_eq!(root.name, "Node 0");
+
+        let mut depth = 0;
+        let mut node = &root;
+        while let Some(child) = node.children.first() {
+            assert_eq!(child.name, format!("Node {}", depth + 1));
+            node = child;
+            depth += 1;
+        }
+        assert_eq!(depth, 499);
+
+        let (_, truncated) = build_tree_bounded(SyntheticElement::chain(100), &options, describe, children).unwrap();
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_cancelling_a_deep_tree_walk_from_another_thread() {
+        let cancel = CancellationToken::new();
+        let options = UITreeOptions { cancel: Some(cancel.clone()), ..UITreeOptions::unlimited() };
+        // A million nodes at 1 ms each would take a quarter of an hour
+        let describe = |element: &SyntheticElement| {
+            thread::sleep(Duration::from_millis(1));
+            describe_element(element)
+        };
+        let children = |element: &SyntheticElement, _: usize| -> Vec<SyntheticElement> {
+            (element.index + 1 < element.total)
+                .then(|| SyntheticElement { index: element.index + 1, total: element.total })
+                .into_iter()
+                .collect()
+        };
+
+        let stop = cancel.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            stop.cancel();
+        });
+        let start = Instant::now();
+        let result = build_tree_bounded(SyntheticElement::chain(1_000_000), &options, describe, children);
+        let elapsed = start.elapsed();
+        canceller.join().unwrap();
+
+        assert!(matches!(result, Err(UiError::Cancelled(_))));
+        assert!(elapsed < Duration::from_secs(2), "cancelling took {:?}", elapsed);
+        assert!(CancellationToken::new().check("walk").is_ok());
+    }
+
+    #[test]
+    fn test_find_descendants_stops_at_the_node_budget() {
+        let children = |element: &SyntheticElement| -> Vec<SyntheticElement> {
+            (element.index + 1 < element.total)
+                .then(|| SyntheticElement { index: element.index + 1, total: element.total })
+                .into_iter()
+                .collect()
+        };
+        let button = UIQuery::ByType("Button".to_string());
+
+        let found = find_descendants(&SyntheticElement::chain(100), children, &button);
+        assert_eq!(found.len(), 1);
+        // The walk gives up before reaching a button past the budget
+        assert!(find_descendants(&SyntheticElement::chain(DEFAULT_MAX_NODES + 10), children, &button).is_empty());
+
+        let third_pane = UIQuery::Nth(Box::new(UIQuery::ByType("Pane".to_string())), 2);
+        let found = find_descendants(&SyntheticElement::chain(100), children, &third_pane);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].get_name().unwrap(), "Node 3");
+    }
+}
@@ -0,0 +1,258 @@
+//! Error type shared by all UI automation operations
+//!
+//! Every trait method in [`crate::core`] returns `Result<T, UiError>`, so callers
+//! can react to specific failures (a missing element, an unsupported control
+//! pattern, a timeout) instead of matching on error strings.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use ui_interaction::error::UiError;
+//! use ui_interaction::core::ScrollDirection;
+//!
+//! match element.scroll(ScrollDirection::Down, 10.0) {
+//!     Ok(()) => {}
+//!     Err(UiError::PatternUnsupported(pattern)) => println!("Not scrollable ({} pattern missing)", pattern),
+//!     Err(e) => return Err(e.into()),
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::error::Error;
+
+/// Errors returned by UI automation operations
+///
+/// Conversions from the platform error types (and from plain strings) map to
+/// [`UiError::PlatformError`] unless the underlying error code identifies a
+/// more specific variant.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UiError {
+    /// The element no longer exists or no element matched
+    #[error("Element not found: {0}")]
+    ElementNotFound(String),
+    /// No window matched, or the window has been closed
+    #[error("Window not found: {0}")]
+    WindowNotFound(String),
+    /// The element does not support the control pattern the operation needs
+    #[error("Element does not support the {0} pattern")]
+    PatternUnsupported(String),
+    /// The operating system denied access, e.g. to an elevated process
+    #[error("Access denied: {0}")]
+    AccessDenied(String),
+    /// The operation did not complete in time
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    /// An argument was malformed, such as an invalid regex or key sequence
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    /// The operation is not available on this platform
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+    /// The caller cancelled the operation through a `CancellationToken`
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+    /// Any other failure reported by the platform
+    #[error("{0}")]
+    PlatformError(String),
+}
+
+impl UiError {
+    /// Create a `PatternUnsupported` error for the named control pattern
+    pub fn pattern_unsupported(pattern: &str) -> Self {
+        UiError::PatternUnsupported(pattern.to_string())
+    }
+
+    /// Whether repeating the same call shortly afterwards may succeed
+    ///
+    /// True only for `ElementNotFound`, which UI Automation reports when the UI
+    /// changes while a call is in flight. A `Timeout` already waited once, so
+    /// it is returned to the caller rather than waited for again. Note that
+    /// `ElementNotFound` also means "nothing matched" for some operations, so
+    /// only retry calls where a missing element cannot be the expected outcome;
+    /// see [`crate::core::with_retry`].
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, UiError::ElementNotFound(_))
+    }
+}
+
+impl From<&str> for UiError {
+    fn from(message: &str) -> Self {
+        UiError::PlatformError(message.to_string())
+    }
+}
+
+impl From<String> for UiError {
+    fn from(message: String) -> Self {
+        UiError::PlatformError(message)
+    }
+}
+
+impl From<Box<dyn Error>> for UiError {
+    fn from(error: Box<dyn Error>) -> Self {
+        match error.downcast::<UiError>() {
+            Ok(error) => *error,
+            Err(error) => UiError::PlatformError(error.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for UiError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::PermissionDenied => UiError::AccessDenied(error.to_string()),
+            std::io::ErrorKind::TimedOut => UiError::Timeout(error.to_string()),
+            _ => UiError::PlatformError(error.to_string()),
+        }
+    }
+}
+
+/// Map an HRESULT to the matching variant, if it has one
+#[cfg(target_os = "windows")]
+fn from_hresult(code: u32, message: String) -> UiError {
+    // Values from winerror.h and UIAutomationCoreApi.h
+    const E_ACCESSDENIED: u32 = 0x8007_0005;
+    const RPC_E_CALL_REJECTED: u32 = 0x8001_0001;
+    const RPC_E_SERVERCALL_RETRYLATER: u32 = 0x8001_010A;
+    const UIA_E_ELEMENTNOTAVAILABLE: u32 = 0x8004_0201;
+    const UIA_E_NOTSUPPORTED: u32 = 0x8004_0204;
+    const UIA_E_TIMEOUT: u32 = 0x8013_1505;
+
+    match code {
+        E_ACCESSDENIED => UiError::AccessDenied(message),
+        UIA_E_ELEMENTNOTAVAILABLE => UiError::ElementNotFound(message),
+        // The provider lacks the pattern or property; the message names neither
+        UIA_E_NOTSUPPORTED => UiError::Unsupported(message),
+        UIA_E_TIMEOUT => UiError::Timeout(message),
+        // The target application was too busy to answer the cross-process call
+        RPC_E_CALL_REJECTED | RPC_E_SERVERCALL_RETRYLATER => UiError::Timeout(message),
+        _ => UiError::PlatformError(message),
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl From<windows::core::Error> for UiError {
+    fn from(error: windows::core::Error) -> Self {
+        from_hresult(error.code().0 as u32, error.message().to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl From<uiautomation::Error> for UiError {
+    fn from(error: uiautomation::Error) -> Self {
+        let message = error.message().to_string();
+        match error.code() {
+            uiautomation::errors::ERR_NOTFOUND => UiError::ElementNotFound(message),
+            uiautomation::errors::ERR_TIMEOUT => UiError::Timeout(message),
+            code if code < 0 => from_hresult(code as u32, message),
+            _ => UiError::PlatformError(message),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<zbus::Error> for UiError {
+    fn from(error: zbus::Error) -> Self {
+        match error {
+            zbus::Error::FDO(fdo) => UiError::from(*fdo),
+            other => UiError::PlatformError(other.to_string()),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<zbus::fdo::Error> for UiError {
+    fn from(error: zbus::fdo::Error) -> Self {
+        match error {
+            zbus::fdo::Error::AccessDenied(message) => UiError::AccessDenied(message),
+            zbus::fdo::Error::Timeout(message) | zbus::fdo::Error::TimedOut(message) => UiError::Timeout(message),
+            zbus::fdo::Error::UnknownObject(message) | zbus::fdo::Error::ServiceUnknown(message) => {
+                UiError::ElementNotFound(message)
+            }
+            zbus::fdo::Error::UnknownInterface(message) => UiError::Unsupported(message),
+            other => UiError::PlatformError(other.to_string()),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<zbus::names::Error> for UiError {
+    fn from(error: zbus::names::Error) -> Self {
+        UiError::PlatformError(error.to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<zbus::zvariant::Error> for UiError {
+    fn from(error: zbus::zvariant::Error) -> Self {
+        UiError::PlatformError(error.to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<atspi::AtspiError> for UiError {
+    fn from(error: atspi::AtspiError) -> Self {
+        UiError::PlatformError(error.to_string())
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl From<accessibility::Error> for UiError {
+    fn from(error: accessibility::Error) -> Self {
+        use accessibility_sys::{kAXErrorAPIDisabled, kAXErrorAttributeUnsupported, kAXErrorActionUnsupported, kAXErrorInvalidUIElement};
+
+        let message = error.to_string();
+        match error {
+            accessibility::Error::NotFound => UiError::ElementNotFound(message),
+            accessibility::Error::Ax(code) if code == kAXErrorAPIDisabled => UiError::AccessDenied(message),
+            accessibility::Error::Ax(code) if code == kAXErrorInvalidUIElement => UiError::ElementNotFound(message),
+            accessibility::Error::Ax(code) if code == kAXErrorAttributeUnsupported || code == kAXErrorActionUnsupported => {
+                UiError::Unsupported(message)
+            }
+            _ => UiError::PlatformError(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_hresults_map_to_specific_variants() {
+        assert!(matches!(from_hresult(0x8007_0005, "denied".into()), UiError::AccessDenied(_)));
+        assert!(matches!(from_hresult(0x8004_0201, "gone".into()), UiError::ElementNotFound(_)));
+        assert!(matches!(from_hresult(0x8004_0204, "no pattern".into()), UiError::Unsupported(_)));
+        assert!(matches!(from_hresult(0x8013_1505, "slow".into()), UiError::Timeout(_)));
+        assert_eq!(from_hresult(0x8000_4005, "failed".into()), UiError::PlatformError("failed".into()));
+    }
+
+    #[test]
+    fn test_only_transient_failures_are_retryable() {
+        #[cfg(target_os = "windows")]
+        {
+            assert!(from_hresult(0x8004_0201, "gone".into()).is_retryable());
+            assert!(!from_hresult(0x8001_010A, "busy".into()).is_retryable());
+            assert!(!from_hresult(0x8013_1505, "slow".into()).is_retryable());
+        }
+        assert!(!UiError::pattern_unsupported("Value").is_retryable());
+        assert!(!UiError::InvalidArgument("bad regex".into()).is_retryable());
+        assert!(!UiError::AccessDenied("elevated".into()).is_retryable());
+        assert!(!UiError::PlatformError("failed".into()).is_retryable());
+    }
+
+    #[test]
+    fn test_boxed_ui_error_round_trips() {
+        let boxed: Box<dyn Error> = Box::new(UiError::Timeout("waiting for window".into()));
+        assert_eq!(UiError::from(boxed), UiError::Timeout("waiting for window".into()));
+
+        let boxed: Box<dyn Error> = "plain message".into();
+        assert_eq!(UiError::from(boxed), UiError::PlatformError("plain message".into()));
+    }
+
+    #[test]
+    fn test_messages_keep_their_text() {
+        assert_eq!(UiError::from("Element is not an input control").to_string(), "Element is not an input control");
+        assert_eq!(UiError::pattern_unsupported("Scroll").to_string(), "Element does not support the Scroll pattern");
+    }
+}
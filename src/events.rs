@@ -0,0 +1,120 @@
+//! Event subscriptions for reacting to UI changes without polling
+//!
+//! Subscriptions are created through [`UIAutomation::subscribe_focus_changed`] and
+//! [`UIAutomation::subscribe_window_opened`]. Each returns an [`EventSubscription`]
+//! that keeps the handler registered until it is dropped.
+//!
+//! # Thread safety
+//!
+//! Callbacks are invoked on a thread owned by the platform accessibility
+//! framework, never on the thread that subscribed. This is why callbacks must be
+//! `Send`. Keep them short and hand heavier work off to your own thread, for
+//! example through an `std::sync::mpsc` channel:
+//!
+//! - Events for the same subscription are delivered one at a time, so the
+//!   callback does not need to be `Sync`.
+//! - A slow callback delays every later event of that subscription.
+//! - Do not drop the subscription from inside its own callback. Unregistering
+//!   waits for running callbacks to finish, so doing it from the callback
+//!   deadlocks.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::mpsc;
+//! use ui_interaction::factory::UIAutomationFactory;
+//!
+//! let automation = UIAutomationFactory::new()?;
+//! let (sender, receiver) = mpsc::channel();
+//!
+//! let subscription = automation.subscribe_focus_changed(Box::new(move |element| {
+//!     let _ = sender.send(element.get_name().unwrap_or_default());
+//! }))?;
+//!
+//! for name in receiver.iter().take(5) {
+//!     println!("Focus moved to '{}'", name);
+//! }
+//! drop(subscription);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`UIAutomation::subscribe_focus_changed`]: crate::core::UIAutomation::subscribe_focus_changed
+//! [`UIAutomation::subscribe_window_opened`]: crate::core::UIAutomation::subscribe_window_opened
+
+use std::fmt;
+
+use crate::core::{UIElement, Window};
+
+/// Callback invoked with the element that received keyboard focus
+pub type FocusChangedCallback = Box<dyn Fn(Box<dyn UIElement>) + Send>;
+
+/// Callback invoked with each newly opened window
+pub type WindowOpenedCallback = Box<dyn Fn(Box<dyn Window>) + Send>;
+
+/// Handle to a registered event handler
+///
+/// The handler is unregistered when the subscription is dropped, or earlier by
+/// calling [`EventSubscription::unsubscribe`].
+pub struct EventSubscription {
+    unsubscribe: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl EventSubscription {
+    /// Create a subscription that runs `unsubscribe` when dropped
+    pub fn new<F: FnOnce() + Send + 'static>(unsubscribe: F) -> Self {
+        EventSubscription {
+            unsubscribe: Some(Box::new(unsubscribe)),
+        }
+    }
+
+    /// Unregister the handler now
+    pub fn unsubscribe(mut self) {
+        self.run_unsubscribe();
+    }
+
+    fn run_unsubscribe(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        self.run_unsubscribe();
+    }
+}
+
+impl fmt::Debug for EventSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSubscription")
+            .field("active", &self.unsubscribe.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_subscription_unsubscribes_exactly_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        let subscription = EventSubscription::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        drop(subscription);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let counter = calls.clone();
+        let subscription = EventSubscription::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        subscription.unsubscribe();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -7,14 +7,14 @@
 //! # Supported Platforms
 //!
 //! - **Windows**: Full support via Windows UI Automation API
-//! - **Linux**: Partial support (planned)
-//! - **macOS**: Not yet supported
+//! - **Linux**: Partial support via AT-SPI (read paths and basic text input)
+//! - **macOS**: Partial support via the Accessibility (AX) API
 //!
 //! # Example
 //!
-//! ```rust
-//! use uia_interaction::factory::{UIAutomationFactory, ApplicationManagerFactory};
-//! use uia_interaction::core::UIQuery;
+//! ```rust,ignore
+//! use ui_interaction::factory::{UIAutomationFactory, ApplicationManagerFactory};
+//! use ui_interaction::core::UIQuery;
 //!
 //! // Create automation instances
 //! let automation = UIAutomationFactory::new()?;
@@ -26,8 +26,9 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
-use std::error::Error;
-use crate::core::{UIAutomation, ApplicationManager};
+use crate::error::UiError;
+use crate::core::{UIAutomation, ApplicationManager, InteractionMode};
+use std::time::Duration;
 
 #[cfg(target_os = "windows")]
 use crate::platform::windows::{WindowsUIAutomation, WindowsApplicationManager};
@@ -35,6 +36,9 @@ use crate::platform::windows::{WindowsUIAutomation, WindowsApplicationManager};
 #[cfg(target_os = "linux")]
 use crate::platform::linux::LinuxUIAutomation;
 
+#[cfg(target_os = "macos")]
+use crate::platform::macos::MacUIAutomation;
+
 /// Factory for creating platform-specific UI automation instances
 ///
 /// This factory provides a cross-platform way to create UI automation instances.
@@ -44,14 +48,15 @@ use crate::platform::linux::LinuxUIAutomation;
 /// # Platform Support
 ///
 /// - **Windows**: Uses Windows UI Automation API for full functionality
-/// - **Linux**: Uses AT-SPI (Assistive Technology Service Provider Interface) - planned
+/// - **Linux**: Uses AT-SPI (Assistive Technology Service Provider Interface) for GTK/Qt applications
+/// - **macOS**: Uses the Accessibility (AX) API; the process needs Accessibility access
 /// - **Other platforms**: Currently unsupported
 ///
 /// # Example
 ///
-/// ```rust
-/// use uia_interaction::factory::UIAutomationFactory;
-/// use uia_interaction::core::UIQuery;
+/// ```rust,ignore
+/// use ui_interaction::factory::UIAutomationFactory;
+/// use ui_interaction::core::UIQuery;
 ///
 /// // Create automation instance (works on any supported platform)
 /// let automation = UIAutomationFactory::new()?;
@@ -91,7 +96,7 @@ impl UIAutomationFactory {
     /// # Example
     ///
     /// ```rust
-    /// use uia_interaction::factory::UIAutomationFactory;
+    /// use ui_interaction::factory::UIAutomationFactory;
     ///
     /// match UIAutomationFactory::new() {
     ///     Ok(automation) => {
@@ -105,7 +110,8 @@ impl UIAutomationFactory {
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn new() -> Result<Box<dyn UIAutomation>, Box<dyn Error>> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> Result<Box<dyn UIAutomation>, UiError> {
         #[cfg(target_os = "windows")]
         {
             Ok(Box::new(WindowsUIAutomation::new()?))
@@ -116,11 +122,96 @@ impl UIAutomationFactory {
             Ok(Box::new(LinuxUIAutomation::new()?))
         }
         
-        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+        #[cfg(target_os = "macos")]
+        {
+            Ok(Box::new(MacUIAutomation::new()?))
+        }
+        
+        #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
         {
-            Err("Unsupported platform: UI automation is currently only supported on Windows and Linux".into())
+            Err(UiError::Unsupported("UI automation is currently only supported on Windows, Linux and macOS".to_string()))
         }
     }
+
+    /// Create a UI automation instance whose elements interact in `mode`
+    ///
+    /// Use `InteractionMode::PatternsOnly` in headless contexts (CI, RDP,
+    /// locked screens) where synthesized mouse and keyboard input is dropped.
+    /// The mode can be changed later with `UIAutomation::set_interaction_mode`.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors of [`UIAutomationFactory::new`], this returns
+    /// `UiError::Unsupported` if the platform cannot honour `mode`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use ui_interaction::factory::UIAutomationFactory;
+    /// use ui_interaction::core::InteractionMode;
+    ///
+    /// let automation = UIAutomationFactory::new_with_mode(InteractionMode::PatternsOnly)?;
+    /// let window = automation.get_active_window()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_with_mode(mode: InteractionMode) -> Result<Box<dyn UIAutomation>, UiError> {
+        let automation = Self::new()?;
+        automation.set_interaction_mode(mode)?;
+        Ok(automation)
+    }
+
+    /// Create a UI automation instance whose calls give up after `timeout`
+    ///
+    /// A misbehaving application can otherwise block `find_elements`,
+    /// `get_text` and similar calls indefinitely. With a timeout they fail
+    /// with `UiError::Timeout` instead. The limit can be changed later with
+    /// `UIAutomation::set_call_timeout`, or overridden for a single call with
+    /// [`crate::core::with_call_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors of [`UIAutomationFactory::new`], this returns
+    /// `UiError::Unsupported` if the platform cannot bound its calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use ui_interaction::factory::UIAutomationFactory;
+    ///
+    /// let automation = UIAutomationFactory::new_with_timeout(Duration::from_secs(5))?;
+    /// let window = automation.get_active_window()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_with_timeout(timeout: Duration) -> Result<Box<dyn UIAutomation>, UiError> {
+        let automation = Self::new()?;
+        automation.set_call_timeout(Some(timeout))?;
+        Ok(automation)
+    }
+
+    /// Create a UI automation instance around an existing `uiautomation` client (Windows only)
+    ///
+    /// Lets applications that already use the `uiautomation` crate share
+    /// their `IUIAutomation` object with this API instead of creating a
+    /// second one, and adopt it incrementally. The client is reference
+    /// counted and stays usable by the caller; COM must already be
+    /// initialized on the calling thread. See
+    /// [`WindowsUIAutomation::from_existing`] for the threading details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ui_interaction::factory::UIAutomationFactory;
+    ///
+    /// let client = uiautomation::UIAutomation::new()?;
+    /// let automation = UIAutomationFactory::from_existing(client)?;
+    /// let window = automation.get_active_window()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(target_os = "windows")]
+    pub fn from_existing(automation: uiautomation::UIAutomation) -> Result<Box<dyn UIAutomation>, UiError> {
+        Ok(Box::new(WindowsUIAutomation::from_existing(automation)?))
+    }
 }
 
 /// Factory for creating platform-specific application manager instances
@@ -137,8 +228,8 @@ impl UIAutomationFactory {
 ///
 /// # Example
 ///
-/// ```rust
-/// use uia_interaction::factory::ApplicationManagerFactory;
+/// ```rust,ignore
+/// use ui_interaction::factory::ApplicationManagerFactory;
 ///
 /// // Create application manager (works on any supported platform)
 /// let app_manager = ApplicationManagerFactory::new()?;
@@ -177,7 +268,7 @@ impl ApplicationManagerFactory {
     /// # Example
     ///
     /// ```rust
-    /// use uia_interaction::factory::ApplicationManagerFactory;
+    /// use ui_interaction::factory::ApplicationManagerFactory;
     ///
     /// match ApplicationManagerFactory::new() {
     ///     Ok(app_manager) => {
@@ -191,7 +282,8 @@ impl ApplicationManagerFactory {
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn new() -> Result<Box<dyn ApplicationManager>, Box<dyn Error>> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> Result<Box<dyn ApplicationManager>, UiError> {
         #[cfg(target_os = "windows")]
         {
             Ok(Box::new(WindowsApplicationManager::new()?))
@@ -199,12 +291,12 @@ impl ApplicationManagerFactory {
         
         #[cfg(target_os = "linux")]
         {
-            Err("Linux application manager not implemented yet. This feature is planned for a future release.".into())
+            Err(UiError::Unsupported("Linux application manager not implemented yet. This feature is planned for a future release.".to_string()))
         }
         
         #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
-            Err("Unsupported platform: Application management is currently only supported on Windows".into())
+            Err(UiError::Unsupported("Application management is currently only supported on Windows".to_string()))
         }
     }
 } 
\ No newline at end of file
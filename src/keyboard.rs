@@ -0,0 +1,363 @@
+//! Named keys and key chords
+//!
+//! [`Window::send_keys`](crate::core::Window::send_keys) takes the backend's
+//! brace syntax, such as `{CTRL}{SHIFT}{HOME}`, where a typo in a key name
+//! only shows up when the keys are sent. [`KeyChord`] builds the same strings
+//! from typed [`Key`]s and [`Modifiers`], checking them before anything is
+//! typed:
+//!
+//! ```rust,ignore
+//! use ui_interaction::keyboard::{Key, KeyChord, Modifiers};
+//!
+//! let select_to_start = KeyChord::new(Key::Home).with(Modifiers::CTRL | Modifiers::SHIFT);
+//! assert_eq!(select_to_start.to_key_string()?, "{CTRL}{SHIFT}{HOME}");
+//! window.send_chord(&select_to_start)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+use std::ops::BitOr;
+
+use crate::error::UiError;
+
+/// A single key on the keyboard
+///
+/// Characters are typed as they are written, so `Char('A')` presses Shift
+/// as well; use lowercase letters for shortcuts such as Ctrl+A.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A printable character
+    Char(char),
+    Enter,
+    Tab,
+    Escape,
+    Space,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Left,
+    Right,
+    Up,
+    Down,
+    Pause,
+    CapsLock,
+    PrintScreen,
+    /// A function key, F1 to F24
+    F(u8),
+}
+
+/// Key names accepted by [`Key::from_name`], with the key each one maps to
+const KEY_NAMES: &[(&str, Key)] = &[
+    ("ENTER", Key::Enter),
+    ("RETURN", Key::Enter),
+    ("TAB", Key::Tab),
+    ("ESC", Key::Escape),
+    ("ESCAPE", Key::Escape),
+    ("SPACE", Key::Space),
+    ("BACK", Key::Backspace),
+    ("BACKSPACE", Key::Backspace),
+    ("DELETE", Key::Delete),
+    ("DEL", Key::Delete),
+    ("INSERT", Key::Insert),
+    ("HOME", Key::Home),
+    ("END", Key::End),
+    ("PAGE_UP", Key::PageUp),
+    ("PAGEUP", Key::PageUp),
+    ("PAGE_DOWN", Key::PageDown),
+    ("PAGEDOWN", Key::PageDown),
+    ("LEFT", Key::Left),
+    ("RIGHT", Key::Right),
+    ("UP", Key::Up),
+    ("DOWN", Key::Down),
+    ("PAUSE", Key::Pause),
+    ("CAPS_LOCK", Key::CapsLock),
+    ("CAPSLOCK", Key::CapsLock),
+    ("PRINT_SCREEN", Key::PrintScreen),
+    ("PRINTSCREEN", Key::PrintScreen),
+];
+
+impl Key {
+    /// Look up a key by name, e.g. `"Enter"`, `"page_down"` or `"F5"`
+    ///
+    /// Names are case-insensitive. A single character names itself, so
+    /// `"a"` is `Key::Char('a')`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Key)` - The named key
+    /// * `Err(UiError::InvalidArgument)` - If no key has that name
+    pub fn from_name(name: &str) -> Result<Key, UiError> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Key::Char(c).validated();
+        }
+
+        let upper = name.to_uppercase();
+        if let Some((_, key)) = KEY_NAMES.iter().find(|(known, _)| *known == upper) {
+            return Ok(*key);
+        }
+        if let Some(number) = upper.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+            return Key::F(number).validated();
+        }
+        Err(UiError::InvalidArgument(format!("Unknown key name '{}'", name)))
+    }
+
+    /// The key in the backend's `send_keys` syntax, e.g. `{HOME}` or `a`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The key as `send_keys` expects it
+    /// * `Err(UiError::InvalidArgument)` - For function keys outside F1-F24 and control characters
+    pub fn to_key_string(&self) -> Result<String, UiError> {
+        let name = match self {
+            // Braces and parentheses are syntax, so they are quoted in braces
+            Key::Char(c @ ('{' | '}' | '(' | ')')) => return Ok(format!("{{{}}}", c)),
+            Key::Char(c) if c.is_control() => {
+                return Err(UiError::InvalidArgument(format!(
+                    "Control character {:?} is not a key; use the named key instead", c
+                )));
+            }
+            Key::Char(c) => return Ok(c.to_string()),
+            Key::F(number) if (1..=24).contains(number) => return Ok(format!("{{F{}}}", number)),
+            Key::F(number) => {
+                return Err(UiError::InvalidArgument(format!("There is no function key F{}; expected F1 to F24", number)));
+            }
+            Key::Enter => "ENTER",
+            Key::Tab => "TAB",
+            Key::Escape => "ESC",
+            Key::Space => "SPACE",
+            Key::Backspace => "BACK",
+            Key::Delete => "DELETE",
+            Key::Insert => "INSERT",
+            Key::Home => "HOME",
+            Key::End => "END",
+            Key::PageUp => "PAGE_UP",
+            Key::PageDown => "PAGE_DOWN",
+            Key::Left => "LEFT",
+            Key::Right => "RIGHT",
+            Key::Up => "UP",
+            Key::Down => "DOWN",
+            Key::Pause => "PAUSE",
+            Key::CapsLock => "CAPITAL",
+            Key::PrintScreen => "PRINT",
+        };
+        Ok(format!("{{{}}}", name))
+    }
+
+    /// `self` if it can be sent, the error from `to_key_string` otherwise
+    fn validated(self) -> Result<Key, UiError> {
+        self.to_key_string().map(|_| self)
+    }
+}
+
+/// Modifier keys held down while the keys of a chord are pressed
+///
+/// Combine them with `|`, e.g. `Modifiers::CTRL | Modifiers::SHIFT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const CTRL: Modifiers = Modifiers(1);
+    pub const ALT: Modifiers = Modifiers(1 << 1);
+    pub const SHIFT: Modifiers = Modifiers(1 << 2);
+    pub const WIN: Modifiers = Modifiers(1 << 3);
+
+    /// Each modifier with its `send_keys` and display names, in the order chords press them
+    const NAMES: [(Modifiers, &'static str, &'static str); 4] = [
+        (Modifiers::CTRL, "CTRL", "Ctrl"),
+        (Modifiers::ALT, "ALT", "Alt"),
+        (Modifiers::SHIFT, "SHIFT", "Shift"),
+        (Modifiers::WIN, "WIN", "Win"),
+    ];
+
+    /// Whether every modifier in `other` is also in `self`
+    pub fn contains(&self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, other: Modifiers) -> Modifiers {
+        Modifiers(self.0 | other.0)
+    }
+}
+
+/// One or more keys pressed together while modifiers are held
+///
+/// A chord with several keys presses them in turn without releasing the
+/// modifiers, so Ctrl with `a` then `c` selects all and copies.
+///
+/// # Example
+///
+/// ```rust
+/// use ui_interaction::keyboard::{Key, KeyChord, Modifiers};
+///
+/// assert_eq!(KeyChord::new(Key::F(4)).with(Modifiers::ALT).to_key_string()?, "{ALT}{F4}");
+/// assert_eq!(KeyChord::new(Key::Char('a')).then(Key::Char('c')).with(Modifiers::CTRL).to_key_string()?, "{CTRL}(ac)");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord {
+    modifiers: Modifiers,
+    keys: Vec<Key>,
+}
+
+impl KeyChord {
+    /// A chord pressing `key` with no modifiers
+    pub fn new(key: Key) -> Self {
+        KeyChord { modifiers: Modifiers::NONE, keys: vec![key] }
+    }
+
+    /// Hold `modifiers` as well as the ones already in the chord
+    pub fn with(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = self.modifiers | modifiers;
+        self
+    }
+
+    /// Hold Ctrl
+    pub fn ctrl(self) -> Self {
+        self.with(Modifiers::CTRL)
+    }
+
+    /// Hold Alt
+    pub fn alt(self) -> Self {
+        self.with(Modifiers::ALT)
+    }
+
+    /// Hold Shift
+    pub fn shift(self) -> Self {
+        self.with(Modifiers::SHIFT)
+    }
+
+    /// Hold the Windows key
+    pub fn win(self) -> Self {
+        self.with(Modifiers::WIN)
+    }
+
+    /// Press `key` after the chord's other keys, still holding the modifiers
+    pub fn then(mut self, key: Key) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+
+    /// The chord in the backend's `send_keys` syntax
+    ///
+    /// Modifiers come first, in the order Ctrl, Alt, Shift, Win. Several
+    /// keys under modifiers are grouped in parentheses, which holds the
+    /// modifiers for the whole group.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - e.g. `{CTRL}{SHIFT}{HOME}`
+    /// * `Err(UiError::InvalidArgument)` - If one of the keys cannot be sent (see [`Key::to_key_string`])
+    pub fn to_key_string(&self) -> Result<String, UiError> {
+        let mut result = String::new();
+        for (modifier, name, _) in Modifiers::NAMES {
+            if self.modifiers.contains(modifier) {
+                result.push_str(&format!("{{{}}}", name));
+            }
+        }
+
+        let keys = self.keys.iter()
+            .map(|key| key.to_key_string())
+            .collect::<Result<String, UiError>>()?;
+        if self.keys.len() > 1 && !self.modifiers.is_empty() {
+            result.push_str(&format!("({})", keys));
+        } else {
+            result.push_str(&keys);
+        }
+        Ok(result)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    /// Writes the chord the way people read shortcuts, e.g. `Ctrl+Shift+Home`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Modifiers::NAMES.iter()
+            .filter(|(modifier, _, _)| self.modifiers.contains(*modifier))
+            .map(|(_, _, display)| display.to_string())
+            .collect();
+        parts.extend(self.keys.iter().map(|key| match key {
+            Key::Char(c) => c.to_string(),
+            Key::F(number) => format!("F{}", number),
+            other => format!("{:?}", other),
+        }));
+        write!(f, "{}", parts.join("+"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chords_map_to_exact_backend_strings() {
+        let cases = [
+            (KeyChord::new(Key::Home).ctrl().shift(), "{CTRL}{SHIFT}{HOME}"),
+            (KeyChord::new(Key::Home).shift().ctrl(), "{CTRL}{SHIFT}{HOME}"),
+            (KeyChord::new(Key::Char('s')).ctrl(), "{CTRL}s"),
+            (KeyChord::new(Key::F(4)).alt(), "{ALT}{F4}"),
+            (KeyChord::new(Key::Escape).with(Modifiers::CTRL | Modifiers::SHIFT), "{CTRL}{SHIFT}{ESC}"),
+            (KeyChord::new(Key::Char('a')).then(Key::Char('c')).ctrl(), "{CTRL}(ac)"),
+            (KeyChord::new(Key::Char('a')).then(Key::Delete), "a{DELETE}"),
+            (KeyChord::new(Key::Char('e')).win(), "{WIN}e"),
+            (KeyChord::new(Key::PageDown), "{PAGE_DOWN}"),
+            (KeyChord::new(Key::Backspace), "{BACK}"),
+        ];
+        for (chord, expected) in cases {
+            assert_eq!(chord.to_key_string().unwrap(), expected, "for {}", chord);
+        }
+    }
+
+    #[test]
+    fn test_syntax_characters_are_quoted() {
+        assert_eq!(KeyChord::new(Key::Char('{')).to_key_string().unwrap(), "{{}");
+        assert_eq!(KeyChord::new(Key::Char(')')).ctrl().to_key_string().unwrap(), "{CTRL}{)}");
+        assert_eq!(KeyChord::new(Key::Char('é')).to_key_string().unwrap(), "é");
+    }
+
+    #[test]
+    fn test_invalid_keys_are_rejected() {
+        assert!(matches!(KeyChord::new(Key::F(0)).to_key_string(), Err(UiError::InvalidArgument(_))));
+        assert!(matches!(KeyChord::new(Key::F(25)).ctrl().to_key_string(), Err(UiError::InvalidArgument(_))));
+        assert!(matches!(KeyChord::new(Key::Char('\n')).to_key_string(), Err(UiError::InvalidArgument(_))));
+        assert!(KeyChord::new(Key::F(24)).to_key_string().is_ok());
+    }
+
+    #[test]
+    fn test_key_names() {
+        assert_eq!(Key::from_name("Enter").unwrap(), Key::Enter);
+        assert_eq!(Key::from_name("page_down").unwrap(), Key::PageDown);
+        assert_eq!(Key::from_name("f12").unwrap(), Key::F(12));
+        assert_eq!(Key::from_name("a").unwrap(), Key::Char('a'));
+        assert_eq!(Key::from_name("A").unwrap(), Key::Char('A'));
+        assert!(Key::from_name("F30").is_err());
+        assert!(Key::from_name("Entr").is_err());
+        assert!(Key::from_name("").is_err());
+    }
+
+    #[test]
+    fn test_display_reads_like_a_shortcut() {
+        assert_eq!(KeyChord::new(Key::Home).ctrl().shift().to_string(), "Ctrl+Shift+Home");
+        assert_eq!(KeyChord::new(Key::F(4)).alt().to_string(), "Alt+F4");
+    }
+}
@@ -0,0 +1,73 @@
+use crate::error::UiError;
+use log::{debug, info};
+use atspi::State;
+use atspi::connection::AccessibilityConnection;
+use futures_lite::future::block_on;
+
+use crate::core::{Window, UIAutomation, UIElement, UIQuery};
+use super::element::LinuxUIElement;
+use super::window::LinuxWindow;
+
+/// UI automation backed by the AT-SPI accessibility bus
+///
+/// Works with any toolkit that exposes AT-SPI (GTK, Qt, Electron with
+/// accessibility enabled, ...). Requires a running accessibility bus.
+pub struct LinuxUIAutomation {
+    connection: AccessibilityConnection,
+}
+
+impl LinuxUIAutomation {
+    pub fn new() -> Result<Self, UiError> {
+        let connection = block_on(AccessibilityConnection::new())
+            .map_err(|e| format!("Failed to connect to the AT-SPI bus: {}", e))?;
+        info!("Connected to the AT-SPI accessibility bus");
+        Ok(LinuxUIAutomation { connection })
+    }
+
+    /// Get every top-level window of every registered application
+    fn get_top_level_windows(&self) -> Result<Vec<LinuxUIElement>, UiError> {
+        let desktop = LinuxUIElement::desktop(&self.connection)?;
+        let mut windows = Vec::new();
+        for application in desktop.get_linux_children()? {
+            match application.get_linux_children() {
+                Ok(children) => windows.extend(children),
+                Err(e) => debug!("Skipping unresponsive application: {}", e),
+            }
+        }
+        Ok(windows)
+    }
+}
+
+impl UIAutomation for LinuxUIAutomation {
+    fn get_active_window(&self) -> Result<Box<dyn Window>, UiError> {
+        self.get_top_level_windows()?
+            .into_iter()
+            .find(|window| window.has_state(State::Active))
+            .map(|window| Box::new(LinuxWindow::new(window)) as Box<dyn Window>)
+            .ok_or_else(|| UiError::WindowNotFound("No active window found".to_string()))
+    }
+
+    fn get_window_containing_focus(&self) -> Result<Box<dyn Window>, UiError> {
+        self.get_active_window()
+    }
+
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError> {
+        self.get_active_window()?.get_focused_element()
+    }
+
+    fn find_element_by_name(&self, name: &str) -> Result<Box<dyn UIElement>, UiError> {
+        self.get_active_window()?
+            .find_elements(&UIQuery::ByName(name.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| UiError::ElementNotFound(format!("Element with name '{}' not found", name)))
+    }
+
+    fn find_element_by_type(&self, element_type: &str) -> Result<Box<dyn UIElement>, UiError> {
+        self.get_active_window()?
+            .find_elements(&UIQuery::ByType(element_type.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| UiError::ElementNotFound(format!("Element of type '{}' not found", element_type)))
+    }
+}
@@ -0,0 +1,389 @@
+use std::any::Any;
+use std::collections::HashMap;
+use crate::error::UiError;
+use log::{debug, warn};
+use atspi::{CoordType, Role, State, StateSet};
+use atspi::connection::AccessibilityConnection;
+use atspi::ObjectRef;
+use atspi::proxy::accessible::AccessibleProxy;
+use atspi::proxy::action::ActionProxy;
+use atspi::proxy::component::ComponentProxy;
+use atspi::proxy::editable_text::EditableTextProxy;
+use atspi::proxy::text::TextProxy;
+use futures_lite::future::block_on;
+use zbus::CacheProperties;
+
+use crate::core::{UIElement, AppendPosition, Rect, ExpandState, Orientation};
+
+/// Map an AT-SPI role to the control type names used by the Windows backend
+///
+/// Keeping the names aligned means queries such as `UIQuery::ByType("Edit")`
+/// work unchanged on both platforms.
+pub(crate) fn role_to_control_type(role: Role) -> &'static str {
+    match role {
+        Role::Frame | Role::Window | Role::Dialog => "Window",
+        Role::PushButton | Role::ToggleButton => "Button",
+        Role::Entry | Role::PasswordText | Role::Text => "Edit",
+        Role::DocumentText | Role::DocumentFrame | Role::DocumentWeb => "Document",
+        Role::Label | Role::Static | Role::Caption | Role::Paragraph | Role::Heading => "Text",
+        Role::CheckBox => "CheckBox",
+        Role::RadioButton => "RadioButton",
+        Role::ComboBox => "ComboBox",
+        Role::List | Role::ListBox => "List",
+        Role::ListItem => "ListItem",
+        Role::Menu => "Menu",
+        Role::MenuItem | Role::CheckMenuItem | Role::RadioMenuItem => "MenuItem",
+        Role::MenuBar => "MenuBar",
+        Role::PageTabList => "Tab",
+        Role::PageTab => "TabItem",
+        Role::Table => "Table",
+        Role::TableCell => "DataItem",
+        Role::Tree | Role::TreeTable => "Tree",
+        Role::TreeItem => "TreeItem",
+        Role::ToolBar => "ToolBar",
+        Role::ToolTip => "ToolTip",
+        Role::ScrollBar => "ScrollBar",
+        Role::ProgressBar => "ProgressBar",
+        Role::Slider => "Slider",
+        Role::SpinButton => "Spinner",
+        Role::Separator => "Separator",
+        Role::Image | Role::Icon => "Image",
+        Role::Link => "Hyperlink",
+        Role::StatusBar => "StatusBar",
+        Role::Grouping | Role::Section | Role::Form => "Group",
+        Role::Panel | Role::Filler | Role::ScrollPane | Role::Viewport
+        | Role::SplitPane | Role::LayeredPane | Role::RootPane => "Pane",
+        _ => "Custom",
+    }
+}
+
+/// An accessible object exposed over AT-SPI
+///
+/// Elements are addressed by their bus name and object path; proxies for the
+/// individual AT-SPI interfaces are created on demand.
+#[derive(Clone)]
+pub struct LinuxUIElement {
+    connection: zbus::Connection,
+    object: ObjectRef,
+}
+
+impl LinuxUIElement {
+    pub fn new(connection: zbus::Connection, object: ObjectRef) -> Self {
+        LinuxUIElement { connection, object }
+    }
+
+    /// Create an element for the desktop root exposed by the AT-SPI registry
+    pub fn desktop(connection: &AccessibilityConnection) -> Result<Self, UiError> {
+        let object = ObjectRef {
+            name: "org.a11y.atspi.Registry".try_into()?,
+            path: "/org/a11y/atspi/accessible/root".try_into()?,
+        };
+        Ok(LinuxUIElement::new(connection.connection().clone(), object))
+    }
+
+    pub(crate) fn connection(&self) -> &zbus::Connection {
+        &self.connection
+    }
+
+    pub(crate) fn object(&self) -> &ObjectRef {
+        &self.object
+    }
+
+    pub(crate) fn accessible(&self) -> Result<AccessibleProxy<'static>, UiError> {
+        Ok(block_on(
+            AccessibleProxy::builder(&self.connection)
+                .destination(self.object.name.clone())?
+                .path(self.object.path.clone())?
+                .cache_properties(CacheProperties::No)
+                .build(),
+        )?)
+    }
+
+    fn text(&self) -> Result<TextProxy<'static>, UiError> {
+        Ok(block_on(
+            TextProxy::builder(&self.connection)
+                .destination(self.object.name.clone())?
+                .path(self.object.path.clone())?
+                .cache_properties(CacheProperties::No)
+                .build(),
+        )?)
+    }
+
+    fn editable_text(&self) -> Result<EditableTextProxy<'static>, UiError> {
+        Ok(block_on(
+            EditableTextProxy::builder(&self.connection)
+                .destination(self.object.name.clone())?
+                .path(self.object.path.clone())?
+                .cache_properties(CacheProperties::No)
+                .build(),
+        )?)
+    }
+
+    pub(crate) fn component(&self) -> Result<ComponentProxy<'static>, UiError> {
+        Ok(block_on(
+            ComponentProxy::builder(&self.connection)
+                .destination(self.object.name.clone())?
+                .path(self.object.path.clone())?
+                .cache_properties(CacheProperties::No)
+                .build(),
+        )?)
+    }
+
+    fn action(&self) -> Result<ActionProxy<'static>, UiError> {
+        Ok(block_on(
+            ActionProxy::builder(&self.connection)
+                .destination(self.object.name.clone())?
+                .path(self.object.path.clone())?
+                .cache_properties(CacheProperties::No)
+                .build(),
+        )?)
+    }
+
+    pub fn get_role(&self) -> Result<Role, UiError> {
+        Ok(block_on(self.accessible()?.get_role())?)
+    }
+
+    pub fn get_state(&self) -> Result<StateSet, UiError> {
+        Ok(block_on(self.accessible()?.get_state())?)
+    }
+
+    pub fn has_state(&self, state: State) -> bool {
+        self.get_state().map(|states| states.contains(state)).unwrap_or(false)
+    }
+
+    /// Get the child elements as concrete Linux elements
+    pub fn get_linux_children(&self) -> Result<Vec<LinuxUIElement>, UiError> {
+        let children = block_on(self.accessible()?.get_children())?;
+        Ok(children.into_iter()
+            .map(|object| LinuxUIElement::new(self.connection.clone(), object))
+            .collect())
+    }
+
+    /// Get the full text content through the Text interface
+    fn read_text(&self) -> Result<String, UiError> {
+        let text = self.text()?;
+        let count = block_on(text.character_count())?;
+        Ok(block_on(text.get_text(0, count))?)
+    }
+
+    fn insert_text(&self, offset: i32, text: &str) -> Result<(), UiError> {
+        let length = text.chars().count() as i32;
+        if block_on(self.editable_text()?.insert_text(offset, text, length))? {
+            Ok(())
+        } else {
+            Err("Element rejected the text insertion".into())
+        }
+    }
+}
+
+impl UIElement for LinuxUIElement {
+    fn get_name(&self) -> Result<String, UiError> {
+        Ok(block_on(self.accessible()?.name())?)
+    }
+
+    fn get_type(&self) -> Result<String, UiError> {
+        Ok(role_to_control_type(self.get_role()?).to_string())
+    }
+
+    fn get_localized_control_type(&self) -> Result<String, UiError> {
+        Ok(block_on(self.accessible()?.get_localized_role_name())?)
+    }
+
+    fn get_text(&self) -> Result<String, UiError> {
+        match self.read_text() {
+            Ok(text) => Ok(text),
+            Err(e) => {
+                debug!("LinuxUIElement::get_text - Text interface unavailable ({}), using name", e);
+                self.get_name()
+            }
+        }
+    }
+
+    fn set_text(&self, text: &str) -> Result<(), UiError> {
+        if block_on(self.editable_text()?.set_text_contents(text))? {
+            Ok(())
+        } else {
+            Err("Element rejected the new text contents".into())
+        }
+    }
+
+    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), UiError> {
+        let text_proxy = self.text()?;
+        let offset = match position {
+            AppendPosition::EndOfText => block_on(text_proxy.character_count())?,
+            AppendPosition::CurrentCursor => block_on(text_proxy.caret_offset())?,
+            AppendPosition::EndOfLine => {
+                let caret = block_on(text_proxy.caret_offset())?;
+                let content = self.read_text()?;
+                let to_line_end = content.chars()
+                    .skip(caret.max(0) as usize)
+                    .take_while(|c| *c != '\n')
+                    .count() as i32;
+                caret + to_line_end
+            }
+        };
+        self.insert_text(offset, text)
+    }
+
+    fn click(&self) -> Result<(), UiError> {
+        // Action 0 is the default action ("click", "press", "activate", ...)
+        if block_on(self.action()?.do_action(0))? {
+            Ok(())
+        } else {
+            Err("Element has no default action to perform".into())
+        }
+    }
+
+    fn invoke(&self) -> Result<(), UiError> {
+        // The default action never involves the pointer
+        self.click()
+    }
+
+    fn right_click(&self) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Right click is not supported on Linux yet".to_string()))
+    }
+
+    fn double_click(&self) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Double click is not supported on Linux yet".to_string()))
+    }
+
+    fn set_focus(&self) -> Result<(), UiError> {
+        if block_on(self.component()?.grab_focus())? {
+            Ok(())
+        } else {
+            Err("Element cannot accept focus".into())
+        }
+    }
+
+    fn is_enabled(&self) -> Result<bool, UiError> {
+        Ok(self.get_state()?.contains(State::Enabled))
+    }
+
+    fn is_offscreen(&self) -> Result<bool, UiError> {
+        Ok(!self.get_state()?.contains(State::Showing))
+    }
+
+    fn get_expand_state(&self) -> Result<Option<ExpandState>, UiError> {
+        let states = self.get_state()?;
+        if !states.contains(State::Expandable) {
+            return Ok(None);
+        }
+        Ok(Some(if states.contains(State::Expanded) { ExpandState::Expanded } else { ExpandState::Collapsed }))
+    }
+
+    fn expand(&self) -> Result<(), UiError> {
+        // Toolkits toggle expandable rows and menus with their default action
+        match self.get_expand_state()? {
+            Some(ExpandState::Expanded) => Ok(()),
+            Some(_) => self.click(),
+            None => Err(UiError::pattern_unsupported("ExpandCollapse")),
+        }
+    }
+
+    fn collapse(&self) -> Result<(), UiError> {
+        match self.get_expand_state()? {
+            Some(ExpandState::Expanded) => self.click(),
+            Some(_) => Ok(()),
+            None => Err(UiError::pattern_unsupported("ExpandCollapse")),
+        }
+    }
+
+    fn is_editable(&self) -> Result<bool, UiError> {
+        Ok(self.get_state()?.contains(State::Editable))
+    }
+
+    fn is_password(&self) -> Result<bool, UiError> {
+        Ok(self.get_role()? == Role::PasswordText)
+    }
+
+    fn is_focusable(&self) -> Result<bool, UiError> {
+        Ok(self.get_state()?.contains(State::Focusable))
+    }
+
+    fn get_orientation(&self) -> Result<Orientation, UiError> {
+        let states = self.get_state()?;
+        Ok(if states.contains(State::Horizontal) {
+            Orientation::Horizontal
+        } else if states.contains(State::Vertical) {
+            Orientation::Vertical
+        } else {
+            Orientation::None
+        })
+    }
+
+    fn get_properties(&self) -> Result<HashMap<String, String>, UiError> {
+        let mut properties = HashMap::new();
+
+        if let Ok(name) = self.get_name() {
+            properties.insert("name".to_string(), name);
+        }
+        if let Ok(role) = self.get_role() {
+            properties.insert("control_type".to_string(), role_to_control_type(role).to_string());
+        }
+        if let Ok(states) = self.get_state() {
+            properties.insert("enabled".to_string(), states.contains(State::Enabled).to_string());
+        }
+
+        Ok(properties)
+    }
+
+    fn get_bounds(&self) -> Result<Option<Rect>, UiError> {
+        match block_on(self.component()?.get_extents(CoordType::Screen)) {
+            Ok((x, y, width, height)) => Ok(Some(Rect::new(x, y, x + width, y + height))),
+            Err(e) => {
+                debug!("LinuxUIElement::get_bounds - Component interface unavailable: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        Ok(self.get_linux_children()?
+            .into_iter()
+            .map(|child| Box::new(child) as Box<dyn UIElement>)
+            .collect())
+    }
+
+    fn get_parent(&self) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        let parent = match block_on(self.accessible()?.parent()) {
+            Ok(parent) => parent,
+            Err(_) => return Ok(None),
+        };
+
+        // The desktop root reports a null path as its parent
+        if parent.path.as_str() == "/org/a11y/atspi/null" {
+            return Ok(None);
+        }
+        if parent == self.object {
+            warn!("LinuxUIElement::get_parent - element reports itself as its parent");
+            return Ok(None);
+        }
+
+        Ok(Some(Box::new(LinuxUIElement::new(self.connection.clone(), parent))))
+    }
+
+    fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError> {
+        Ok(Box::new(self.clone()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_map_to_windows_control_type_names() {
+        assert_eq!(role_to_control_type(Role::PushButton), "Button");
+        assert_eq!(role_to_control_type(Role::Entry), "Edit");
+        assert_eq!(role_to_control_type(Role::PasswordText), "Edit");
+        assert_eq!(role_to_control_type(Role::DocumentText), "Document");
+        assert_eq!(role_to_control_type(Role::Frame), "Window");
+        assert_eq!(role_to_control_type(Role::Label), "Text");
+        assert_eq!(role_to_control_type(Role::PageTab), "TabItem");
+        assert_eq!(role_to_control_type(Role::Unknown), "Custom");
+    }
+}
@@ -0,0 +1,7 @@
+pub mod automation;
+pub mod window;
+pub mod element;
+
+pub use automation::LinuxUIAutomation;
+pub use window::LinuxWindow;
+pub use element::LinuxUIElement;
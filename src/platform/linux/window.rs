@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use crate::error::UiError;
+use std::fs;
+use chrono::Utc;
+use log::debug;
+use atspi::State;
+use atspi::proxy::application::ApplicationProxy;
+use futures_lite::future::block_on;
+use zbus::CacheProperties;
+use zbus::fdo::DBusProxy;
+
+use crate::core::{
+    Rect, Window, UIElement, UIQuery, UITree, TextElementInfo, TextExtractionOptions, UITreeOptions,
+    build_tree_bounded, describe_tree_node, find_descendants, find_many_in_one_pass, find_with_paths, for_each_descendant,
+    resolve_path, visit_text_elements,
+};
+use super::element::LinuxUIElement;
+
+/// A top-level frame or dialog exposed over AT-SPI
+#[derive(Clone)]
+pub struct LinuxWindow {
+    element: LinuxUIElement,
+}
+
+impl LinuxWindow {
+    pub fn new(element: LinuxUIElement) -> Self {
+        LinuxWindow { element }
+    }
+
+    fn unsupported(operation: &str) -> UiError {
+        UiError::Unsupported(format!("{} is not supported by the Linux AT-SPI backend yet", operation))
+    }
+}
+
+/// The children of `element`, or none when they cannot be read
+fn children(element: &LinuxUIElement) -> Vec<LinuxUIElement> {
+    element.get_linux_children().unwrap_or_default()
+}
+
+impl Window for LinuxWindow {
+    fn get_title(&self) -> Result<String, UiError> {
+        self.element.get_name()
+    }
+
+    fn window_id(&self) -> Result<u64, UiError> {
+        // The bus name and object path identify the accessible object for as long as it lives
+        let object = self.element.object();
+        let mut hasher = DefaultHasher::new();
+        object.name.as_str().hash(&mut hasher);
+        object.path.as_str().hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
+    fn get_class_name(&self) -> Result<String, UiError> {
+        // AT-SPI has no window classes; the toolkit name is the closest equivalent
+        let application = block_on(self.element.accessible()?.get_application())?;
+        let proxy = block_on(
+            ApplicationProxy::builder(self.element.connection())
+                .destination(application.name)?
+                .path(application.path)?
+                .cache_properties(CacheProperties::No)
+                .build(),
+        )?;
+        Ok(block_on(proxy.toolkit_name())?)
+    }
+
+    fn get_process_id(&self) -> Result<u32, UiError> {
+        let dbus = block_on(DBusProxy::new(self.element.connection()))?;
+        let bus_name = self.element.object().name.clone().into_inner().into();
+        Ok(block_on(dbus.get_connection_unix_process_id(bus_name))?)
+    }
+
+    fn get_thread_id(&self) -> Result<u32, UiError> {
+        Err(Self::unsupported("Getting the window thread id"))
+    }
+
+    fn get_process_name(&self) -> Result<String, UiError> {
+        let pid = self.get_process_id()?;
+        Ok(fs::read_to_string(format!("/proc/{}/comm", pid))?.trim_end().to_string())
+    }
+
+    fn get_process_path(&self) -> Result<String, UiError> {
+        let pid = self.get_process_id()?;
+        Ok(fs::read_link(format!("/proc/{}/exe", pid))?.to_string_lossy().into_owned())
+    }
+
+    fn is_visible(&self) -> Result<bool, UiError> {
+        Ok(self.element.get_state()?.contains(State::Showing))
+    }
+
+    fn is_minimized(&self) -> Result<bool, UiError> {
+        Ok(self.element.get_state()?.contains(State::Iconified))
+    }
+
+    fn is_maximized(&self) -> Result<bool, UiError> {
+        Err(Self::unsupported("Querying the maximized state"))
+    }
+
+    fn get_rect(&self) -> Result<Rect, UiError> {
+        Ok(self.element.get_bounds()?
+            .ok_or("Window does not expose its extents")?)
+    }
+
+    fn get_dpi(&self) -> Result<u32, UiError> {
+        // AT-SPI reports coordinates in logical pixels
+        Ok(96)
+    }
+
+    fn activate(&self) -> Result<(), UiError> {
+        if block_on(self.element.component()?.grab_focus())? {
+            Ok(())
+        } else {
+            Err("Window refused to take focus".into())
+        }
+    }
+
+    fn bring_to_top(&self) -> Result<(), UiError> {
+        self.activate()
+    }
+
+    fn set_foreground(&self) -> Result<(), UiError> {
+        self.activate()
+    }
+
+    fn minimize(&self) -> Result<(), UiError> {
+        Err(Self::unsupported("Minimizing windows"))
+    }
+
+    fn maximize(&self) -> Result<(), UiError> {
+        Err(Self::unsupported("Maximizing windows"))
+    }
+
+    fn restore(&self) -> Result<(), UiError> {
+        Err(Self::unsupported("Restoring windows"))
+    }
+
+    fn close(&self) -> Result<(), UiError> {
+        Err(Self::unsupported("Closing windows"))
+    }
+
+    fn send_keys(&self, _keys: &str) -> Result<(), UiError> {
+        Err(Self::unsupported("Sending key sequences"))
+    }
+
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError> {
+        let mut focused = None;
+        for_each_descendant(&self.element, children, |element| {
+            if element.has_state(State::Focused) {
+                focused = Some(element.clone());
+                return false;
+            }
+            true
+        });
+
+        focused
+            .map(|element| Box::new(element) as Box<dyn UIElement>)
+            .ok_or_else(|| UiError::ElementNotFound("No focused element found in window".to_string()))
+    }
+
+    fn get_ui_tree_with_options(&self, options: &UITreeOptions) -> Result<UITree, UiError> {
+        let (root, truncated) = build_tree_bounded(
+            self.element.clone(),
+            options,
+            |element| describe_tree_node(element, options, element.has_state(State::Showing)),
+            |element, limit| children(element).into_iter().take(limit).collect(),
+        )?;
+        Ok(UITree {
+            root,
+            timestamp: Utc::now(),
+            window_title: self.get_title().unwrap_or_default(),
+            window_class: self.get_class_name().unwrap_or_default(),
+            truncated,
+        })
+    }
+
+    fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        let results = find_descendants(&self.element, children, query);
+        debug!("LinuxWindow::find_elements - {} matches for {:?}", results.len(), query);
+        Ok(results)
+    }
+
+    fn find_many(&self, queries: &HashMap<String, UIQuery>) -> Result<HashMap<String, Vec<Box<dyn UIElement>>>, UiError> {
+        // find_elements tests every descendant with `UIQuery::matches`, so every query can share the walk
+        let queries: Vec<(&str, &UIQuery)> = queries.iter().map(|(label, query)| (label.as_str(), query)).collect();
+        Ok(find_many_in_one_pass(&queries, |visit| {
+            for_each_descendant(&self.element, children, |element| {
+                visit(element, None);
+                true
+            });
+        }))
+    }
+
+    fn find_elements_with_path(&self, query: &UIQuery) -> Result<Vec<(Box<dyn UIElement>, Vec<String>)>, UiError> {
+        let found = find_with_paths(&self.element, query, |_| None);
+        debug!("LinuxWindow::find_elements_with_path - {} matches for {:?}", found.len(), query);
+        Ok(found)
+    }
+
+    fn find_by_path(&self, path: &[String]) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        resolve_path(Box::new(self.element.clone()), path)
+    }
+
+    fn for_each_text_element(&self, options: &TextExtractionOptions, f: &mut dyn FnMut(&TextElementInfo) -> bool) -> Result<(), UiError> {
+        visit_text_elements(
+            children(&self.element),
+            self.element.get_name().ok(),
+            options,
+            |element| {
+                let mut info = element.get_text_element_info().ok()?;
+                info.is_visible = element.has_state(State::Showing);
+                Some(info)
+            },
+            children,
+            f,
+        )?;
+        Ok(())
+    }
+}
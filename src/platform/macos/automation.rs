@@ -0,0 +1,71 @@
+use crate::error::UiError;
+use log::info;
+use accessibility::AXUIElement;
+use accessibility_sys::AXIsProcessTrusted;
+
+use crate::core::{Window, UIAutomation, UIElement, UIQuery};
+use super::element::{MacUIElement, AX_FOCUSED_UI_ELEMENT};
+use super::window::MacWindow;
+
+/// UI automation backed by the macOS Accessibility (AX) API
+///
+/// The host process (usually the terminal running Python or the binary
+/// itself) must be granted access under System Settings > Privacy & Security >
+/// Accessibility.
+pub struct MacUIAutomation;
+
+impl MacUIAutomation {
+    pub fn new() -> Result<Self, UiError> {
+        if !unsafe { AXIsProcessTrusted() } {
+            return Err(UiError::AccessDenied(
+                "This process has not been granted Accessibility access in System Settings".to_string(),
+            ));
+        }
+        info!("Accessibility access granted, using the AX API");
+        Ok(MacUIAutomation)
+    }
+
+    /// The application that currently owns keyboard focus
+    fn focused_application(&self) -> Result<MacUIElement, UiError> {
+        let system = MacUIElement::new(AXUIElement::system_wide());
+        let application = system.attribute::<AXUIElement>("AXFocusedApplication")
+            .map_err(|_| UiError::WindowNotFound("No application has focus".to_string()))?;
+        Ok(MacUIElement::new(application))
+    }
+}
+
+impl UIAutomation for MacUIAutomation {
+    fn get_active_window(&self) -> Result<Box<dyn Window>, UiError> {
+        let window = self.focused_application()?
+            .attribute::<AXUIElement>("AXFocusedWindow")
+            .map_err(|_| UiError::WindowNotFound("No active window found".to_string()))?;
+        Ok(Box::new(MacWindow::new(MacUIElement::new(window))))
+    }
+
+    fn get_window_containing_focus(&self) -> Result<Box<dyn Window>, UiError> {
+        self.get_active_window()
+    }
+
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError> {
+        let system = MacUIElement::new(AXUIElement::system_wide());
+        let focused = system.attribute::<AXUIElement>(AX_FOCUSED_UI_ELEMENT)
+            .map_err(|_| UiError::ElementNotFound("No focused element found".to_string()))?;
+        Ok(Box::new(MacUIElement::new(focused)))
+    }
+
+    fn find_element_by_name(&self, name: &str) -> Result<Box<dyn UIElement>, UiError> {
+        self.get_active_window()?
+            .find_elements(&UIQuery::ByName(name.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| UiError::ElementNotFound(format!("Element with name '{}' not found", name)))
+    }
+
+    fn find_element_by_type(&self, element_type: &str) -> Result<Box<dyn UIElement>, UiError> {
+        self.get_active_window()?
+            .find_elements(&UIQuery::ByType(element_type.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| UiError::ElementNotFound(format!("Element of type '{}' not found", element_type)))
+    }
+}
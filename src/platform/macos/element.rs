@@ -0,0 +1,280 @@
+use std::any::Any;
+use std::collections::HashMap;
+use crate::error::UiError;
+use log::debug;
+use accessibility::{AXAttribute, AXUIElement};
+use core_foundation::array::CFArray;
+use core_foundation::base::{CFType, TCFType};
+use core_foundation::boolean::CFBoolean;
+use core_foundation::string::CFString;
+
+use crate::core::{UIElement, AppendPosition, Rect, Orientation};
+
+pub(crate) const AX_ROLE: &str = "AXRole";
+pub(crate) const AX_ROLE_DESCRIPTION: &str = "AXRoleDescription";
+pub(crate) const AX_SUBROLE: &str = "AXSubrole";
+pub(crate) const AX_TITLE: &str = "AXTitle";
+pub(crate) const AX_DESCRIPTION: &str = "AXDescription";
+pub(crate) const AX_VALUE: &str = "AXValue";
+pub(crate) const AX_CHILDREN: &str = "AXChildren";
+pub(crate) const AX_PARENT: &str = "AXParent";
+pub(crate) const AX_ENABLED: &str = "AXEnabled";
+pub(crate) const AX_FOCUSED: &str = "AXFocused";
+pub(crate) const AX_FOCUSED_UI_ELEMENT: &str = "AXFocusedUIElement";
+pub(crate) const AX_ORIENTATION: &str = "AXOrientation";
+/// The subrole of text fields that hide their contents
+const AX_SECURE_TEXT_FIELD: &str = "AXSecureTextField";
+
+/// Map an AX role to the control type names used by the Windows backend
+///
+/// Keeping the names aligned means queries such as `UIQuery::ByType("Edit")`
+/// work unchanged on every platform.
+pub(crate) fn role_to_control_type(role: &str) -> &'static str {
+    match role {
+        "AXWindow" | "AXSheet" | "AXDrawer" => "Window",
+        "AXButton" | "AXDisclosureTriangle" => "Button",
+        "AXTextField" | "AXTextArea" => "Edit",
+        "AXWebArea" => "Document",
+        "AXStaticText" | "AXHeading" => "Text",
+        "AXCheckBox" => "CheckBox",
+        "AXRadioButton" => "RadioButton",
+        "AXComboBox" | "AXPopUpButton" => "ComboBox",
+        "AXList" => "List",
+        "AXMenu" => "Menu",
+        "AXMenuItem" | "AXMenuBarItem" => "MenuItem",
+        "AXMenuBar" => "MenuBar",
+        "AXTabGroup" => "Tab",
+        "AXTable" => "Table",
+        "AXRow" | "AXCell" => "DataItem",
+        "AXOutline" | "AXBrowser" => "Tree",
+        "AXToolbar" => "ToolBar",
+        "AXHelpTag" => "ToolTip",
+        "AXScrollBar" => "ScrollBar",
+        "AXProgressIndicator" | "AXBusyIndicator" => "ProgressBar",
+        "AXSlider" => "Slider",
+        "AXIncrementor" => "Spinner",
+        "AXSplitter" => "Separator",
+        "AXImage" => "Image",
+        "AXLink" => "Hyperlink",
+        "AXGroup" | "AXRadioGroup" => "Group",
+        "AXScrollArea" | "AXSplitGroup" | "AXLayoutArea" => "Pane",
+        _ => "Custom",
+    }
+}
+
+/// An accessibility object exposed through the macOS Accessibility (AX) API
+#[derive(Clone)]
+pub struct MacUIElement {
+    element: AXUIElement,
+}
+
+impl MacUIElement {
+    pub fn new(element: AXUIElement) -> Self {
+        MacUIElement { element }
+    }
+
+    pub(crate) fn ax_element(&self) -> &AXUIElement {
+        &self.element
+    }
+
+    /// Read an attribute by its AX name, e.g. `"AXTitle"`
+    pub(crate) fn attribute<T: TCFType>(&self, name: &'static str) -> Result<T, UiError> {
+        Ok(self.element.attribute(&AXAttribute::<T>::new(&CFString::from_static_string(name)))?)
+    }
+
+    pub(crate) fn string_attribute(&self, name: &'static str) -> Result<String, UiError> {
+        Ok(self.attribute::<CFString>(name)?.to_string())
+    }
+
+    pub(crate) fn bool_attribute(&self, name: &'static str) -> Result<bool, UiError> {
+        Ok(self.attribute::<CFBoolean>(name)?.into())
+    }
+
+    pub(crate) fn set_bool_attribute(&self, name: &'static str, value: bool) -> Result<(), UiError> {
+        let value = if value { CFBoolean::true_value() } else { CFBoolean::false_value() };
+        Ok(self.element.set_attribute(&AXAttribute::<CFBoolean>::new(&CFString::from_static_string(name)), value)?)
+    }
+
+    /// Perform an AX action by name, e.g. `"AXPress"`
+    pub(crate) fn perform(&self, action: &'static str) -> Result<(), UiError> {
+        Ok(self.element.perform_action(&CFString::from_static_string(action))?)
+    }
+
+    pub fn get_role(&self) -> Result<String, UiError> {
+        self.string_attribute(AX_ROLE)
+    }
+
+    pub fn get_pid(&self) -> Result<i32, UiError> {
+        Ok(self.element.pid()?)
+    }
+
+    /// Get the child elements as concrete macOS elements
+    pub fn get_mac_children(&self) -> Result<Vec<MacUIElement>, UiError> {
+        let children = self.attribute::<CFArray<AXUIElement>>(AX_CHILDREN)?;
+        Ok(children.iter()
+            .map(|child| MacUIElement::new((*child).clone()))
+            .collect())
+    }
+
+    /// Read `kAXValueAttribute` when it holds a string
+    fn read_value(&self) -> Result<String, UiError> {
+        self.attribute::<CFType>(AX_VALUE)?
+            .downcast::<CFString>()
+            .map(|value| value.to_string())
+            .ok_or_else(|| UiError::pattern_unsupported("Value"))
+    }
+}
+
+impl UIElement for MacUIElement {
+    fn get_name(&self) -> Result<String, UiError> {
+        // Controls without a visible title usually carry their label in the description
+        match self.string_attribute(AX_TITLE) {
+            Ok(title) if !title.is_empty() => Ok(title),
+            _ => Ok(self.string_attribute(AX_DESCRIPTION).unwrap_or_default()),
+        }
+    }
+
+    fn get_type(&self) -> Result<String, UiError> {
+        Ok(role_to_control_type(&self.get_role()?).to_string())
+    }
+
+    fn get_localized_control_type(&self) -> Result<String, UiError> {
+        self.string_attribute(AX_ROLE_DESCRIPTION)
+    }
+
+    fn get_text(&self) -> Result<String, UiError> {
+        match self.read_value() {
+            Ok(text) => Ok(text),
+            Err(e) => {
+                debug!("MacUIElement::get_text - no string value ({}), using name", e);
+                self.get_name()
+            }
+        }
+    }
+
+    fn set_text(&self, text: &str) -> Result<(), UiError> {
+        let value = CFString::new(text);
+        Ok(self.element.set_attribute(
+            &AXAttribute::<CFType>::new(&CFString::from_static_string(AX_VALUE)),
+            value.as_CFType(),
+        )?)
+    }
+
+    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), UiError> {
+        match position {
+            AppendPosition::EndOfText => {
+                let current = self.read_value()?;
+                self.set_text(&format!("{}{}", current, text))
+            }
+            _ => Err(UiError::Unsupported("Appending at the caret is not supported on macOS yet".to_string())),
+        }
+    }
+
+    fn click(&self) -> Result<(), UiError> {
+        self.perform("AXPress")
+    }
+
+    fn invoke(&self) -> Result<(), UiError> {
+        // AXPress never involves the pointer
+        self.click()
+    }
+
+    fn right_click(&self) -> Result<(), UiError> {
+        self.perform("AXShowMenu")
+    }
+
+    fn double_click(&self) -> Result<(), UiError> {
+        Err(UiError::Unsupported("Double click is not supported on macOS yet".to_string()))
+    }
+
+    fn set_focus(&self) -> Result<(), UiError> {
+        self.set_bool_attribute(AX_FOCUSED, true)
+    }
+
+    fn is_enabled(&self) -> Result<bool, UiError> {
+        self.bool_attribute(AX_ENABLED)
+    }
+
+    fn is_password(&self) -> Result<bool, UiError> {
+        // Most elements have no subrole at all
+        Ok(self.string_attribute(AX_SUBROLE).is_ok_and(|subrole| subrole == AX_SECURE_TEXT_FIELD))
+    }
+
+    fn get_orientation(&self) -> Result<Orientation, UiError> {
+        // Elements without an orientation do not have the attribute at all
+        Ok(match self.string_attribute(AX_ORIENTATION).as_deref() {
+            Ok("AXHorizontalOrientation") => Orientation::Horizontal,
+            Ok("AXVerticalOrientation") => Orientation::Vertical,
+            _ => Orientation::None,
+        })
+    }
+
+    fn is_offscreen(&self) -> Result<bool, UiError> {
+        // Without decoded bounds the default would call every element offscreen
+        Err(UiError::Unsupported("Element visibility is not implemented on macOS yet".to_string()))
+    }
+
+    fn get_properties(&self) -> Result<HashMap<String, String>, UiError> {
+        let mut properties = HashMap::new();
+
+        if let Ok(name) = self.get_name() {
+            properties.insert("name".to_string(), name);
+        }
+        if let Ok(role) = self.get_role() {
+            properties.insert("control_type".to_string(), role_to_control_type(&role).to_string());
+            properties.insert("role".to_string(), role);
+        }
+        if let Ok(subrole) = self.string_attribute(AX_SUBROLE) {
+            properties.insert("subrole".to_string(), subrole);
+        }
+        if let Ok(enabled) = self.is_enabled() {
+            properties.insert("enabled".to_string(), enabled.to_string());
+        }
+
+        Ok(properties)
+    }
+
+    fn get_bounds(&self) -> Result<Option<Rect>, UiError> {
+        // AXPosition and AXSize are boxed AXValue structs that are not decoded yet
+        Ok(None)
+    }
+
+    fn get_children(&self) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        Ok(self.get_mac_children()?
+            .into_iter()
+            .map(|child| Box::new(child) as Box<dyn UIElement>)
+            .collect())
+    }
+
+    fn get_parent(&self) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        match self.attribute::<AXUIElement>(AX_PARENT) {
+            Ok(parent) => Ok(Some(Box::new(MacUIElement::new(parent)))),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn to_tree_node(&self) -> Result<Box<dyn UIElement>, UiError> {
+        Ok(Box::new(self.clone()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_map_to_windows_control_type_names() {
+        assert_eq!(role_to_control_type("AXButton"), "Button");
+        assert_eq!(role_to_control_type("AXTextField"), "Edit");
+        assert_eq!(role_to_control_type("AXTextArea"), "Edit");
+        assert_eq!(role_to_control_type("AXWebArea"), "Document");
+        assert_eq!(role_to_control_type("AXWindow"), "Window");
+        assert_eq!(role_to_control_type("AXStaticText"), "Text");
+        assert_eq!(role_to_control_type("AXPopUpButton"), "ComboBox");
+        assert_eq!(role_to_control_type("AXUnknown"), "Custom");
+    }
+}
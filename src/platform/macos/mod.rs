@@ -0,0 +1,7 @@
+pub mod automation;
+pub mod window;
+pub mod element;
+
+pub use automation::MacUIAutomation;
+pub use window::MacWindow;
+pub use element::MacUIElement;
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use crate::error::UiError;
+use chrono::Utc;
+use log::debug;
+use accessibility::AXUIElement;
+use core_foundation::base::{CFHash, TCFType};
+
+use crate::core::{
+    Rect, Window, UIElement, UIQuery, UITree, TextElementInfo, TextExtractionOptions, UITreeOptions,
+    build_tree_bounded, describe_tree_node, find_descendants, find_many_in_one_pass, find_with_paths, for_each_descendant,
+    resolve_path, visit_text_elements,
+};
+use super::element::{MacUIElement, AX_FOCUSED_UI_ELEMENT, AX_ROLE, AX_SUBROLE, AX_TITLE};
+
+/// A top-level window exposed through the Accessibility API
+#[derive(Clone)]
+pub struct MacWindow {
+    element: MacUIElement,
+}
+
+impl MacWindow {
+    pub fn new(element: MacUIElement) -> Self {
+        MacWindow { element }
+    }
+
+    /// Full executable path of the owning process, as reported by `ps`
+    fn executable_path(&self) -> Result<String, UiError> {
+        let pid = self.get_process_id()?;
+        let output = Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "comm="])
+            .output()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if path.is_empty() {
+            return Err(UiError::WindowNotFound(format!("Process {} is no longer running", pid)));
+        }
+        Ok(path)
+    }
+
+    fn unsupported(operation: &str) -> UiError {
+        UiError::Unsupported(format!("{} is not supported by the macOS Accessibility backend yet", operation))
+    }
+}
+
+/// The children of `element`, or none when they cannot be read
+fn children(element: &MacUIElement) -> Vec<MacUIElement> {
+    element.get_mac_children().unwrap_or_default()
+}
+
+impl Window for MacWindow {
+    fn get_title(&self) -> Result<String, UiError> {
+        self.element.string_attribute(AX_TITLE)
+    }
+
+    fn window_id(&self) -> Result<u64, UiError> {
+        // CFHash is consistent with CFEqual, which treats references to the same AX object as equal
+        Ok(unsafe { CFHash(self.element.ax_element().as_CFTypeRef()) } as u64)
+    }
+
+    fn get_class_name(&self) -> Result<String, UiError> {
+        // macOS has no window classes; the subrole (AXStandardWindow, AXDialog, ...) is the closest equivalent
+        self.element.string_attribute(AX_SUBROLE)
+            .or_else(|_| self.element.string_attribute(AX_ROLE))
+    }
+
+    fn get_process_id(&self) -> Result<u32, UiError> {
+        Ok(self.element.get_pid()? as u32)
+    }
+
+    fn get_thread_id(&self) -> Result<u32, UiError> {
+        Err(Self::unsupported("Getting the window thread id"))
+    }
+
+    fn get_process_name(&self) -> Result<String, UiError> {
+        let path = self.executable_path()?;
+        Ok(Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or(path))
+    }
+
+    fn get_process_path(&self) -> Result<String, UiError> {
+        self.executable_path()
+    }
+
+    fn is_visible(&self) -> Result<bool, UiError> {
+        Ok(!self.is_minimized()?)
+    }
+
+    fn is_minimized(&self) -> Result<bool, UiError> {
+        self.element.bool_attribute("AXMinimized")
+    }
+
+    fn is_maximized(&self) -> Result<bool, UiError> {
+        Ok(self.element.bool_attribute("AXFullScreen").unwrap_or(false))
+    }
+
+    fn get_rect(&self) -> Result<Rect, UiError> {
+        Err(Self::unsupported("Reading the window rectangle"))
+    }
+
+    fn get_dpi(&self) -> Result<u32, UiError> {
+        // AX reports coordinates in points, which map to logical pixels
+        Ok(96)
+    }
+
+    fn activate(&self) -> Result<(), UiError> {
+        self.element.perform("AXRaise")?;
+        self.element.set_bool_attribute("AXMain", true)
+    }
+
+    fn bring_to_top(&self) -> Result<(), UiError> {
+        self.activate()
+    }
+
+    fn set_foreground(&self) -> Result<(), UiError> {
+        self.activate()
+    }
+
+    fn minimize(&self) -> Result<(), UiError> {
+        self.element.set_bool_attribute("AXMinimized", true)
+    }
+
+    fn maximize(&self) -> Result<(), UiError> {
+        Err(Self::unsupported("Maximizing windows"))
+    }
+
+    fn restore(&self) -> Result<(), UiError> {
+        self.element.set_bool_attribute("AXMinimized", false)
+    }
+
+    fn close(&self) -> Result<(), UiError> {
+        let close_button = self.element.attribute::<AXUIElement>("AXCloseButton")
+            .map_err(|_| UiError::pattern_unsupported("Close"))?;
+        MacUIElement::new(close_button).click()
+    }
+
+    fn send_keys(&self, _keys: &str) -> Result<(), UiError> {
+        Err(Self::unsupported("Sending key sequences"))
+    }
+
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError> {
+        // Focus is tracked per application, not per window
+        let application = MacUIElement::new(AXUIElement::application(self.element.get_pid()?));
+        let focused = application.attribute::<AXUIElement>(AX_FOCUSED_UI_ELEMENT)
+            .map_err(|_| UiError::ElementNotFound("No focused element found in window".to_string()))?;
+        Ok(Box::new(MacUIElement::new(focused)))
+    }
+
+    fn get_ui_tree_with_options(&self, options: &UITreeOptions) -> Result<UITree, UiError> {
+        let (root, truncated) = build_tree_bounded(
+            self.element.clone(),
+            options,
+            // AX only exposes elements that are on screen
+            |element| describe_tree_node(element, options, true),
+            |element, limit| children(element).into_iter().take(limit).collect(),
+        )?;
+        Ok(UITree {
+            root,
+            timestamp: Utc::now(),
+            window_title: self.get_title().unwrap_or_default(),
+            window_class: self.get_class_name().unwrap_or_default(),
+            truncated,
+        })
+    }
+
+    fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        let results = find_descendants(&self.element, children, query);
+        debug!("MacWindow::find_elements - {} matches for {:?}", results.len(), query);
+        Ok(results)
+    }
+
+    fn find_many(&self, queries: &HashMap<String, UIQuery>) -> Result<HashMap<String, Vec<Box<dyn UIElement>>>, UiError> {
+        // find_elements tests every descendant with `UIQuery::matches`, so every query can share the walk
+        let queries: Vec<(&str, &UIQuery)> = queries.iter().map(|(label, query)| (label.as_str(), query)).collect();
+        Ok(find_many_in_one_pass(&queries, |visit| {
+            for_each_descendant(&self.element, children, |element| {
+                visit(element, None);
+                true
+            });
+        }))
+    }
+
+    fn find_elements_with_path(&self, query: &UIQuery) -> Result<Vec<(Box<dyn UIElement>, Vec<String>)>, UiError> {
+        let found = find_with_paths(&self.element, query, |_| None);
+        debug!("MacWindow::find_elements_with_path - {} matches for {:?}", found.len(), query);
+        Ok(found)
+    }
+
+    fn find_by_path(&self, path: &[String]) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        resolve_path(Box::new(self.element.clone()), path)
+    }
+
+    fn for_each_text_element(&self, options: &TextExtractionOptions, f: &mut dyn FnMut(&TextElementInfo) -> bool) -> Result<(), UiError> {
+        visit_text_elements(
+            children(&self.element),
+            self.element.get_name().ok(),
+            options,
+            |element| element.get_text_element_info().ok(),
+            children,
+            f,
+        )?;
+        Ok(())
+    }
+}
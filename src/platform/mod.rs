@@ -4,9 +4,15 @@ pub mod windows;
 #[cfg(target_os = "linux")]
 pub mod linux;
 
+#[cfg(target_os = "macos")]
+pub mod macos;
+
 // Re-export platform-specific types
 #[cfg(target_os = "windows")]
 pub use windows::*;
 
 #[cfg(target_os = "linux")]
-pub use linux::*; 
\ No newline at end of file
+pub use linux::*;
+
+#[cfg(target_os = "macos")]
+pub use macos::*; 
\ No newline at end of file
@@ -1,28 +1,37 @@
-use std::error::Error;
+use crate::error::UiError;
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
 use std::collections::HashMap;
-use log::debug;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+use log::{debug, info};
 
-use windows::Win32::Foundation::{HWND, LPARAM};
 use windows::core::BOOL;
 use windows::Win32::UI::WindowsAndMessaging::{
     EnumWindows, GetWindowTextW, GetClassNameW, IsWindowVisible, 
     GetWindowThreadProcessId, GetWindow, GW_OWNER, GetParent
 };
-use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+use windows::Win32::Foundation::{HWND, LPARAM, CloseHandle, HANDLE, ERROR_INVALID_PARAMETER, STILL_ACTIVE};
+use windows::Win32::System::Threading::{
+    OpenProcess, TerminateProcess, GetExitCodeProcess, PROCESS_ACCESS_RIGHTS,
+    PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE, PROCESS_VM_READ
+};
 use windows::Win32::System::ProcessStatus::GetModuleFileNameExW;
 
-use crate::core::{ApplicationManager, ApplicationInfo, Window};
+use crate::core::{search_windows, ApplicationManager, ApplicationInfo, UIQuery, Window, WindowMatches};
 use super::automation::WindowsUIAutomation;
 
+/// How long `launch_application` waits for the new process to show a window
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Windows-specific application manager
 pub struct WindowsApplicationManager {
     automation: WindowsUIAutomation,
 }
 
 impl WindowsApplicationManager {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
+    pub fn new() -> Result<Self, UiError> {
         let automation = WindowsUIAutomation::new()?;
         Ok(Self { automation })
     }
@@ -58,6 +67,20 @@ impl WindowsApplicationManager {
         (process_name, process_path)
     }
 
+    /// Open a process handle, reporting a missing process as `InvalidArgument`
+    /// 
+    /// Access-denied failures keep their `AccessDenied` mapping so callers can
+    /// tell a protected process from one that has exited.
+    fn open_process(process_id: u32, access: PROCESS_ACCESS_RIGHTS) -> Result<ProcessHandle, UiError> {
+        match unsafe { OpenProcess(access, false, process_id) } {
+            Ok(handle) => Ok(ProcessHandle(handle)),
+            Err(e) if e.code() == ERROR_INVALID_PARAMETER.to_hresult() => {
+                Err(UiError::InvalidArgument(format!("No process with PID {}", process_id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn get_window_info(hwnd: HWND) -> Option<(String, String, u32, bool)> {
         unsafe {
             // Skip invalid windows
@@ -98,8 +121,8 @@ impl WindowsApplicationManager {
             let parent = GetParent(hwnd).ok();
             let owner = GetWindow(hwnd, GW_OWNER).ok();
             
-            let is_top_level = parent.map_or(true, |h| h.is_invalid()) && 
-                              owner.map_or(true, |h| h.is_invalid());
+            let is_top_level = parent.is_none_or(|h| h.is_invalid()) && 
+                              owner.is_none_or(|h| h.is_invalid());
 
             if is_top_level && is_visible && !title.is_empty() && process_id != 0 {
                 Some((title, class_name, process_id, is_visible))
@@ -108,6 +131,171 @@ impl WindowsApplicationManager {
             }
         }
     }
+
+    /// Wrap a top-level window handle in a `WindowsWindow`
+    fn window_from_hwnd(&self, hwnd: HWND) -> Result<Box<dyn Window>, UiError> {
+        let element = {
+            let automation = self.automation.automation.lock()
+                .map_err(|e| format!("Failed to lock automation: {}", e))?;
+            
+            automation.element_from_handle(hwnd.into())
+                .map_err(|e| format!("Failed to get element from HWND {:?}: {}", hwnd, e))?
+        };
+        
+        let window = super::window::WindowsWindow::new(element, std::sync::Arc::new(self.automation.clone()))?;
+        Ok(Box::new(window))
+    }
+
+    /// Handles of the visible top-level windows of a process, in Z-order
+    /// 
+    /// Only titled windows without an owner are kept, unless `include_popups`
+    /// is set, in which case owned and untitled windows (dialogs, menus,
+    /// tooltips) are kept too.
+    fn process_window_handles(process_id: u32, include_popups: bool) -> Vec<HWND> {
+        struct FindWindowsState {
+            target_process_id: u32,
+            include_popups: bool,
+            found_hwnds: Vec<HWND>,
+        }
+        
+        extern "system" fn find_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+            unsafe {
+                let state_ptr = lparam.0 as *mut FindWindowsState;
+                let state = &mut *state_ptr;
+                
+                let mut window_process_id = 0u32;
+                GetWindowThreadProcessId(hwnd, Some(&mut window_process_id));
+                
+                if window_process_id == state.target_process_id && IsWindowVisible(hwnd).as_bool() {
+                    // Check if this is a main window (has title, no parent/owner)
+                    let mut title_buf = [0u16; 512];
+                    let title_len = GetWindowTextW(hwnd, &mut title_buf);
+                    let has_title = title_len > 0;
+                    
+                    let parent = GetParent(hwnd).ok();
+                    let owner = GetWindow(hwnd, GW_OWNER).ok();
+                    
+                    let is_top_level = parent.is_none_or(|h| h.is_invalid()) && 
+                                      owner.is_none_or(|h| h.is_invalid());
+                    
+                    if state.include_popups || (has_title && is_top_level) {
+                        state.found_hwnds.push(hwnd);
+                    }
+                }
+            }
+            BOOL(1) // Continue enumeration
+        }
+        
+        let mut find_state = FindWindowsState {
+            target_process_id: process_id,
+            include_popups,
+            found_hwnds: Vec::new(),
+        };
+        
+        unsafe {
+            let state_ptr = &mut find_state as *mut FindWindowsState;
+            let _ = EnumWindows(Some(find_windows_proc), LPARAM(state_ptr as isize));
+        }
+        
+        find_state.found_hwnds
+    }
+
+    /// Enumerate all visible, titled, top-level windows as (title, class, pid, visible)
+    fn enumerate_windows() -> Result<Vec<(String, String, u32, bool)>, UiError> {
+        let mut state = EnumWindowsState {
+            windows: Vec::new(),
+        };
+        
+        unsafe {
+            let state_ptr = &mut state as *mut EnumWindowsState;
+            if EnumWindows(Some(enum_windows_proc), LPARAM(state_ptr as isize)).is_err() {
+                return Err("Failed to enumerate windows".into());
+            }
+        }
+
+        Ok(state.windows)
+    }
+}
+
+/// Process handle that is closed when dropped
+struct ProcessHandle(HANDLE);
+
+impl Drop for ProcessHandle {
+    fn drop(&mut self) {
+        let _ = unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// Look up process info for each distinct PID on a pool of scoped threads
+///
+/// PIDs are deduplicated first, and the results are sorted by PID so callers
+/// see a stable order regardless of which lookup finishes first.
+fn lookup_processes_parallel<I, F>(process_ids: I, lookup: F) -> Vec<(u32, (String, String))>
+where
+    I: IntoIterator<Item = u32>,
+    F: Fn(u32) -> (String, String) + Sync,
+{
+    let mut process_ids: Vec<u32> = process_ids.into_iter().collect();
+    process_ids.sort_unstable();
+    process_ids.dedup();
+    if process_ids.is_empty() {
+        return Vec::new();
+    }
+
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(process_ids.len());
+    let chunk_size = process_ids.len().div_ceil(workers);
+    let lookup = &lookup;
+
+    // Chunks are contiguous ranges of the sorted PIDs, so joining them in order keeps the sort
+    thread::scope(|scope| {
+        let handles: Vec<_> = process_ids
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter().map(|&process_id| (process_id, lookup(process_id))).collect::<Vec<_>>()
+            }))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    })
+}
+
+/// Turn enumerated (title, class, pid, visible) windows into one `ApplicationInfo` per process
+///
+/// The first window seen for a process is its main window, and processes
+/// whose name cannot be read are skipped. The result is sorted by PID, so
+/// repeated calls list the same processes in the same order.
+fn collect_applications<L>(windows: Vec<(String, String, u32, bool)>, lookup: L) -> Vec<ApplicationInfo>
+where
+    L: FnOnce(Vec<u32>) -> Vec<(u32, (String, String))>,
+{
+    let mut main_windows: HashMap<u32, (String, String, bool)> = HashMap::new();
+    for (title, class_name, process_id, is_visible) in windows {
+        main_windows.entry(process_id).or_insert((title, class_name, is_visible));
+    }
+
+    let mut apps: Vec<ApplicationInfo> = lookup(main_windows.keys().copied().collect())
+        .into_iter()
+        .filter(|(_, (process_name, _))| !process_name.is_empty())
+        .filter_map(|(process_id, (process_name, process_path))| {
+            let (main_window_title, main_window_class, is_visible) = main_windows.remove(&process_id)?;
+            Some(ApplicationInfo {
+                process_id,
+                process_name,
+                process_path,
+                main_window_title,
+                main_window_class,
+                is_visible,
+            })
+        })
+        .collect();
+    apps.sort_by_key(|app| app.process_id);
+    apps
 }
 
 // Global state for window enumeration
@@ -128,65 +316,44 @@ extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
 }
 
 impl ApplicationManager for WindowsApplicationManager {
-    fn get_all_applications(&self) -> Result<Vec<ApplicationInfo>, Box<dyn Error>> {
+    fn get_all_applications(&self) -> Result<Vec<ApplicationInfo>, UiError> {
         debug!("Enumerating all applications");
         
-        let mut state = EnumWindowsState {
-            windows: Vec::new(),
-        };
-        
-        unsafe {
-            let state_ptr = &mut state as *mut EnumWindowsState;
-            if EnumWindows(Some(enum_windows_proc), LPARAM(state_ptr as isize)).is_err() {
-                return Err("Failed to enumerate windows".into());
-            }
-        }
-
-        debug!("Found {} windows", state.windows.len());
-
-        // Group windows by process ID and get process info
-        let mut apps_map: HashMap<u32, ApplicationInfo> = HashMap::new();
-        
-        for (title, class_name, process_id, is_visible) in state.windows {
-            if !apps_map.contains_key(&process_id) {
-                let (process_name, process_path) = Self::get_process_info(process_id);
-                
-                if !process_name.is_empty() {
-                    apps_map.insert(process_id, ApplicationInfo {
-                        process_id,
-                        process_name,
-                        process_path,
-                        main_window_title: title,
-                        main_window_class: class_name,
-                        is_visible,
-                    });
-                }
-            }
-        }
+        let windows = Self::enumerate_windows()?;
+        debug!("Found {} windows", windows.len());
 
-        let apps: Vec<ApplicationInfo> = apps_map.into_values().collect();
+        // Process lookups dominate the cost, so run them in parallel
+        let apps = collect_applications(windows, |process_ids| lookup_processes_parallel(process_ids, Self::get_process_info));
         debug!("Found {} unique applications", apps.len());
         
         Ok(apps)
     }
 
-    fn find_applications_by_name(&self, name: &str) -> Result<Vec<ApplicationInfo>, Box<dyn Error>> {
+    fn find_applications_by_name(&self, name: &str) -> Result<Vec<ApplicationInfo>, UiError> {
         debug!("Finding applications by name: {}", name);
         let all_apps = self.get_all_applications()?;
         
         let filtered_apps: Vec<ApplicationInfo> = all_apps
             .into_iter()
-            .filter(|app| {
-                app.process_name.to_lowercase().contains(&name.to_lowercase()) ||
-                app.process_name.to_lowercase() == name.to_lowercase()
-            })
+            .filter(|app| app.name_contains(name))
             .collect();
             
         debug!("Found {} applications matching name '{}'", filtered_apps.len(), name);
         Ok(filtered_apps)
     }
 
-    fn find_applications_by_title(&self, title: &str) -> Result<Vec<ApplicationInfo>, Box<dyn Error>> {
+    fn find_applications_by_path(&self, path_substring: &str) -> Result<Vec<ApplicationInfo>, UiError> {
+        debug!("Finding applications by path: {}", path_substring);
+        let filtered_apps: Vec<ApplicationInfo> = self.get_all_applications()?
+            .into_iter()
+            .filter(|app| app.path_contains(path_substring))
+            .collect();
+
+        debug!("Found {} applications matching path '{}'", filtered_apps.len(), path_substring);
+        Ok(filtered_apps)
+    }
+
+    fn find_applications_by_title(&self, title: &str) -> Result<Vec<ApplicationInfo>, UiError> {
         debug!("Finding applications by title: {}", title);
         let all_apps = self.get_all_applications()?;
         
@@ -201,82 +368,33 @@ impl ApplicationManager for WindowsApplicationManager {
         Ok(filtered_apps)
     }
 
-    fn get_window_by_process_id(&self, process_id: u32) -> Result<Box<dyn Window>, Box<dyn Error>> {
+    fn get_window_by_process_id(&self, process_id: u32) -> Result<Box<dyn Window>, UiError> {
         debug!("Getting window for process ID: {}", process_id);
         
-        // Find the HWND for the main window of this process
-        struct FindWindowState {
-            target_process_id: u32,
-            found_hwnd: Option<HWND>,
-        }
-        
-        extern "system" fn find_window_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
-            unsafe {
-                let state_ptr = lparam.0 as *mut FindWindowState;
-                let state = &mut *state_ptr;
-                
-                let mut window_process_id = 0u32;
-                GetWindowThreadProcessId(hwnd, Some(&mut window_process_id));
-                
-                if window_process_id == state.target_process_id {
-                    // Check if this is a main window (visible, has title, no parent/owner)
-                    let mut title_buf = [0u16; 512];
-                    let title_len = GetWindowTextW(hwnd, &mut title_buf);
-                    let has_title = title_len > 0;
-                    
-                    let is_visible = IsWindowVisible(hwnd).as_bool();
-                    let parent = GetParent(hwnd).ok();
-                    let owner = GetWindow(hwnd, GW_OWNER).ok();
-                    
-                    let is_top_level = parent.map_or(true, |h| h.is_invalid()) && 
-                                      owner.map_or(true, |h| h.is_invalid());
-                    
-                    if is_visible && has_title && is_top_level {
-                        state.found_hwnd = Some(hwnd);
-                        return BOOL(0); // Stop enumeration
-                    }
-                }
-            }
-            BOOL(1) // Continue enumeration
-        }
-        
-        let mut find_state = FindWindowState {
-            target_process_id: process_id,
-            found_hwnd: None,
-        };
+        self.get_windows_by_process_id(process_id)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| UiError::WindowNotFound(format!("No main window found for process ID {}", process_id)))
+    }
+
+    fn get_windows_by_process_id(&self, process_id: u32) -> Result<Vec<Box<dyn Window>>, UiError> {
+        debug!("Getting all windows for process ID: {}", process_id);
         
-        unsafe {
-            let state_ptr = &mut find_state as *mut FindWindowState;
-            let _ = EnumWindows(Some(find_window_proc), LPARAM(state_ptr as isize));
-        }
+        let found_hwnds = Self::process_window_handles(process_id, false);
+        debug!("Found {} windows for process ID {}", found_hwnds.len(), process_id);
         
-        if let Some(hwnd) = find_state.found_hwnd {
-            debug!("Found HWND for process ID {}: {:?}", process_id, hwnd);
-            
-            // Get UIAutomation element from HWND
-            let automation = self.automation.automation.lock()
-                .map_err(|e| format!("Failed to lock automation: {}", e))?;
-            
-            let element = automation.element_from_handle(hwnd.into())
-                .map_err(|e| format!("Failed to get element from HWND: {}", e))?;
-            
-            debug!("Successfully created UIAutomation element from HWND");
-            
-            // Create WindowsWindow with the specific element
-            let window = super::window::WindowsWindow::new(element, std::sync::Arc::new(self.automation.clone()))?;
-            
-            Ok(Box::new(window))
-        } else {
-            Err(format!("No main window found for process ID {}", process_id).into())
-        }
+        found_hwnds
+            .into_iter()
+            .map(|hwnd| self.window_from_hwnd(hwnd))
+            .collect()
     }
 
-    fn get_window_by_process_name(&self, name: &str) -> Result<Box<dyn Window>, Box<dyn Error>> {
+    fn get_window_by_process_name(&self, name: &str) -> Result<Box<dyn Window>, UiError> {
         debug!("Getting window for process name: {}", name);
         
         let apps = self.find_applications_by_name(name)?;
         if apps.is_empty() {
-            return Err(format!("No application found with name '{}'", name).into());
+            return Err(UiError::WindowNotFound(format!("No application found with name '{}'", name)));
         }
 
         // Use the first matching application
@@ -286,4 +404,354 @@ impl ApplicationManager for WindowsApplicationManager {
         // Use the proper get_window_by_process_id method
         self.get_window_by_process_id(app.process_id)
     }
-} 
\ No newline at end of file
+
+    fn launch_application(&self, path: &str, args: &[String]) -> Result<ApplicationInfo, UiError> {
+        info!("Launching application: {} {:?}", path, args);
+
+        let mut child = Command::new(path)
+            .args(args)
+            .spawn()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => UiError::AccessDenied(format!("Failed to launch '{}': {}", path, e)),
+                _ => UiError::PlatformError(format!("Failed to launch '{}': {}", path, e)),
+            })?;
+        let process_id = child.id();
+        debug!("Launched '{}' with PID {}", path, process_id);
+
+        let start = Instant::now();
+        let error = loop {
+            let windows = match Self::enumerate_windows() {
+                Ok(windows) => windows,
+                Err(e) => break e,
+            };
+            let window = windows.into_iter().find(|(_, _, pid, _)| *pid == process_id);
+
+            if let Some((title, class_name, _, is_visible)) = window {
+                let (process_name, process_path) = Self::get_process_info(process_id);
+                debug!("'{}' showed window '{}' after {:?}", path, title, start.elapsed());
+                return Ok(ApplicationInfo {
+                    process_id,
+                    process_name,
+                    process_path,
+                    main_window_title: title,
+                    main_window_class: class_name,
+                    is_visible,
+                });
+            }
+
+            // Some launchers hand off to another process and exit immediately
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(format!(
+                    "'{}' (PID {}) exited with {} before showing a window",
+                    path, process_id, status
+                ).into());
+            }
+            if start.elapsed() >= LAUNCH_TIMEOUT {
+                break UiError::Timeout(format!(
+                    "'{}' (PID {}) did not show a window within {:?}",
+                    path, process_id, LAUNCH_TIMEOUT
+                ));
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        };
+
+        // The caller never learns the PID, so don't leave the process running
+        if let Err(e) = child.kill() {
+            debug!("Could not terminate PID {} after failed launch: {}", process_id, e);
+        }
+        let _ = child.wait();
+        Err(error)
+    }
+
+    fn launch_and_wait(
+        &self,
+        path: &str,
+        args: &[String],
+        title_contains: Option<&str>,
+        timeout: Duration,
+    ) -> Result<Box<dyn Window>, UiError> {
+        info!("Launching application: {} {:?} (waiting for title {:?})", path, args, title_contains);
+
+        let mut child = Command::new(path)
+            .args(args)
+            .spawn()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => UiError::AccessDenied(format!("Failed to launch '{}': {}", path, e)),
+                _ => UiError::PlatformError(format!("Failed to launch '{}': {}", path, e)),
+            })?;
+        let process_id = child.id();
+        debug!("Launched '{}' with PID {}", path, process_id);
+
+        let start = Instant::now();
+        let error = loop {
+            let windows = self.get_windows_by_process_id(process_id).unwrap_or_default();
+            for window in windows {
+                let title = window.get_title().unwrap_or_default();
+                if title_contains.is_none_or(|needle| title.contains(needle)) {
+                    debug!("'{}' showed window '{}' after {:?}", path, title, start.elapsed());
+                    return Ok(window);
+                }
+            }
+
+            if let Ok(Some(status)) = child.try_wait() {
+                break UiError::PlatformError(format!(
+                    "'{}' (PID {}) exited with {} before showing a matching window",
+                    path, process_id, status
+                ));
+            }
+            if start.elapsed() >= timeout {
+                break UiError::Timeout(format!(
+                    "'{}' (PID {}) did not show a window matching {:?} within {:?}",
+                    path, process_id, title_contains, timeout
+                ));
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        };
+
+        // Don't leave the process running when the caller never gets a window for it
+        if let Err(e) = child.kill() {
+            debug!("Could not terminate PID {} after failed launch: {}", process_id, e);
+        }
+        let _ = child.wait();
+        Err(error)
+    }
+
+    fn kill_application(&self, pid: u32) -> Result<(), UiError> {
+        info!("Terminating process {}", pid);
+        let process = Self::open_process(pid, PROCESS_TERMINATE)?;
+        unsafe { TerminateProcess(process.0, 1)? };
+        Ok(())
+    }
+
+    fn is_process_running(&self, pid: u32) -> Result<bool, UiError> {
+        let process = match Self::open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION) {
+            Ok(process) => process,
+            Err(UiError::InvalidArgument(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        // Exited processes can still be opened while other handles to them exist
+        let mut exit_code = 0u32;
+        unsafe { GetExitCodeProcess(process.0, &mut exit_code)? };
+        Ok(exit_code == STILL_ACTIVE.0 as u32)
+    }
+
+    fn find_elements_in_application(&self, pid: u32, query: &UIQuery) -> Result<Vec<WindowMatches>, UiError> {
+        let hwnds = Self::process_window_handles(pid, true);
+        debug!("Searching {} windows of process ID {}", hwnds.len(), pid);
+        
+        // A popup can close between enumeration and wrapping; skip it like a window closing mid-search
+        let windows = hwnds.into_iter()
+            .filter_map(|hwnd| match self.window_from_hwnd(hwnd) {
+                Ok(window) => Some(window),
+                Err(e) => {
+                    debug!("Skipping window {:?} of process ID {}: {}", hwnd, pid, e);
+                    None
+                }
+            })
+            .collect();
+        search_windows(windows, query)
+    }
+
+    fn refresh_application(&self, pid: u32) -> Result<ApplicationInfo, UiError> {
+        debug!("Refreshing application info for PID {}", pid);
+        if !self.is_process_running(pid)? {
+            return Err(UiError::ElementNotFound(format!("Process {} has exited", pid)));
+        }
+
+        let (process_name, process_path) = Self::get_process_info(pid);
+        let main_window = Self::enumerate_windows()?
+            .into_iter()
+            .find(|(_, _, process_id, _)| *process_id == pid);
+
+        // A process that is still starting up may not show a titled window yet
+        let (main_window_title, main_window_class, is_visible) = match main_window {
+            Some((title, class_name, _, is_visible)) => (title, class_name, is_visible),
+            None => (String::new(), String::new(), false),
+        };
+        Ok(ApplicationInfo {
+            process_id: pid,
+            process_name,
+            process_path,
+            main_window_title,
+            main_window_class,
+            is_visible,
+        })
+    }
+}
+
+/// Notepad launched for a desktop test, killed when dropped
+/// 
+/// Tests hold one for their whole body, so a failing `expect` or assertion
+/// does not leave Notepad running.
+#[cfg(test)]
+pub(crate) struct TestNotepad {
+    pub(crate) app_manager: WindowsApplicationManager,
+    pub(crate) window: Box<dyn Window>,
+    pub(crate) process_id: u32,
+}
+
+#[cfg(test)]
+impl TestNotepad {
+    pub(crate) fn launch() -> Self {
+        let app_manager = WindowsApplicationManager::new().expect("Failed to create application manager");
+        let window = app_manager.launch_and_wait("notepad.exe", &[], None, Duration::from_secs(10))
+            .expect("Failed to launch Notepad");
+        let process_id = window.get_process_id().expect("Failed to read the Notepad process id");
+        TestNotepad { app_manager, window, process_id }
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestNotepad {
+    fn drop(&mut self) {
+        let _ = self.app_manager.kill_application(self.process_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::same_window;
+
+    #[test]
+    fn test_parallel_process_lookup_is_complete_and_sorted() {
+        let process_ids = vec![4242, 7, 1000, 7, 88, 4242, 3, 65000, 512, 88];
+
+        let results = lookup_processes_parallel(process_ids, |process_id| {
+            (format!("app{}.exe", process_id), format!("C:\\apps\\app{}.exe", process_id))
+        });
+
+        let found: Vec<u32> = results.iter().map(|(process_id, _)| *process_id).collect();
+        assert_eq!(found, vec![3, 7, 88, 512, 1000, 4242, 65000]);
+        for (process_id, (process_name, _)) in &results {
+            assert_eq!(process_name, &format!("app{}.exe", process_id));
+        }
+
+        assert!(lookup_processes_parallel(Vec::new(), |_| (String::new(), String::new())).is_empty());
+    }
+
+    #[test]
+    fn test_collect_applications_is_sorted_with_one_entry_per_process() {
+        let window = |title: &str, process_id: u32| (title.to_string(), "Class".to_string(), process_id, true);
+        let windows = vec![
+            window("Editor - b.txt", 900),
+            window("Terminal", 12),
+            window("Editor - a.txt", 900),
+            window("Hidden helper", 55),
+            window("Browser", 300),
+        ];
+
+        // Report lookups out of order to make sure the order does not come from the lookup
+        let apps = collect_applications(windows, |mut process_ids| {
+            process_ids.sort_unstable_by(|a, b| b.cmp(a));
+            process_ids.into_iter()
+                .map(|process_id| {
+                    let name = if process_id == 55 { String::new() } else { format!("app{}.exe", process_id) };
+                    (process_id, (name, String::new()))
+                })
+                .collect()
+        });
+
+        let process_ids: Vec<u32> = apps.iter().map(|app| app.process_id).collect();
+        assert_eq!(process_ids, vec![12, 300, 900]);
+        assert_eq!(apps[2].main_window_title, "Editor - b.txt");
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session and a Notepad build that opens new windows in the same process
+    fn test_get_windows_by_process_id_returns_every_window() {
+        let app_manager = WindowsApplicationManager::new().expect("Failed to create application manager");
+        let app = app_manager.launch_application("notepad.exe", &[])
+            .expect("Failed to launch Notepad");
+
+        let first = app_manager.get_window_by_process_id(app.process_id)
+            .expect("Failed to get Notepad window");
+        first.send_keys("{CTRL}{SHIFT}n").expect("Failed to open a second window");
+        thread::sleep(Duration::from_millis(1500));
+
+        let windows = app_manager.get_windows_by_process_id(app.process_id)
+            .expect("Failed to list Notepad windows");
+        for window in &windows {
+            let _ = window.close();
+        }
+
+        assert_eq!(windows.len(), 2, "Expected both Notepad windows for PID {}", app.process_id);
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_window_equals_itself_refetched_by_process_id() {
+        let app_manager = WindowsApplicationManager::new().expect("Failed to create application manager");
+        let app = app_manager.launch_application("notepad.exe", &[])
+            .expect("Failed to launch Notepad");
+
+        let first = app_manager.get_window_by_process_id(app.process_id)
+            .expect("Failed to get Notepad window");
+        let again = app_manager.get_window_by_process_id(app.process_id)
+            .expect("Failed to get Notepad window again");
+        let others: Vec<Box<dyn Window>> = app_manager.get_all_applications()
+            .expect("Failed to list applications")
+            .iter()
+            .filter(|other| other.process_id != app.process_id)
+            .filter_map(|other| app_manager.get_window_by_process_id(other.process_id).ok())
+            .collect();
+        let _ = first.close();
+
+        assert_eq!(first.window_id().unwrap(), again.window_id().unwrap());
+        assert!(same_window(first.as_ref(), again.as_ref()).unwrap());
+        for other in &others {
+            assert!(!same_window(first.as_ref(), other.as_ref()).unwrap_or(false));
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_refresh_application_sees_title_change_after_load() {
+        let app_manager = WindowsApplicationManager::new().expect("Failed to create application manager");
+        let script = "Start-Sleep -Milliseconds 1500; $Host.UI.RawUI.WindowTitle = 'refreshed-title'; Start-Sleep -Seconds 30";
+        let app = app_manager.launch_application("powershell.exe", &["-NoProfile".to_string(), "-Command".to_string(), script.to_string()])
+            .expect("Failed to launch PowerShell");
+
+        let start = Instant::now();
+        let refreshed = loop {
+            let refreshed = app_manager.refresh_application(app.process_id)
+                .expect("Failed to refresh application info");
+            if refreshed.main_window_title.contains("refreshed-title") || start.elapsed() > Duration::from_secs(10) {
+                break refreshed;
+            }
+            thread::sleep(Duration::from_millis(200));
+        };
+        app_manager.kill_application(app.process_id).expect("Failed to kill PowerShell");
+
+        assert_ne!(app.main_window_title, refreshed.main_window_title);
+        assert!(refreshed.main_window_title.contains("refreshed-title"), "title is '{}'", refreshed.main_window_title);
+        assert_eq!(refreshed.process_name, app.process_name);
+
+        let start = Instant::now();
+        while app_manager.is_process_running(app.process_id).unwrap() {
+            assert!(start.elapsed() < Duration::from_secs(5), "PID {} is still running", app.process_id);
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert!(matches!(app_manager.refresh_application(app.process_id), Err(UiError::ElementNotFound(_))));
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_kill_application_stops_launched_process() {
+        let app_manager = WindowsApplicationManager::new().expect("Failed to create application manager");
+        let app = app_manager.launch_application("notepad.exe", &[])
+            .expect("Failed to launch Notepad");
+        assert!(app_manager.is_process_running(app.process_id).unwrap());
+
+        app_manager.kill_application(app.process_id).expect("Failed to kill Notepad");
+
+        let start = Instant::now();
+        while app_manager.is_process_running(app.process_id).unwrap() {
+            assert!(start.elapsed() < Duration::from_secs(5), "PID {} is still running", app.process_id);
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
@@ -1,27 +1,35 @@
-use std::error::Error;
+use crate::error::UiError;
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use log::{debug, info, warn};
 use uiautomation::UIElement as UIAutomationElement;
-use uiautomation::types::{TreeScope, UIProperty};
+use uiautomation::UITreeWalker;
+use uiautomation::core::UICondition;
+use uiautomation::types::{TreeScope, UIProperty, Point};
 use uiautomation::variants::Variant;
 use uiautomation::controls::ControlType;
 use uiautomation::patterns::{UIValuePattern, UITextPattern};
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
-use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Foundation::{HWND, RECT, POINT};
 use windows::Win32::UI::WindowsAndMessaging::{
     GetForegroundWindow, GetWindowTextW, GetClassNameW, GetWindowLongPtrW, GWL_EXSTYLE,
     GWL_STYLE, IsWindowVisible, GetWindowThreadProcessId, GetWindowRect, GetParent,
     GetWindow, GW_OWNER, GetMenu, GetWindowPlacement, WINDOWPLACEMENT, SW_SHOWMINIMIZED,
-    SW_SHOWMAXIMIZED, SW_SHOWNORMAL
+    SW_SHOWMAXIMIZED, SW_SHOWNORMAL, WindowFromPoint, GetAncestor, GA_ROOT, GetDesktopWindow, GetShellWindow
 };
 use windows::Win32::System::ProcessStatus::GetModuleFileNameExW;
 use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
 use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use windows::Win32::UI::Accessibility::{IUIAutomation, IUIAutomation2};
+use windows::core::Interface;
 
-use crate::core::{Window, UIAutomation as CoreUIAutomation, UIElement as CoreUIElement};
-use super::window::WindowsWindow;
+use crate::core::{Window, UIAutomation as CoreUIAutomation, UIElement as CoreUIElement, InteractionMode};
+use crate::events::{EventSubscription, FocusChangedCallback, WindowOpenedCallback};
+use super::window::{WindowsWindow, control_type_from_name};
 use super::element::WindowsElement;
+use super::capture::DpiAwarenessGuard;
 
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
@@ -182,7 +190,7 @@ impl<T> ThreadSafe<T> {
         Self(Arc::new(Mutex::new(value)))
     }
 
-    pub fn lock(&self) -> Result<std::sync::MutexGuard<T>, Box<dyn Error>> {
+    pub fn lock(&self) -> Result<std::sync::MutexGuard<'_, T>, UiError> {
         self.0.lock().map_err(|_| "Failed to lock automation".into())
     }
 }
@@ -191,35 +199,189 @@ impl<T> ThreadSafe<T> {
 unsafe impl<T> Send for ThreadSafe<T> {}
 unsafe impl<T> Sync for ThreadSafe<T> {}
 
-thread_local! {
-    pub static AUTOMATION: std::cell::RefCell<Option<ThreadSafe<uiautomation::core::UIAutomation>>> = std::cell::RefCell::new(None);
+/// UIA objects created once per `WindowsUIAutomation` and reused by every search
+///
+/// UI Automation client objects are agile (they aggregate the free-threaded
+/// marshaler), so the walker and conditions may be used from any thread that
+/// has initialized COM, not only the one that created them. They stay valid as
+/// long as the `IUIAutomation` instance that created them is alive; both are
+/// owned by the same `WindowsUIAutomation` and its clones, so this holds.
+#[derive(Clone)]
+struct AutomationCache {
+    walker: UITreeWalker,
+    true_condition: UICondition,
 }
 
 /// Windows-specific UIAutomation implementation
 #[derive(Clone)]
 pub struct WindowsUIAutomation {
     pub automation: ThreadSafe<uiautomation::core::UIAutomation>,
+    cache: ThreadSafe<AutomationCache>,
+    mode: ThreadSafe<InteractionMode>,
+    call_timeout: ThreadSafe<Option<Duration>>,
+}
+
+/// UI Automation's own limit on connecting to a provider
+const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// UI Automation's own limit on a single call into a provider
+const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Clamp a duration to the whole milliseconds `IUIAutomation2` expects
+fn timeout_millis(timeout: Duration) -> u32 {
+    timeout.as_millis().clamp(1, u32::MAX as u128) as u32
+}
+
+thread_local! {
+    /// This thread's automation client, for elements that were created without a walker
+    static AUTOMATION: RefCell<Option<uiautomation::UIAutomation>> = const { RefCell::new(None) };
+}
+
+/// Create the current thread's automation client
+///
+/// Elements built without a tree walker (`WindowsElement::new(element, None)`)
+/// fall back to a per-thread client for `get_children`, `get_parent`,
+/// `get_subtree` and similar calls. Every thread needs its own, because it
+/// also joins the thread to COM. Constructing a `WindowsUIAutomation` does
+/// this for the constructing thread; threads you spawn yourself should call
+/// it before using elements, so that COM errors (for example a thread
+/// already in a single-threaded apartment) surface here rather than at the
+/// first element call. Calling it again on the same thread does nothing.
+pub fn init_thread_automation() -> Result<(), UiError> {
+    AUTOMATION.with(|automation| {
+        let mut automation = automation.borrow_mut();
+        if automation.is_none() {
+            debug!("Creating the automation client for thread {:?}", std::thread::current().id());
+            *automation = Some(uiautomation::UIAutomation::new()?);
+        }
+        Ok(())
+    })
+}
+
+/// The current thread's automation client, created on first use
+pub(crate) fn thread_automation() -> Result<uiautomation::UIAutomation, UiError> {
+    init_thread_automation()?;
+    AUTOMATION.with(|automation| {
+        automation.borrow().clone()
+            .ok_or_else(|| "No global automation instance for this thread".into())
+    })
 }
 
 impl WindowsUIAutomation {
     /// Create a new instance of WindowsUIAutomation
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let automation = uiautomation::UIAutomation::new()?;
+    pub fn new() -> Result<Self, UiError> {
+        init_thread_automation()?;
+        Self::with_client(uiautomation::UIAutomation::new()?)
+    }
+
+    /// Wrap an automation client created by the caller instead of creating one
+    ///
+    /// For hosts that already use the `uiautomation` crate (the same 0.18
+    /// release this crate depends on) and want to share one `IUIAutomation`
+    /// object with this API. The client is reference counted: the returned
+    /// instance, its clones and the windows and elements it hands out keep it
+    /// alive, and the caller may keep using its own handle alongside.
+    ///
+    /// The calling thread must already have joined COM, which creating the
+    /// client did if it happened on this thread. If this thread has no
+    /// per-thread client yet (see [`init_thread_automation`]) it adopts this
+    /// one, so no second `IUIAutomation` is created here. Other threads still
+    /// get their own client on first use. `set_call_timeout` changes the
+    /// timeouts of the shared client, so they apply to the host's calls too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ui_interaction::platform::windows::WindowsUIAutomation;
+    ///
+    /// let client = uiautomation::UIAutomation::new()?;
+    /// let automation = WindowsUIAutomation::from_existing(client.clone())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_existing(automation: uiautomation::UIAutomation) -> Result<Self, UiError> {
+        AUTOMATION.with(|slot| {
+            let mut slot = slot.borrow_mut();
+            if slot.is_none() {
+                debug!("Adopting the caller's automation client for thread {:?}", std::thread::current().id());
+                *slot = Some(automation.clone());
+            }
+        });
+        Self::with_client(automation)
+    }
+
+    /// Build an instance around `automation`, creating the objects every search reuses
+    fn with_client(automation: uiautomation::UIAutomation) -> Result<Self, UiError> {
+        let cache = AutomationCache {
+            walker: automation.create_tree_walker()?,
+            true_condition: automation.create_true_condition()?,
+        };
         Ok(WindowsUIAutomation {
             automation: ThreadSafe::new(automation),
+            cache: ThreadSafe::new(cache),
+            mode: ThreadSafe::new(InteractionMode::default()),
+            call_timeout: ThreadSafe::new(None),
         })
     }
 
+    /// Create a new instance whose calls fail with `UiError::Timeout` after `timeout`
+    pub fn new_with_timeout(timeout: Duration) -> Result<Self, UiError> {
+        let automation = Self::new()?;
+        automation.set_call_timeout(Some(timeout))?;
+        Ok(automation)
+    }
+
+    /// Apply connection and transaction timeouts to the underlying client
+    ///
+    /// Requires `IUIAutomation2` (Windows 8 and later). The timeouts belong to
+    /// the client object, so they also bound calls on elements already
+    /// retrieved from it; a call that exceeds them fails with `UIA_E_TIMEOUT`.
+    fn apply_call_timeout(&self, timeout: Option<Duration>) -> Result<(), UiError> {
+        let automation = self.automation.lock()?;
+        let client: &IUIAutomation = automation.as_ref();
+        let client: IUIAutomation2 = client.cast().map_err(|_| {
+            UiError::Unsupported("Call timeouts require UI Automation 2 (Windows 8 or later)".to_string())
+        })?;
+        let (connection, transaction) = match timeout {
+            Some(timeout) => (timeout.min(DEFAULT_CONNECTION_TIMEOUT), timeout),
+            None => (DEFAULT_CONNECTION_TIMEOUT, DEFAULT_TRANSACTION_TIMEOUT),
+        };
+        unsafe {
+            client.SetConnectionTimeout(timeout_millis(connection))?;
+            client.SetTransactionTimeout(timeout_millis(transaction))?;
+        }
+        Ok(())
+    }
+
+    /// The shared tree walker
+    ///
+    /// Cloning a walker only adds a COM reference, so this is much cheaper than
+    /// `create_tree_walker`. It takes its own lock rather than the automation
+    /// lock, so it can be called while the automation lock is held.
+    pub fn tree_walker(&self) -> Result<UITreeWalker, UiError> {
+        Ok(self.cache.lock()?.walker.clone())
+    }
+
+    /// The shared condition matching every element
+    pub fn true_condition(&self) -> Result<UICondition, UiError> {
+        Ok(self.cache.lock()?.true_condition.clone())
+    }
+
+    /// Wrap a UIAutomationElement in a `WindowsElement` that shares the cached walker
+    pub fn wrap_element(&self, element: UIAutomationElement) -> WindowsElement {
+        WindowsElement::new(element, self.tree_walker().ok())
+            .with_mode(CoreUIAutomation::interaction_mode(self))
+    }
+
     /// Convert a UIAutomationElement to a Box<dyn UIElement>
     pub fn element_to_ui_element(&self, element: UIAutomationElement) -> Box<dyn CoreUIElement> {
-        Box::new(WindowsElement::new(element, None)) as Box<dyn CoreUIElement>
+        Box::new(self.wrap_element(element)) as Box<dyn CoreUIElement>
     }
 
     /// Get the currently focused window
-    pub fn get_focused_window(&self) -> Result<UIAutomationElement, Box<dyn Error>> {
+    pub fn get_focused_window(&self) -> Result<UIAutomationElement, UiError> {
         debug!("Getting focused window");
         let window_info = WindowInfo::get_current()
-            .ok_or_else(|| "Failed to get window info using Windows API")?;
+            .ok_or_else(|| UiError::WindowNotFound("Failed to get window info using Windows API".to_string()))?;
         
         info!("Focused window - Title: {}, ClassName: {}", 
             window_info.title, window_info.class_name);
@@ -261,10 +423,10 @@ impl WindowsUIAutomation {
     }
 
     /// Get the currently active (foreground) window - the top-level application window
-    pub fn get_active_window(&self) -> Result<UIAutomationElement, Box<dyn Error>> {
+    pub fn get_active_window(&self) -> Result<UIAutomationElement, UiError> {
         debug!("Getting active window");
         let window_info = WindowInfo::get_current()
-            .ok_or_else(|| "Failed to get window info using Windows API")?;
+            .ok_or_else(|| UiError::WindowNotFound("Failed to get window info using Windows API".to_string()))?;
         
         info!("Active window - Title: {}, ClassName: {}", 
             window_info.title, window_info.class_name);
@@ -320,13 +482,13 @@ impl WindowsUIAutomation {
     }
 
     /// Get the window that contains the currently focused element
-    pub fn get_window_containing_focus(&self) -> Result<UIAutomationElement, Box<dyn Error>> {
+    pub fn get_window_containing_focus(&self) -> Result<UIAutomationElement, UiError> {
         // This is the same as the old get_focused_window behavior
         self.get_focused_window()
     }
 
     /// Get the currently focused element with its details
-    pub fn get_focused_element_details(&self) -> Result<(UIAutomationElement, String, String), Box<dyn Error>> {
+    pub fn get_focused_element_details(&self) -> Result<(UIAutomationElement, String, String), UiError> {
         debug!("Getting focused element details");
         let automation = self.automation.lock()?;
         let element = automation.get_focused_element()?;
@@ -336,7 +498,7 @@ impl WindowsUIAutomation {
     }
 
     /// Get the currently focused input element
-    pub fn get_focused_input_element(&self) -> Result<UIAutomationElement, Box<dyn Error>> {
+    pub fn get_focused_input_element(&self) -> Result<UIAutomationElement, UiError> {
         debug!("Getting currently focused input element");
         let automation = self.automation.lock()?;
         let focused_element = automation.get_focused_element()?;
@@ -371,7 +533,7 @@ impl WindowsUIAutomation {
     }
 
     /// Find an element by its name
-    pub fn find_element_by_name(&self, name: &str) -> Result<UIAutomationElement, Box<dyn Error>> {
+    pub fn find_element_by_name(&self, name: &str) -> Result<UIAutomationElement, UiError> {
         debug!("Finding element by name: {}", name);
         let automation = self.automation.lock()?;
         let root = automation.get_root_element()?;
@@ -381,36 +543,14 @@ impl WindowsUIAutomation {
     }
 
     /// Find an element by its type
-    pub fn find_element_by_type(&self, element_type: &str) -> Result<UIAutomationElement, Box<dyn Error>> {
+    pub fn find_element_by_type(&self, element_type: &str) -> Result<UIAutomationElement, UiError> {
         debug!("Finding element by type: {}", element_type);
         
         // Convert string type to ControlType enum
-        let control_type = match element_type {
-            "Button" => ControlType::Button,
-            "Edit" => ControlType::Edit,
-            "Text" => ControlType::Text,
-            "ComboBox" => ControlType::ComboBox,
-            "CheckBox" => ControlType::CheckBox,
-            "RadioButton" => ControlType::RadioButton,
-            "ListItem" => ControlType::ListItem,
-            "List" => ControlType::List,
-            "TreeItem" => ControlType::TreeItem,
-            "Tree" => ControlType::Tree,
-            "TabItem" => ControlType::TabItem,
-            "Tab" => ControlType::Tab,
-            "Table" => ControlType::Table,
-            "Document" => ControlType::Document,
-            "Pane" => ControlType::Pane,
-            "Window" => ControlType::Window,
-            "Group" => ControlType::Group,
-            "Image" => ControlType::Image,
-            "Hyperlink" => ControlType::Hyperlink,
-            "Custom" => ControlType::Custom,
-            _ => {
-                warn!("Unknown element type '{}', using Custom", element_type);
-                ControlType::Custom
-            }
-        };
+        let control_type = control_type_from_name(element_type).unwrap_or_else(|| {
+            warn!("Unknown element type '{}', using Custom", element_type);
+            ControlType::Custom
+        });
         
         let control_type_id = control_type as i32;
         debug!("Converted element type '{}' to ControlType::{:?} (ID: {})", element_type, control_type, control_type_id);
@@ -440,63 +580,41 @@ impl WindowsUIAutomation {
 }
 
 impl CoreUIAutomation for WindowsUIAutomation {
-    fn get_active_window(&self) -> Result<Box<dyn Window>, Box<dyn Error>> {
+    fn get_active_window(&self) -> Result<Box<dyn Window>, UiError> {
         let element = self.get_active_window()?;
         Ok(Box::new(WindowsWindow::new(element, Arc::new(self.clone()))?))
     }
 
-    fn get_window_containing_focus(&self) -> Result<Box<dyn Window>, Box<dyn Error>> {
+    fn get_window_containing_focus(&self) -> Result<Box<dyn Window>, UiError> {
         let element = self.get_window_containing_focus()?;
         Ok(Box::new(WindowsWindow::new(element, Arc::new(self.clone()))?))
     }
 
-    fn get_focused_window(&self) -> Result<Box<dyn Window>, Box<dyn Error>> {
+    fn get_focused_window(&self) -> Result<Box<dyn Window>, UiError> {
         let element = self.get_focused_window()?;
         Ok(Box::new(WindowsWindow::new(element, Arc::new(self.clone()))?))
     }
 
-    fn get_focused_element(&self) -> Result<Box<dyn CoreUIElement>, Box<dyn Error>> {
+    fn get_focused_element(&self) -> Result<Box<dyn CoreUIElement>, UiError> {
         let element = self.automation.lock()?.get_focused_element()?;
         Ok(self.element_to_ui_element(element))
     }
 
-    fn find_element_by_name(&self, name: &str) -> Result<Box<dyn CoreUIElement>, Box<dyn Error>> {
+    fn find_element_by_name(&self, name: &str) -> Result<Box<dyn CoreUIElement>, UiError> {
         let automation = self.automation.lock()?;
         let condition = automation.create_property_condition(UIProperty::Name, Variant::from(name), None)?;
         let element = automation.get_root_element()?.find_first(TreeScope::Descendants, &condition)?;
         Ok(self.element_to_ui_element(element))
     }
 
-    fn find_element_by_type(&self, element_type: &str) -> Result<Box<dyn CoreUIElement>, Box<dyn Error>> {
+    fn find_element_by_type(&self, element_type: &str) -> Result<Box<dyn CoreUIElement>, UiError> {
         debug!("CoreUIAutomation::find_element_by_type - Finding element by type: {}", element_type);
         
         // Convert string type to ControlType enum
-        let control_type = match element_type {
-            "Button" => ControlType::Button,
-            "Edit" => ControlType::Edit,
-            "Text" => ControlType::Text,
-            "ComboBox" => ControlType::ComboBox,
-            "CheckBox" => ControlType::CheckBox,
-            "RadioButton" => ControlType::RadioButton,
-            "ListItem" => ControlType::ListItem,
-            "List" => ControlType::List,
-            "TreeItem" => ControlType::TreeItem,
-            "Tree" => ControlType::Tree,
-            "TabItem" => ControlType::TabItem,
-            "Tab" => ControlType::Tab,
-            "Table" => ControlType::Table,
-            "Document" => ControlType::Document,
-            "Pane" => ControlType::Pane,
-            "Window" => ControlType::Window,
-            "Group" => ControlType::Group,
-            "Image" => ControlType::Image,
-            "Hyperlink" => ControlType::Hyperlink,
-            "Custom" => ControlType::Custom,
-            _ => {
-                warn!("Unknown element type '{}', using Custom", element_type);
-                ControlType::Custom
-            }
-        };
+        let control_type = control_type_from_name(element_type).unwrap_or_else(|| {
+            warn!("Unknown element type '{}', using Custom", element_type);
+            ControlType::Custom
+        });
         
         let control_type_id = control_type as i32;
         debug!("CoreUIAutomation::find_element_by_type - Converted '{}' to ID: {}", element_type, control_type_id);
@@ -506,4 +624,66 @@ impl CoreUIAutomation for WindowsUIAutomation {
         let element = automation.get_root_element()?.find_first(TreeScope::Descendants, &condition)?;
         Ok(self.element_to_ui_element(element))
     }
-} 
\ No newline at end of file
+
+    fn subscribe_focus_changed(&self, callback: FocusChangedCallback) -> Result<EventSubscription, UiError> {
+        super::events::subscribe_focus_changed(self, callback)
+    }
+
+    fn subscribe_window_opened(&self, callback: WindowOpenedCallback) -> Result<EventSubscription, UiError> {
+        super::events::subscribe_window_opened(self, callback)
+    }
+
+    fn element_from_point(&self, x: i32, y: i32) -> Result<Box<dyn CoreUIElement>, UiError> {
+        // Per-monitor awareness keeps the point in physical pixels on every monitor
+        let _dpi = DpiAwarenessGuard::per_monitor();
+        let automation = self.automation.lock()?;
+        let element = automation.element_from_point(Point::new(x, y))?;
+
+        // Points outside every window resolve to the desktop itself
+        let root = automation.get_root_element()?;
+        if automation.compare_elements(&element, &root).unwrap_or(false) {
+            return Err(UiError::ElementNotFound(format!("No element at ({}, {}), the point is outside every window", x, y)));
+        }
+        drop(automation);
+        Ok(self.element_to_ui_element(element))
+    }
+
+    fn window_from_point(&self, x: i32, y: i32) -> Result<Box<dyn Window>, UiError> {
+        let _dpi = DpiAwarenessGuard::per_monitor();
+        let hwnd = unsafe { WindowFromPoint(POINT { x, y }) };
+        let top_level = if hwnd.0.is_null() {
+            hwnd
+        } else {
+            unsafe { GetAncestor(hwnd, GA_ROOT) }
+        };
+        let desktop = unsafe { (GetDesktopWindow(), GetShellWindow()) };
+        if top_level.0.is_null() || top_level == desktop.0 || top_level == desktop.1 {
+            return Err(UiError::WindowNotFound(format!("No window at ({}, {})", x, y)));
+        }
+
+        debug!("Window at ({}, {}) is HWND {:?}", x, y, top_level);
+        let element = self.automation.lock()?.element_from_handle(top_level.into())?;
+        Ok(Box::new(WindowsWindow::new(element, Arc::new(self.clone()))?))
+    }
+
+    fn interaction_mode(&self) -> InteractionMode {
+        self.mode.lock().map(|mode| *mode).unwrap_or_default()
+    }
+
+    fn set_interaction_mode(&self, mode: InteractionMode) -> Result<(), UiError> {
+        debug!("Switching interaction mode to {:?}", mode);
+        *self.mode.lock()? = mode;
+        Ok(())
+    }
+
+    fn call_timeout(&self) -> Option<Duration> {
+        self.call_timeout.lock().ok().and_then(|timeout| *timeout)
+    }
+
+    fn set_call_timeout(&self, timeout: Option<Duration>) -> Result<(), UiError> {
+        debug!("Setting call timeout to {:?}", timeout);
+        self.apply_call_timeout(timeout)?;
+        *self.call_timeout.lock()? = timeout;
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+//! Screen capture of windows and elements as PNG images
+
+use crate::error::UiError;
+use log::debug;
+use windows::Win32::Graphics::Gdi::{
+    GetDC, ReleaseDC, CreateCompatibleDC, CreateCompatibleBitmap, SelectObject, BitBlt, GetDIBits,
+    DeleteObject, DeleteDC, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY, HGDIOBJ
+};
+use windows::Win32::UI::HiDpi::{
+    SetThreadDpiAwarenessContext, GetDpiForSystem, DPI_AWARENESS_CONTEXT, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN
+};
+
+use crate::core::{Rect, physical_to_logical};
+
+/// Switches the current thread to per-monitor DPI awareness until dropped
+///
+/// While active, UI Automation bounds and GDI both use physical pixels, so
+/// scaled windows are captured in full rather than cropped or offset.
+pub(crate) struct DpiAwarenessGuard {
+    previous: DPI_AWARENESS_CONTEXT,
+}
+
+impl DpiAwarenessGuard {
+    pub(crate) fn per_monitor() -> Self {
+        let previous = unsafe { SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) };
+        DpiAwarenessGuard { previous }
+    }
+
+    /// Whether the thread actually switched; it cannot before Windows 10 1703
+    pub(crate) fn is_active(&self) -> bool {
+        !self.previous.0.is_null()
+    }
+
+    /// Convert physical bounds from UI Automation to this thread's screen coordinates
+    ///
+    /// With the guard active they are the same. Otherwise the thread keeps the
+    /// process awareness, and a DPI-unaware process sees logical pixels at the
+    /// system DPI.
+    pub(crate) fn to_thread_coordinates(&self, bounds: &Rect) -> Rect {
+        if self.is_active() {
+            return bounds.clone();
+        }
+        physical_to_logical(bounds, unsafe { GetDpiForSystem() })
+    }
+}
+
+impl Drop for DpiAwarenessGuard {
+    fn drop(&mut self) {
+        if !self.previous.0.is_null() {
+            unsafe { SetThreadDpiAwarenessContext(self.previous) };
+        }
+    }
+}
+
+/// Capture the screen area described by `get_bounds` as PNG bytes
+///
+/// The bounds are queried with per-monitor DPI awareness enabled so they match
+/// the physical pixels copied from the screen.
+pub fn capture_png<F>(get_bounds: F) -> Result<Vec<u8>, UiError>
+where
+    F: FnOnce() -> Result<Option<Rect>, UiError>,
+{
+    let dpi = DpiAwarenessGuard::per_monitor();
+
+    let bounds = dpi.to_thread_coordinates(&get_bounds()?.ok_or("Element has no on-screen bounds to capture")?);
+    let area = visible_capture_area(&bounds, &virtual_screen())?;
+    debug!("Capturing screen area {:?}", area);
+
+    let pixels = copy_screen_pixels(&area)?;
+    encode_png(area.width() as u32, area.height() as u32, &bgra_to_rgba(pixels))
+}
+
+/// Get the bounding rectangle of all monitors
+fn virtual_screen() -> Rect {
+    unsafe {
+        let left = GetSystemMetrics(SM_XVIRTUALSCREEN);
+        let top = GetSystemMetrics(SM_YVIRTUALSCREEN);
+        Rect::new(
+            left,
+            top,
+            left + GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            top + GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    }
+}
+
+/// Clip `bounds` to the screen, rejecting empty and fully offscreen areas
+fn visible_capture_area(bounds: &Rect, screen: &Rect) -> Result<Rect, UiError> {
+    if bounds.width() <= 0 || bounds.height() <= 0 {
+        return Err("Cannot capture an element with zero-area bounds".into());
+    }
+    if !bounds.intersects(screen) {
+        return Err("Cannot capture an element that is entirely offscreen".into());
+    }
+
+    Ok(Rect::new(
+        bounds.left.max(screen.left),
+        bounds.top.max(screen.top),
+        bounds.right.min(screen.right),
+        bounds.bottom.min(screen.bottom),
+    ))
+}
+
+/// Copy a screen area into a top-down 32-bit BGRA buffer
+fn copy_screen_pixels(area: &Rect) -> Result<Vec<u8>, UiError> {
+    let (width, height) = (area.width(), area.height());
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+    unsafe {
+        let screen_dc = GetDC(None);
+        if screen_dc.is_invalid() {
+            return Err("Failed to get the screen device context".into());
+        }
+        let memory_dc = CreateCompatibleDC(Some(screen_dc));
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let previous = SelectObject(memory_dc, HGDIOBJ::from(bitmap));
+
+        let result = BitBlt(memory_dc, 0, 0, width, height, Some(screen_dc), area.left, area.top, SRCCOPY)
+            .map_err(|e| format!("Failed to copy screen pixels: {}", e).into())
+            .and_then(|()| {
+                let mut info = BITMAPINFO {
+                    bmiHeader: BITMAPINFOHEADER {
+                        biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                        biWidth: width,
+                        // Negative height requests a top-down bitmap
+                        biHeight: -height,
+                        biPlanes: 1,
+                        biBitCount: 32,
+                        biCompression: BI_RGB.0,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                let lines = GetDIBits(
+                    memory_dc,
+                    bitmap,
+                    0,
+                    height as u32,
+                    Some(pixels.as_mut_ptr() as *mut _),
+                    &mut info,
+                    DIB_RGB_COLORS,
+                );
+                if lines == height {
+                    Ok(())
+                } else {
+                    Err::<(), UiError>("Failed to read captured pixels".into())
+                }
+            });
+
+        SelectObject(memory_dc, previous);
+        let _ = DeleteObject(HGDIOBJ::from(bitmap));
+        let _ = DeleteDC(memory_dc);
+        ReleaseDC(None, screen_dc);
+
+        result?;
+    }
+
+    Ok(pixels)
+}
+
+/// Convert GDI's BGRA pixel order to opaque RGBA
+fn bgra_to_rgba(mut pixels: Vec<u8>) -> Vec<u8> {
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        // GDI leaves the alpha channel undefined for screen copies
+        pixel[3] = 255;
+    }
+    pixels
+}
+
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, UiError> {
+    let mut png_bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_bytes, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()
+            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+        writer.write_image_data(rgba)
+            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    }
+    Ok(png_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_area_rejects_zero_area_and_offscreen_bounds() {
+        let screen = Rect::new(0, 0, 1920, 1080);
+
+        assert!(visible_capture_area(&Rect::new(100, 100, 100, 200), &screen).is_err());
+        assert!(visible_capture_area(&Rect::new(-500, 0, -100, 200), &screen).is_err());
+
+        let clipped = visible_capture_area(&Rect::new(1800, 1000, 2000, 1200), &screen).unwrap();
+        assert_eq!((clipped.left, clipped.top, clipped.right, clipped.bottom), (1800, 1000, 1920, 1080));
+    }
+
+    #[test]
+    fn test_encoded_capture_is_png() {
+        let rgba = bgra_to_rgba(vec![10, 20, 30, 0, 40, 50, 60, 0]);
+        assert_eq!(rgba, vec![30, 20, 10, 255, 60, 50, 40, 255]);
+
+        let png_bytes = encode_png(2, 1, &rgba).unwrap();
+        assert_eq!(&png_bytes[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
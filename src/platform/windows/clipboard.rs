@@ -0,0 +1,135 @@
+//! Minimal access to the text contents of the Windows clipboard
+
+use crate::error::UiError;
+use std::thread;
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::{GlobalFree, HANDLE, HGLOBAL};
+use windows::Win32::System::DataExchange::{
+    OpenClipboard, CloseClipboard, EmptyClipboard, GetClipboardData, SetClipboardData,
+    GetClipboardSequenceNumber, IsClipboardFormatAvailable
+};
+use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GlobalSize, GMEM_MOVEABLE};
+use windows::Win32::System::Ole::CF_UNICODETEXT;
+
+/// How many times opening the clipboard is retried while another process holds it
+const OPEN_ATTEMPTS: u32 = 10;
+
+/// Holds the clipboard open until dropped
+struct OpenClipboardGuard;
+
+impl OpenClipboardGuard {
+    fn open() -> Result<Self, UiError> {
+        let mut attempts = 0;
+        loop {
+            match unsafe { OpenClipboard(None) } {
+                Ok(()) => return Ok(OpenClipboardGuard),
+                Err(e) if attempts + 1 >= OPEN_ATTEMPTS => return Err(e.into()),
+                Err(_) => {
+                    attempts += 1;
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for OpenClipboardGuard {
+    fn drop(&mut self) {
+        let _ = unsafe { CloseClipboard() };
+    }
+}
+
+/// Decode clipboard UTF-16 text, which is NUL terminated within a possibly larger allocation
+pub(crate) fn decode_clipboard_text(units: &[u16]) -> String {
+    let end = units.iter().position(|&unit| unit == 0).unwrap_or(units.len());
+    String::from_utf16_lossy(&units[..end])
+}
+
+/// Counter that Windows bumps every time the clipboard contents change
+pub fn sequence_number() -> u32 {
+    unsafe { GetClipboardSequenceNumber() }
+}
+
+/// Read the clipboard as text, or `None` if it holds no text
+pub fn read_text() -> Result<Option<String>, UiError> {
+    let _clipboard = OpenClipboardGuard::open()?;
+    if unsafe { IsClipboardFormatAvailable(CF_UNICODETEXT.0 as u32) }.is_err() {
+        return Ok(None);
+    }
+
+    let handle = unsafe { GetClipboardData(CF_UNICODETEXT.0 as u32)? };
+    let memory = HGLOBAL(handle.0);
+    let pointer = unsafe { GlobalLock(memory) } as *const u16;
+    if pointer.is_null() {
+        return Err("Failed to lock the clipboard contents".into());
+    }
+
+    let length = unsafe { GlobalSize(memory) } / std::mem::size_of::<u16>();
+    let text = decode_clipboard_text(unsafe { std::slice::from_raw_parts(pointer, length) });
+    let _ = unsafe { GlobalUnlock(memory) };
+    Ok(Some(text))
+}
+
+/// Replace the clipboard contents with `text`
+pub fn write_text(text: &str) -> Result<(), UiError> {
+    let units: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let _clipboard = OpenClipboardGuard::open()?;
+    unsafe { EmptyClipboard()? };
+
+    let memory = unsafe { GlobalAlloc(GMEM_MOVEABLE, units.len() * std::mem::size_of::<u16>())? };
+    let pointer = unsafe { GlobalLock(memory) } as *mut u16;
+    if pointer.is_null() {
+        let _ = unsafe { GlobalFree(Some(memory)) };
+        return Err("Failed to lock clipboard memory".into());
+    }
+    unsafe { std::ptr::copy_nonoverlapping(units.as_ptr(), pointer, units.len()) };
+    let _ = unsafe { GlobalUnlock(memory) };
+
+    // The clipboard owns the memory once SetClipboardData succeeds; until then it is ours to free
+    if let Err(e) = unsafe { SetClipboardData(CF_UNICODETEXT.0 as u32, Some(HANDLE(memory.0))) } {
+        let _ = unsafe { GlobalFree(Some(memory)) };
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Empty the clipboard so stale contents cannot be mistaken for a fresh copy
+pub fn clear() -> Result<(), UiError> {
+    let _clipboard = OpenClipboardGuard::open()?;
+    unsafe { EmptyClipboard()? };
+    Ok(())
+}
+
+/// Wait until the clipboard changes after `since` and holds text, then return it
+pub fn wait_for_text(since: u32, timeout: Duration) -> Result<String, UiError> {
+    let start = Instant::now();
+    loop {
+        if sequence_number() != since {
+            if let Some(text) = read_text()? {
+                return Ok(text);
+            }
+        }
+        if start.elapsed() >= timeout {
+            return Err(UiError::Timeout(format!("Copied text did not reach the clipboard within {:?}", timeout)));
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_clipboard_text_stops_at_nul() {
+        let units: Vec<u16> = "héllo\0junk".encode_utf16().collect();
+        assert_eq!(decode_clipboard_text(&units), "héllo");
+    }
+
+    #[test]
+    fn test_decode_clipboard_text_without_terminator() {
+        let units: Vec<u16> = "line 1\r\nline 2".encode_utf16().collect();
+        assert_eq!(decode_clipboard_text(&units), "line 1\r\nline 2");
+    }
+}
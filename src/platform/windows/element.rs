@@ -1,56 +1,503 @@
 use crate::core::UIElement as CoreUIElement;
 use uiautomation::core::UIElement as UIAutomationElement;
-use uiautomation::patterns::{UIValuePattern, UITextPattern};
-use uiautomation::types::{TreeScope, UIProperty};
+use uiautomation::patterns::{
+    UIValuePattern, UITextPattern, UITogglePattern, UIExpandCollapsePattern, UISelectionItemPattern, UISelectionPattern,
+    UIScrollPattern, UIScrollItemPattern, UIRangeValuePattern
+};
+use uiautomation::types::{Point, ToggleState, ExpandCollapseState, OrientationType, TreeScope, UIProperty, TextPatternRangeEndpoint, TextUnit, ElementMode};
+use uiautomation::patterns::UITextRange;
+use uiautomation::variants::SafeArray;
+use uiautomation::inputs::{Keyboard, Mouse};
 use uiautomation::variants::Variant;
 use uiautomation::controls::ControlType;
 use uiautomation::UITreeWalker;
-use windows::Win32::Foundation::RECT;
-use std::error::Error;
+use uiautomation::core::UICacheRequest;
+use windows::Win32::Foundation::{RECT, POINT};
+use windows::Win32::UI::WindowsAndMessaging::{GetCursorPos, SetCursorPos};
+use windows::Win32::System::Variant::VT_R8;
+use windows::Win32::UI::Accessibility::IUIAutomationTextRange;
+use crate::error::UiError;
+use std::cell::RefCell;
 use std::collections::HashMap;
-use crate::core::{Rect, TextElementInfo, SelectedTextInfo};
+use crate::core::{Rect, TextElementInfo, SelectedTextInfo, RangeValue, ExpandState, Orientation, UITreeNode, UITreeOptions, build_tree_bounded, with_retry, truncate_chars, loggable_text, DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_DELAY, DEFAULT_TEXT_LIMIT};
 use std::any::Any;
 use std::convert::TryInto;
-use crate::platform::windows::automation::AUTOMATION;
+use crate::platform::windows::capture::DpiAwarenessGuard;
+use crate::platform::windows::clipboard;
+use crate::platform::windows::input;
+use crate::platform::windows::automation::thread_automation;
+use crate::timing::OperationTimer;
 use std::thread;
 use std::time::Duration;
 use log::{debug, info, warn};
-use crate::core::AppendPosition;
+use crate::core::{AppendPosition, ScrollDirection, TextInputOptions, CorrectionMode, InteractionMode};
+use crate::keyboard::KeyChord;
+
+/// How long typed text is given to reach the control before it is read back
+const TEXT_SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// How long `get_text_via_clipboard` waits for the copied text to reach the clipboard
+const CLIPBOARD_COPY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Properties prefetched for every node captured by `get_subtree`
+const SUBTREE_PROPERTIES: &[UIProperty] = &[
+    UIProperty::Name,
+    UIProperty::ControlType,
+    UIProperty::BoundingRectangle,
+    UIProperty::IsEnabled,
+    UIProperty::IsOffscreen,
+];
+
+/// Describe an element from the properties cached by `get_subtree`, without touching the provider
+fn describe_cached_node(element: &UIAutomationElement) -> UITreeNode {
+    let name = element.get_cached_name().unwrap_or_default();
+    let control_type = element.get_cached_control_type()
+        .map(|control_type| control_type.to_string())
+        .unwrap_or_else(|_| "Unknown".to_string());
+    let bounds = cached_bounds(element);
+
+    let mut properties = HashMap::new();
+    properties.insert("name".to_string(), name.clone());
+    properties.insert("control_type".to_string(), control_type.clone());
+
+    UITreeNode {
+        name,
+        control_type,
+        properties,
+        children: Vec::new(),
+        bounds,
+        is_enabled: cached_flag(element, UIProperty::IsEnabled).unwrap_or(false),
+        is_visible: !cached_flag(element, UIProperty::IsOffscreen).unwrap_or(true),
+    }
+}
+
+/// A cached boolean property, or None when it was not cached or is not a boolean
+fn cached_flag(element: &UIAutomationElement, property: UIProperty) -> Option<bool> {
+    element.get_cached_property_value(property).ok()
+        .and_then(|value| TryInto::<bool>::try_into(value).ok())
+}
+
+/// The cached bounding rectangle, or None when it was not cached
+fn cached_bounds(element: &UIAutomationElement) -> Option<Rect> {
+    element.get_cached_bounding_rectangle().ok()
+        .and_then(|rect| TryInto::<RECT>::try_into(rect).ok())
+        .map(Rect::from)
+}
+
+/// Properties fetched in a single round trip by `get_info_bulk`
+pub(crate) const BULK_INFO_PROPERTIES: &[UIProperty] = &[
+    UIProperty::Name,
+    UIProperty::ControlType,
+    UIProperty::LocalizedControlType,
+    UIProperty::ClassName,
+    UIProperty::AutomationId,
+    UIProperty::BoundingRectangle,
+    UIProperty::IsEnabled,
+    UIProperty::IsOffscreen,
+];
+
+/// How a property read by `get_properties_filtered` is turned into a string
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PropertyFormat {
+    Text,
+    /// "true" or "false", like `enabled` in `get_properties`
+    Flag,
+    Number,
+    /// The control type name, like `control_type` in `get_properties`
+    ControlType,
+    /// "None", "Horizontal" or "Vertical"
+    Orientation,
+}
+
+/// The properties `get_properties_filtered` can read, under the keys it reports them with
+const FILTERABLE_PROPERTIES: &[(&str, UIProperty, PropertyFormat)] = &[
+    ("name", UIProperty::Name, PropertyFormat::Text),
+    ("control_type", UIProperty::ControlType, PropertyFormat::ControlType),
+    ("class_name", UIProperty::ClassName, PropertyFormat::Text),
+    ("automation_id", UIProperty::AutomationId, PropertyFormat::Text),
+    ("enabled", UIProperty::IsEnabled, PropertyFormat::Flag),
+    ("offscreen", UIProperty::IsOffscreen, PropertyFormat::Flag),
+    ("help_text", UIProperty::HelpText, PropertyFormat::Text),
+    ("accelerator_key", UIProperty::AcceleratorKey, PropertyFormat::Text),
+    ("access_key", UIProperty::AccessKey, PropertyFormat::Text),
+    ("is_password", UIProperty::IsPassword, PropertyFormat::Flag),
+    ("orientation", UIProperty::Orientation, PropertyFormat::Orientation),
+    ("localized_control_type", UIProperty::LocalizedControlType, PropertyFormat::Text),
+    ("item_status", UIProperty::ItemStatus, PropertyFormat::Text),
+    ("item_type", UIProperty::ItemType, PropertyFormat::Text),
+    ("framework_id", UIProperty::FrameworkId, PropertyFormat::Text),
+    ("process_id", UIProperty::ProcessId, PropertyFormat::Number),
+    ("has_keyboard_focus", UIProperty::HasKeyboardFocus, PropertyFormat::Flag),
+    ("is_keyboard_focusable", UIProperty::IsKeyboardFocusable, PropertyFormat::Flag),
+    ("is_content_element", UIProperty::IsContentElement, PropertyFormat::Flag),
+    ("is_control_element", UIProperty::IsControlElement, PropertyFormat::Flag),
+    ("is_required_for_form", UIProperty::IsRequiredForForm, PropertyFormat::Flag),
+];
+
+/// Look up a filterable property, ignoring case and underscores so "HelpText" finds `help_text`
+fn filterable_property(key: &str) -> Option<(UIProperty, PropertyFormat)> {
+    let normalize = |key: &str| -> String {
+        key.chars().filter(|c| *c != '_').map(|c| c.to_ascii_lowercase()).collect()
+    };
+    let wanted = normalize(key);
+    FILTERABLE_PROPERTIES.iter()
+        .find(|(name, _, _)| normalize(name) == wanted)
+        .map(|(_, property, format)| (*property, *format))
+}
+
+/// Format a property value, or `None` if the element does not support the property
+fn format_property(value: &Variant, format: PropertyFormat) -> Option<String> {
+    let number = || TryInto::<i32>::try_into(value).ok();
+    match format {
+        PropertyFormat::Text => TryInto::<String>::try_into(value).ok(),
+        PropertyFormat::Flag => TryInto::<bool>::try_into(value).ok().map(|flag| flag.to_string()),
+        PropertyFormat::Number => number().map(|n| n.to_string()),
+        PropertyFormat::ControlType => number()
+            .and_then(|id| ControlType::try_from(id).ok())
+            .map(|control_type| control_type.to_string()),
+        PropertyFormat::Orientation => number().and_then(|orientation| match orientation {
+            0 => Some("None".to_string()),
+            1 => Some("Horizontal".to_string()),
+            2 => Some("Vertical".to_string()),
+            _ => None,
+        }),
+    }
+}
+
+/// The commonly read properties of an element, fetched together by `WindowsElement::get_info_bulk`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementInfoBulk {
+    pub name: String,
+    /// None when the provider reports a control type this crate does not know
+    pub control_type: Option<ControlType>,
+    /// None when the element reports no localized control type
+    pub localized_control_type: Option<String>,
+    pub class_name: String,
+    /// None when the element has no automation id
+    pub automation_id: Option<String>,
+    pub bounds: Option<Rect>,
+    pub is_enabled: bool,
+    pub is_offscreen: bool,
+}
+
+impl ElementInfoBulk {
+    /// The control type name, or "Unknown"
+    pub fn control_type_name(&self) -> String {
+        self.control_type
+            .map(|control_type| control_type.to_string())
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
+    /// The same map as `get_properties`
+    pub fn properties(&self) -> HashMap<String, String> {
+        let mut properties = HashMap::new();
+        properties.insert("name".to_string(), self.name.clone());
+        properties.insert("class_name".to_string(), self.class_name.clone());
+        if let Some(control_type) = self.control_type {
+            properties.insert("control_type".to_string(), control_type.to_string());
+        }
+        if let Some(automation_id) = &self.automation_id {
+            properties.insert("automation_id".to_string(), automation_id.clone());
+        }
+        properties.insert("enabled".to_string(), self.is_enabled.to_string());
+        properties
+    }
+}
+
+/// Describes an element whose properties could not be read: nameless, disabled and offscreen
+impl Default for ElementInfoBulk {
+    fn default() -> Self {
+        ElementInfoBulk {
+            name: String::new(),
+            control_type: None,
+            localized_control_type: None,
+            class_name: String::new(),
+            automation_id: None,
+            bounds: None,
+            is_enabled: false,
+            is_offscreen: true,
+        }
+    }
+}
+
+/// Create the cache request behind `get_info_bulk`
+///
+/// Walks over many elements should create it once and pass it to
+/// `fetch_info_bulk` for each element.
+pub(crate) fn info_cache_request(automation: &uiautomation::UIAutomation) -> Result<UICacheRequest, UiError> {
+    let request = automation.create_cache_request()?;
+    for property in BULK_INFO_PROPERTIES {
+        request.add_property(*property)?;
+    }
+    // Only the cached properties are read back, so no live reference is needed
+    request.set_element_mode(ElementMode::None)?;
+    Ok(request)
+}
+
+thread_local! {
+    /// This thread's `info_cache_request`, built on first use by `get_info_bulk`
+    static INFO_REQUEST: RefCell<Option<UICacheRequest>> = const { RefCell::new(None) };
+    /// The request `get_properties_filtered` last built on this thread, with the properties it covers
+    static FILTERED_REQUEST: RefCell<Option<(Vec<UIProperty>, UICacheRequest)>> = const { RefCell::new(None) };
+}
+
+/// This thread's `info_cache_request`, built once from the thread's automation client
+fn thread_info_request() -> Result<UICacheRequest, UiError> {
+    INFO_REQUEST.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if let Some(request) = slot.as_ref() {
+            return Ok(request.clone());
+        }
+        let request = info_cache_request(&thread_automation()?)?;
+        *slot = Some(request.clone());
+        Ok(request)
+    })
+}
+
+/// A cache request for `properties`, reusing this thread's previous one when they are the same
+/// 
+/// Callers usually read the same handful of properties from many elements in a row.
+fn thread_filtered_request(properties: &[UIProperty]) -> Result<UICacheRequest, UiError> {
+    FILTERED_REQUEST.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if let Some((cached, request)) = slot.as_ref() {
+            if cached.as_slice() == properties {
+                return Ok(request.clone());
+            }
+        }
+        let request = thread_automation()?.create_cache_request()?;
+        for property in properties {
+            request.add_property(*property)?;
+        }
+        request.set_element_mode(ElementMode::None)?;
+        *slot = Some((properties.to_vec(), request.clone()));
+        Ok(request)
+    })
+}
+
+/// Fetch every property in `BULK_INFO_PROPERTIES` of `element` in one cross-process call
+pub(crate) fn fetch_info_bulk(element: &UIAutomationElement, request: &UICacheRequest) -> Result<ElementInfoBulk, UiError> {
+    Ok(cached_info_bulk(&element.build_updated_cache(request)?))
+}
+
+/// Read `BULK_INFO_PROPERTIES` from an element already cached through an `info_cache_request`
+pub(crate) fn cached_info_bulk(cached: &UIAutomationElement) -> ElementInfoBulk {
+    // Unreadable flags default like `ElementInfoBulk::default()`: disabled and offscreen
+    ElementInfoBulk {
+        name: cached.get_cached_name().unwrap_or_default(),
+        control_type: cached.get_cached_control_type().ok(),
+        localized_control_type: cached.get_cached_localized_control_type().ok().filter(|name| !name.is_empty()),
+        class_name: cached.get_cached_classname().unwrap_or_default(),
+        automation_id: cached.get_cached_automation_id().ok().filter(|id| !id.is_empty()),
+        bounds: cached_bounds(cached),
+        is_enabled: cached_flag(cached, UIProperty::IsEnabled).unwrap_or(false),
+        is_offscreen: cached_flag(cached, UIProperty::IsOffscreen).unwrap_or(true),
+    }
+}
+
+/// What `insert_text_at` logs, with secret text redacted
+fn insertion_message(text: &str, offset: usize, secret: bool) -> String {
+    format!("Inserting text '{}' at offset {}", loggable_text(text, secret), offset)
+}
 
 /// Windows-specific UI element implementation
 pub struct WindowsElement {
     element: UIAutomationElement,
     automation: Option<UITreeWalker>,
+    mode: InteractionMode,
 }
 
 impl WindowsElement {
     pub fn new(element: UIAutomationElement, automation: Option<UITreeWalker>) -> Self {
-        WindowsElement { element, automation }
+        WindowsElement { element, automation, mode: InteractionMode::default() }
     }
 
-    pub fn is_offscreen(&self) -> Result<bool, Box<dyn Error>> {
-        Ok(self.element.is_offscreen()?)
+    /// Use `mode` for this element and every element reached from it
+    pub fn with_mode(mut self, mode: InteractionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// One attempt at `get_children`
+    fn children_once(&self) -> Result<Vec<Box<dyn CoreUIElement>>, UiError> {
+        // Children share this element's walker, so only the root of a walk ever creates one
+        let walker = self.tree_walker()?;
+        // The walker answers "no child" and "no sibling" with an error too; only
+        // a vanished element or a timeout means the walk itself failed
+        let step = |result: uiautomation::Result<UIAutomationElement>| match result {
+            Ok(element) => Ok(Some(element)),
+            Err(e) => match UiError::from(e) {
+                e @ (UiError::ElementNotFound(_) | UiError::Timeout(_)) => Err(e),
+                _ => Ok(None),
+            },
+        };
+        let mut children = Vec::new();
+        let mut child = step(walker.get_first_child(&self.element))?;
+        while let Some(current) = child {
+            child = step(walker.get_next_sibling(&current))?;
+            children.push(Box::new(WindowsElement::new(current, Some(walker.clone())).with_mode(self.mode)) as Box<dyn CoreUIElement>);
+        }
+        if children.is_empty() {
+            return Err("No children found".into());
+        }
+        Ok(children)
+    }
+
+    /// Refuse operations that only work with synthesized mouse or keyboard input
+    fn require_physical_input(&self, operation: &str) -> Result<(), UiError> {
+        match self.mode {
+            InteractionMode::Physical => Ok(()),
+            InteractionMode::PatternsOnly => Err(UiError::Unsupported(format!(
+                "{} needs synthesized input, which is disabled in PatternsOnly mode", operation
+            ))),
+        }
+    }
+
+    /// Activate the element through the first pattern that supports it
+    ///
+    /// Invoke covers buttons and menu items, Toggle covers check boxes, and
+    /// SelectionItem covers list items, tabs and radio buttons.
+    fn click_by_patterns(&self) -> Result<(), UiError> {
+        use uiautomation::patterns::UIInvokePattern;
+        if let Ok(pattern) = self.element.get_pattern::<UIInvokePattern>() {
+            debug!("WindowsElement::click - Invoking element");
+            return Ok(pattern.invoke()?);
+        }
+        if let Some(pattern) = self.toggle_pattern() {
+            debug!("WindowsElement::click - Toggling element");
+            return Ok(pattern.toggle()?);
+        }
+        if let Ok(pattern) = self.element.get_pattern::<UISelectionItemPattern>() {
+            debug!("WindowsElement::click - Selecting element");
+            return Ok(pattern.select()?);
+        }
+        Err(UiError::pattern_unsupported("Invoke"))
+    }
+
+    /// Get the tree walker for this element, creating one if none was provided
+    fn tree_walker(&self) -> Result<UITreeWalker, UiError> {
+        if let Some(walker) = &self.automation {
+            return Ok(walker.clone());
+        }
+        let automation = thread_automation()?;
+        Ok(automation.create_tree_walker()?)
+    }
+
+    /// Clear the element's content with select-all and delete keystrokes
+    fn clear_by_keystrokes(&self, interval: u64) {
+        if let Err(e) = self.element.send_keys("{Ctrl}a{Delete}", interval) {
+            warn!("Failed to clear existing text: {}", e);
+        }
+    }
+
+    /// Type `text` into the element
+    ///
+    /// Text with non-ASCII characters is injected as Unicode code points, since
+    /// typing through the keyboard layout drops or mangles characters the
+    /// active layout cannot produce directly.
+    fn type_text(&self, text: &str, interval: u64) -> Result<(), UiError> {
+        if text.is_ascii() {
+            return Ok(self.element.send_text(text, interval)?);
+        }
+        self.element.try_focus();
+        input::send_unicode_text(text, Duration::from_millis(interval))
+    }
+
+    /// Type `text` one word at a time, falling back to single characters for words that fail
+    fn type_word_by_word(&self, text: &str, interval: u64) {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        for (i, word) in words.iter().enumerate() {
+            debug!("Setting word {}: '{}'", i + 1, word);
+
+            if let Err(e) = self.type_text(word, interval) {
+                warn!("Failed to send word '{}': {}, trying character-by-character", word, e);
+                for ch in word.chars() {
+                    if let Err(e2) = self.type_text(&ch.to_string(), interval) {
+                        warn!("Failed to send character '{}': {}", ch, e2);
+                    }
+                }
+            }
+
+            // Add space between words (except for last word)
+            if i < words.len() - 1 {
+                if let Err(e) = self.type_text(" ", interval) {
+                    warn!("Failed to send space: {}", e);
+                }
+            }
+        }
+        info!("Completed text setting with corrections");
+    }
+
+    /// Read the text back when `options.verify` is set and fail on a mismatch
+    ///
+    /// Secret text is never read back, since a mismatch would put it in the error.
+    fn verify_typed_text(&self, expected: &str, options: &TextInputOptions, secret: bool) -> Result<(), UiError> {
+        if !options.verify {
+            return Ok(());
+        }
+        if secret {
+            debug!("Skipping verification of secret text");
+            return Ok(());
+        }
+        thread::sleep(TEXT_SETTLE_DELAY);
+        let actual = self.get_text()?;
+        if actual != expected {
+            return Err(UiError::PlatformError(format!(
+                "Text mismatch after input: expected '{}', got '{}'", expected, actual
+            )));
+        }
+        info!("✓ Text verified");
+        Ok(())
+    }
+
+    /// Get the screen point at the center of the element for synthesized mouse input
+    ///
+    /// The point is in the coordinates of the thread holding `dpi`: physical
+    /// pixels, like the element bounds, unless the thread could not switch
+    /// DPI awareness. Elements without on-screen bounds return an error
+    /// instead of resolving to (0, 0).
+    fn click_point(&self, dpi: &DpiAwarenessGuard) -> Result<Point, UiError> {
+        match self.get_bounds()? {
+            Some(bounds) if bounds.width() > 0 && bounds.height() > 0 => {
+                let (x, y) = dpi.to_thread_coordinates(&bounds).center();
+                Ok(Point::new(x, y))
+            },
+            _ => Err("Element has no on-screen bounds to click".into()),
+        }
+    }
+
+    /// Fetch name, control types, class, automation id, bounds, enabled and offscreen in one call
+    ///
+    /// Reading these through the individual getters costs one cross-process
+    /// round trip each; this costs one in total.
+    pub fn get_info_bulk(&self) -> Result<ElementInfoBulk, UiError> {
+        self.get_info_bulk_with(&thread_info_request()?)
+    }
+
+    /// `get_info_bulk` with a request from `info_cache_request`, reused across a walk
+    pub(crate) fn get_info_bulk_with(&self, request: &UICacheRequest) -> Result<ElementInfoBulk, UiError> {
+        fetch_info_bulk(&self.element, request)
     }
 
-    pub fn get_control_type_variant(&self) -> Result<i32, Box<dyn Error>> {
+    pub fn get_control_type_variant(&self) -> Result<i32, UiError> {
         let variant = self.element.get_property_value(UIProperty::ControlType)?;
         let control_type_id: i32 = variant.try_into()?;
         Ok(control_type_id)
     }
 
     #[allow(dead_code)]
-    fn get_control_type(&self) -> Result<ControlType, Box<dyn Error>> {
+    fn get_control_type(&self) -> Result<ControlType, UiError> {
         let control_type_id = self.get_control_type_variant()?;
         Ok(ControlType::try_from(control_type_id).unwrap_or(ControlType::Custom))
     }
 
     #[allow(dead_code)]
-    fn get_class_name(&self) -> Result<String, Box<dyn Error>> {
+    fn get_class_name(&self) -> Result<String, UiError> {
         Ok(self.element.get_classname()?)
     }
 
     #[allow(dead_code)]
-    fn is_input_control(&self) -> Result<bool, Box<dyn Error>> {
+    fn is_input_control(&self) -> Result<bool, UiError> {
         let control_type = self.get_control_type()?;
         Ok(match control_type {
             ControlType::Edit => true,
@@ -73,7 +520,7 @@ impl WindowsElement {
 }
 
 impl CoreUIElement for WindowsElement {
-    fn get_name(&self) -> Result<String, Box<dyn Error>> {
+    fn get_name(&self) -> Result<String, UiError> {
         debug!("WindowsElement::get_name - Getting name");
         match self.element.get_name() {
             Ok(name) => {
@@ -87,14 +534,18 @@ impl CoreUIElement for WindowsElement {
         }
     }
 
-    fn get_type(&self) -> Result<String, Box<dyn Error>> {
+    fn get_type(&self) -> Result<String, UiError> {
         debug!("WindowsElement::get_type - Getting control type");
         let control_type = self.get_control_type()?;
         debug!("WindowsElement::get_type - Success: {}", control_type);
         Ok(control_type.to_string())
     }
 
-    fn get_text(&self) -> Result<String, Box<dyn Error>> {
+    fn get_localized_control_type(&self) -> Result<String, UiError> {
+        Ok(self.element.get_localized_control_type()?)
+    }
+
+    fn get_text(&self) -> Result<String, UiError> {
         debug!("Getting text from element");
         
         let control_type = self.get_control_type()?;
@@ -129,8 +580,45 @@ impl CoreUIElement for WindowsElement {
         Ok(String::new())
     }
 
-    fn set_text(&self, text: &str) -> Result<(), Box<dyn Error>> {
-        debug!("Setting text '{}' in element", text);
+    fn get_text_limited(&self, max_chars: usize) -> Result<(String, bool), UiError> {
+        let (text, truncated) = element_text_limited(&self.element, max_chars)?;
+        debug!("WindowsElement::get_text_limited - Read {} characters, truncated: {}", text.chars().count(), truncated);
+        Ok((text, truncated))
+    }
+
+    fn get_text_via_clipboard(&self) -> Result<String, UiError> {
+        self.require_physical_input("Copying text through the clipboard")?;
+
+        // Only the text format is preserved; other clipboard formats are lost
+        let saved = clipboard::read_text().unwrap_or_else(|e| {
+            warn!("Could not save the clipboard before copying: {}", e);
+            None
+        });
+
+        clipboard::clear()?;
+        let since = clipboard::sequence_number();
+        let copied = self.set_focus()
+            .and_then(|_| Ok(self.element.send_keys("{Ctrl}a{Ctrl}c", 10)?))
+            .and_then(|_| clipboard::wait_for_text(since, CLIPBOARD_COPY_TIMEOUT));
+
+        let restored = match saved {
+            Some(text) => clipboard::write_text(&text),
+            None => clipboard::clear(),
+        };
+        if let Err(e) = restored {
+            warn!("Could not restore the clipboard after copying: {}", e);
+        }
+        copied
+    }
+
+    fn set_text(&self, text: &str) -> Result<(), UiError> {
+        self.set_text_with_options(text, &TextInputOptions::default())
+    }
+
+    fn set_text_with_options(&self, text: &str, options: &TextInputOptions) -> Result<(), UiError> {
+        let secret = options.secret || self.is_password().unwrap_or(false);
+        debug!("Setting text '{}' in element with {:?}", loggable_text(text, secret), options);
+        let mut timer = OperationTimer::start("set_text");
         
         if !self.is_input_control()? {
             return Err("Element is not an input control".into());
@@ -144,6 +632,29 @@ impl CoreUIElement for WindowsElement {
             "Element details - Name: {}, Type: {:?}, ClassName: {}",
             name, control_type, class_name
         );
+        timer.phase("inspect element");
+
+        if self.mode == InteractionMode::PatternsOnly {
+            if self.writable_value_pattern().is_none() {
+                return Err(UiError::pattern_unsupported("Value"));
+            }
+            self.set_value(text)?;
+            timer.phase("Value pattern");
+            return self.verify_typed_text(text, options, secret);
+        }
+
+        // Prefer replacing the value directly; keystrokes are only needed for
+        // controls without a writable Value pattern
+        if options.use_value_pattern_if_available && self.writable_value_pattern().is_some() {
+            match self.set_value(text) {
+                Ok(()) => {
+                    info!("Set text using Value pattern");
+                    timer.phase("Value pattern");
+                    return self.verify_typed_text(text, options, secret);
+                }
+                Err(e) => warn!("Value pattern rejected the text ({}), falling back to keystrokes", e),
+            }
+        }
 
         // First try to select all text using Text pattern
         if let Ok(text_pattern) = self.element.get_pattern::<UITextPattern>() {
@@ -156,111 +667,100 @@ impl CoreUIElement for WindowsElement {
             }
         }
 
-        // Clear any existing text by sending Ctrl+A and Delete
-        thread::sleep(Duration::from_millis(50));
-        if let Err(e) = self.element.send_keys("{Ctrl}a{Delete}", 10) {
-            warn!("Failed to clear existing text: {}", e);
-        }
-        thread::sleep(Duration::from_millis(50));
+        let interval = u64::from(options.interval_ms);
+        self.clear_by_keystrokes(interval);
 
-        // Step 1: Try to send the whole text at once
-        info!("Attempting to set entire text: '{}'", text);
-        
-        // Try sending the whole text
-        if let Err(e) = self.element.send_text(text, 30) {
+        info!("Attempting to set entire text: '{}'", loggable_text(text, secret));
+        if let Err(e) = self.type_text(text, interval) {
             warn!("Failed to send entire text: {}", e);
             return Err(format!("Failed to send text: {}", e).into());
         }
-        
-        // Step 2: Verify what actually got input
-        thread::sleep(Duration::from_millis(200)); // Give time for text to be processed
-        let actual_text = self.get_text().unwrap_or_default();
-        
-        info!("Expected text: '{}'", text);
-        info!("Actual text: '{}'", actual_text);
-        
-        // Step 3: Check if we got what we wanted
-        if actual_text == text {
-            info!("✓ Text set correctly on first try");
-            return Ok(());
+        timer.phase("type text");
+
+        // Correcting means reading the text back, which secrets never are
+        if options.correction == CorrectionMode::WordByWord && !secret {
+            thread::sleep(TEXT_SETTLE_DELAY);
+            let actual_text = self.get_text().unwrap_or_default();
+            if actual_text == text {
+                info!("✓ Text set correctly on first try");
+            } else {
+                warn!("Text not set correctly (got '{}'), retyping word by word", actual_text);
+                self.clear_by_keystrokes(interval);
+                self.type_word_by_word(text, interval);
+            }
         }
-        
-        // Step 4: If not perfect, clear and try correction approach
-        warn!("Text not set correctly, attempting corrections...");
-        
-        // Clear everything and try word-by-word
-        if let Err(e) = self.element.send_keys("{Ctrl}a{Delete}", 10) {
-            warn!("Failed to clear text for correction: {}", e);
+        timer.phase("correct text");
+
+        self.verify_typed_text(text, options, secret)
+    }
+
+    fn clear(&self) -> Result<(), UiError> {
+        if !self.is_editable()? {
+            return Err(UiError::InvalidArgument("Cannot clear an element that is not editable".to_string()));
         }
-        thread::sleep(Duration::from_millis(50));
-        
-        // Try word-by-word correction
-        let words: Vec<&str> = text.split_whitespace().collect();
-        for (i, word) in words.iter().enumerate() {
-            info!("Setting word {}: '{}'", i + 1, word);
-            
-            if let Err(e) = self.element.send_text(word, 20) {
-                warn!("Failed to send word '{}': {}, trying character-by-character", word, e);
-                
-                // If word fails, try character by character for this word only
-                for ch in word.chars() {
-                    let char_str = ch.to_string();
-                    
-                    // Handle newlines specially
-                    if ch == '\n' {
-                        if let Err(e) = self.element.send_keys("{shift}{enter}", 20) {
-                            warn!("Failed to add newline: {}", e);
-                        }
-                        continue;
-                    }
-                    
-                    if let Err(e2) = self.element.send_text(&char_str, 15) {
-                        warn!("Failed to send character '{}': {}", ch, e2);
-                    }
-                    
-                    // Small delay for non-ASCII characters
-                    if !ch.is_ascii() {
-                        thread::sleep(Duration::from_millis(10));
-                    }
-                }
-            }
-            
-            // Add space between words (except for last word)
-            if i < words.len() - 1 {
-                if let Err(e) = self.element.send_text(" ", 10) {
-                    warn!("Failed to send space: {}", e);
-                }
+
+        if let Some(pattern) = self.writable_value_pattern() {
+            match pattern.set_value("") {
+                Ok(()) => return Ok(()),
+                Err(e) => warn!("Value pattern rejected clearing ({}), falling back to keystrokes", e),
             }
-            
-            // Small delay between words
-            thread::sleep(Duration::from_millis(20));
+        }
+        if self.mode == InteractionMode::PatternsOnly {
+            return Err(UiError::pattern_unsupported("Value"));
         }
 
-        info!("Completed text setting with corrections");
+        self.element.try_focus();
+        self.element.send_keys("{Ctrl}a{Delete}", 10)?;
+        thread::sleep(TEXT_SETTLE_DELAY);
 
-        // Final verification
-        thread::sleep(Duration::from_millis(100));
-        match self.get_text() {
-            Ok(current_text) => {
-                if current_text == text {
-                    info!("✓ Text verified correctly after corrections");
-                } else {
-                    warn!("Text still not correct after corrections. Expected: '{}', Got: '{}'", text, current_text);
-                }
+        // Keystrokes can be swallowed, so only an empty field counts as cleared
+        let remaining = self.get_text()?;
+        if !remaining.is_empty() {
+            // Only the length, so the contents of a password field never reach errors or logs
+            return Err(UiError::PlatformError(format!(
+                "Field still contains {} characters after clearing", remaining.chars().count()
+            )));
+        }
+        Ok(())
+    }
+
+    fn select_all(&self) -> Result<(), UiError> {
+        if let Ok(text_pattern) = self.element.get_pattern::<UITextPattern>() {
+            match text_pattern.get_document_range().and_then(|range| range.select()) {
+                Ok(()) => return Ok(()),
+                Err(e) => warn!("Failed to select the document range ({}), falling back to keystrokes", e),
             }
-            Err(e) => warn!("Failed to verify final text: {}", e),
+        }
+        if self.mode == InteractionMode::PatternsOnly {
+            return Err(UiError::pattern_unsupported("Text"));
         }
 
-        Ok(())
+        self.element.try_focus();
+        Ok(self.element.send_keys("{Ctrl}a", 10)?)
     }
 
-    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), Box<dyn Error>> {
-        debug!("Appending text '{}' to element at position {:?}", text, position);
+    fn append_text(&self, text: &str, position: AppendPosition) -> Result<(), UiError> {
+        let secret = self.is_password().unwrap_or(false);
+        debug!("Appending text '{}' to element at position {:?}", loggable_text(text, secret), position);
+        let mut timer = OperationTimer::start("append_text");
         
         if !self.is_input_control()? {
             return Err("Element is not an input control".into());
         }
 
+        if self.mode == InteractionMode::PatternsOnly {
+            // Without a caret the Value pattern can only append at the very end
+            if !matches!(position, AppendPosition::EndOfText) {
+                return Err(UiError::Unsupported(format!(
+                    "Appending at {:?} needs keystrokes, which are disabled in PatternsOnly mode", position
+                )));
+            }
+            let pattern = self.writable_value_pattern()
+                .ok_or_else(|| UiError::pattern_unsupported("Value"))?;
+            let current = pattern.get_value()?;
+            return Ok(pattern.set_value(&format!("{}{}", current, text))?);
+        }
+
         // Try to set focus first
         if let Err(e) = self.element.set_focus() {
             warn!("Failed to set focus: {}", e);
@@ -294,16 +794,23 @@ impl CoreUIElement for WindowsElement {
         thread::sleep(Duration::from_millis(50));
 
         // Step 1: Try to send the whole text at once
-        info!("Attempting to send entire text: '{}'", text);
+        info!("Attempting to send entire text: '{}'", loggable_text(text, secret));
         
         // Get text before appending to know where we started
-        let text_before = self.get_text().unwrap_or_default();
+        let text_before = if secret { String::new() } else { self.get_text().unwrap_or_default() };
         
         // Try sending the whole text
-        if let Err(e) = self.element.send_text(text, 30) {
+        if let Err(e) = self.type_text(text, 30) {
             warn!("Failed to send entire text: {}", e);
             return Err(format!("Failed to send text: {}", e).into());
         }
+        timer.phase("type text");
+
+        // Reading a password field back would log the secret, and usually gives masked text anyway
+        if secret {
+            debug!("Skipping read-back of text appended to a password field");
+            return Ok(());
+        }
         
         // Step 2: Verify what actually got input
         thread::sleep(Duration::from_millis(200)); // Give time for text to be processed
@@ -346,13 +853,13 @@ impl CoreUIElement for WindowsElement {
         for (i, word) in words.iter().enumerate() {
             info!("Sending word {}: '{}'", i + 1, word);
             
-            if let Err(e) = self.element.send_text(word, 20) {
+            if let Err(e) = self.type_text(word, 20) {
                 warn!("Failed to send word '{}': {}, trying character-by-character", word, e);
                 
                 // If word fails, try character by character for this word only
                 for ch in word.chars() {
                     let char_str = ch.to_string();
-                    if let Err(e2) = self.element.send_text(&char_str, 15) {
+                    if let Err(e2) = self.type_text(&char_str, 15) {
                         warn!("Failed to send character '{}': {}", ch, e2);
                     }
                     
@@ -365,7 +872,7 @@ impl CoreUIElement for WindowsElement {
             
             // Add space between words (except for last word)
             if i < words.len() - 1 {
-                if let Err(e) = self.element.send_text(" ", 10) {
+                if let Err(e) = self.type_text(" ", 10) {
                     warn!("Failed to send space: {}", e);
                 }
             }
@@ -378,7 +885,58 @@ impl CoreUIElement for WindowsElement {
         Ok(())
     }
 
-    fn click(&self) -> Result<(), Box<dyn Error>> {
+    fn insert_text_at(&self, offset: usize, text: &str) -> Result<(), UiError> {
+        let secret = self.is_password().unwrap_or(false);
+        debug!("{}", insertion_message(text, offset, secret));
+
+        if !self.is_input_control()? {
+            return Err("Element is not an input control".into());
+        }
+
+        // Typing at a caret needs keystrokes, so PatternsOnly always rewrites the value
+        let text_pattern = match self.mode {
+            InteractionMode::Physical => self.element.get_pattern::<UITextPattern>().ok(),
+            InteractionMode::PatternsOnly => None,
+        };
+        if let Some(text_pattern) = text_pattern {
+            // Collapse the document range to its start, then walk it forward;
+            // UIA stops moving at the end of the document, which clamps the offset
+            let caret = text_pattern.get_document_range()?;
+            caret.move_endpoint_by_range(TextPatternRangeEndpoint::End, &caret, TextPatternRangeEndpoint::Start)?;
+            let moved = caret.move_text(TextUnit::Character, i32::try_from(offset).unwrap_or(i32::MAX))?;
+            debug!("Caret moved {} characters", moved);
+
+            if let Err(e) = self.element.set_focus() {
+                warn!("Failed to set focus: {}", e);
+            }
+            caret.select()?;
+            return self.type_text(text, 30)
+                .map_err(|e| format!("Failed to send text: {}", e).into());
+        }
+
+        // Without a Text pattern, rewrite the whole value with the text spliced in
+        match self.writable_value_pattern() {
+            Some(pattern) => {
+                let current = pattern.get_value()?;
+                pattern.set_value(&splice_at_char_offset(&current, offset, text))
+                    .map_err(|e| format!("Failed to set value: {}", e).into())
+            }
+            None => Err(UiError::pattern_unsupported("Text")),
+        }
+    }
+
+    fn invoke(&self) -> Result<(), UiError> {
+        use uiautomation::patterns::UIInvokePattern;
+        let pattern = self.element.get_pattern::<UIInvokePattern>()
+            .map_err(|_| UiError::pattern_unsupported("Invoke"))?;
+        Ok(pattern.invoke()?)
+    }
+
+    fn click(&self) -> Result<(), UiError> {
+        if self.mode == InteractionMode::PatternsOnly {
+            return self.click_by_patterns();
+        }
+
         // Try to get the element's bounds for clicking
         if let Ok(Some(bounds)) = self.get_bounds() {
             // Calculate center point
@@ -416,53 +974,151 @@ impl CoreUIElement for WindowsElement {
         }
     }
 
-    fn is_enabled(&self) -> Result<bool, Box<dyn Error>> {
-        match self.element.is_enabled() {
-            Ok(enabled) => Ok(enabled),
-            Err(e) => Err(e.into())
+    fn right_click(&self) -> Result<(), UiError> {
+        debug!("WindowsElement::right_click - Right-clicking element");
+        if self.mode == InteractionMode::PatternsOnly {
+            return self.element.show_context_menu()
+                .map_err(|e| format!("Failed to show the context menu: {}", e).into());
         }
-    }
 
-    fn get_properties(&self) -> Result<HashMap<String, String>, Box<dyn Error>> {
-        let mut properties = HashMap::new();
-        
-        // Get only essential properties for performance
-        if let Ok(name) = self.element.get_name() {
-            properties.insert("name".to_string(), name);
+        let dpi = DpiAwarenessGuard::per_monitor();
+        let mouse_result = self.click_point(&dpi).and_then(|point| {
+            self.element.try_focus();
+            Mouse::default().right_click(point).map_err(|e| e.into())
+        });
+
+        match mouse_result {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // Fallback: ask the provider to show its context menu directly
+                warn!("WindowsElement::right_click - Mouse right-click failed: {}, trying ShowContextMenu", e);
+                self.element.show_context_menu()
+                    .map_err(|e2| format!("Failed to right-click element: {} ({})", e, e2).into())
+            }
         }
-        if let Ok(class_name) = self.element.get_classname() {
-            properties.insert("class_name".to_string(), class_name);
+    }
+
+    fn double_click(&self) -> Result<(), UiError> {
+        debug!("WindowsElement::double_click - Double-clicking element");
+        if self.mode == InteractionMode::PatternsOnly {
+            use uiautomation::patterns::UIInvokePattern;
+            let pattern = self.element.get_pattern::<UIInvokePattern>()
+                .map_err(|_| UiError::pattern_unsupported("Invoke"))?;
+            return Ok(pattern.invoke()?);
         }
-        
-        // Get control type efficiently
-        if let Ok(variant) = self.element.get_property_value(UIProperty::ControlType) {
-            if let Ok(control_type_id) = <Variant as TryInto<i32>>::try_into(variant) {
-                if let Ok(control_type) = ControlType::try_from(control_type_id) {
-                    properties.insert("control_type".to_string(), control_type.to_string());
+
+        let dpi = DpiAwarenessGuard::per_monitor();
+        let mouse_result = self.click_point(&dpi).and_then(|point| {
+            self.element.try_focus();
+            Mouse::default().double_click(point).map_err(|e| e.into())
+        });
+
+        match mouse_result {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // Fallback: double-activation usually maps to the invoke pattern
+                use uiautomation::patterns::UIInvokePattern;
+                warn!("WindowsElement::double_click - Mouse double-click failed: {}, trying Invoke pattern", e);
+                if let Ok(invoke_pattern) = self.element.get_pattern::<UIInvokePattern>() {
+                    invoke_pattern.invoke()
+                        .map_err(|e2| format!("Failed to double-click element: {}", e2).into())
+                } else {
+                    Err(format!("Failed to double-click element: {}", e).into())
                 }
             }
         }
-        
-        // Get automation ID if available (useful for finding elements)
-        if let Ok(variant) = self.element.get_property_value(UIProperty::AutomationId) {
-            let automation_id = variant.to_string();
-            if !automation_id.is_empty() && automation_id != "null" {
-                properties.insert("automation_id".to_string(), automation_id);
-            }
-        }
-        
-        // Get enabled state efficiently
-        if let Ok(enabled) = self.element.is_enabled() {
-            properties.insert("enabled".to_string(), enabled.to_string());
-        }
-        
+    }
+
+    fn set_focus(&self) -> Result<(), UiError> {
+        debug!("WindowsElement::set_focus - Setting focus on element");
+        self.element.set_focus()
+            .map_err(|e| format!("Failed to set focus on element: {}", e).into())
+    }
+
+    fn is_enabled(&self) -> Result<bool, UiError> {
+        match self.element.is_enabled() {
+            Ok(enabled) => Ok(enabled),
+            Err(e) => Err(e.into())
+        }
+    }
+
+    fn is_offscreen(&self) -> Result<bool, UiError> {
+        Ok(self.element.is_offscreen()?)
+    }
+
+    fn is_editable(&self) -> Result<bool, UiError> {
+        element_is_editable(&self.element, self.get_control_type()?)
+    }
+
+    fn is_focusable(&self) -> Result<bool, UiError> {
+        Ok(self.element.is_keyboard_focusable()?)
+    }
+
+    fn get_orientation(&self) -> Result<Orientation, UiError> {
+        Ok(match self.element.get_orientation()? {
+            OrientationType::None => Orientation::None,
+            OrientationType::Horizontal => Orientation::Horizontal,
+            OrientationType::Vertical => Orientation::Vertical,
+        })
+    }
+
+    fn is_content_element(&self) -> Result<bool, UiError> {
+        Ok(self.element.is_content_element()?)
+    }
+
+    fn is_control_element(&self) -> Result<bool, UiError> {
+        Ok(self.element.is_control_element()?)
+    }
+
+    fn get_access_key(&self) -> Result<Option<String>, UiError> {
+        Ok(key_hint(self.element.get_access_key()?))
+    }
+
+    fn get_accelerator_key(&self) -> Result<Option<String>, UiError> {
+        Ok(key_hint(self.element.get_accelerator_key()?))
+    }
+
+    fn get_properties(&self) -> Result<HashMap<String, String>, UiError> {
+        // Only essential properties, all fetched in a single round trip
+        Ok(self.get_info_bulk()?.properties())
+    }
+
+    fn is_password(&self) -> Result<bool, UiError> {
+        Ok(self.element.is_password()?)
+    }
+
+    fn get_properties_filtered(&self, keys: &[&str]) -> Result<HashMap<String, String>, UiError> {
+        let mut wanted = Vec::new();
+        for key in keys {
+            match filterable_property(key) {
+                Some((property, format)) => wanted.push((*key, property, format)),
+                None => warn!("WindowsElement::get_properties_filtered - skipping unknown property '{}'", key),
+            }
+        }
+        if wanted.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // One cross-process call for all the requested properties
+        let properties: Vec<UIProperty> = wanted.iter().map(|(_, property, _)| *property).collect();
+        let cached = self.element.build_updated_cache(&thread_filtered_request(&properties)?)?;
+
+        let mut properties = HashMap::new();
+        for (key, property, format) in wanted {
+            match cached.get_cached_property_value(property).ok().and_then(|value| format_property(&value, format)) {
+                Some(value) => {
+                    properties.insert(key.to_string(), value);
+                }
+                None => debug!("WindowsElement::get_properties_filtered - element does not report '{}'", key),
+            }
+        }
         Ok(properties)
     }
 
-    fn get_bounds(&self) -> Result<Option<Rect>, Box<dyn Error>> {
+    fn get_bounds(&self) -> Result<Option<Rect>, UiError> {
         match self.element.get_bounding_rectangle() {
             Ok(rect) => {
-                let rect: RECT = rect.try_into()?;
+                let rect: RECT = rect.into();
                 Ok(Some(Rect {
                     left: rect.left,
                     top: rect.top,
@@ -474,39 +1130,359 @@ impl CoreUIElement for WindowsElement {
         }
     }
 
-    fn get_children(&self) -> Result<Vec<Box<dyn CoreUIElement>>, Box<dyn Error>> {
-        let mut children = Vec::new();
-        let automation = self.automation.clone();
-        if let Some(walker) = &self.automation {
-            if let Ok(first_child) = walker.get_first_child(&self.element) {
-                children.push(Box::new(WindowsElement::new(first_child.clone(), automation.clone())) as Box<dyn CoreUIElement>);
-                let mut next = first_child;
-                while let Ok(sibling) = walker.get_next_sibling(&next) {
-                    children.push(Box::new(WindowsElement::new(sibling.clone(), automation.clone())) as Box<dyn CoreUIElement>);
-                    next = sibling;
-                }
+    fn get_children(&self) -> Result<Vec<Box<dyn CoreUIElement>>, UiError> {
+        // A UI that changes mid-walk makes UI Automation fail with a transient error
+        with_retry(DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_DELAY, || self.children_once())
+    }
+
+    fn get_parent(&self) -> Result<Option<Box<dyn CoreUIElement>>, UiError> {
+        let walker = self.tree_walker()?;
+        let parent = match walker.get_parent(&self.element) {
+            Ok(parent) => parent,
+            // The desktop root has no parent
+            Err(_) => return Ok(None),
+        };
+
+        // Guard against providers that report an element as its own parent
+        if let (Ok(own_id), Ok(parent_id)) = (self.element.get_runtime_id(), parent.get_runtime_id()) {
+            if own_id == parent_id {
+                warn!("WindowsElement::get_parent - Element reported itself as its parent");
+                return Ok(None);
             }
+        }
+
+        Ok(Some(Box::new(WindowsElement::new(parent, Some(walker)).with_mode(self.mode)) as Box<dyn CoreUIElement>))
+    }
+
+    fn get_subtree(&self, max_depth: u32) -> Result<UITreeNode, UiError> {
+        let mut timer = OperationTimer::start("get_subtree");
+        let automation = thread_automation()?;
+        let true_condition = automation.create_true_condition()?;
+
+        // Each node's children arrive with their properties in one call, level by
+        // level, so nothing below max_depth is ever fetched. The cached elements keep
+        // a live reference because the next level is fetched from them.
+        let request = automation.create_cache_request()?;
+        for property in SUBTREE_PROPERTIES {
+            request.add_property(*property)?;
+        }
+        request.set_tree_scope(TreeScope::Element)?;
+        // The raw view, like the walker behind get_children
+        request.set_tree_filter(true_condition.clone())?;
+
+        let root = self.element.build_updated_cache(&request)?;
+        timer.phase("cache root");
+
+        let options = UITreeOptions { max_depth: Some(max_depth), ..UITreeOptions::unlimited() };
+        let (node, _) = build_tree_bounded(
+            root,
+            &options,
+            describe_cached_node,
+            |element, limit| element.find_all_build_cache(TreeScope::Children, &true_condition, &request)
+                .map(|children| children.into_iter().take(limit).collect())
+                .unwrap_or_default(),
+        )?;
+        timer.phase("fetch cached levels");
+        Ok(node)
+    }
+
+    fn index_in_parent(&self) -> Result<Option<usize>, UiError> {
+        let walker = self.tree_walker()?;
+        if walker.get_parent(&self.element).is_err() {
+            return Ok(None);
+        }
+
+        // Counting previous siblings avoids materializing the whole sibling list
+        let mut index = 0;
+        let mut sibling = walker.get_previous_sibling(&self.element).ok();
+        while let Some(current) = sibling {
+            index += 1;
+            sibling = walker.get_previous_sibling(&current).ok();
+        }
+        Ok(Some(index))
+    }
+
+    fn get_toggle_state(&self) -> Result<Option<bool>, UiError> {
+        match self.toggle_pattern() {
+            Some(pattern) => Ok(Some(toggle_state_to_bool(pattern.get_toggle_state()?)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn set_toggle_state(&self, checked: bool) -> Result<(), UiError> {
+        let pattern = self.toggle_pattern()
+            .ok_or_else(|| UiError::pattern_unsupported("Toggle"))?;
+
+        if toggle_state_to_bool(pattern.get_toggle_state()?)? == checked {
+            debug!("WindowsElement::set_toggle_state - Already in state {}", checked);
+            return Ok(());
+        }
+
+        pattern.toggle()?;
+
+        // Tri-state controls cycle through Indeterminate; report it rather than keep cycling
+        let new_state = toggle_state_to_bool(pattern.get_toggle_state()?)?;
+        if new_state != checked {
+            return Err(format!("Element did not reach toggle state {} after toggling", checked).into());
+        }
+        Ok(())
+    }
+
+    fn get_selection_options(&self) -> Result<Vec<String>, UiError> {
+        let expander = self.expand_dropdown();
+        let items = self.list_items();
+        self.collapse_dropdown(expander);
+
+        Ok(items?.iter()
+            .filter_map(|item| item.get_name().ok())
+            .collect())
+    }
+
+    fn select_option(&self, value: &str) -> Result<(), UiError> {
+        let expander = self.expand_dropdown();
+        let selected = self.select_list_item(value);
+        self.collapse_dropdown(expander);
+
+        if selected? {
+            return Ok(());
+        }
+
+        // Editable combo boxes accept values that are not in the list
+        let accepts_free_text = self.element.get_pattern::<UIValuePattern>()
+            .and_then(|pattern| pattern.is_readonly())
+            .map(|readonly| !readonly)
+            .unwrap_or(false);
+        if accepts_free_text {
+            debug!("WindowsElement::select_option - No option '{}', typing it instead", value);
+            return self.set_text(value);
+        }
+
+        Err(format!("No option named '{}' found", value).into())
+    }
+
+    fn get_selected_items(&self) -> Result<Vec<Box<dyn CoreUIElement>>, UiError> {
+        let pattern = self.element.get_pattern::<UISelectionPattern>()
+            .map_err(|_| UiError::pattern_unsupported("Selection"))?;
+        Ok(pattern.get_selection()?
+            .into_iter()
+            .map(|item| Box::new(WindowsElement::new(item, self.automation.clone()).with_mode(self.mode)) as Box<dyn CoreUIElement>)
+            .collect())
+    }
+
+    fn select_item(&self, add_to_selection: bool) -> Result<(), UiError> {
+        let pattern = self.element.get_pattern::<UISelectionItemPattern>()
+            .map_err(|_| UiError::pattern_unsupported("SelectionItem"))?;
+        let container_multiple = || pattern.get_selection_container().ok()
+            .and_then(|container| container.get_pattern::<UISelectionPattern>().ok())
+            .and_then(|selection| selection.can_select_multiple().ok());
+
+        if adds_to_selection(add_to_selection, container_multiple) {
+            Ok(pattern.add_to_selection()?)
         } else {
-            // Use a match-all condition (TrueCondition) for children
-            let condition = AUTOMATION.with(|cell| {
-                let automation = cell.borrow();
-                let automation = automation.as_ref().ok_or_else(|| Box::<dyn Error>::from("No global automation instance"))?;
-                let automation = automation.lock().map_err(|e| Box::<dyn Error>::from(format!("Failed to lock automation: {}", e)))?;
-                automation.create_true_condition().map_err(|e| Box::<dyn Error>::from(format!("Failed to create true condition: {}", e)))
-            })?;
-            let child_elements = self.element.find_all(TreeScope::Children, &condition)?;
-            for child in child_elements {
-                children.push(Box::new(WindowsElement::new(child.clone(), None)) as Box<dyn CoreUIElement>);
+            Ok(pattern.select()?)
+        }
+    }
+
+    fn deselect_item(&self) -> Result<(), UiError> {
+        let pattern = self.element.get_pattern::<UISelectionItemPattern>()
+            .map_err(|_| UiError::pattern_unsupported("SelectionItem"))?;
+        Ok(pattern.remove_from_selection()?)
+    }
+
+    fn get_value(&self) -> Result<String, UiError> {
+        let pattern = self.element.get_pattern::<UIValuePattern>()
+            .map_err(|_| UiError::pattern_unsupported("Value"))?;
+        Ok(pattern.get_value()?)
+    }
+
+    fn set_value(&self, value: &str) -> Result<(), UiError> {
+        let pattern = self.element.get_pattern::<UIValuePattern>()
+            .map_err(|_| UiError::pattern_unsupported("Value"))?;
+        if pattern.is_readonly()? {
+            return Err("Element value is read-only".into());
+        }
+        pattern.set_value(value)
+            .map_err(|e| format!("Failed to set value: {}", e).into())
+    }
+
+    fn get_expand_state(&self) -> Result<Option<ExpandState>, UiError> {
+        match self.element.get_pattern::<UIExpandCollapsePattern>() {
+            Ok(pattern) => Ok(Some(expand_state_from(pattern.get_state()?))),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn expand(&self) -> Result<(), UiError> {
+        let pattern = self.element.get_pattern::<UIExpandCollapsePattern>()
+            .map_err(|_| UiError::pattern_unsupported("ExpandCollapse"))?;
+        match expand_state_from(pattern.get_state()?) {
+            ExpandState::Expanded => Ok(()),
+            ExpandState::LeafNode => Err(UiError::InvalidArgument(format!(
+                "'{}' is a leaf node with nothing to expand", self.get_name().unwrap_or_default()
+            ))),
+            ExpandState::Collapsed | ExpandState::PartiallyExpanded => Ok(pattern.expand()?),
+        }
+    }
+
+    fn collapse(&self) -> Result<(), UiError> {
+        let pattern = self.element.get_pattern::<UIExpandCollapsePattern>()
+            .map_err(|_| UiError::pattern_unsupported("ExpandCollapse"))?;
+        match expand_state_from(pattern.get_state()?) {
+            ExpandState::Collapsed | ExpandState::LeafNode => Ok(()),
+            ExpandState::Expanded | ExpandState::PartiallyExpanded => Ok(pattern.collapse()?),
+        }
+    }
+
+    fn get_range_value(&self) -> Result<Option<RangeValue>, UiError> {
+        let pattern = match self.element.get_pattern::<UIRangeValuePattern>() {
+            Ok(pattern) => pattern,
+            Err(_) => return Ok(None),
+        };
+        Ok(Some(RangeValue {
+            value: pattern.get_value()?,
+            min: pattern.get_minimum()?,
+            max: pattern.get_maximum()?,
+            small_change: pattern.get_small_change()?,
+            large_change: pattern.get_large_change()?,
+        }))
+    }
+
+    fn set_range_value(&self, value: f64) -> Result<(), UiError> {
+        let pattern = self.element.get_pattern::<UIRangeValuePattern>()
+            .map_err(|_| UiError::pattern_unsupported("RangeValue"))?;
+        if pattern.is_readonly()? {
+            return Err("Element range value is read-only".into());
+        }
+
+        let range = RangeValue {
+            value: pattern.get_value()?,
+            min: pattern.get_minimum()?,
+            max: pattern.get_maximum()?,
+            small_change: 0.0,
+            large_change: 0.0,
+        };
+        let clamped = range.clamp(value);
+        if clamped != value {
+            debug!("WindowsElement::set_range_value - Clamped {} to {}", value, clamped);
+        }
+        pattern.set_value(clamped)
+            .map_err(|e| format!("Failed to set range value: {}", e).into())
+    }
+
+    fn hover_for(&self, dwell: Duration, hover_restore: bool) -> Result<(), UiError> {
+        self.require_physical_input("Hovering")?;
+
+        // Bounds and cursor coordinates must both be physical pixels on scaled monitors
+        let dpi = DpiAwarenessGuard::per_monitor();
+        let point = self.click_point(&dpi)?;
+
+        let mut original = POINT::default();
+        if hover_restore {
+            unsafe { GetCursorPos(&mut original)? };
+        }
+
+        debug!("WindowsElement::hover_for - Moving cursor to ({}, {})", point.get_x(), point.get_y());
+        unsafe { SetCursorPos(point.get_x(), point.get_y())? };
+        if !dwell.is_zero() {
+            thread::sleep(dwell);
+        }
+
+        if hover_restore {
+            unsafe { SetCursorPos(original.x, original.y)? };
+        }
+        Ok(())
+    }
+
+    fn send_chord(&self, chord: &KeyChord) -> Result<(), UiError> {
+        let keys = chord.to_key_string()?;
+        self.require_physical_input("Sending keys")?;
+        self.set_focus()?;
+
+        debug!("WindowsElement::send_chord - Sending {} as {:?}", chord, keys);
+        Keyboard::new()
+            .interval(10)
+            .send_keys(&keys)
+            .map_err(|e| UiError::PlatformError(format!("Failed to send {}: {}", chord, e)))
+    }
+
+    fn scroll(&self, direction: ScrollDirection, amount: f64) -> Result<(), UiError> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("Scroll amount must be a positive percentage, got {}", amount).into());
+        }
+
+        let pattern = self.element.get_pattern::<UIScrollPattern>()
+            .map_err(|_| UiError::pattern_unsupported("Scroll"))?;
+
+        let (scrollable, current) = if direction.is_horizontal() {
+            (pattern.is_horizontally_scrollable()?, pattern.get_horizontal_scroll_percent()?)
+        } else {
+            (pattern.is_vertically_scrollable()?, pattern.get_vertical_scroll_percent()?)
+        };
+        if !scrollable {
+            debug!("WindowsElement::scroll - Element cannot scroll {:?}", direction);
+            return Err(UiError::pattern_unsupported("Scroll"));
+        }
+
+        let target = scroll_target(current, direction, amount);
+        debug!("WindowsElement::scroll - {:?} from {:.1}% to {:.1}%", direction, current, target);
+        if direction.is_horizontal() {
+            pattern.set_scroll_percent(target, NO_SCROLL)?;
+        } else {
+            pattern.set_scroll_percent(NO_SCROLL, target)?;
+        }
+        Ok(())
+    }
+
+    fn scroll_into_view(&self) -> Result<(), UiError> {
+        if !self.is_offscreen()? {
+            return Ok(());
+        }
+
+        let pattern = self.element.get_pattern::<UIScrollItemPattern>()
+            .map_err(|_| UiError::pattern_unsupported("ScrollItem"))?;
+        pattern.scroll_into_view()?;
+        Ok(())
+    }
+
+    fn supported_patterns(&self) -> Result<Vec<String>, UiError> {
+        let mut patterns = Vec::new();
+        for (name, property) in PATTERN_AVAILABILITY {
+            let available: bool = self.element.get_property_value(*property)?
+                .try_into()
+                .unwrap_or(false);
+            if available {
+                patterns.push(name.to_string());
             }
         }
-        if children.is_empty() {
-            return Err("No children found".into());
+        Ok(patterns)
+    }
+
+    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, UiError> {
+        self.get_selected_text_impl()
+    }
+
+    fn capture_image(&self) -> Result<Vec<u8>, UiError> {
+        super::capture::capture_png(|| self.get_bounds())
+    }
+
+    fn highlight(&self, duration: Duration) -> Result<(), UiError> {
+        if self.is_offscreen()? {
+            debug!("WindowsElement::highlight - Element is offscreen, nothing to highlight");
+            return Ok(());
         }
-        Ok(children)
+        super::highlight::highlight_bounds(|| self.get_bounds(), duration)
+    }
+
+    fn runtime_id(&self) -> Result<Vec<i32>, UiError> {
+        Ok(self.element.get_runtime_id()?)
+    }
+
+    fn get_text_element_info(&self) -> Result<TextElementInfo, UiError> {
+        self.get_text_element_info_impl()
     }
 
-    fn to_tree_node(&self) -> Result<Box<dyn CoreUIElement>, Box<dyn Error>> {
-        Ok(Box::new(WindowsElement::new(self.element.clone(), self.automation.clone())) as Box<dyn CoreUIElement>)
+    fn to_tree_node(&self) -> Result<Box<dyn CoreUIElement>, UiError> {
+        Ok(Box::new(WindowsElement::new(self.element.clone(), self.automation.clone()).with_mode(self.mode)) as Box<dyn CoreUIElement>)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -517,74 +1493,623 @@ impl CoreUIElement for WindowsElement {
 // Additional WindowsElement methods
 impl WindowsElement {
     /// For backward compatibility
-    pub fn append_text_compat(&self, text: &str) -> Result<(), Box<dyn Error>> {
+    pub fn append_text_compat(&self, text: &str) -> Result<(), UiError> {
         self.append_text(text, AppendPosition::EndOfText)
     }
 
-    /// Get selected text using UIA TextPattern - platform-specific implementation
-    pub fn get_selected_text_impl(&self) -> Result<Option<SelectedTextInfo>, Box<dyn Error>> {
-        // Try to get text selection using TextPattern
-        if let Ok(text_pattern) = self.element.get_pattern::<UITextPattern>() {
-            if let Ok(selections) = text_pattern.get_selection() {
-                if !selections.is_empty() {
-                    // Get the first selection range
-                    if let Some(range) = selections.get(0) {
-                        if let Ok(text) = range.get_text(-1) {
-                            if !text.is_empty() {
-                                // Get element info
-                                let element_info = self.get_text_element_info().ok();
-
-                                // Use element bounds as fallback for selection bounds
-                                let bounds = self.get_bounds().ok().flatten();
-
-                                return Ok(Some(SelectedTextInfo {
-                                    text,
-                                    start_offset: 0, // UIA doesn't easily provide offsets
-                                    end_offset: 0,
-                                    bounds,
-                                    element_info,
-                                }));
-                            }
-                        }
-                    }
-                }
+    /// Get the Value pattern if the element supports it and its value is writable
+    fn writable_value_pattern(&self) -> Option<UIValuePattern> {
+        let pattern = self.element.get_pattern::<UIValuePattern>().ok()?;
+        match pattern.is_readonly() {
+            Ok(false) => Some(pattern),
+            _ => None,
+        }
+    }
+
+    /// Get the Toggle pattern if the element supports it
+    fn toggle_pattern(&self) -> Option<UITogglePattern> {
+        self.element.get_pattern::<UITogglePattern>().ok()
+    }
+
+    /// Expand the element's drop-down, if it has one, so its list items are realized
+    fn expand_dropdown(&self) -> Option<UIExpandCollapsePattern> {
+        let pattern = self.element.get_pattern::<UIExpandCollapsePattern>().ok()?;
+        if let Err(e) = pattern.expand() {
+            warn!("WindowsElement::expand_dropdown - Failed to expand: {}", e);
+            return None;
+        }
+        // Give the popup a moment to populate
+        thread::sleep(Duration::from_millis(100));
+        Some(pattern)
+    }
+
+    /// Collapse a drop-down opened by `expand_dropdown`
+    fn collapse_dropdown(&self, pattern: Option<UIExpandCollapsePattern>) {
+        if let Some(pattern) = pattern {
+            // Selecting an item often closes the popup already, so failures are expected
+            if let Err(e) = pattern.collapse() {
+                debug!("WindowsElement::collapse_dropdown - Collapse failed: {}", e);
             }
         }
+    }
 
-        Ok(None)
+    /// Get all ListItem descendants of this element
+    fn list_items(&self) -> Result<Vec<UIAutomationElement>, UiError> {
+        let automation = thread_automation()?;
+        let condition = automation.create_property_condition(
+            UIProperty::ControlType,
+            Variant::from(ControlType::ListItem as i32),
+            None,
+        )?;
+        Ok(self.element.find_all(TreeScope::Descendants, &condition)?)
     }
 
-    /// Get comprehensive text element info - platform-specific implementation
-    pub fn get_text_element_info_impl(&self) -> Result<TextElementInfo, Box<dyn Error>> {
-        let text = self.get_text().unwrap_or_default();
-        let name = self.get_name().unwrap_or_default();
-        let control_type = self.get_type().unwrap_or_default();
-        let bounds = self.get_bounds().unwrap_or(None);
-        let is_enabled = self.is_enabled().unwrap_or(true);
+    /// Select the ListItem whose name is exactly `value`
+    ///
+    /// Returns `Ok(false)` if no item has that name.
+    fn select_list_item(&self, value: &str) -> Result<bool, UiError> {
+        let item = self.list_items()?
+            .into_iter()
+            .find(|item| item.get_name().map(|name| name == value).unwrap_or(false));
 
-        let props = self.get_properties().unwrap_or_default();
-        let automation_id = props.get("automation_id").cloned();
-        let class_name = props.get("class_name").cloned();
+        match item {
+            Some(item) => {
+                let pattern = item.get_pattern::<UISelectionItemPattern>()
+                    .map_err(|e| format!("Option '{}' cannot be selected: {}", value, e))?;
+                pattern.select()?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Get selected text using UIA TextPattern - platform-specific implementation
+    pub fn get_selected_text_impl(&self) -> Result<Option<SelectedTextInfo>, UiError> {
+        let text_pattern = match self.element.get_pattern::<UITextPattern>() {
+            Ok(pattern) => pattern,
+            Err(_) => return Ok(None),
+        };
 
-        // Determine if element is editable based on control type
-        let is_editable = matches!(control_type.as_str(), "Edit" | "Document" | "ComboBox");
+        // Only the first range is reported when the control supports multiple selections
+        let selection = match text_pattern.get_selection()?.into_iter().next() {
+            Some(range) => range,
+            None => return Ok(None),
+        };
+        let text = selection.get_text(-1)?;
+        if text.is_empty() {
+            return Ok(None);
+        }
 
-        // Check visibility
-        let is_visible = !self.is_offscreen().unwrap_or(true);
+        // The start offset is the length of the text preceding the selection
+        let preceding = text_pattern.get_document_range()?;
+        preceding.move_endpoint_by_range(TextPatternRangeEndpoint::End, &selection, TextPatternRangeEndpoint::Start)?;
+        let start_offset = preceding.get_text(-1)?.chars().count() as i32;
+        let end_offset = start_offset + text.chars().count() as i32;
 
-        Ok(TextElementInfo {
+        let bounds = match text_range_bounds(&selection) {
+            Ok(Some(bounds)) => Some(bounds),
+            Ok(None) => self.get_bounds().ok().flatten(),
+            Err(e) => {
+                debug!("WindowsElement::get_selected_text - No range bounds ({}), using element bounds", e);
+                self.get_bounds().ok().flatten()
+            }
+        };
+
+        Ok(Some(SelectedTextInfo {
             text,
-            name,
-            control_type,
-            automation_id,
-            class_name,
+            start_offset,
+            end_offset,
             bounds,
+            element_info: self.get_text_element_info_impl().ok(),
+        }))
+    }
+
+    /// Get comprehensive text element info - platform-specific implementation
+    pub fn get_text_element_info_impl(&self) -> Result<TextElementInfo, UiError> {
+        let info = self.get_info_bulk()?;
+        let (text, _) = self.get_text_limited(DEFAULT_TEXT_LIMIT).unwrap_or_default();
+        let is_editable = info.control_type
+            .is_some_and(|control_type| element_is_editable(&self.element, control_type).unwrap_or(false));
+
+        Ok(TextElementInfo {
+            text,
+            control_type: info.control_type_name(),
+            localized_control_type: info.localized_control_type,
+            name: info.name,
+            automation_id: info.automation_id,
+            class_name: Some(info.class_name),
+            bounds: info.bounds,
             is_selected: false,
             is_editable,
-            is_visible,
-            is_enabled,
+            is_visible: !info.is_offscreen,
+            is_enabled: info.is_enabled,
             parent_name: None,
             depth: 0,
         })
     }
-} 
\ No newline at end of file
+} 
+
+/// UTF-16 units to request from a Text pattern so that `max_chars + 1`
+/// characters come back whenever the document has that many
+///
+/// A character takes at most two units, and the extra character tells a
+/// document of exactly `max_chars` characters from a longer one.
+fn text_range_request(max_chars: usize) -> i32 {
+    max_chars.saturating_add(1).saturating_mul(2).min(i32::MAX as usize) as i32
+}
+
+/// Read at most `max_chars` characters of an element's text, Value pattern first
+///
+/// The Value pattern has no bounded read, so its value is cut after the fact;
+/// the Text pattern only transfers a bounded range of the document.
+pub(crate) fn element_text_limited(element: &UIAutomationElement, max_chars: usize) -> Result<(String, bool), UiError> {
+    if let Ok(value_pattern) = element.get_pattern::<UIValuePattern>() {
+        let value = value_pattern.get_value()?;
+        if !value.is_empty() {
+            return Ok(truncate_chars(value, max_chars));
+        }
+    }
+    if let Ok(text_pattern) = element.get_pattern::<UITextPattern>() {
+        let text = text_pattern.get_document_range()?.get_text(text_range_request(max_chars))?;
+        return Ok(truncate_chars(text, max_chars));
+    }
+    Ok((String::new(), false))
+}
+
+/// Whether a control accepts text input, given what it reports about itself
+///
+/// A Value pattern is authoritative, so read-only fields are not editable
+/// whatever their control type. Rich editors often expose only the Text
+/// pattern; without a Value pattern, edits and documents count as editable
+/// when they take keyboard focus.
+fn editable_from(value_read_only: Option<bool>, control_type: ControlType, keyboard_focusable: impl FnOnce() -> bool) -> bool {
+    match value_read_only {
+        Some(read_only) => !read_only,
+        None => matches!(control_type, ControlType::Edit | ControlType::Document) && keyboard_focusable(),
+    }
+}
+
+/// Whether a raw UI Automation element accepts text input, see `editable_from`
+pub(crate) fn element_is_editable(element: &UIAutomationElement, control_type: ControlType) -> Result<bool, UiError> {
+    let value_read_only = match element.get_pattern::<UIValuePattern>() {
+        Ok(pattern) => Some(pattern.is_readonly()?),
+        Err(_) => None,
+    };
+    Ok(editable_from(value_read_only, control_type, || element.is_keyboard_focusable().unwrap_or(false)))
+}
+
+/// Get the screen bounds covered by a text range
+///
+/// A range spanning several lines has one rectangle per line; the union of
+/// them is returned. Ranges with no visible text yield `Ok(None)`.
+fn text_range_bounds(range: &UITextRange) -> Result<Option<Rect>, UiError> {
+    let native: &IUIAutomationTextRange = range.as_ref();
+    let array = SafeArray::from(unsafe { native.GetBoundingRectangles()? });
+    let values: Vec<f64> = array.into_vector(VT_R8)?;
+    Ok(union_of_rectangles(&values))
+}
+
+/// Union of rectangles packed as `[left, top, width, height, ...]`
+fn union_of_rectangles(values: &[f64]) -> Option<Rect> {
+    values.chunks_exact(4)
+        .filter(|r| r[2] > 0.0 && r[3] > 0.0)
+        .map(|r| Rect::new(r[0] as i32, r[1] as i32, (r[0] + r[2]) as i32, (r[1] + r[3]) as i32))
+        .reduce(|a, b| Rect::new(
+            a.left.min(b.left),
+            a.top.min(b.top),
+            a.right.max(b.right),
+            a.bottom.max(b.bottom),
+        ))
+}
+
+/// Whether `select_item` should add to the selection rather than replace it
+///
+/// Single-select containers reject AddToSelection once an item is selected,
+/// so adding is only attempted when the container is known to allow several.
+fn adds_to_selection(add_requested: bool, container_multiple: impl FnOnce() -> Option<bool>) -> bool {
+    add_requested && container_multiple() == Some(true)
+}
+
+/// Interpret an AccessKey or AcceleratorKey property, which is empty when not set
+fn key_hint(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Value passed to `set_scroll_percent` to leave an axis where it is
+const NO_SCROLL: f64 = -1.0;
+
+/// Compute the new scroll percentage after moving `amount` in `direction`
+fn scroll_target(current: f64, direction: ScrollDirection, amount: f64) -> f64 {
+    let delta = match direction {
+        ScrollDirection::Up | ScrollDirection::Left => -amount,
+        ScrollDirection::Down | ScrollDirection::Right => amount,
+    };
+    (current + delta).clamp(0.0, 100.0)
+}
+
+/// Convert a UIA toggle state to a checked flag, rejecting the indeterminate state
+fn toggle_state_to_bool(state: ToggleState) -> Result<bool, UiError> {
+    match state {
+        ToggleState::On => Ok(true),
+        ToggleState::Off => Ok(false),
+        ToggleState::Indeterminate => Err("Element is in the indeterminate toggle state".into()),
+    }
+}
+
+/// Convert a UIA expand/collapse state to its platform-neutral equivalent
+fn expand_state_from(state: ExpandCollapseState) -> ExpandState {
+    match state {
+        ExpandCollapseState::Collapsed => ExpandState::Collapsed,
+        ExpandCollapseState::Expanded => ExpandState::Expanded,
+        ExpandCollapseState::PartiallyExpanded => ExpandState::PartiallyExpanded,
+        ExpandCollapseState::LeafNode => ExpandState::LeafNode,
+    }
+}
+
+/// Pattern names reported by `supported_patterns`, with the UIA property that flags each one
+const PATTERN_AVAILABILITY: &[(&str, UIProperty)] = &[
+    ("Value", UIProperty::IsValuePatternAvailable),
+    ("Text", UIProperty::IsTextPatternAvailable),
+    ("Toggle", UIProperty::IsTogglePatternAvailable),
+    ("Invoke", UIProperty::IsInvokePatternAvailable),
+    ("ExpandCollapse", UIProperty::IsExpandCollapsePatternAvailable),
+    ("Scroll", UIProperty::IsScrollPatternAvailable),
+    ("ScrollItem", UIProperty::IsScrollItemPatternAvailable),
+    ("Selection", UIProperty::IsSelectionPatternAvailable),
+    ("SelectionItem", UIProperty::IsSelectionItemPatternAvailable),
+    ("RangeValue", UIProperty::IsRangeValuePatternAvailable),
+    ("Window", UIProperty::IsWindowPatternAvailable),
+];
+
+/// Insert `insertion` into `text` at a character offset, clamped to the end
+fn splice_at_char_offset(text: &str, offset: usize, insertion: &str) -> String {
+    let byte_offset = text.char_indices().nth(offset).map_or(text.len(), |(index, _)| index);
+    let mut result = String::with_capacity(text.len() + insertion.len());
+    result.push_str(&text[..byte_offset]);
+    result.push_str(insertion);
+    result.push_str(&text[byte_offset..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_range_request_leaves_room_for_surrogate_pairs() {
+        assert_eq!(text_range_request(0), 2);
+        assert_eq!(text_range_request(100), 202);
+        assert_eq!(text_range_request(usize::MAX), i32::MAX);
+    }
+
+    #[test]
+    fn test_pattern_names_cover_pattern_unsupported_errors() {
+        let names: Vec<&str> = PATTERN_AVAILABILITY.iter().map(|(name, _)| *name).collect();
+        for pattern in ["Value", "Text", "Toggle", "Scroll", "ScrollItem", "Selection"] {
+            assert!(names.contains(&pattern), "{} missing from PATTERN_AVAILABILITY", pattern);
+        }
+        let unique: std::collections::HashSet<&str> = names.iter().copied().collect();
+        assert_eq!(unique.len(), names.len());
+    }
+
+    #[test]
+    fn test_read_only_edit_is_not_editable() {
+        assert!(!editable_from(Some(true), ControlType::Edit, || true));
+        assert!(editable_from(Some(false), ControlType::Edit, || true));
+        // A writable Value pattern makes any control editable, such as a custom editor
+        assert!(editable_from(Some(false), ControlType::Custom, || false));
+        // Without a Value pattern only focusable edits and documents qualify
+        assert!(editable_from(None, ControlType::Document, || true));
+        assert!(!editable_from(None, ControlType::Document, || false));
+        assert!(!editable_from(None, ControlType::Text, || true));
+    }
+
+    #[test]
+    fn test_splice_at_char_offset_clamps_and_respects_chars() {
+        assert_eq!(splice_at_char_offset("Hello world", 5, ","), "Hello, world");
+        assert_eq!(splice_at_char_offset("Hello", 0, ">"), ">Hello");
+        assert_eq!(splice_at_char_offset("Hello", 99, "!"), "Hello!");
+        assert_eq!(splice_at_char_offset("héllo", 2, "-"), "hé-llo");
+    }
+
+    #[test]
+    fn test_add_to_selection_falls_back_to_select_in_single_select_lists() {
+        assert!(adds_to_selection(true, || Some(true)));
+        assert!(!adds_to_selection(true, || Some(false)));
+        assert!(!adds_to_selection(true, || None));
+        // The container is not even inspected for a plain select
+        assert!(!adds_to_selection(false, || panic!("container inspected")));
+    }
+
+    /// Collects every log message, so tests can check what would have been logged
+    struct CapturingLogger(std::sync::Mutex<Vec<String>>);
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            if let Ok(mut messages) = self.0.lock() {
+                messages.push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGS: CapturingLogger = CapturingLogger(std::sync::Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_inserted_secret_text_is_redacted_in_logs() {
+        let message = insertion_message("s3cret-Passw0rd!", 4, true);
+        assert!(!message.contains("s3cret-Passw0rd!"), "{}", message);
+        assert!(message.contains("<redacted>") && message.contains("offset 4"), "{}", message);
+        assert!(insertion_message("hello", 0, false).contains("'hello'"));
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_secret_text_never_appears_in_logs() {
+        use crate::core::{UIQuery, TextInputOptions};
+        use crate::platform::windows::app_manager::TestNotepad;
+
+        // The logger can only be installed once per process; tests sharing it only look for their own text
+        let _ = log::set_logger(&LOGS);
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let secret = "s3cret-Passw0rd!";
+        let options = TextInputOptions { secret: true, verify: true, use_value_pattern_if_available: false, ..Default::default() };
+        let typed = window.find_first(&UIQuery::ByType("Document".to_string()))
+            .and_then(|editor| editor.ok_or_else(|| "Notepad has no editor".into()))
+            .and_then(|editor| editor.set_text_with_options(secret, &options));
+
+        typed.expect("Failed to type into Notepad");
+        let logs = LOGS.0.lock().unwrap();
+        assert!(logs.iter().any(|message| message.contains("<redacted>")), "Typing should still be logged");
+        assert!(logs.iter().all(|message| !message.contains(secret)), "The secret was logged");
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_view_flags_tell_a_toolbar_from_a_document() {
+        use crate::core::UIQuery;
+        use crate::platform::windows::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+        let document = window.find_first(&UIQuery::ByType("Document".to_string()));
+        let document_flags = document.map(|document| {
+            let document = document.expect("Notepad has no document");
+            (document.is_content_element(), document.is_control_element(), document.get_orientation())
+        });
+
+        // The taskbar's notification area is a toolbar on every desktop
+        let automation = uiautomation::UIAutomation::new().unwrap();
+        let condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::ToolBar as i32), None).unwrap();
+        let toolbar = WindowsElement::new(
+            automation.get_root_element().unwrap().find_first(TreeScope::Descendants, &condition).expect("no toolbar on the desktop"),
+            None,
+        );
+
+        let (content, control, orientation) = document_flags.unwrap();
+        assert!(content.unwrap());
+        assert!(control.unwrap());
+        assert_eq!(orientation.unwrap(), Orientation::None);
+        assert!(!toolbar.is_content_element().unwrap());
+        assert!(toolbar.is_control_element().unwrap());
+    }
+
+    #[test]
+    fn test_filterable_properties_accept_friendly_and_uia_names() {
+        assert_eq!(filterable_property("help_text").map(|(p, _)| p), Some(UIProperty::HelpText));
+        assert_eq!(filterable_property("HelpText").map(|(p, _)| p), Some(UIProperty::HelpText));
+        assert_eq!(filterable_property("IsPassword"), Some((UIProperty::IsPassword, PropertyFormat::Flag)));
+        assert!(filterable_property("favourite_colour").is_none());
+    }
+
+    #[test]
+    fn test_format_property_matches_get_properties_conventions() {
+        assert_eq!(format_property(&Variant::from(true), PropertyFormat::Flag).as_deref(), Some("true"));
+        assert_eq!(format_property(&Variant::from(2), PropertyFormat::Orientation).as_deref(), Some("Vertical"));
+        assert_eq!(
+            format_property(&Variant::from(ControlType::Button as i32), PropertyFormat::ControlType).as_deref(),
+            Some("Button")
+        );
+        assert_eq!(format_property(&Variant::from("Ctrl+S"), PropertyFormat::Text).as_deref(), Some("Ctrl+S"));
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_get_children_on_a_new_thread_after_init() {
+        use crate::platform::windows::automation::init_thread_automation;
+
+        let children = thread::spawn(|| {
+            init_thread_automation().expect("Failed to initialize the thread's automation");
+            let root = thread_automation().unwrap().get_root_element().unwrap();
+            // No walker, so the thread's own automation client is used
+            WindowsElement::new(root, None).get_children().map(|children| children.len())
+        }).join().expect("Worker thread panicked");
+
+        assert!(children.expect("get_children failed on the new thread") > 0);
+    }
+
+    #[test]
+    #[ignore] // Requires switching to an Explorer window on a folder with several files
+    fn test_multi_select_in_explorer_file_list() {
+        use crate::core::UIAutomation;
+
+        let automation = crate::platform::windows::WindowsUIAutomation::new().unwrap();
+        println!("Switch to an Explorer window within 5 seconds...");
+        thread::sleep(Duration::from_secs(5));
+        // The inherent get_active_window returns the raw element, so go through the trait
+        let window = UIAutomation::get_active_window(&automation).unwrap();
+        let list = window.find_first(&crate::core::UIQuery::ByType("List".to_string())).unwrap()
+            .expect("Explorer window has no file list");
+        let items = list.get_children().unwrap();
+        assert!(items.len() >= 2, "Folder needs at least two files");
+
+        items[0].select_item(false).unwrap();
+        items[1].select_item(true).unwrap();
+        assert_eq!(list.get_selected_items().unwrap().len(), 2);
+
+        items[1].deselect_item().unwrap();
+        let selected = list.get_selected_items().unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].get_name().unwrap(), items[0].get_name().unwrap());
+    }
+
+    #[test]
+    fn test_unset_key_hints_are_none() {
+        assert_eq!(key_hint(String::new()), None);
+        assert_eq!(key_hint("  ".to_string()), None);
+        assert_eq!(key_hint("Ctrl+S".to_string()), Some("Ctrl+S".to_string()));
+        assert_eq!(key_hint("Alt+F".to_string()), Some("Alt+F".to_string()));
+    }
+
+    #[test]
+    fn test_scroll_target_moves_and_clamps() {
+        assert_eq!(scroll_target(50.0, ScrollDirection::Down, 20.0), 70.0);
+        assert_eq!(scroll_target(50.0, ScrollDirection::Left, 20.0), 30.0);
+        assert_eq!(scroll_target(90.0, ScrollDirection::Right, 25.0), 100.0);
+        assert_eq!(scroll_target(10.0, ScrollDirection::Up, 25.0), 0.0);
+    }
+
+    #[test]
+    fn test_union_of_text_range_rectangles() {
+        assert!(union_of_rectangles(&[]).is_none());
+        assert!(union_of_rectangles(&[10.0, 10.0, 0.0, 16.0]).is_none());
+
+        // Selection spanning the end of one line and the start of the next
+        let bounds = union_of_rectangles(&[100.0, 20.0, 50.0, 16.0, 10.0, 36.0, 30.0, 16.0]).unwrap();
+        assert_eq!((bounds.left, bounds.top, bounds.right, bounds.bottom), (10, 20, 150, 52));
+    }
+
+    #[test]
+    fn test_bulk_info_properties_match_get_properties() {
+        let mut info = ElementInfoBulk {
+            name: "OK".to_string(),
+            control_type: Some(ControlType::Button),
+            localized_control_type: Some("button".to_string()),
+            class_name: "Button".to_string(),
+            automation_id: Some("1".to_string()),
+            bounds: None,
+            is_enabled: false,
+            is_offscreen: false,
+        };
+        let properties = info.properties();
+        assert_eq!(properties.get("name").map(String::as_str), Some("OK"));
+        assert_eq!(properties.get("control_type").map(String::as_str), Some("Button"));
+        assert_eq!(properties.get("automation_id").map(String::as_str), Some("1"));
+        assert_eq!(properties.get("enabled").map(String::as_str), Some("false"));
+
+        info.automation_id = None;
+        info.control_type = None;
+        assert!(!info.properties().contains_key("automation_id"));
+        assert_eq!(info.control_type_name(), "Unknown");
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session; run with --nocapture to see the timings
+    fn test_info_bulk_matches_and_outpaces_individual_getters() {
+        use std::time::Instant;
+
+        let automation = uiautomation::UIAutomation::new().unwrap();
+        let condition = automation.create_true_condition().unwrap();
+        let elements = automation.get_root_element().unwrap()
+            .find_all(TreeScope::Children, &condition).unwrap();
+        let request = info_cache_request(&automation).unwrap();
+
+        let start = Instant::now();
+        for element in &elements {
+            let _ = (element.get_name(), element.get_control_type(), element.get_classname(),
+                element.get_automation_id(), element.get_bounding_rectangle(),
+                element.is_enabled(), element.is_offscreen());
+        }
+        let individual_time = start.elapsed();
+
+        let start = Instant::now();
+        let bulk: Vec<ElementInfoBulk> = elements.iter()
+            .map(|element| fetch_info_bulk(element, &request).unwrap())
+            .collect();
+        let bulk_time = start.elapsed();
+
+        println!(
+            "{} top-level elements: {} property calls in {:?}, {} bulk calls in {:?}",
+            elements.len(), elements.len() * BULK_INFO_PROPERTIES.len(), individual_time, elements.len(), bulk_time
+        );
+        for (element, info) in elements.iter().zip(&bulk) {
+            assert_eq!(element.get_name().unwrap_or_default(), info.name);
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_localized_control_type_follows_ui_language() {
+        use windows::Win32::Globalization::GetUserDefaultUILanguage;
+
+        let automation = uiautomation::UIAutomation::new().unwrap();
+        let condition = automation.create_true_condition().unwrap();
+        let window = automation.get_root_element().unwrap()
+            .find_all(TreeScope::Children, &condition).unwrap()
+            .into_iter()
+            .map(|element| WindowsElement::new(element, None))
+            .find(|element| element.get_type().is_ok_and(|control_type| control_type == "Window"))
+            .expect("no top-level window on the desktop");
+
+        let localized = window.get_localized_control_type().unwrap();
+        assert!(!localized.is_empty());
+        assert_eq!(window.get_type().unwrap(), "Window");
+        assert_eq!(window.get_info_bulk().unwrap().localized_control_type.as_deref(), Some(localized.as_str()));
+
+        // Only a non-English UI renames the role; LANG_ENGLISH is the primary language 0x09
+        let english = unsafe { GetUserDefaultUILanguage() } & 0x3ff == 0x09;
+        if english {
+            println!("English UI language, localized control type is {:?}", localized);
+        } else {
+            assert!(!localized.eq_ignore_ascii_case("Window"), "{:?} was not localized", localized);
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session; run with --nocapture to see the timings
+    fn test_get_subtree_matches_and_outpaces_per_node_walk() {
+        use crate::core::describe_element;
+        use std::time::Instant;
+
+        fn count(node: &UITreeNode) -> usize {
+            1 + node.children.iter().map(count).sum::<usize>()
+        }
+
+        let automation = uiautomation::UIAutomation::new().unwrap();
+        let desktop = WindowsElement::new(automation.get_root_element().unwrap(), automation.create_tree_walker().ok());
+        let max_depth = 3;
+
+        let start = Instant::now();
+        let options = UITreeOptions { max_depth: Some(max_depth), ..UITreeOptions::unlimited() };
+        let (walked, _) = build_tree_bounded(
+            desktop.to_tree_node().unwrap(),
+            &options,
+            |element| describe_element(element.as_ref()),
+            |element, limit| element.get_children()
+                .map(|children| children.into_iter().take(limit).collect())
+                .unwrap_or_default(),
+        ).unwrap();
+        let walk_time = start.elapsed();
+
+        let start = Instant::now();
+        let cached = desktop.get_subtree(max_depth).unwrap();
+        let cached_time = start.elapsed();
+
+        println!(
+            "Desktop subtree to depth {}: per-node walk {} nodes in {:?}, cached get_subtree {} nodes in {:?}",
+            max_depth, count(&walked), walk_time, count(&cached), cached_time
+        );
+        // Windows may open or close between the two captures, so allow a little drift
+        assert!(count(&cached).abs_diff(count(&walked)) <= 10);
+    }
+}
@@ -0,0 +1,101 @@
+//! UI Automation event handlers backing the `crate::events` subscriptions
+//!
+//! UIA invokes handlers on its own MTA worker threads. The user callback is
+//! kept behind a mutex so events for one subscription are delivered one at a
+//! time, and the registered handler is wrapped in `ThreadSafe` so the
+//! subscription can unregister it from whichever thread drops it.
+
+use crate::error::UiError;
+use std::sync::{Arc, Mutex};
+use log::{debug, warn};
+use uiautomation::UIElement as UIAutomationElement;
+use uiautomation::events::{
+    UIEventType, UIEventHandler, UIFocusChangedEventHandler, CustomEventHandlerFn,
+    CustomFocusChangedEventHandlerFn
+};
+use uiautomation::types::TreeScope;
+
+use crate::core::{UIElement, Window};
+use crate::events::{EventSubscription, FocusChangedCallback, WindowOpenedCallback};
+use super::automation::{ThreadSafe, WindowsUIAutomation};
+use super::window::WindowsWindow;
+
+/// Register a handler for UIA focus-changed events
+pub fn subscribe_focus_changed(
+    automation: &WindowsUIAutomation,
+    callback: FocusChangedCallback,
+) -> Result<EventSubscription, UiError> {
+    let owner = automation.clone();
+    let callback = Mutex::new(callback);
+    let handler_fn: Box<CustomFocusChangedEventHandlerFn> = Box::new(move |sender: &UIAutomationElement| {
+        match callback.lock() {
+            Ok(callback) => callback(Box::new(owner.wrap_element(sender.clone())) as Box<dyn UIElement>),
+            Err(_) => warn!("Focus-changed callback panicked earlier, dropping event"),
+        }
+        Ok(())
+    });
+    let handler = UIFocusChangedEventHandler::from(handler_fn);
+
+    automation.automation.lock()?.add_focus_changed_event_handler(None, &handler)?;
+    debug!("Subscribed to focus-changed events");
+
+    let uia = automation.automation.clone();
+    let handler = ThreadSafe::new(handler);
+    Ok(EventSubscription::new(move || {
+        let result = uia.lock().and_then(|uia| {
+            let handler = handler.lock()?;
+            uia.remove_focus_changed_event_handler(&handler).map_err(|e| e.into())
+        });
+        match result {
+            Ok(()) => debug!("Unsubscribed from focus-changed events"),
+            Err(e) => warn!("Failed to remove focus-changed handler: {}", e),
+        }
+    }))
+}
+
+/// Register a handler for UIA window-opened events on the whole desktop
+pub fn subscribe_window_opened(
+    automation: &WindowsUIAutomation,
+    callback: WindowOpenedCallback,
+) -> Result<EventSubscription, UiError> {
+    let owner = Arc::new(automation.clone());
+    let callback = Mutex::new(callback);
+    let handler_fn: Box<CustomEventHandlerFn> = Box::new(move |sender: &UIAutomationElement, _event: UIEventType| {
+        let window = match WindowsWindow::new(sender.clone(), owner.clone()) {
+            Ok(window) => window,
+            Err(e) => {
+                warn!("Ignoring window-opened event for an unreadable window: {}", e);
+                return Ok(());
+            }
+        };
+        match callback.lock() {
+            Ok(callback) => callback(Box::new(window) as Box<dyn Window>),
+            Err(_) => warn!("Window-opened callback panicked earlier, dropping event"),
+        }
+        Ok(())
+    });
+    let handler = UIEventHandler::from(handler_fn);
+
+    let root = {
+        let uia = automation.automation.lock()?;
+        let root = uia.get_root_element()?;
+        uia.add_automation_event_handler(UIEventType::Window_WindowOpened, &root, TreeScope::Subtree, None, &handler)?;
+        root
+    };
+    debug!("Subscribed to window-opened events");
+
+    let uia = automation.automation.clone();
+    let registration = ThreadSafe::new((root, handler));
+    Ok(EventSubscription::new(move || {
+        let result = uia.lock().and_then(|uia| {
+            let registration = registration.lock()?;
+            let (root, handler) = &*registration;
+            uia.remove_automation_event_handler(UIEventType::Window_WindowOpened, root, handler)
+                .map_err(|e| e.into())
+        });
+        match result {
+            Ok(()) => debug!("Unsubscribed from window-opened events"),
+            Err(e) => warn!("Failed to remove window-opened handler: {}", e),
+        }
+    }))
+}
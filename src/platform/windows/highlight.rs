@@ -0,0 +1,126 @@
+//! Outlines drawn on the screen around elements, to check which element a query matched
+
+use crate::error::UiError;
+use log::debug;
+use std::thread;
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::{COLORREF, RECT};
+use windows::Win32::Graphics::Gdi::{
+    GetDC, ReleaseDC, CreatePen, SelectObject, GetStockObject, DeleteObject, Rectangle, RedrawWindow,
+    HGDIOBJ, NULL_BRUSH, PS_SOLID, RDW_ALLCHILDREN, RDW_ERASE, RDW_FRAME, RDW_INVALIDATE, RDW_UPDATENOW
+};
+
+use super::capture::DpiAwarenessGuard;
+use crate::core::Rect;
+
+/// Outline color, as 0x00BBGGRR
+const OUTLINE_COLOR: COLORREF = COLORREF(0x0000_00FF);
+
+/// Outline thickness in physical pixels
+const OUTLINE_WIDTH: i32 = 3;
+
+/// How often the outline is drawn again, since windows repainting underneath erase it
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Outline the screen area described by `get_bounds` for `duration`, then erase it
+///
+/// The bounds are queried with per-monitor DPI awareness enabled so the
+/// outline lands on the element on scaled monitors too. Elements without
+/// bounds or with zero-area bounds are not outlined.
+pub fn highlight_bounds<F>(get_bounds: F, duration: Duration) -> Result<(), UiError>
+where
+    F: FnOnce() -> Result<Option<Rect>, UiError>,
+{
+    let dpi = DpiAwarenessGuard::per_monitor();
+
+    let outline = match get_bounds()?.and_then(|bounds| outline_rect(&dpi.to_thread_coordinates(&bounds))) {
+        Some(outline) => outline,
+        None => {
+            debug!("Element has no on-screen bounds, nothing to highlight");
+            return Ok(());
+        }
+    };
+    debug!("Highlighting {:?} for {:?}", outline, duration);
+
+    let start = Instant::now();
+    loop {
+        draw_outline(&outline)?;
+        let remaining = duration.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            break;
+        }
+        thread::sleep(remaining.min(REDRAW_INTERVAL));
+    }
+    erase_outline(&outline);
+    Ok(())
+}
+
+/// The rectangle the outline is drawn along, just outside `bounds`
+fn outline_rect(bounds: &Rect) -> Option<Rect> {
+    if bounds.width() <= 0 || bounds.height() <= 0 {
+        return None;
+    }
+    Some(Rect::new(
+        bounds.left - OUTLINE_WIDTH,
+        bounds.top - OUTLINE_WIDTH,
+        bounds.right + OUTLINE_WIDTH,
+        bounds.bottom + OUTLINE_WIDTH,
+    ))
+}
+
+/// Draw an unfilled rectangle directly on the screen
+fn draw_outline(outline: &Rect) -> Result<(), UiError> {
+    unsafe {
+        let screen_dc = GetDC(None);
+        if screen_dc.is_invalid() {
+            return Err("Failed to get the screen device context".into());
+        }
+        let pen = CreatePen(PS_SOLID, OUTLINE_WIDTH, OUTLINE_COLOR);
+        let previous_pen = SelectObject(screen_dc, HGDIOBJ::from(pen));
+        let previous_brush = SelectObject(screen_dc, GetStockObject(NULL_BRUSH));
+
+        let _ = Rectangle(screen_dc, outline.left, outline.top, outline.right, outline.bottom);
+
+        SelectObject(screen_dc, previous_brush);
+        SelectObject(screen_dc, previous_pen);
+        let _ = DeleteObject(HGDIOBJ::from(pen));
+        ReleaseDC(None, screen_dc);
+    }
+    Ok(())
+}
+
+/// Have the windows under the outline repaint themselves, which erases it
+fn erase_outline(outline: &Rect) {
+    // Widen by the pen so its outer half, drawn past the rectangle, is repainted too
+    let area = RECT {
+        left: outline.left - OUTLINE_WIDTH,
+        top: outline.top - OUTLINE_WIDTH,
+        right: outline.right + OUTLINE_WIDTH,
+        bottom: outline.bottom + OUTLINE_WIDTH,
+    };
+    unsafe {
+        let _ = RedrawWindow(
+            None,
+            Some(&area as *const RECT),
+            None,
+            RDW_INVALIDATE | RDW_ERASE | RDW_FRAME | RDW_ALLCHILDREN | RDW_UPDATENOW,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_surrounds_bounds() {
+        let outline = outline_rect(&Rect::new(100, 50, 200, 80)).unwrap();
+        assert_eq!((outline.left, outline.top, outline.right, outline.bottom), (97, 47, 203, 83));
+    }
+
+    #[test]
+    fn test_zero_area_bounds_have_no_outline() {
+        assert!(outline_rect(&Rect::new(10, 10, 10, 40)).is_none());
+        assert!(outline_rect(&Rect::new(10, 10, 40, 5)).is_none());
+    }
+}
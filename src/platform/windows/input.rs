@@ -0,0 +1,141 @@
+//! Keyboard input that injects characters directly instead of translating them through the layout
+
+use crate::error::UiError;
+use std::mem::size_of;
+use std::thread;
+use std::time::Duration;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+    KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, VIRTUAL_KEY, VK_RETURN, VK_TAB,
+};
+
+/// One key to press and release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyStroke {
+    /// A UTF-16 code unit delivered as-is with `KEYEVENTF_UNICODE`
+    Unicode(u16),
+    /// A virtual key, for characters applications expect as real keys
+    VirtualKey(u16),
+}
+
+/// The keystrokes that type `ch`
+///
+/// Line breaks and tabs are sent as Enter and Tab, since many controls ignore
+/// them as characters; a `\r` is dropped so that `\r\n` gives a single Enter.
+/// Anything else becomes one Unicode keystroke per UTF-16 unit, so characters
+/// outside the Basic Multilingual Plane are sent as a surrogate pair.
+pub(crate) fn keystrokes_for(ch: char) -> Vec<KeyStroke> {
+    match ch {
+        '\r' => Vec::new(),
+        '\n' => vec![KeyStroke::VirtualKey(VK_RETURN.0)],
+        '\t' => vec![KeyStroke::VirtualKey(VK_TAB.0)],
+        _ => {
+            let mut units = [0u16; 2];
+            ch.encode_utf16(&mut units).iter().map(|&unit| KeyStroke::Unicode(unit)).collect()
+        }
+    }
+}
+
+/// Key-down and key-up events for `strokes`, in the order they are sent
+fn key_events(strokes: &[KeyStroke]) -> Vec<INPUT> {
+    strokes.iter()
+        .flat_map(|&stroke| [key_event(stroke, false), key_event(stroke, true)])
+        .collect()
+}
+
+fn key_event(stroke: KeyStroke, key_up: bool) -> INPUT {
+    let (vk, scan, mut flags) = match stroke {
+        KeyStroke::Unicode(unit) => (VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE),
+        KeyStroke::VirtualKey(vk) => (VIRTUAL_KEY(vk), 0, KEYBD_EVENT_FLAGS(0)),
+    };
+    if key_up {
+        flags |= KEYEVENTF_KEYUP;
+    }
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: vk,
+                wScan: scan,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
+
+/// Type `text` into the window that has keyboard focus
+///
+/// Characters are injected as Unicode code points, bypassing the keyboard
+/// layout, so accented and non-Latin text arrives exactly as given whatever
+/// layout is active. Each character is sent in one `SendInput` call, so
+/// surrogate pairs cannot be split by other input.
+///
+/// # Errors
+///
+/// Returns `UiError::AccessDenied` if Windows rejects the input, which
+/// happens when the focused window belongs to a process with a higher
+/// integrity level (UIPI).
+pub fn send_unicode_text(text: &str, interval: Duration) -> Result<(), UiError> {
+    for ch in text.chars() {
+        let events = key_events(&keystrokes_for(ch));
+        if events.is_empty() {
+            continue;
+        }
+        let sent = unsafe { SendInput(&events, size_of::<INPUT>() as i32) };
+        if sent as usize != events.len() {
+            return Err(UiError::AccessDenied(format!(
+                "SendInput injected {} of {} key events for '{}'; the target may run at a higher integrity level",
+                sent, events.len(), ch
+            )));
+        }
+        if !interval.is_zero() {
+            thread::sleep(interval);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keystrokes(text: &str) -> Vec<KeyStroke> {
+        text.chars().flat_map(keystrokes_for).collect()
+    }
+
+    #[test]
+    fn test_accented_text_maps_to_its_code_points() {
+        let text = "Demain, réunion de famille";
+        let expected: Vec<KeyStroke> = text.encode_utf16().map(KeyStroke::Unicode).collect();
+        assert_eq!(keystrokes(text), expected);
+        assert!(keystrokes("é").contains(&KeyStroke::Unicode(0x00E9)));
+    }
+
+    #[test]
+    fn test_astral_characters_become_surrogate_pairs() {
+        assert_eq!(keystrokes_for('😀'), vec![KeyStroke::Unicode(0xD83D), KeyStroke::Unicode(0xDE00)]);
+    }
+
+    #[test]
+    fn test_line_breaks_and_tabs_are_virtual_keys() {
+        assert_eq!(keystrokes("a\r\nb"), vec![
+            KeyStroke::Unicode(u16::from(b'a')),
+            KeyStroke::VirtualKey(VK_RETURN.0),
+            KeyStroke::Unicode(u16::from(b'b')),
+        ]);
+        assert_eq!(keystrokes_for('\t'), vec![KeyStroke::VirtualKey(VK_TAB.0)]);
+    }
+
+    #[test]
+    fn test_key_events_press_then_release_each_unit() {
+        let events = key_events(&keystrokes_for('ç'));
+        assert_eq!(events.len(), 2);
+        let (down, up) = unsafe { (events[0].Anonymous.ki, events[1].Anonymous.ki) };
+        assert_eq!(down.wScan, 0x00E7);
+        assert_eq!(down.dwFlags, KEYEVENTF_UNICODE);
+        assert_eq!(up.dwFlags, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP);
+        assert_eq!(down.wVk, VIRTUAL_KEY(0));
+    }
+}
@@ -2,8 +2,13 @@ pub mod automation;
 pub mod window;
 pub mod element;
 pub mod app_manager;
+pub mod capture;
+pub mod clipboard;
+pub mod input;
+pub mod events;
+pub mod highlight;
 
-pub use automation::WindowsUIAutomation;
+pub use automation::{WindowsUIAutomation, init_thread_automation};
 pub use window::WindowsWindow;
-pub use element::WindowsElement;
+pub use element::{WindowsElement, ElementInfoBulk};
 pub use app_manager::WindowsApplicationManager; 
\ No newline at end of file
@@ -1,6 +1,8 @@
-use std::error::Error;
+use crate::error::UiError;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
 // use chrono::Utc;
@@ -8,22 +10,32 @@ use log::debug;
 use std::convert::TryInto;
 
 use uiautomation::core::UIElement as UIAutomationElement;
-use uiautomation::types::{TreeScope, UIProperty};
+use uiautomation::core::{UICacheRequest, UICondition};
+use uiautomation::types::{ElementMode, PropertyConditionFlags, TreeScope, UIProperty};
 use uiautomation::variants::Variant;
 use uiautomation::controls::ControlType;
+use uiautomation::inputs::Keyboard;
 
-use crate::core::{Window, UIElement, UITree, UIQuery, UITreeNode, TextElementInfo, TextExtractionOptions, Rect, SelectedTextInfo};
+use crate::core::{Window, UIElement, UIAutomation as _, InteractionMode, UITree, UIQuery, NameMatch, UITreeNode, TextElementInfo, TextExtractionOptions, UITreeOptions, Rect, SelectedTextInfo, intersect_by_runtime_id, build_tree_bounded, find_many_in_one_pass, find_with_paths, resolve_path, visit_text_elements, walk_nodes_bounded, walk_menu_path, menu_item_query, with_retry, CLOSE_POLL_INTERVAL, DEFAULT_MAX_NODES, MAX_ANCESTOR_DEPTH, DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_DELAY, DEFAULT_TEXT_LIMIT};
 use super::automation::WindowsUIAutomation;
-use windows::Win32::Foundation::{HWND, RECT};
+use super::element::{element_is_editable, element_text_limited, info_cache_request, cached_info_bulk, fetch_info_bulk, BULK_INFO_PROPERTIES};
+use crate::control_type;
+use windows::Win32::Foundation::{HWND, RECT, WPARAM, LPARAM};
+use windows::core::BOOL;
 use windows::Win32::UI::WindowsAndMessaging::{
     GetForegroundWindow, GetWindowTextW, GetClassNameW, GetWindowLongPtrW, GWL_EXSTYLE,
     GWL_STYLE, IsWindowVisible, GetWindowThreadProcessId, GetWindowRect, GetParent,
-    GetWindow, GW_OWNER, GetMenu, GetWindowPlacement, WINDOWPLACEMENT, SW_SHOWMINIMIZED,
-    SW_SHOWMAXIMIZED, SW_SHOWNORMAL
+    GetWindow, GW_OWNER, GetMenu, EnumWindows, GetWindowPlacement, WINDOWPLACEMENT, SW_SHOWMINIMIZED,
+    SW_SHOWMAXIMIZED, SW_SHOWNORMAL, ShowWindow, SW_MINIMIZE, SW_MAXIMIZE, SW_RESTORE,
+    SHOW_WINDOW_CMD, PostMessageW, WM_CLOSE, IsWindow, IsIconic, SetWindowPos, SET_WINDOW_POS_FLAGS,
+    SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SetForegroundWindow, BringWindowToTop,
+    AllowSetForegroundWindow, ASFW_ANY, HWND_TOP
 };
 use windows::Win32::System::ProcessStatus::GetModuleFileNameExW;
-use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ, AttachThreadInput, GetCurrentThreadId};
 use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use super::capture::DpiAwarenessGuard;
+use crate::timing::OperationTimer;
 
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
@@ -195,7 +207,7 @@ pub struct WindowsWindow {
 }
 
 impl WindowsWindow {
-    pub fn new(element: UIAutomationElement, automation: Arc<WindowsUIAutomation>) -> Result<Self, Box<dyn Error>> {
+    pub fn new(element: UIAutomationElement, automation: Arc<WindowsUIAutomation>) -> Result<Self, UiError> {
         // Try to get window info for this specific element's window handle
         let window_info = if let Ok(hwnd) = element.get_native_window_handle() {
             WindowInfo::from_hwnd(hwnd.into())
@@ -215,8 +227,554 @@ impl WindowsWindow {
     }
 }
 
+/// Reject key sequences that cannot be sent
+fn validate_key_sequence(keys: &str) -> Result<(), UiError> {
+    if keys.is_empty() {
+        return Err(UiError::InvalidArgument("Key sequence must not be empty".to_string()));
+    }
+    Ok(())
+}
+
+/// How long `close()` waits for the window handle to disappear
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many times `set_foreground()` asks for the foreground before giving up
+const FOREGROUND_ATTEMPTS: u32 = 5;
+
+/// How long `set_foreground()` waits for the switch to land before checking it
+const FOREGROUND_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Run `attempt` until `verify` holds, sleeping `delay` after each try
+///
+/// Returns whether `verify` held within `attempts` tries. `verify` is checked
+/// once before the first attempt so an already satisfied state costs nothing.
+fn retry_until(attempts: u32, delay: Duration, mut attempt: impl FnMut(), mut verify: impl FnMut() -> bool) -> bool {
+    if verify() {
+        return true;
+    }
+    for _ in 0..attempts {
+        attempt();
+        thread::sleep(delay);
+        if verify() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Ask Windows to make `hwnd` the foreground window, once
+///
+/// `SetForegroundWindow` is refused unless the calling thread owns the current
+/// foreground window or received the last input event. Attaching our input
+/// queue to the foreground thread makes us share its input state for the
+/// duration of the call, which satisfies that rule in most sessions.
+fn request_foreground(hwnd: HWND) {
+    unsafe {
+        // Lets whichever process we end up activating take the foreground in turn
+        let _ = AllowSetForegroundWindow(ASFW_ANY);
+
+        let current_thread = GetCurrentThreadId();
+        let foreground = GetForegroundWindow();
+        let foreground_thread = if foreground.0.is_null() {
+            0
+        } else {
+            GetWindowThreadProcessId(foreground, None)
+        };
+        let attached = foreground_thread != 0
+            && foreground_thread != current_thread
+            && AttachThreadInput(current_thread, foreground_thread, true).as_bool();
+
+        let granted = SetForegroundWindow(hwnd).as_bool();
+        let _ = BringWindowToTop(hwnd);
+        debug!("SetForegroundWindow on HWND {:?} returned {} (input attached: {})", hwnd, granted, attached);
+
+        if attached {
+            let _ = AttachThreadInput(current_thread, foreground_thread, false);
+        }
+    }
+}
+
+impl WindowsWindow {
+    /// Get the native window handle
+    fn hwnd(&self) -> Result<HWND, UiError> {
+        if let Some(info) = &self.window_info {
+            Ok(info.hwnd)
+        } else {
+            Ok(self.element.get_native_window_handle()?.into())
+        }
+    }
+
+    /// Change the window's show state via `ShowWindow`
+    fn show_window(&self, command: SHOW_WINDOW_CMD) -> Result<(), UiError> {
+        let hwnd = self.hwnd()?;
+        debug!("Calling ShowWindow({:?}) on HWND {:?}", command, hwnd);
+        // The return value is the previous visibility state, not a success flag
+        let _ = unsafe { ShowWindow(hwnd, command) };
+        Ok(())
+    }
+
+    /// Reposition the window via `SetWindowPos` in physical screen pixels
+    /// 
+    /// The minimized state is checked live rather than from the cached window
+    /// info, since a minimized window's rect is meaningless.
+    fn set_window_pos(&self, x: i32, y: i32, width: i32, height: i32, flags: SET_WINDOW_POS_FLAGS) -> Result<(), UiError> {
+        let hwnd = self.hwnd()?;
+        if unsafe { IsIconic(hwnd) }.as_bool() {
+            return Err(UiError::InvalidArgument("Cannot move or resize a minimized window; restore it first".to_string()));
+        }
+
+        // Per-monitor awareness makes the coordinates physical pixels on every monitor
+        let _dpi = DpiAwarenessGuard::per_monitor();
+        debug!("Calling SetWindowPos({}, {}, {}x{}) on HWND {:?}", x, y, width, height, hwnd);
+        unsafe { SetWindowPos(hwnd, None, x, y, width, height, flags | SWP_NOZORDER | SWP_NOACTIVATE)? };
+        Ok(())
+    }
+
+    /// Find the first descendant matching a single native property condition
+    fn find_first_by_property(&self, property: UIProperty, value: Variant) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        let automation = self.automation.automation.lock()?;
+        let condition = automation.create_property_condition(property, value, None)?;
+        // FindFirst reports "no match" as an error rather than an empty result
+        Ok(self.element.find_first(TreeScope::Descendants, &condition)
+            .ok()
+            .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>))
+    }
+
+    /// Find a menu item by name in the popup menus this window's process has open
+    /// 
+    /// Classic Win32 submenus are separate top-level windows, so their items
+    /// are not descendants of the menu item that opened them.
+    fn find_popup_menu_item(&self, name: &str) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        let process_id = self.get_process_id()?;
+        let automation = self.automation.automation.lock()?;
+        let popup_condition = automation.create_and_condition(
+            automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Menu as i32), None)?,
+            automation.create_property_condition(UIProperty::ProcessId, Variant::from(process_id as i32), None)?,
+        )?;
+        let item_condition = automation.create_and_condition(
+            automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::MenuItem as i32), None)?,
+            automation.create_property_condition(UIProperty::Name, Variant::from(name), None)?,
+        )?;
+
+        let popups = automation.get_root_element()?.find_all(TreeScope::Children, &popup_condition).unwrap_or_default();
+        // The most recently opened popup is usually last, and holds the item we are after
+        for popup in popups.iter().rev() {
+            if let Ok(item) = popup.find_first(TreeScope::Descendants, &item_condition) {
+                return Ok(Some(Box::new(self.automation.wrap_element(item))));
+            }
+        }
+        Ok(None)
+    }
+
+    /// One native condition matching any of `control_types`, or `None` for an empty list
+    ///
+    /// Names are mapped like `ByType` does, so unknown names match `Custom`
+    /// controls; duplicate names are folded into one condition.
+    fn control_types_condition(&self, control_types: &[String]) -> Result<Option<UICondition>, UiError> {
+        let automation = self.automation.automation.lock()?;
+        let mut ids: Vec<i32> = Vec::new();
+        for control_type in control_types {
+            let id = control_type_from_name(control_type).unwrap_or(ControlType::Custom) as i32;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        let mut condition: Option<UICondition> = None;
+        for id in ids {
+            let type_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(id), None)?;
+            condition = Some(match condition {
+                None => type_condition,
+                Some(previous) => automation.create_or_condition(previous, type_condition)?,
+            });
+        }
+        Ok(condition)
+    }
+
+    /// The native condition for a `ByNameMatch` query, or `None` when names must be filtered in Rust
+    ///
+    /// `Exact` and `CaseInsensitive` map to plain property conditions.
+    /// `Contains` uses substring matching, which UI Automation only supports
+    /// from Windows 10 1809; older systems reject the flag and get `None`.
+    /// There are no prefix or suffix flags, so `StartsWith` and `EndsWith`
+    /// always return `None`.
+    fn name_match_condition(&self, name: &str, mode: NameMatch) -> Result<Option<UICondition>, UiError> {
+        let flags = match mode {
+            NameMatch::Exact => PropertyConditionFlags::None,
+            NameMatch::CaseInsensitive => PropertyConditionFlags::IgnoreCase,
+            NameMatch::Contains => PropertyConditionFlags::MatchSubstring,
+            NameMatch::StartsWith | NameMatch::EndsWith => return Ok(None),
+        };
+        let automation = self.automation.automation.lock()?;
+        match automation.create_property_condition(UIProperty::Name, Variant::from(name), Some(flags)) {
+            Ok(condition) => Ok(Some(condition)),
+            Err(e) if mode == NameMatch::Contains => {
+                debug!("Substring name condition unavailable, filtering names instead: {}", e);
+                Ok(None)
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Find all descendants of this window matching a single native property condition
+    fn find_by_property(&self, property: UIProperty, value: Variant) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        let automation = self.automation.automation.lock()?;
+        let condition = automation.create_property_condition(property, value, None)?;
+        // Search within THIS WINDOW's element tree, not from desktop root
+        let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
+        Ok(elements.into_iter()
+            .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>)
+            .collect())
+    }
+
+    /// One attempt at `find_elements`; recursive searches call this so retries do not nest
+    fn find_elements_once(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        let mut timer = OperationTimer::start("find_elements");
+        match query {
+            // UIA returns FindAll results in document order, so slicing them
+            // gives the positional semantics documented on `UIQuery`
+            UIQuery::Nth(query, index) => {
+                Ok(self.find_elements_once(query)?.into_iter().nth(*index).into_iter().collect())
+            },
+            UIQuery::Limit(query, limit) => {
+                Ok(self.find_elements_once(query)?.into_iter().take(*limit).collect())
+            },
+            UIQuery::ByName(name) => {
+                let automation = self.automation.automation.lock()?;
+                let condition = automation.create_property_condition(UIProperty::Name, Variant::from(name), None)?;
+                // Search within THIS WINDOW's element tree, not from desktop root
+                let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
+                timer.phase("native FindAll");
+                let mut result = Vec::new();
+                for element in elements {
+                    result.push(Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>);
+                }
+                timer.phase("wrap results");
+                Ok(result)
+            },
+            UIQuery::ByType(control_type) => {
+                let control_type_enum = control_type_from_name(control_type).unwrap_or(ControlType::Custom);
+                let control_type_id = control_type_enum as i32;
+                
+                let automation = self.automation.automation.lock()?;
+                let condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(control_type_id), None)?;
+                // Search within THIS WINDOW's element tree, not from desktop root
+                let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
+                timer.phase("native FindAll");
+                let mut result = Vec::new();
+                for element in elements {
+                    result.push(Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>);
+                }
+                timer.phase("wrap results");
+                Ok(result)
+            },
+            UIQuery::ByTypes(control_types) => {
+                // A single FindAll on an OR condition: one walk, document order, no duplicates
+                let condition = match self.control_types_condition(control_types)? {
+                    Some(condition) => condition,
+                    None => return Ok(Vec::new()),
+                };
+                let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
+                timer.phase("native FindAll");
+                Ok(elements.into_iter()
+                    .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>)
+                    .collect())
+            },
+            UIQuery::ByAutomationId(_) | UIQuery::ByClassName(_) | UIQuery::ByControlTypeId(_) => {
+                // Resolved with a single native condition rather than a descendant scan
+                let (property, value) = native_property_condition(query)
+                    .ok_or("No native condition for query")?;
+                self.find_by_property(property, value)
+            },
+            UIQuery::ByProperty(key, value) => {
+                // Well-known keys are resolved natively, see `native_property_condition`
+                if let Some((property, native_value)) = native_property_condition(query) {
+                    return self.find_by_property(property, native_value);
+                }
+
+                // Arbitrary keys: get all descendants and filter on the properties map
+                let all_condition = self.automation.true_condition()?;
+                let children = self.element.find_all(TreeScope::Descendants, &all_condition)?;
+                let mut result = Vec::new();
+                for child in children {
+                    let windows_element = self.automation.wrap_element(child.clone());
+                    if let Ok(properties) = windows_element.get_properties() {
+                        if properties.get(key) == Some(value) {
+                            result.push(Box::new(windows_element) as Box<dyn UIElement>);
+                        }
+                    }
+                }
+                Ok(result)
+            },
+            UIQuery::ByNameMatch(pattern, mode) => {
+                if let Some(condition) = self.name_match_condition(pattern, *mode)? {
+                    let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
+                    timer.phase("native FindAll");
+                    return Ok(elements.into_iter()
+                        .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>)
+                        .collect());
+                }
+                // No native condition for this mode: enumerate descendants and filter on name
+                let all_condition = self.automation.true_condition()?;
+                let children = self.element.find_all(TreeScope::Descendants, &all_condition)?;
+                timer.phase("native FindAll");
+                Ok(children.into_iter()
+                    .filter(|child| child.get_name().is_ok_and(|name| mode.matches(pattern, &name)))
+                    .map(|child| Box::new(self.automation.wrap_element(child)) as Box<dyn UIElement>)
+                    .collect())
+            },
+            UIQuery::ByNameRegex(pattern) => {
+                // UI Automation has no regex condition: enumerate descendants and filter on name
+                let all_condition = self.automation.true_condition()?;
+                let children = self.element.find_all(TreeScope::Descendants, &all_condition)?;
+                let mut result = Vec::new();
+                for child in children {
+                    if child.get_name().is_ok_and(|name| pattern.is_match(&name)) {
+                        result.push(Box::new(self.automation.wrap_element(child)) as Box<dyn UIElement>);
+                    }
+                }
+                Ok(result)
+            },
+            UIQuery::ByText(text) => {
+                // UI Automation has no condition on text content: read the text of
+                // every element, bounded like text extraction
+                let walker = self.automation.tree_walker()?;
+                let max_depth = TextExtractionOptions::default().max_depth;
+                let elements = find_by_text(&self.element, &walker, text, max_depth, DEFAULT_MAX_NODES);
+                timer.phase("read element text");
+                Ok(elements.into_iter()
+                    .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>)
+                    .collect())
+            },
+            UIQuery::And(queries) => {
+                let mut results: Option<Vec<Box<dyn UIElement>>> = None;
+                for query in queries {
+                    let elements = self.find_elements_once(query)?;
+                    results = Some(match results {
+                        None => elements,
+                        // Keep only elements that exist in both results
+                        Some(previous) => intersect_by_runtime_id(previous, &elements),
+                    });
+                    if results.as_ref().is_some_and(|r| r.is_empty()) {
+                        break;
+                    }
+                }
+                Ok(results.unwrap_or_default())
+            },
+            UIQuery::Or(queries) => {
+                let mut results = Vec::new();
+                for query in queries {
+                    let elements = self.find_elements_once(query)?;
+                    results.extend(elements);
+                }
+                Ok(results)
+            },
+            UIQuery::Visible(inner) | UIQuery::Enabled(inner) => {
+                let (flag, wanted) = match query {
+                    UIQuery::Visible(_) => (UIProperty::IsOffscreen, false),
+                    _ => (UIProperty::IsEnabled, true),
+                };
+
+                // A native inner condition lets UI Automation apply the flag in the same FindAll
+                if let Some((property, value)) = native_query_condition(inner) {
+                    let automation = self.automation.automation.lock()?;
+                    let condition = automation.create_and_condition(
+                        automation.create_property_condition(property, value, None)?,
+                        automation.create_property_condition(flag, Variant::from(wanted), None)?,
+                    )?;
+                    let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
+                    timer.phase("native FindAll");
+                    return Ok(elements.into_iter()
+                        .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>)
+                        .collect());
+                }
+
+                let keep = |element: &dyn UIElement| match query {
+                    UIQuery::Visible(_) => element.is_offscreen().map(|offscreen| !offscreen),
+                    _ => element.is_enabled(),
+                }.unwrap_or(false);
+                Ok(self.find_elements_once(inner)?.into_iter()
+                    .filter(|element| keep(element.as_ref()))
+                    .collect())
+            },
+            UIQuery::Not(query) => {
+                let request = info_cache_request(&*self.automation.automation.lock()?)?;
+                let all_condition = self.automation.true_condition()?;
+                let children = self.element.find_all(TreeScope::Descendants, &all_condition)?;
+                let mut result = Vec::new();
+                for child in children {
+                    let windows_element = self.automation.wrap_element(child.clone());
+                    if !matches_bulk(query, &windows_element, &request)? {
+                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
+                    }
+                }
+                Ok(result)
+            },
+            UIQuery::Child(query) => {
+                let request = info_cache_request(&*self.automation.automation.lock()?)?;
+                let all_condition = self.automation.true_condition()?;
+                let children = self.element.find_all(TreeScope::Children, &all_condition)?;
+                let mut result = Vec::new();
+                for child in children {
+                    let windows_element = self.automation.wrap_element(child.clone());
+                    if matches_bulk(query, &windows_element, &request)? {
+                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
+                    }
+                }
+                Ok(result)
+            },
+            UIQuery::Descendant(query) => {
+                let request = info_cache_request(&*self.automation.automation.lock()?)?;
+                let all_condition = self.automation.true_condition()?;
+                let descendants = self.element.find_all(TreeScope::Descendants, &all_condition)?;
+                let mut result = Vec::new();
+                for descendant in descendants {
+                    let windows_element = self.automation.wrap_element(descendant.clone());
+                    if matches_bulk(query, &windows_element, &request)? {
+                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
+                    }
+                }
+                Ok(result)
+            },
+            UIQuery::Parent(query) => {
+                let request = info_cache_request(&*self.automation.automation.lock()?)?;
+                let walker = self.automation.tree_walker()?;
+                // The desktop root has no parent
+                if let Ok(parent) = walker.get_parent(&self.element) {
+                    let windows_element = self.automation.wrap_element(parent);
+                    if matches_bulk(query, &windows_element, &request)? {
+                        return Ok(vec![Box::new(windows_element) as Box<dyn UIElement>]);
+                    }
+                }
+                Ok(Vec::new())
+            },
+            UIQuery::Ancestor(query) => {
+                let request = info_cache_request(&*self.automation.automation.lock()?)?;
+                let walker = self.automation.tree_walker()?;
+                let mut current = self.element.clone();
+                let mut result = Vec::new();
+                // The depth cap guards against providers that report an element as its own parent
+                for _ in 0..MAX_ANCESTOR_DEPTH {
+                    let Ok(parent) = walker.get_parent(&current) else { break };
+                    let windows_element = self.automation.wrap_element(parent.clone());
+                    if matches_bulk(query, &windows_element, &request)? {
+                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
+                    }
+                    current = parent;
+                }
+                Ok(result)
+            },
+        }
+    }
+}
+
+/// Visible top-level windows whose owner is `owner`, in Z-order
+fn owned_windows(owner: HWND) -> Vec<HWND> {
+    struct OwnedWindowsState {
+        owner: HWND,
+        found: Vec<HWND>,
+    }
+
+    extern "system" fn owned_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            let state = &mut *(lparam.0 as *mut OwnedWindowsState);
+            let owner = GetWindow(hwnd, GW_OWNER).ok();
+            if owner == Some(state.owner) && IsWindowVisible(hwnd).as_bool() {
+                state.found.push(hwnd);
+            }
+        }
+        BOOL(1) // Continue enumeration
+    }
+
+    let mut state = OwnedWindowsState { owner, found: Vec::new() };
+    unsafe {
+        let state_ptr = &mut state as *mut OwnedWindowsState;
+        let _ = EnumWindows(Some(owned_windows_proc), LPARAM(state_ptr as isize));
+    }
+    state.found
+}
+
+/// Whether `find_elements` returns the descendants that `UIQuery::matches` accepts, in document order
+///
+/// `find_many` tests these against a single walk of the window. `Not` and the
+/// relational variants are resolved relative to the window itself, `ByText`
+/// walks with its own bounds, and `Or` lists its branches one after another
+/// (which positions count along), so those go through `find_elements`.
+fn evaluates_per_element(query: &UIQuery) -> bool {
+    match query {
+        UIQuery::ByName(_) | UIQuery::ByType(_) | UIQuery::ByTypes(_) | UIQuery::ByProperty(_, _)
+        | UIQuery::ByAutomationId(_) | UIQuery::ByClassName(_) | UIQuery::ByControlTypeId(_)
+        | UIQuery::ByNameMatch(_, _) | UIQuery::ByNameRegex(_) => true,
+        UIQuery::And(queries) | UIQuery::Or(queries) => queries.iter().all(evaluates_per_element),
+        UIQuery::Visible(query) | UIQuery::Enabled(query) => evaluates_per_element(query),
+        UIQuery::Nth(query, _) | UIQuery::Limit(query, _) => {
+            !matches!(**query, UIQuery::Or(_)) && evaluates_per_element(query)
+        },
+        UIQuery::ByText(_) | UIQuery::Not(_) | UIQuery::Child(_) | UIQuery::Descendant(_)
+        | UIQuery::Parent(_) | UIQuery::Ancestor(_) => false,
+    }
+}
+
+/// Match a candidate against `query` with its properties fetched through `request` in one round trip
+///
+/// If the bulk fetch fails the properties are read on demand instead.
+fn matches_bulk(query: &UIQuery, element: &super::element::WindowsElement, request: &UICacheRequest) -> Result<bool, UiError> {
+    let mut properties = element.get_info_bulk_with(request).ok().map(|info| info.properties());
+    query.matches_with_properties(element, &mut properties)
+}
+
+/// Map a query onto a native UIA property condition when one exists
+///
+/// Queries that map here are resolved by UI Automation itself in a single
+/// `find_all` call instead of enumerating descendants and filtering in Rust.
+///
+/// `ByProperty` is accelerated for these keys (the same keys `get_properties` reports):
+/// - `name` - exact element name
+/// - `control_type` - a known control type name such as "Button" or "Edit"
+/// - `automation_id` - exact automation ID
+/// - `class_name` - exact class name
+/// - `enabled` - "true" or "false"
+///
+/// Any other key, or a value that cannot be converted (e.g. an unknown control
+/// type), falls back to filtering descendants on their properties map.
+fn native_property_condition(query: &UIQuery) -> Option<(UIProperty, Variant)> {
+    match query {
+        UIQuery::ByAutomationId(automation_id) => Some((UIProperty::AutomationId, Variant::from(automation_id))),
+        UIQuery::ByClassName(class_name) => Some((UIProperty::ClassName, Variant::from(class_name))),
+        UIQuery::ByControlTypeId(control_type_id) => Some((UIProperty::ControlType, Variant::from(*control_type_id))),
+        UIQuery::ByProperty(key, value) => match key.as_str() {
+            "name" => Some((UIProperty::Name, Variant::from(value))),
+            "automation_id" => Some((UIProperty::AutomationId, Variant::from(value))),
+            "class_name" => Some((UIProperty::ClassName, Variant::from(value))),
+            "control_type" => control_type_from_name(value)
+                .map(|control_type| (UIProperty::ControlType, Variant::from(control_type as i32))),
+            "enabled" => match value.as_str() {
+                "true" => Some((UIProperty::IsEnabled, Variant::from(true))),
+                "false" => Some((UIProperty::IsEnabled, Variant::from(false))),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Like `native_property_condition`, but also covering `ByName` and known `ByType` names
+fn native_query_condition(query: &UIQuery) -> Option<(UIProperty, Variant)> {
+    match query {
+        UIQuery::ByName(name) => Some((UIProperty::Name, Variant::from(name))),
+        UIQuery::ByType(control_type) => control_type_from_name(control_type)
+            .map(|control_type| (UIProperty::ControlType, Variant::from(control_type as i32))),
+        _ => native_property_condition(query),
+    }
+}
+
+/// Convert a control type name (as reported by `UIElement::get_type`) to a `ControlType`
+pub(crate) fn control_type_from_name(name: &str) -> Option<ControlType> {
+    control_type::control_type_id(name).and_then(|id| ControlType::try_from(id).ok())
+}
+
 impl Window for WindowsWindow {
-    fn get_title(&self) -> Result<String, Box<dyn Error>> {
+    fn get_title(&self) -> Result<String, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.title.clone())
         } else {
@@ -224,7 +782,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_class_name(&self) -> Result<String, Box<dyn Error>> {
+    fn get_class_name(&self) -> Result<String, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.class_name.clone())
         } else {
@@ -232,7 +790,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_process_id(&self) -> Result<u32, Box<dyn Error>> {
+    fn get_process_id(&self) -> Result<u32, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.process_id)
         } else {
@@ -240,7 +798,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_thread_id(&self) -> Result<u32, Box<dyn Error>> {
+    fn get_thread_id(&self) -> Result<u32, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.thread_id)
         } else {
@@ -250,7 +808,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_process_name(&self) -> Result<String, Box<dyn Error>> {
+    fn get_process_name(&self) -> Result<String, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.process_name.clone())
         } else {
@@ -281,7 +839,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_process_path(&self) -> Result<String, Box<dyn Error>> {
+    fn get_process_path(&self) -> Result<String, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.process_path.clone())
         } else {
@@ -306,7 +864,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn is_visible(&self) -> Result<bool, Box<dyn Error>> {
+    fn is_visible(&self) -> Result<bool, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.is_visible)
         } else {
@@ -314,7 +872,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn is_minimized(&self) -> Result<bool, Box<dyn Error>> {
+    fn is_minimized(&self) -> Result<bool, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.is_minimized)
         } else {
@@ -325,7 +883,7 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn is_maximized(&self) -> Result<bool, Box<dyn Error>> {
+    fn is_maximized(&self) -> Result<bool, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.is_maximized)
         } else {
@@ -337,17 +895,41 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_rect(&self) -> Result<RECT, Box<dyn Error>> {
+    fn get_rect(&self) -> Result<Rect, UiError> {
         if let Some(info) = &self.window_info {
-            Ok(info.rect)
+            Ok(Rect::from(info.rect))
         } else {
             let mut rect = RECT::default();
             unsafe { GetWindowRect(self.element.get_native_window_handle()?.into(), &mut rect) }?;
-            Ok(rect)
+            Ok(Rect::from(rect))
+        }
+    }
+
+    fn get_bounds(&self) -> Result<Rect, UiError> {
+        // The live UI Automation bounds, in the same physical pixels as element bounds
+        self.automation.wrap_element(self.element.clone()).get_bounds()?
+            .ok_or_else(|| UiError::PlatformError("Window has no bounding rectangle".to_string()))
+    }
+
+    fn move_to(&self, x: i32, y: i32) -> Result<(), UiError> {
+        self.set_window_pos(x, y, 0, 0, SWP_NOSIZE)
+    }
+
+    fn resize(&self, width: i32, height: i32) -> Result<(), UiError> {
+        if width <= 0 || height <= 0 {
+            return Err(UiError::InvalidArgument(format!("Window size must be positive, got {}x{}", width, height)));
+        }
+        self.set_window_pos(0, 0, width, height, SWP_NOMOVE)
+    }
+
+    fn set_rect(&self, rect: Rect) -> Result<(), UiError> {
+        if rect.width() <= 0 || rect.height() <= 0 {
+            return Err(UiError::InvalidArgument(format!("Window rect must not be empty, got {:?}", rect)));
         }
+        self.set_window_pos(rect.left, rect.top, rect.width(), rect.height(), SET_WINDOW_POS_FLAGS(0))
     }
 
-    fn get_dpi(&self) -> Result<u32, Box<dyn Error>> {
+    fn get_dpi(&self) -> Result<u32, UiError> {
         if let Some(info) = &self.window_info {
             Ok(info.dpi)
         } else {
@@ -356,12 +938,13 @@ impl Window for WindowsWindow {
         }
     }
 
-    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, Box<dyn Error>> {
+    fn get_focused_element(&self) -> Result<Box<dyn UIElement>, UiError> {
         let element = self.automation.automation.lock()?.get_focused_element()?;
         Ok(self.automation.element_to_ui_element(element))
     }
 
-    fn get_ui_tree(&self) -> Result<UITree, Box<dyn Error>> {
+    fn get_ui_tree_with_options(&self, options: &UITreeOptions) -> Result<UITree, UiError> {
+        let mut timer = OperationTimer::start("get_ui_tree");
         let root_element = self.element.clone();
         let root_name = root_element.get_name().unwrap_or_default();
         let root_class = root_element.get_classname().unwrap_or_default();
@@ -388,448 +971,376 @@ impl Window for WindowsWindow {
         
         properties.insert("control_type".to_string(), control_type.clone());
         
-        // Create automation and walker once
-        let automation = self.automation.automation.lock()?;
-        let walker = automation.create_tree_walker()?;
-        
-        // Create root element wrapper
-        let root_windows_element = super::element::WindowsElement::new(root_element.clone(), Some(walker));
+        // Create root element wrapper; every node in the tree shares the cached walker
+        let root_windows_element = self.automation.wrap_element(root_element.clone());
         
-        // Build tree with depth limit for performance
-        fn build_tree_node(element: &super::element::WindowsElement, depth: usize, max_depth: usize) -> Result<UITreeNode, Box<dyn Error>> {
-            let name = element.get_name().unwrap_or_default();
+        // Build tree within the limits given by the options
+        fn describe_tree_node(element: &dyn UIElement, options: &UITreeOptions, request: &UICacheRequest) -> UITreeNode {
+            // Every property below comes from a single round trip
+            let info = element.as_any().downcast_ref::<super::element::WindowsElement>()
+                .and_then(|element| element.get_info_bulk_with(request).ok())
+                .unwrap_or_default();
+            let control_type = info.control_type_name();
             
-            let control_type = if let Ok(variant) = element.get_control_type_variant() {
-                if let Ok(control_type) = ControlType::try_from(variant) {
-                    control_type.to_string()
-                } else {
-                    "Unknown".to_string()
-                }
+            // Only get essential properties unless the full set was requested
+            let mut properties = if options.include_properties {
+                info.properties()
             } else {
-                "Unknown".to_string()
+                HashMap::new()
             };
-            
-            // Only get essential properties, skip expensive ones for performance
-            let mut properties = HashMap::new();
-            properties.insert("name".to_string(), name.clone());
+            properties.insert("name".to_string(), info.name.clone());
             properties.insert("control_type".to_string(), control_type.clone());
             
-            let bounds = element.get_bounds().ok().flatten();
-            let is_enabled = element.is_enabled().unwrap_or(false);
-            let is_visible = !element.is_offscreen().unwrap_or(true);
-            
-            // Limit tree depth to avoid performance issues
-            let mut children = Vec::new();
-            if depth < max_depth {
-                if let Ok(child_elements) = element.get_children() {
-                    // Limit number of children processed to avoid slowdown
-                    let max_children = if depth == 0 { 50 } else { 20 };
-                    for child in child_elements.into_iter().take(max_children) {
-                        if let Some(child_windows_element) = child.as_any().downcast_ref::<super::element::WindowsElement>() {
-                            if let Ok(child_node) = build_tree_node(child_windows_element, depth + 1, max_depth) {
-                                children.push(child_node);
-                            }
-                        }
-                    }
-                }
-            }
-            
-            Ok(UITreeNode {
-                name,
+            UITreeNode {
+                name: info.name,
                 control_type,
                 properties,
-                children,
-                bounds,
-                is_enabled,
-                is_visible,
-            })
+                children: Vec::new(),
+                bounds: if options.include_bounds { info.bounds } else { None },
+                is_enabled: info.is_enabled,
+                is_visible: !info.is_offscreen,
+            }
         }
         
-        // Build tree with reasonable depth limit (3 levels should be enough for most cases)
-        let root_node = build_tree_node(&root_windows_element, 0, 3)?;
+        let request = {
+            let automation = self.automation.automation.lock()?;
+            info_cache_request(&*automation)?
+        };
+        let mut described = 0;
+        // The walk keeps its own work stack, so deep UIs cannot overflow the call stack
+        let (root_node, truncated) = build_tree_bounded(
+            Box::new(root_windows_element) as Box<dyn UIElement>,
+            options,
+            |element| {
+                described += 1;
+                describe_tree_node(element.as_ref(), options, &request)
+            },
+            |element, limit| element.get_children()
+                .map(|children| children.into_iter().take(limit).collect())
+                .unwrap_or_default(),
+        )?;
+        timer.phase("walk tree");
+        debug!(
+            "WindowsWindow::get_ui_tree - {} nodes described with {} property calls instead of {}",
+            described, described, described * BULK_INFO_PROPERTIES.len()
+        );
         
         Ok(UITree {
             root: root_node,
             timestamp: chrono::Utc::now(),
             window_title: root_name,
             window_class: root_class,
+            truncated,
         })
     }
 
-    fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, Box<dyn Error>> {
+    fn find_first(&self, query: &UIQuery) -> Result<Option<Box<dyn UIElement>>, UiError> {
         match query {
-            UIQuery::ByName(name) => {
-                let automation = self.automation.automation.lock()?;
-                let condition = automation.create_property_condition(UIProperty::Name, Variant::from(name), None)?;
-                // Search within THIS WINDOW's element tree, not from desktop root
-                let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
-                let mut result = Vec::new();
-                for element in elements {
-                    result.push(Box::new(super::element::WindowsElement::new(element, None)) as Box<dyn UIElement>);
-                }
-                Ok(result)
-            },
+            UIQuery::ByName(name) => self.find_first_by_property(UIProperty::Name, Variant::from(name)),
             UIQuery::ByType(control_type) => {
-                // Convert string type to ControlType enum (same logic as before)
-                let control_type_enum = match control_type.as_str() {
-                    "Button" => ControlType::Button,
-                    "Edit" => ControlType::Edit,
-                    "Text" => ControlType::Text,
-                    "ComboBox" => ControlType::ComboBox,
-                    "CheckBox" => ControlType::CheckBox,
-                    "RadioButton" => ControlType::RadioButton,
-                    "ListItem" => ControlType::ListItem,
-                    "List" => ControlType::List,
-                    "TreeItem" => ControlType::TreeItem,
-                    "Tree" => ControlType::Tree,
-                    "TabItem" => ControlType::TabItem,
-                    "Tab" => ControlType::Tab,
-                    "Table" => ControlType::Table,
-                    "Document" => ControlType::Document,
-                    "Pane" => ControlType::Pane,
-                    "Window" => ControlType::Window,
-                    "Group" => ControlType::Group,
-                    "Image" => ControlType::Image,
-                    "Hyperlink" => ControlType::Hyperlink,
-                    "Custom" => ControlType::Custom,
-                    "MenuBar" => ControlType::MenuBar,
-                    "MenuItem" => ControlType::MenuItem,
-                    "Menu" => ControlType::Menu,
-                    _ => ControlType::Custom,
+                let control_type_id = control_type_from_name(control_type).unwrap_or(ControlType::Custom) as i32;
+                self.find_first_by_property(UIProperty::ControlType, Variant::from(control_type_id))
+            },
+            UIQuery::ByTypes(control_types) => {
+                let condition = match self.control_types_condition(control_types)? {
+                    Some(condition) => condition,
+                    None => return Ok(None),
                 };
-                
-                let control_type_id = control_type_enum as i32;
-                
-                let automation = self.automation.automation.lock()?;
-                let condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(control_type_id), None)?;
-                // Search within THIS WINDOW's element tree, not from desktop root
-                let elements = self.element.find_all(TreeScope::Descendants, &condition)?;
-                let mut result = Vec::new();
-                for element in elements {
-                    result.push(Box::new(super::element::WindowsElement::new(element, None)) as Box<dyn UIElement>);
-                }
-                Ok(result)
+                // FindFirst reports "no match" as an error rather than an empty result
+                Ok(self.element.find_first(TreeScope::Descendants, &condition)
+                    .ok()
+                    .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>))
             },
-            UIQuery::ByProperty(key, value) => {
-                let automation = self.automation.automation.lock()?;
-                // For property queries, we need to get all children and filter
-                let all_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Custom as i32), None)?;
-                let children = self.element.find_all(TreeScope::Descendants, &all_condition)?;
-                let mut result = Vec::new();
-                for child in children {
-                    let windows_element = super::element::WindowsElement::new(child.clone(), None);
-                    if let Ok(properties) = windows_element.get_properties() {
-                        if properties.get(key) == Some(value) {
-                            result.push(Box::new(windows_element) as Box<dyn UIElement>);
-                        }
-                    }
-                }
-                Ok(result)
+            UIQuery::ByAutomationId(_) | UIQuery::ByClassName(_) | UIQuery::ByControlTypeId(_) => {
+                let (property, value) = native_property_condition(query)
+                    .ok_or("No native condition for query")?;
+                self.find_first_by_property(property, value)
             },
-            UIQuery::And(queries) => {
-                let mut results = Vec::new();
-                for query in queries {
-                    let elements = self.find_elements(query)?;
-                    if results.is_empty() {
-                        results = elements;
-                    } else {
-                        // Keep only elements that exist in both results
-                        results.retain(|e1| {
-                            elements.iter().any(|e2| {
-                                e1.get_name().ok() == e2.get_name().ok() &&
-                                e1.get_type().ok() == e2.get_type().ok()
-                            })
-                        });
-                    }
-                }
-                Ok(results)
+            UIQuery::ByProperty(_, _) if native_property_condition(query).is_some() => {
+                let (property, value) = native_property_condition(query)
+                    .ok_or("No native condition for query")?;
+                self.find_first_by_property(property, value)
             },
-            UIQuery::Or(queries) => {
-                let mut results = Vec::new();
-                for query in queries {
-                    let elements = self.find_elements(query)?;
-                    results.extend(elements);
-                }
-                Ok(results)
+            UIQuery::ByNameMatch(pattern, mode) => match self.name_match_condition(pattern, *mode)? {
+                Some(condition) => Ok(self.element.find_first(TreeScope::Descendants, &condition)
+                    .ok()
+                    .map(|element| Box::new(self.automation.wrap_element(element)) as Box<dyn UIElement>)),
+                None => Ok(self.find_elements(query)?.into_iter().next()),
             },
-            UIQuery::Not(query) => {
-                let automation = self.automation.automation.lock()?;
-                let all_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Custom as i32), None)?;
-                let children = self.element.find_all(TreeScope::Descendants, &all_condition)?;
-                let mut result = Vec::new();
-                for child in children {
-                    let windows_element = super::element::WindowsElement::new(child.clone(), None);
-                    if !query.matches(&windows_element)? {
-                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
+            UIQuery::ByText(_) => Ok(self.find_elements(query)?.into_iter().next()),
+            UIQuery::ByProperty(_, _) | UIQuery::ByNameRegex(_) | UIQuery::And(_) | UIQuery::Or(_) | UIQuery::Not(_) => {
+                // No single native condition: walk the descendants and stop at the first match
+                let root = self.automation.wrap_element(self.element.clone());
+                for child in root.get_children().unwrap_or_default() {
+                    if let Some(found) = query.find_first(child.as_ref())? {
+                        return Ok(Some(found));
                     }
                 }
-                Ok(result)
+                Ok(None)
             },
-            UIQuery::Child(query) => {
-                let automation = self.automation.automation.lock()?;
-                let all_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Custom as i32), None)?;
-                let children = self.element.find_all(TreeScope::Children, &all_condition)?;
-                let mut result = Vec::new();
-                for child in children {
-                    let windows_element = super::element::WindowsElement::new(child.clone(), None);
-                    if query.matches(&windows_element)? {
-                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
-                    }
-                }
-                Ok(result)
+            UIQuery::Limit(_, 0) => Ok(None),
+            UIQuery::Limit(query, _) => self.find_first(query),
+            // Relational queries are relative to the window itself, as in `find_elements`
+            UIQuery::Child(_) | UIQuery::Descendant(_) | UIQuery::Parent(_) | UIQuery::Ancestor(_) | UIQuery::Nth(_, _)
+            | UIQuery::Visible(_) | UIQuery::Enabled(_) => {
+                Ok(self.find_elements(query)?.into_iter().next())
             },
-            UIQuery::Descendant(query) => {
-                let automation = self.automation.automation.lock()?;
-                let all_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Custom as i32), None)?;
-                let descendants = self.element.find_all(TreeScope::Descendants, &all_condition)?;
-                let mut result = Vec::new();
-                for descendant in descendants {
-                    let windows_element = super::element::WindowsElement::new(descendant.clone(), None);
-                    if query.matches(&windows_element)? {
-                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
-                    }
+        }
+    }
+
+    fn window_id(&self) -> Result<u64, UiError> {
+        Ok(self.hwnd()?.0 as usize as u64)
+    }
+
+    fn get_child_windows(&self) -> Result<Vec<Box<dyn Window>>, UiError> {
+        let mut handles = owned_windows(self.hwnd()?);
+
+        // MDI documents and similar parented windows only show up in the UIA tree
+        let children = {
+            let automation = self.automation.automation.lock()?;
+            let condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Window as i32), None)?;
+            // FindAll reports "no match" as an error on some providers
+            self.element.find_all(TreeScope::Children, &condition).unwrap_or_default()
+        };
+        for child in children {
+            if let Ok(handle) = child.get_native_window_handle() {
+                let hwnd: HWND = handle.into();
+                if !hwnd.is_invalid() && !handles.contains(&hwnd) && unsafe { IsWindowVisible(hwnd) }.as_bool() {
+                    handles.push(hwnd);
                 }
-                Ok(result)
-            },
-            UIQuery::Parent(query) => {
-                let automation = self.automation.automation.lock()?;
-                let parent_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Window as i32), None)?;
-                if let Ok(parent) = self.element.find_first(TreeScope::Parent, &parent_condition) {
-                    let windows_element = super::element::WindowsElement::new(parent.clone(), None);
-                    if query.matches(&windows_element)? {
-                        return Ok(vec![Box::new(windows_element) as Box<dyn UIElement>]);
-                    }
+            }
+        }
+        debug!("WindowsWindow::get_child_windows - {} owned or child windows", handles.len());
+
+        let mut windows: Vec<Box<dyn Window>> = Vec::new();
+        for hwnd in handles {
+            // A dialog can close between enumeration and wrapping
+            let element = match self.automation.automation.lock()?.element_from_handle(hwnd.into()) {
+                Ok(element) => element,
+                Err(e) => {
+                    debug!("WindowsWindow::get_child_windows - skipping HWND {:?}: {}", hwnd, e);
+                    continue;
                 }
-                Ok(Vec::new())
-            },
-            UIQuery::Ancestor(query) => {
+            };
+            windows.push(Box::new(WindowsWindow::new(element, self.automation.clone())?));
+        }
+        Ok(windows)
+    }
+
+    fn find_elements(&self, query: &UIQuery) -> Result<Vec<Box<dyn UIElement>>, UiError> {
+        // A UI that changes mid-search makes UI Automation fail with a transient error
+        with_retry(DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_DELAY, || self.find_elements_once(query))
+    }
+
+    fn find_many(&self, queries: &HashMap<String, UIQuery>) -> Result<HashMap<String, Vec<Box<dyn UIElement>>>, UiError> {
+        let (one_pass, separate): (Vec<(&str, &UIQuery)>, Vec<(&str, &UIQuery)>) = queries.iter()
+            .map(|(label, query)| (label.as_str(), query))
+            .partition(|(_, query)| evaluates_per_element(query));
+
+        let mut results = HashMap::new();
+        if !one_pass.is_empty() {
+            let mut timer = OperationTimer::start("find_many");
+            let all_condition = self.automation.true_condition()?;
+            let request = {
                 let automation = self.automation.automation.lock()?;
-                let ancestor_condition = automation.create_property_condition(UIProperty::ControlType, Variant::from(ControlType::Window as i32), None)?;
-                let mut current = self.element.clone();
-                let mut result = Vec::new();
-                while let Ok(parent) = current.find_first(TreeScope::Parent, &ancestor_condition) {
-                    let windows_element = super::element::WindowsElement::new(parent.clone(), None);
-                    if query.matches(&windows_element)? {
-                        result.push(Box::new(windows_element) as Box<dyn UIElement>);
-                    }
-                    current = parent;
+                info_cache_request(&*automation)?
+            };
+            // The matches are handed back to the caller, so they keep a live reference
+            request.set_element_mode(ElementMode::Full)?;
+            // One call returns every descendant with its properties already cached
+            let descendants = with_retry(DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_DELAY, || {
+                Ok(self.element.find_all_build_cache(TreeScope::Descendants, &all_condition, &request)?)
+            })?;
+            timer.phase("native FindAllBuildCache");
+            results = find_many_in_one_pass(&one_pass, |visit| {
+                for descendant in descendants {
+                    let properties = cached_info_bulk(&descendant).properties();
+                    visit(&self.automation.wrap_element(descendant), Some(properties));
                 }
-                Ok(result)
-            },
+            });
+            timer.phase("match queries");
         }
+        for (label, query) in separate {
+            results.insert(label.to_string(), self.find_elements(query)?);
+        }
+        Ok(results)
     }
 
-    fn activate(&self) -> Result<(), Box<dyn Error>> {
-        // For now, provide a basic implementation that focuses the window using UIAutomation
-        debug!("Attempting to activate window using UIAutomation SetFocus");
-        self.element.set_focus()
-            .map_err(|e| format!("Failed to activate window: {}", e).into())
+    fn find_elements_with_path(&self, query: &UIQuery) -> Result<Vec<(Box<dyn UIElement>, Vec<String>)>, UiError> {
+        let mut timer = OperationTimer::start("find_elements_with_path");
+        let root = self.automation.wrap_element(self.element.clone());
+        let request = info_cache_request(&*self.automation.automation.lock()?)?;
+        // Each element's control type and properties arrive in one round trip
+        let found = find_with_paths(&root, query, |element| {
+            element.as_any().downcast_ref::<super::element::WindowsElement>()
+                .and_then(|element| element.get_info_bulk_with(&request).ok())
+                .map(|info| info.properties())
+        });
+        timer.phase("walk descendants");
+        debug!("find_elements_with_path - {} matches for {:?}", found.len(), query);
+        Ok(found)
     }
 
-    fn bring_to_top(&self) -> Result<(), Box<dyn Error>> {
-        // For now, use the same approach as activate
-        debug!("Attempting to bring window to top using UIAutomation SetFocus");
-        self.element.set_focus()
-            .map_err(|e| format!("Failed to bring window to top: {}", e).into())
+    fn find_by_path(&self, path: &[String]) -> Result<Option<Box<dyn UIElement>>, UiError> {
+        resolve_path(Box::new(self.automation.wrap_element(self.element.clone())), path)
     }
 
-    fn set_foreground(&self) -> Result<(), Box<dyn Error>> {
-        // For now, use the same approach as activate
-        debug!("Attempting to set window as foreground using UIAutomation SetFocus");
-        self.element.set_focus()
-            .map_err(|e| format!("Failed to set window as foreground: {}", e).into())
+    fn activate(&self) -> Result<(), UiError> {
+        let hwnd = self.hwnd()?;
+        if unsafe { IsIconic(hwnd) }.as_bool() {
+            debug!("Restoring minimized HWND {:?} before activating it", hwnd);
+            self.show_window(SW_RESTORE)?;
+        }
+        self.set_foreground()?;
+
+        // Foreground is what matters; keyboard focus inside the window is a bonus
+        if let Err(e) = self.element.set_focus() {
+            debug!("Window is foreground but UIA SetFocus failed: {}", e);
+        }
+        Ok(())
     }
 
-    fn get_text_elements(&self, options: &TextExtractionOptions) -> Result<Vec<TextElementInfo>, Box<dyn Error>> {
-        let mut results = Vec::new();
+    fn bring_to_top(&self) -> Result<(), UiError> {
+        let hwnd = self.hwnd()?;
+        debug!("Calling SetWindowPos(HWND_TOP) on HWND {:?}", hwnd);
+        unsafe { SetWindowPos(hwnd, Some(HWND_TOP), 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE)? };
+        Ok(())
+    }
 
-        // Create automation and walker
-        let automation = self.automation.automation.lock()?;
-        let walker = automation.create_tree_walker()?;
-
-        // Helper function to extract text elements recursively
-        fn extract_text_elements(
-            element: &UIAutomationElement,
-            walker: &uiautomation::UITreeWalker,
-            options: &TextExtractionOptions,
-            results: &mut Vec<TextElementInfo>,
-            depth: u32,
-            parent_name: Option<String>,
-        ) {
-            // Check max depth
-            if let Some(max_depth) = options.max_depth {
-                if depth > max_depth {
-                    return;
-                }
-            }
+    fn set_foreground(&self) -> Result<(), UiError> {
+        let hwnd = self.hwnd()?;
+        if !unsafe { IsWindow(Some(hwnd)) }.as_bool() {
+            return Err(UiError::WindowNotFound(format!("Window handle {:?} no longer exists", hwnd)));
+        }
 
-            // Get control type
-            let control_type = if let Ok(variant) = element.get_property_value(UIProperty::ControlType) {
-                if let Ok(control_type_id) = <Variant as TryInto<i32>>::try_into(variant) {
-                    if let Ok(ct) = ControlType::try_from(control_type_id) {
-                        ct.to_string()
-                    } else {
-                        "Unknown".to_string()
-                    }
-                } else {
-                    "Unknown".to_string()
-                }
-            } else {
-                "Unknown".to_string()
-            };
+        let is_foreground = || unsafe { GetForegroundWindow() } == hwnd;
+        if retry_until(FOREGROUND_ATTEMPTS, FOREGROUND_RETRY_DELAY, || request_foreground(hwnd), is_foreground) {
+            return Ok(());
+        }
+        Err(UiError::Timeout(format!(
+            "Window {:?} is still not the foreground window after {} attempts; \
+             Windows may be blocking the focus change (foreground lock)",
+            hwnd, FOREGROUND_ATTEMPTS
+        )))
+    }
 
-            // Check if we should include this control type
-            if let Some(ref allowed_types) = options.control_types {
-                if !allowed_types.contains(&control_type) {
-                    // Still recurse into children
-                    if let Ok(first_child) = walker.get_first_child(element) {
-                        extract_text_elements(&first_child, walker, options, results, depth + 1, parent_name.clone());
-                        let mut next = first_child;
-                        while let Ok(sibling) = walker.get_next_sibling(&next) {
-                            extract_text_elements(&sibling, walker, options, results, depth + 1, parent_name.clone());
-                            next = sibling;
-                        }
-                    }
-                    return;
-                }
-            }
+    fn minimize(&self) -> Result<(), UiError> {
+        self.show_window(SW_MINIMIZE)
+    }
 
-            // Check visibility
-            let is_offscreen = element.is_offscreen().unwrap_or(true);
-            let is_visible = !is_offscreen;
-
-            if !options.include_hidden && !is_visible {
-                // Still recurse into children - some may be visible
-                if let Ok(first_child) = walker.get_first_child(element) {
-                    extract_text_elements(&first_child, walker, options, results, depth + 1, parent_name.clone());
-                    let mut next = first_child;
-                    while let Ok(sibling) = walker.get_next_sibling(&next) {
-                        extract_text_elements(&sibling, walker, options, results, depth + 1, parent_name.clone());
-                        next = sibling;
-                    }
-                }
-                return;
-            }
+    fn maximize(&self) -> Result<(), UiError> {
+        self.show_window(SW_MAXIMIZE)
+    }
 
-            // Check if enabled
-            let is_enabled = element.is_enabled().unwrap_or(true);
-            if !options.include_disabled && !is_enabled {
-                if let Ok(first_child) = walker.get_first_child(element) {
-                    extract_text_elements(&first_child, walker, options, results, depth + 1, parent_name.clone());
-                    let mut next = first_child;
-                    while let Ok(sibling) = walker.get_next_sibling(&next) {
-                        extract_text_elements(&sibling, walker, options, results, depth + 1, parent_name.clone());
-                        next = sibling;
-                    }
-                }
-                return;
-            }
+    fn restore(&self) -> Result<(), UiError> {
+        self.show_window(SW_RESTORE)
+    }
 
-            // Get name
-            let name = element.get_name().unwrap_or_default();
+    fn close(&self) -> Result<(), UiError> {
+        let hwnd = self.hwnd()?;
+        debug!("Posting WM_CLOSE to HWND {:?}", hwnd);
+        unsafe { PostMessageW(Some(hwnd), WM_CLOSE, WPARAM(0), LPARAM(0)) }
+            .map_err(|e| format!("Failed to close window: {}", e))?;
 
-            // Get text content
-            let mut text = String::new();
+        if self.wait_for_close(CLOSE_TIMEOUT)? {
+            return Ok(());
+        }
+        Err(UiError::Timeout(format!("Window still open {:?} after close request", CLOSE_TIMEOUT)))
+    }
 
-            // Try Value pattern first
-            use uiautomation::patterns::{UIValuePattern, UITextPattern};
-            if let Ok(value_pattern) = element.get_pattern::<UIValuePattern>() {
-                if let Ok(value) = value_pattern.get_value() {
-                    text = value;
-                }
-            }
+    fn wait_for_close(&self, timeout: Duration) -> Result<bool, UiError> {
+        let hwnd = match self.hwnd() {
+            Ok(hwnd) => hwnd,
+            // The element went away along with its window
+            Err(UiError::ElementNotFound(_)) => return Ok(true),
+            Err(e) => return Err(e),
+        };
 
-            // Try Text pattern if Value pattern didn't work
-            if text.is_empty() {
-                if let Ok(text_pattern) = element.get_pattern::<UITextPattern>() {
-                    if let Ok(doc_range) = text_pattern.get_document_range() {
-                        if let Ok(t) = doc_range.get_text(-1) {
-                            text = t;
-                        }
-                    }
-                }
+        let start = Instant::now();
+        loop {
+            if !unsafe { IsWindow(Some(hwnd)) }.as_bool() {
+                debug!("Window closed after {:?}", start.elapsed());
+                return Ok(true);
             }
 
-            // Use name as text if configured and text is empty
-            if text.is_empty() && options.include_names_as_text {
-                text = name.clone();
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Ok(false);
             }
+            thread::sleep(CLOSE_POLL_INTERVAL.min(timeout - elapsed));
+        }
+    }
 
-            // Check minimum text length
-            if text.trim().len() >= options.min_text_length {
-                // Get bounds
-                let bounds = if let Ok(rect) = element.get_bounding_rectangle() {
-                    if let Ok(r) = <uiautomation::types::Rect as TryInto<RECT>>::try_into(rect) {
-                        Some(Rect {
-                            left: r.left,
-                            top: r.top,
-                            right: r.right,
-                            bottom: r.bottom,
-                        })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
+    fn invoke_menu_path(&self, path: &[&str]) -> Result<(), UiError> {
+        let menu_bar = self.get_menu_bar()?
+            .ok_or_else(|| UiError::ElementNotFound("The window has no menu bar".to_string()))?;
+        debug!("Invoking menu path {:?}", path);
+        walk_menu_path(menu_bar, path, |name| match self.find_popup_menu_item(name)? {
+            Some(item) => Ok(Some(item)),
+            None => self.find_first(&menu_item_query(name)),
+        })
+    }
 
-                // Get automation ID
-                let automation_id = if let Ok(variant) = element.get_property_value(UIProperty::AutomationId) {
-                    let id = variant.to_string();
-                    if !id.is_empty() && id != "null" {
-                        Some(id)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
+    fn send_keys(&self, keys: &str) -> Result<(), UiError> {
+        validate_key_sequence(keys)?;
+        if self.automation.interaction_mode() == InteractionMode::PatternsOnly {
+            return Err(UiError::Unsupported("Sending keys is disabled in PatternsOnly mode".to_string()));
+        }
+        self.activate()?;
 
-                // Get class name
-                let class_name = element.get_classname().ok();
-
-                // Determine if editable
-                let is_editable = matches!(control_type.as_str(), "Edit" | "Document" | "ComboBox");
-
-                let text_info = TextElementInfo {
-                    text,
-                    name,
-                    control_type: control_type.clone(),
-                    automation_id,
-                    class_name,
-                    bounds,
-                    is_selected: false,
-                    is_editable,
-                    is_visible,
-                    is_enabled,
-                    parent_name: parent_name.clone(),
-                    depth,
-                };
+        debug!("Sending key sequence {:?} to window", keys);
+        Keyboard::new()
+            .interval(10)
+            .send_keys(keys)
+            .map_err(|e| match e.code() {
+                uiautomation::errors::ERR_FORMAT => UiError::InvalidArgument(format!("Malformed key sequence {:?}: {}", keys, e)),
+                _ => UiError::PlatformError(format!("Failed to send keys {:?}: {}", keys, e)),
+            })
+    }
 
-                results.push(text_info);
-            }
+    fn capture_image(&self) -> Result<Vec<u8>, UiError> {
+        // Query live bounds rather than the cached window info so moved windows are captured correctly
+        let element = self.automation.wrap_element(self.element.clone());
+        super::capture::capture_png(|| element.get_bounds())
+    }
 
-            // Get the current element's name for child context
-            let current_name = element.get_name().ok();
+    fn for_each_text_element(&self, options: &TextExtractionOptions, f: &mut dyn FnMut(&TextElementInfo) -> bool) -> Result<(), UiError> {
+        let mut timer = OperationTimer::start("get_text_elements");
 
-            // Recurse into children
-            if let Ok(first_child) = walker.get_first_child(element) {
-                extract_text_elements(&first_child, walker, options, results, depth + 1, current_name.clone());
-                let mut next = first_child;
-                while let Ok(sibling) = walker.get_next_sibling(&next) {
-                    extract_text_elements(&sibling, walker, options, results, depth + 1, current_name.clone());
-                    next = sibling;
-                }
-            }
-        }
+        let walker = self.automation.tree_walker()?;
+        let request = {
+            let automation = self.automation.automation.lock()?;
+            info_cache_request(&*automation)?
+        };
+        timer.phase("create walker");
 
-        // Start extraction from root element
-        extract_text_elements(&self.element, &walker, options, &mut results, 0, None);
+        let mut reported = 0;
+        let mut visited = 0;
+        let completed = visit_text_elements(
+            walker_children(&walker, &self.element),
+            self.element.get_name().ok(),
+            options,
+            |element| {
+                visited += 1;
+                Some(describe_text_element(element, &request, options))
+            },
+            |element| walker_children(&walker, element),
+            &mut |info| {
+                reported += 1;
+                f(info)
+            },
+        )?;
+        timer.phase("collect elements");
+        debug!("WindowsWindow::for_each_text_element - {} elements reported, stopped early: {}", reported, !completed);
+        debug!(
+            "WindowsWindow::for_each_text_element - {} elements described with {} property calls instead of {}",
+            visited, visited, visited * BULK_INFO_PROPERTIES.len()
+        );
 
-        Ok(results)
+        Ok(())
     }
 
-    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, Box<dyn Error>> {
+    fn get_selected_text(&self) -> Result<Option<SelectedTextInfo>, UiError> {
         use uiautomation::patterns::UITextPattern;
 
         // Get focused element
@@ -845,7 +1356,7 @@ impl Window for WindowsWindow {
                         if let Ok(text) = range.get_text(-1) {
                             if !text.is_empty() {
                                 // Get element info
-                                let element_wrapper = super::element::WindowsElement::new(focused, None);
+                                let element_wrapper = self.automation.wrap_element(focused);
                                 let element_info = element_wrapper.get_text_element_info().ok();
 
                                 // Try to get bounds from the enclosing element as a fallback
@@ -867,4 +1378,484 @@ impl Window for WindowsWindow {
 
         Ok(None)
     }
-} 
\ No newline at end of file
+}
+
+/// Children of `element` through `walker`, in document order
+fn walker_children(walker: &uiautomation::UITreeWalker, element: &UIAutomationElement) -> Vec<UIAutomationElement> {
+    let mut children = Vec::new();
+    let mut child = walker.get_first_child(element).ok();
+    while let Some(current) = child {
+        child = walker.get_next_sibling(&current).ok();
+        children.push(current);
+    }
+    children
+}
+
+/// Describe an element for text extraction from properties fetched through `request` in one round trip
+///
+/// The text and the editable flag cost further calls, so they are only read
+/// for elements that `options` would report.
+fn describe_text_element(element: &UIAutomationElement, request: &UICacheRequest, options: &TextExtractionOptions) -> TextElementInfo {
+    let info = fetch_info_bulk(element, request).unwrap_or_default();
+    let native_control_type = info.control_type;
+    let control_type = native_control_type
+        .map(|control_type| control_type.to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let is_visible = !info.is_offscreen;
+    let is_enabled = info.is_enabled;
+
+    let mut text = String::new();
+    let mut is_editable = false;
+    if options.accepts_control_type(&control_type)
+        && (options.include_hidden || is_visible)
+        && (options.include_disabled || is_enabled)
+    {
+        text = element_text_limited(element, DEFAULT_TEXT_LIMIT)
+            .map(|(text, _)| text)
+            .unwrap_or_default();
+        let reported_text = if text.is_empty() && options.include_names_as_text { &info.name } else { &text };
+        if options.accepts_text(reported_text) {
+            is_editable = native_control_type
+                .is_some_and(|control_type| element_is_editable(element, control_type).unwrap_or(false));
+        }
+    }
+
+    TextElementInfo {
+        text,
+        name: info.name,
+        control_type,
+        localized_control_type: info.localized_control_type,
+        automation_id: info.automation_id,
+        class_name: Some(info.class_name),
+        bounds: info.bounds,
+        is_selected: false,
+        is_editable,
+        is_visible,
+        is_enabled,
+        // Filled in by the walk
+        parent_name: None,
+        depth: 0,
+    }
+}
+
+/// Collect the descendants of `root` whose text content contains `needle`, in document order
+/// 
+/// Walks at most `max_depth` levels below `root` (None = unlimited) and visits
+/// at most `max_nodes` elements. Elements without a Value or Text pattern have
+/// no text and never match; longer text is only read up to `DEFAULT_TEXT_LIMIT`.
+fn find_by_text(
+    root: &UIAutomationElement,
+    walker: &uiautomation::UITreeWalker,
+    needle: &str,
+    max_depth: Option<u32>,
+    max_nodes: usize,
+) -> Vec<UIAutomationElement> {
+    let roots = walker_children(walker, root).into_iter().map(|child| (child, 1)).collect();
+    let mut matches = Vec::new();
+    let truncated = walk_nodes_bounded(
+        roots,
+        max_nodes,
+        |(element, depth): &(UIAutomationElement, u32)| {
+            if max_depth.is_some_and(|max_depth| *depth >= max_depth) {
+                return Vec::new();
+            }
+            walker_children(walker, element).into_iter().map(|child| (child, depth + 1)).collect()
+        },
+        |(element, _)| {
+            let text = element_text_limited(element, DEFAULT_TEXT_LIMIT).map(|(text, _)| text).unwrap_or_default();
+            if text.contains(needle) {
+                matches.push(element.clone());
+            }
+            Ok(true)
+        },
+    );
+    if let Ok(true) = truncated {
+        debug!("find_by_text - gave up after visiting {} nodes", max_nodes);
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_automation_id_uses_native_condition() {
+        let query = UIQuery::ByAutomationId("SaveButton".to_string());
+        let (property, _) = native_property_condition(&query)
+            .expect("ByAutomationId should map to a native condition");
+        assert_eq!(property, UIProperty::AutomationId);
+    }
+
+    #[test]
+    fn test_by_class_name_uses_native_condition() {
+        let query = UIQuery::ByClassName("Chrome_WidgetWin_1".to_string());
+        let (property, _) = native_property_condition(&query)
+            .expect("ByClassName should map to a native condition");
+        assert_eq!(property, UIProperty::ClassName);
+    }
+
+    #[test]
+    fn test_find_many_runs_window_relative_queries_separately() {
+        let name = || UIQuery::ByName("Save".to_string());
+        assert!(evaluates_per_element(&name()));
+        assert!(evaluates_per_element(&UIQuery::Nth(Box::new(UIQuery::Visible(Box::new(name()))), 1)));
+        assert!(evaluates_per_element(&UIQuery::Or(vec![name(), UIQuery::ByType("Edit".to_string())])));
+
+        assert!(!evaluates_per_element(&UIQuery::Child(Box::new(name()))));
+        assert!(!evaluates_per_element(&UIQuery::And(vec![name(), UIQuery::Not(Box::new(name()))])));
+        assert!(!evaluates_per_element(&UIQuery::ByText("Save".to_string())));
+        assert!(!evaluates_per_element(&UIQuery::Limit(Box::new(UIQuery::Or(vec![name()])), 1)));
+    }
+
+    #[test]
+    fn test_by_property_well_known_keys_are_accelerated() {
+        let cases = [
+            ("name", "Save", UIProperty::Name),
+            ("automation_id", "SaveButton", UIProperty::AutomationId),
+            ("class_name", "Notepad", UIProperty::ClassName),
+            ("control_type", "Button", UIProperty::ControlType),
+            ("enabled", "false", UIProperty::IsEnabled),
+        ];
+        for (key, value, expected) in cases {
+            let query = UIQuery::ByProperty(key.to_string(), value.to_string());
+            let (property, _) = native_property_condition(&query)
+                .unwrap_or_else(|| panic!("'{}' should map to a native condition", key));
+            assert_eq!(property, expected);
+        }
+    }
+
+    #[test]
+    fn test_by_property_unknown_values_fall_back_to_filtering() {
+        let unknown_key = UIQuery::ByProperty("help_text".to_string(), "x".to_string());
+        assert!(native_property_condition(&unknown_key).is_none());
+
+        let unknown_type = UIQuery::ByProperty("control_type".to_string(), "Gizmo".to_string());
+        assert!(native_property_condition(&unknown_type).is_none());
+
+        let bad_bool = UIQuery::ByProperty("enabled".to_string(), "True".to_string());
+        assert!(native_property_condition(&bad_bool).is_none());
+    }
+
+    #[test]
+    fn test_range_value_control_types_resolve() {
+        assert_eq!(control_type_from_name("Slider"), Some(ControlType::Slider));
+        assert_eq!(control_type_from_name("ProgressBar"), Some(ControlType::ProgressBar));
+        assert_eq!(control_type_from_name("Spinner"), Some(ControlType::Spinner));
+        // Round-trips through the names reported in the UI tree
+        assert_eq!(ControlType::Slider.to_string(), "Slider");
+    }
+
+    #[test]
+    fn test_every_control_type_round_trips_through_its_name() {
+        for id in 50000..=50040 {
+            let native = ControlType::try_from(id).expect("uiautomation knows every identifier in the table");
+            let name = control_type::control_type_name(id).expect("the table covers every control type");
+            assert_eq!(format!("{:?}", native), name);
+            assert_eq!(control_type_from_name(name), Some(native));
+            assert_eq!(native as i32, id);
+        }
+    }
+
+    #[test]
+    fn test_by_control_type_id_uses_native_condition() {
+        let (property, _) = native_property_condition(&UIQuery::ByControlTypeId(50015))
+            .expect("ByControlTypeId should map to a native condition");
+        assert_eq!(property, UIProperty::ControlType);
+    }
+
+    #[test]
+    fn test_send_keys_rejects_empty_sequence() {
+        assert!(validate_key_sequence("").is_err());
+        assert!(validate_key_sequence("{CTRL}s").is_ok());
+        assert!(validate_key_sequence(" ").is_ok());
+    }
+
+    #[test]
+    fn test_retry_until_stops_once_verified() {
+        use std::cell::Cell;
+
+        // Both closures look at the count, so it lives in a Cell
+        let attempts = Cell::new(0);
+        let done = retry_until(5, Duration::ZERO, || attempts.set(attempts.get() + 1), || attempts.get() >= 2);
+        assert!(done);
+        assert_eq!(attempts.get(), 2);
+
+        let attempts = Cell::new(0);
+        assert!(retry_until(5, Duration::ZERO, || attempts.set(attempts.get() + 1), || true));
+        assert_eq!(attempts.get(), 0, "an already satisfied state must not be retried");
+
+        let attempts = Cell::new(0);
+        assert!(!retry_until(3, Duration::ZERO, || attempts.set(attempts.get() + 1), || false));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_activate_restores_and_foregrounds_minimized_notepad() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+        window.minimize().unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        let activated = window.activate();
+        let hwnd = HWND(window.window_id().unwrap() as usize as *mut _);
+        let foreground = unsafe { GetForegroundWindow() } == hwnd;
+        let minimized = unsafe { IsIconic(hwnd) }.as_bool();
+
+        activated.expect("Failed to activate Notepad");
+        assert!(foreground, "Notepad should be the foreground window");
+        assert!(!minimized, "Notepad should have been restored");
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_wait_for_close_returns_once_notepad_closes() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+        let hwnd = HWND(window.window_id().unwrap() as usize as *mut _);
+
+        let still_open = window.wait_for_close(Duration::from_millis(200));
+        // Ask for the close without waiting, as clicking a dialog's OK button would
+        unsafe { PostMessageW(Some(hwnd), WM_CLOSE, WPARAM(0), LPARAM(0)) }.unwrap();
+        let start = Instant::now();
+        let closed = window.wait_for_close(Duration::from_secs(5));
+        let waited = start.elapsed();
+
+        assert!(!still_open.unwrap());
+        assert!(closed.unwrap());
+        assert!(waited < Duration::from_secs(2), "close took {:?}", waited);
+        assert!(window.wait_for_close(Duration::ZERO).unwrap());
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_invoke_menu_path_reports_the_failing_step() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let menu_bar = window.get_menu_bar();
+        // "Edit" opens, then the second step has nothing to find
+        let result = window.invoke_menu_path(&["Edit", "No Such Item"]);
+
+        assert!(menu_bar.unwrap().is_some());
+        match result {
+            Err(UiError::ElementNotFound(message)) => assert!(message.contains("step 2 of 2"), "{}", message),
+            other => panic!("Expected ElementNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_get_child_windows_finds_the_save_as_dialog() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let before = window.get_child_windows().map(|windows| windows.len());
+        window.send_keys("{CTRL}{SHIFT}s").expect("Failed to open Save As");
+        thread::sleep(Duration::from_millis(1500));
+        let titles: Result<Vec<String>, UiError> = window.get_child_windows()
+            .and_then(|windows| windows.iter().map(|dialog| dialog.get_title()).collect());
+
+        assert_eq!(before.unwrap(), 0);
+        let titles = titles.unwrap();
+        assert!(titles.iter().any(|title| title == "Save As" || title == "Save as"), "Found {:?}", titles);
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_by_property_enabled_is_not_restricted_to_custom_controls() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let enabled = window.find_elements(&UIQuery::ByProperty("enabled".to_string(), "true".to_string()));
+
+        let types: Vec<String> = enabled.unwrap().iter().map(|element| element.get_type().unwrap()).collect();
+        assert!(types.iter().any(|t| t == "Button"), "Found {:?}", types);
+        assert!(types.iter().any(|t| t == "Edit" || t == "Document"), "Found {:?}", types);
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_relational_queries_are_not_restricted_to_custom_controls() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let button = || Box::new(UIQuery::ByType("Button".to_string()));
+        let non_buttons = window.find_elements(&UIQuery::Not(button()));
+        let buttons = window.find_elements(&UIQuery::Descendant(button()));
+        let children = window.find_elements(&UIQuery::Child(Box::new(UIQuery::Not(button()))));
+        let ancestors = window.find_elements(&UIQuery::Ancestor(Box::new(UIQuery::ByType("Pane".to_string()))));
+
+        let types = |elements: Vec<Box<dyn UIElement>>| -> Vec<String> {
+            elements.iter().map(|element| element.get_type().unwrap()).collect()
+        };
+        let non_buttons = types(non_buttons.unwrap());
+        assert!(non_buttons.iter().any(|t| t != "Custom"), "Found {:?}", non_buttons);
+        assert!(non_buttons.iter().all(|t| t != "Button"), "Found {:?}", non_buttons);
+        let buttons = types(buttons.unwrap());
+        assert!(!buttons.is_empty() && buttons.iter().all(|t| t == "Button"), "Found {:?}", buttons);
+        assert!(!children.unwrap().is_empty());
+        // Notepad's parent is the desktop, which UI Automation reports as a Pane
+        assert_eq!(types(ancestors.unwrap()), vec!["Pane".to_string()]);
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_find_many_finds_the_same_elements_as_find_elements() {
+        use crate::core::same_element;
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let mut queries = HashMap::new();
+        queries.insert("menu_items".to_string(), UIQuery::ByType("MenuItem".to_string()));
+        queries.insert("enabled".to_string(), UIQuery::ByProperty("enabled".to_string(), "true".to_string()));
+        queries.insert("second_button".to_string(), UIQuery::Nth(Box::new(UIQuery::ByType("Button".to_string())), 1));
+        let start = Instant::now();
+        let found = window.find_many(&queries);
+        let one_pass_time = start.elapsed();
+        let start = Instant::now();
+        let separate: Result<Vec<(String, Vec<Box<dyn UIElement>>)>, UiError> = queries.iter()
+            .map(|(label, query)| window.find_elements(query).map(|elements| (label.clone(), elements)))
+            .collect();
+        let separate_time = start.elapsed();
+
+        println!("find_many in {:?}, per-query find_elements in {:?}", one_pass_time, separate_time);
+        let found = found.unwrap();
+        for (label, expected) in separate.unwrap() {
+            let actual = &found[&label];
+            assert!(!expected.is_empty(), "'{}' found nothing", label);
+            assert_eq!(actual.len(), expected.len(), "'{}'", label);
+            for element in &expected {
+                assert!(actual.iter().any(|other| same_element(element.as_ref(), other.as_ref()).unwrap_or(false)), "'{}'", label);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_by_types_finds_the_same_elements_as_or() {
+        use crate::core::same_element;
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+
+        let types = ["Edit", "Document", "MenuItem"];
+        let by_types = window.find_elements(&UIQuery::ByTypes(types.iter().map(|t| t.to_string()).collect()));
+        let or = window.find_elements(&UIQuery::Or(types.iter().map(|t| UIQuery::ByType(t.to_string())).collect()));
+
+        let (by_types, or) = (by_types.unwrap(), or.unwrap());
+        assert!(!by_types.is_empty());
+        assert_eq!(by_types.len(), or.len());
+        for element in &by_types {
+            assert!(or.iter().any(|other| same_element(element.as_ref(), other.as_ref()).unwrap_or(false)));
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_patterns_only_mode_never_synthesizes_input() {
+        use crate::core::UIAutomation;
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let launched = &notepad.window;
+
+        let automation = WindowsUIAutomation::new().unwrap();
+        automation.set_interaction_mode(InteractionMode::PatternsOnly).unwrap();
+        launched.activate().unwrap();
+        let window = UIAutomation::get_active_window(&automation);
+
+        let keys = window.as_ref().map(|window| window.send_keys("abc"));
+        let hover = window.as_ref().ok()
+            .and_then(|window| window.find_elements(&UIQuery::ByType("MenuItem".to_string())).ok())
+            .and_then(|items| items.into_iter().next())
+            .map(|item| item.hover_for(Duration::ZERO, true));
+
+        assert!(matches!(keys, Ok(Err(UiError::Unsupported(_)))), "send_keys should be refused: {:?}", keys.err());
+        assert!(matches!(hover, Some(Err(UiError::Unsupported(_)))), "hover should be refused");
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_point_lookup_finds_notepad_and_rejects_offscreen_points() {
+        use crate::core::UIAutomation;
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+        window.activate().unwrap();
+        let (x, y) = {
+            let _dpi = DpiAwarenessGuard::per_monitor();
+            let rect = window.get_rect().unwrap();
+            ((rect.left + rect.right) / 2, (rect.top + rect.bottom) / 2)
+        };
+
+        let automation = WindowsUIAutomation::new().unwrap();
+        let found = automation.window_from_point(x, y).and_then(|found| found.get_process_id());
+        let element = automation.element_from_point(x, y).and_then(|element| element.get_bounds());
+        let outside = automation.window_from_point(-100_000, -100_000).map(|_| ());
+
+        assert_eq!(found.unwrap(), notepad.process_id);
+        let bounds = element.unwrap().expect("the element under the point has bounds");
+        assert!(bounds.left <= x && x <= bounds.right && bounds.top <= y && y <= bounds.bottom);
+        assert!(matches!(outside, Err(UiError::WindowNotFound(_))));
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_set_rect_moves_notepad_window() {
+        use crate::core::ApplicationManager;
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+        window.restore().unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        let target = Rect::new(120, 80, 820, 580);
+        let moved = window.set_rect(target.clone());
+        thread::sleep(Duration::from_millis(300));
+
+        // A fresh window reads the live rect instead of the cached snapshot
+        let rect = {
+            let _dpi = DpiAwarenessGuard::per_monitor();
+            notepad.app_manager.get_window_by_process_id(notepad.process_id).unwrap().get_rect()
+        };
+
+        moved.expect("Failed to reposition Notepad");
+        let rect = rect.expect("Failed to read the Notepad rect");
+        for (actual, expected) in [(rect.left, target.left), (rect.top, target.top), (rect.right, target.right), (rect.bottom, target.bottom)] {
+            assert!((actual - expected).abs() <= 2, "Expected {:?}, got {:?}", target, rect);
+        }
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_set_rect_refuses_minimized_window() {
+        use super::super::app_manager::TestNotepad;
+
+        let notepad = TestNotepad::launch();
+        let window = &notepad.window;
+        window.minimize().unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        let result = window.move_to(0, 0);
+        assert!(matches!(result, Err(UiError::InvalidArgument(_))));
+    }
+}
@@ -4,33 +4,161 @@
 // windows, and applications using UI Automation.
 
 use pyo3::prelude::*;
-use std::sync::{Arc, Mutex};
+use pyo3::types::PyBytes;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::collections::HashMap;
+use std::time::Duration;
 use log::{debug, warn};
-use ::ui_interaction::core::{UIAutomation, Window, UIElement, UITree, UITreeNode, UIQuery, ApplicationManager, ApplicationInfo, AppendPosition};
+use ::ui_interaction::core::{UIAutomation, Window, UIElement, CachedElement, UITree, UITreeNode, UIQuery, NameMatch, ApplicationManager, ApplicationInfo, AppendPosition, ScrollDirection, Rect, UITreeOptions, SelectedTextInfo, TextVerification, RangeValue, ExpandState, Orientation, TextInputOptions, CorrectionMode, ReadinessOptions, CancellationToken, InteractionMode, TextExtractionOptions, same_element, same_window, text_elements_to_json as core_text_elements_to_json, with_retry, logical_to_physical, physical_to_logical, DEFAULT_RETRY_DELAY, DEFAULT_ROW_TOLERANCE};
 use ::ui_interaction::factory::{UIAutomationFactory, ApplicationManagerFactory};
+use ::ui_interaction::control_type;
+use ::ui_interaction::keyboard::{Key, KeyChord, Modifiers};
+use ::ui_interaction::error::UiError;
 
 // =============================================================================
 // THREAD SAFETY UTILITIES
 // =============================================================================
 
-// Thread-safe wrapper for our non-thread-safe types
-pub struct ThreadSafe<T>(pub(crate) Mutex<T>);
+/// Lets Python objects holding platform handles be shared between Python threads
+/// 
+/// # Threading model
+/// 
+/// The Windows backend creates its UI Automation objects in the multithreaded
+/// COM apartment (MTA). Such objects may be called from any thread that is
+/// also in the MTA, but not from a thread in a single-threaded apartment (STA)
+/// without marshaling. Python threads start outside any apartment, and some
+/// libraries (pythoncom, tkinter dialogs) put them in an STA. So every access
+/// goes through [`ThreadSafe::lock`], which first joins the calling thread to
+/// the MTA and refuses threads already in an STA, and the mutex serializes
+/// calls made through the same handle.
+/// 
+/// Only the types marked [`ComShared`] can be wrapped; nothing else gains
+/// `Send` or `Sync` through this type.
+pub struct ThreadSafe<T>(Mutex<T>);
 
 impl<T> ThreadSafe<T> {
     pub fn new(value: T) -> Self {
         Self(Mutex::new(value))
     }
+
+    /// Lock the value for a call from the current thread
+    /// 
+    /// Fails with UiAutomationError when the thread is in a single-threaded COM apartment.
+    pub fn lock(&self) -> PyResult<MutexGuard<'_, T>> {
+        join_multithreaded_apartment()?;
+        // A panic during an earlier call leaves no half-updated state behind:
+        // the value is only a handle to objects owned by the platform
+        Ok(self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// Types that may move between threads that are in the multithreaded COM apartment
+/// 
+/// # Safety
+/// 
+/// Implementors must only hold platform handles that are valid on any MTA
+/// thread (on Windows, UI Automation objects created in the MTA), and must only
+/// be reached through [`ThreadSafe::lock`] so that callers are in the MTA.
+pub unsafe trait ComShared {}
+
+// SAFETY: every backend creates these objects in the MTA (the Windows one
+// through `uiautomation::UIAutomation::new`), and the Linux and macOS handles
+// are plain D-Bus and Core Foundation references with no apartment affinity
+unsafe impl ComShared for Box<dyn UIAutomation> {}
+unsafe impl ComShared for Box<dyn Window> {}
+unsafe impl ComShared for Box<dyn UIElement> {}
+unsafe impl ComShared for Box<dyn ApplicationManager> {}
+
+// SAFETY: see `ComShared`; `lock` puts the calling thread in the MTA before
+// the value is reached, and the mutex prevents concurrent calls on one handle
+unsafe impl<T: ComShared> Send for ThreadSafe<T> {}
+unsafe impl<T: ComShared> Sync for ThreadSafe<T> {}
+
+/// Join the current thread to the multithreaded COM apartment, once per thread
+/// 
+/// The thread stays in the MTA until it exits, like the threads the
+/// `uiautomation` crate initializes itself.
+#[cfg(target_os = "windows")]
+fn join_multithreaded_apartment() -> PyResult<()> {
+    use std::cell::Cell;
+    use windows::Win32::Foundation::RPC_E_CHANGED_MODE;
+    use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+
+    thread_local! {
+        static IN_MTA: Cell<bool> = const { Cell::new(false) };
+    }
+    if IN_MTA.with(Cell::get) {
+        return Ok(());
+    }
+
+    let result = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+    if result == RPC_E_CHANGED_MODE {
+        return Err(UiAutomationError::new_err(
+            "This thread is in a single-threaded COM apartment (e.g. after pythoncom.CoInitialize()); \
+             use uia_interaction from a thread that has not initialized COM, or one initialized with COINIT_MULTITHREADED"
+        ));
+    }
+    result.ok()
+        .map_err(|e| UiAutomationError::new_err(format!("Failed to initialize COM on this thread: {}", e)))?;
+    IN_MTA.with(|in_mta| in_mta.set(true));
+    Ok(())
+}
+
+/// Only COM has apartments
+#[cfg(not(target_os = "windows"))]
+fn join_multithreaded_apartment() -> PyResult<()> {
+    Ok(())
 }
 
-// Make ThreadSafe Send + Sync
-unsafe impl<T> Send for ThreadSafe<T> {}
-unsafe impl<T> Sync for ThreadSafe<T> {}
+// =============================================================================
+// EXCEPTIONS
+// =============================================================================
+
+// Every exception derives from UiAutomationError, which itself derives from
+// RuntimeError so existing `except RuntimeError` handlers keep working.
+pyo3::create_exception!(uia_interaction, UiAutomationError, pyo3::exceptions::PyRuntimeError, "Base class for UI automation failures");
+pyo3::create_exception!(uia_interaction, ElementNotFoundError, UiAutomationError, "No element matched, or the element no longer exists");
+pyo3::create_exception!(uia_interaction, WindowNotFoundError, UiAutomationError, "No window matched, or the window has been closed");
+pyo3::create_exception!(uia_interaction, PatternUnsupportedError, UiAutomationError, "The element does not support the required control pattern");
+pyo3::create_exception!(uia_interaction, AccessDeniedError, UiAutomationError, "The operating system denied access to the element or process");
+pyo3::create_exception!(uia_interaction, UiTimeoutError, UiAutomationError, "The operation did not complete in time");
+pyo3::create_exception!(uia_interaction, UnsupportedOperationError, UiAutomationError, "The operation is not available on this platform");
+pyo3::create_exception!(uia_interaction, CancelledError, UiAutomationError, "The operation was stopped through a PyCancellationToken");
+
+/// Convert a `UiError` into the matching Python exception
+///
+/// Invalid arguments raise the built-in `ValueError`; every other variant
+/// raises a subclass of `UiAutomationError`.
+fn to_py_err(error: UiError) -> PyErr {
+    let message = error.to_string();
+    match error {
+        UiError::ElementNotFound(_) => ElementNotFoundError::new_err(message),
+        UiError::WindowNotFound(_) => WindowNotFoundError::new_err(message),
+        UiError::PatternUnsupported(_) => PatternUnsupportedError::new_err(message),
+        UiError::AccessDenied(_) => AccessDeniedError::new_err(message),
+        UiError::Timeout(_) => UiTimeoutError::new_err(message),
+        UiError::InvalidArgument(_) => pyo3::exceptions::PyValueError::new_err(message),
+        UiError::Unsupported(_) => UnsupportedOperationError::new_err(message),
+        UiError::Cancelled(_) => CancelledError::new_err(message),
+        UiError::PlatformError(_) => UiAutomationError::new_err(message),
+    }
+}
 
 // =============================================================================
 // UI AUTOMATION MAIN CLASS
 // =============================================================================
 
+/// Parse the Python spelling of an interaction mode
+fn interaction_mode_from_name(mode: &str) -> PyResult<InteractionMode> {
+    match mode {
+        "physical" => Ok(InteractionMode::Physical),
+        "patterns" => Ok(InteractionMode::PatternsOnly),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Unknown interaction mode '{}', expected 'physical' or 'patterns'", other
+        ))),
+    }
+}
+
 /// Main UI Automation interface for Python
 /// 
 /// This class provides the primary entry point for UI automation tasks.
@@ -54,20 +182,88 @@ pub struct PyAutomation {
 impl PyAutomation {
     /// Create a new UI Automation instance
     /// 
+    /// Args:
+    ///     mode (str, optional): "physical" (default) lets clicks and text input
+    ///         fall back to synthesized mouse and keyboard input; "patterns" only
+    ///         uses automation patterns and never moves the mouse, for CI, RDP
+    ///         and locked-screen sessions
+    ///     timeout_ms (int, optional): Fail calls into an unresponsive application
+    ///         with UiTimeoutError after this many milliseconds instead of blocking
+    /// 
     /// Returns:
     ///     PyAutomation: A new automation instance
     /// 
     /// Raises:
+    ///     ValueError: If `mode` is not a known mode
+    ///     UnsupportedOperationError: If the platform cannot honour `mode` or `timeout_ms`
     ///     RuntimeError: If the automation system cannot be initialized
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// automation = PyAutomation(mode="patterns")
+    /// button.click()  # Raises PatternUnsupportedError instead of moving the mouse
+    /// ```
     #[new]
-    pub fn new() -> PyResult<Self> {
-        let factory = UIAutomationFactory::new()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    #[pyo3(signature = (mode=None, timeout_ms=None))]
+    pub fn new(mode: Option<&str>, timeout_ms: Option<u64>) -> PyResult<Self> {
+        let mode = mode.map(interaction_mode_from_name).transpose()?.unwrap_or_default();
+        let factory = UIAutomationFactory::new_with_mode(mode)
+            .map_err(to_py_err)?;
+        if let Some(timeout_ms) = timeout_ms {
+            factory.set_call_timeout(Some(Duration::from_millis(timeout_ms)))
+                .map_err(to_py_err)?;
+        }
         Ok(Self { 
             inner: Arc::new(ThreadSafe::new(factory))
         })
     }
 
+    /// The interaction mode, "physical" or "patterns"
+    #[getter]
+    fn mode(&self) -> PyResult<&'static str> {
+        let inner = self.inner.lock()?;
+        Ok(match inner.interaction_mode() {
+            InteractionMode::Physical => "physical",
+            InteractionMode::PatternsOnly => "patterns",
+        })
+    }
+
+    /// Change the interaction mode of elements retrieved from now on
+    /// 
+    /// Args:
+    ///     mode (str): "physical" or "patterns"
+    /// 
+    /// Raises:
+    ///     ValueError: If `mode` is not a known mode
+    ///     UnsupportedOperationError: If the platform cannot honour `mode`
+    fn set_mode(&self, mode: &str) -> PyResult<()> {
+        let mode = interaction_mode_from_name(mode)?;
+        let inner = self.inner.lock()?;
+        inner.set_interaction_mode(mode)
+            .map_err(to_py_err)
+    }
+
+    /// The call timeout in milliseconds, or None if calls may block indefinitely
+    #[getter]
+    fn timeout_ms(&self) -> PyResult<Option<u64>> {
+        let inner = self.inner.lock()?;
+        Ok(inner.call_timeout().map(|timeout| timeout.as_millis() as u64))
+    }
+
+    /// Change the call timeout; None restores the platform defaults
+    /// 
+    /// Applies to windows and elements already retrieved from this automation.
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If the platform cannot bound its calls
+    #[setter]
+    fn set_timeout_ms(&self, timeout_ms: Option<u64>) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.set_call_timeout(timeout_ms.map(Duration::from_millis))
+            .map_err(to_py_err)
+    }
+
     /// Get the currently active (foreground) window
     /// 
     /// This returns the top-level application window that currently has focus.
@@ -87,12 +283,12 @@ impl PyAutomation {
     /// ```
     fn active_window(&self) -> PyResult<Py<PyWindow>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let window = inner.get_active_window()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            Ok(Py::new(py, PyWindow { 
+                .map_err(to_py_err)?;
+            Py::new(py, PyWindow { 
                 inner: Arc::new(ThreadSafe::new(window))
-            })?)
+            })
         })
     }
 
@@ -107,12 +303,12 @@ impl PyAutomation {
     ///     RuntimeError: If no window with focus can be found
     fn window_containing_focus(&self) -> PyResult<Py<PyWindow>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let window = inner.get_window_containing_focus()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            Ok(Py::new(py, PyWindow { 
+                .map_err(to_py_err)?;
+            Py::new(py, PyWindow { 
                 inner: Arc::new(ThreadSafe::new(window))
-            })?)
+            })
         })
     }
 
@@ -135,27 +331,87 @@ impl PyAutomation {
     /// ```
     fn focused_element(&self) -> PyResult<Py<PyUIElement>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let element = inner.get_focused_element()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            Ok(Py::new(py, PyUIElement { 
+                .map_err(to_py_err)?;
+            Py::new(py, PyUIElement { 
                 inner: Arc::new(ThreadSafe::new(element))
-            })?)
+            })
         })
     }
 
+    /// Get the element under a point on the screen
+    /// 
+    /// Args:
+    ///     x (int): Horizontal screen coordinate in physical pixels
+    ///     y (int): Vertical screen coordinate in physical pixels
+    /// 
+    /// Coordinates use the same physical pixels as PyUIElement.bounds. A
+    /// cursor position read by a DPI-unaware program must be multiplied by the
+    /// monitor's scale factor first.
+    /// 
+    /// Returns:
+    ///     PyUIElement: The deepest element at the point
+    /// 
+    /// Raises:
+    ///     ElementNotFoundError: If the point is not over any window
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// element = automation.element_from_point(640, 360)
+    /// print(f"Under the point: {element.name} ({element.control_type})")
+    /// ```
+    fn element_from_point(&self, x: i32, y: i32) -> PyResult<Py<PyUIElement>> {
+        let element = {
+            let inner = self.inner.lock()?;
+            inner.element_from_point(x, y).map_err(to_py_err)?
+        };
+        Python::with_gil(|py| Py::new(py, PyUIElement {
+            inner: Arc::new(ThreadSafe::new(element))
+        }))
+    }
+
+    /// Get the top-level window under a point on the screen
+    /// 
+    /// Args:
+    ///     x (int): Horizontal screen coordinate in physical pixels
+    ///     y (int): Vertical screen coordinate in physical pixels
+    /// 
+    /// Returns:
+    ///     PyWindow: The top-level window containing the point
+    /// 
+    /// Raises:
+    ///     WindowNotFoundError: If the point is over the desktop or outside every screen
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window = automation.window_from_point(640, 360)
+    /// print(f"Window under the point: {window.title}")
+    /// ```
+    fn window_from_point(&self, x: i32, y: i32) -> PyResult<Py<PyWindow>> {
+        let window = {
+            let inner = self.inner.lock()?;
+            inner.window_from_point(x, y).map_err(to_py_err)?
+        };
+        Python::with_gil(|py| Py::new(py, PyWindow {
+            inner: Arc::new(ThreadSafe::new(window))
+        }))
+    }
+
     /// DEPRECATED: Use active_window() instead
     /// 
     /// This method is deprecated and will be removed in a future version.
     /// Use active_window() for the same functionality.
     fn focused_window(&self) -> PyResult<Py<PyWindow>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let window = inner.get_active_window()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            Ok(Py::new(py, PyWindow { 
+                .map_err(to_py_err)?;
+            Py::new(py, PyWindow { 
                 inner: Arc::new(ThreadSafe::new(window))
-            })?)
+            })
         })
     }
 }
@@ -202,7 +458,7 @@ impl PyUIElement {
     #[getter]
     fn name(&self) -> PyResult<String> {
         debug!("Getting name for UI element");
-        let inner = self.inner.0.lock().unwrap();
+        let inner = self.inner.lock()?;
         match inner.get_name() {
             Ok(name) => {
                 debug!("Successfully got name: {}", name);
@@ -230,7 +486,7 @@ impl PyUIElement {
     #[getter]
     fn control_type(&self) -> PyResult<String> {
         debug!("Getting control type for UI element");
-        let inner = self.inner.0.lock().unwrap();
+        let inner = self.inner.lock()?;
         match inner.get_type() {
             Ok(typ) => {
                 debug!("Successfully got control type: {}", typ);
@@ -243,6 +499,29 @@ impl PyUIElement {
         }
     }
 
+    /// Get the control type as shown to users, in the system's UI language
+    /// 
+    /// Use it for display only; `control_type` stays the English name queries match on.
+    /// 
+    /// Returns:
+    ///     str: The localized control type (e.g., "button", or "bouton" on a French system)
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If the platform does not report localized control types
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// element = automation.focused_element()
+    /// print(f"{element.name} ({element.localized_control_type})")
+    /// ```
+    #[getter]
+    fn localized_control_type(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_localized_control_type()
+            .map_err(to_py_err)
+    }
+
     /// Check if the element is enabled for interaction
     /// 
     /// Returns:
@@ -260,7 +539,7 @@ impl PyUIElement {
     #[getter]
     fn is_enabled(&self) -> PyResult<bool> {
         debug!("Getting enabled state for UI element");
-        let inner = self.inner.0.lock().unwrap();
+        let inner = self.inner.lock()?;
         match inner.is_enabled() {
             Ok(val) => {
                 debug!("Successfully got enabled state: {}", val);
@@ -273,10 +552,170 @@ impl PyUIElement {
         }
     }
 
+    /// Check if the element accepts text input
+    /// 
+    /// Read-only fields report False even when they look like text boxes.
+    /// 
+    /// Returns:
+    ///     bool: True if text can be typed or set into the element
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element could not be inspected
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// fields = [e for e in window.find_elements(PyUIQuery.by_type("Edit")) if e.is_editable]
+    /// ```
+    #[getter]
+    fn is_editable(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_editable()
+            .map_err(to_py_err)
+    }
+
+    /// Check if the element can receive keyboard focus
+    /// 
+    /// Returns:
+    ///     bool: True if the element can take keyboard focus
+    /// 
+    /// Raises:
+    ///     RuntimeError: If focusability is not reported on this platform
+    #[getter]
+    fn is_focusable(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_focusable()
+            .map_err(to_py_err)
+    }
+
+    /// Get the direction a scroll bar, slider, toolbar or tab list is laid out in
+    /// 
+    /// Returns:
+    ///     str: "horizontal", "vertical" or "none"
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If orientation is not reported on this platform
+    #[getter]
+    fn orientation(&self) -> PyResult<&'static str> {
+        let inner = self.inner.lock()?;
+        Ok(match inner.get_orientation().map_err(to_py_err)? {
+            Orientation::None => "none",
+            Orientation::Horizontal => "horizontal",
+            Orientation::Vertical => "vertical",
+        })
+    }
+
+    /// Check if the element belongs to the content view of the accessibility tree
+    /// 
+    /// Content elements carry information the user reads; scroll bars,
+    /// toolbars and layout panes do not, so filtering on this cuts the noise
+    /// when extracting text.
+    /// 
+    /// Returns:
+    ///     bool: True for content elements
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If the content view is not reported on this platform
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// texts = [e for e in window.find_elements(PyUIQuery.by_type("Text")) if e.is_content_element]
+    /// ```
+    #[getter]
+    fn is_content_element(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_content_element()
+            .map_err(to_py_err)
+    }
+
+    /// Check if the element belongs to the control view of the accessibility tree
+    /// 
+    /// Returns:
+    ///     bool: False only for elements that exist purely for layout
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If the control view is not reported on this platform
+    #[getter]
+    fn is_control_element(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_control_element()
+            .map_err(to_py_err)
+    }
+
+    /// Get the access key that activates the element, such as "Alt+F"
+    /// 
+    /// Returns:
+    ///     str | None: The access key, or None if the element has none
+    #[getter]
+    fn access_key(&self) -> PyResult<Option<String>> {
+        let inner = self.inner.lock()?;
+        inner.get_access_key()
+            .map_err(to_py_err)
+    }
+
+    /// Get the keyboard shortcut that invokes the element's command, such as "Ctrl+S"
+    /// 
+    /// Returns:
+    ///     str | None: The accelerator key, or None if the element has none
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// save = window.find_first(PyUIQuery.by_name("Save"))
+    /// if save is not None and save.accelerator_key == "Ctrl+S":
+    ///     window.send_keys("^s")
+    /// ```
+    #[getter]
+    fn accelerator_key(&self) -> PyResult<Option<String>> {
+        let inner = self.inner.lock()?;
+        inner.get_accelerator_key()
+            .map_err(to_py_err)
+    }
+
+    /// Get the screen rectangle of the element
+    /// 
+    /// Returns:
+    ///     PyRect | None: The element's screen bounds, or None if not available
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// button = window.find_elements(PyUIQuery.by_name("OK"))[0]
+    /// if button.bounds is not None:
+    ///     x, y = button.bounds.center()
+    ///     print(f"OK button center: ({x}, {y})")
+    /// ```
+    #[getter]
+    fn bounds(&self) -> PyResult<Option<Py<PyRect>>> {
+        debug!("Getting bounds for UI element");
+        let inner = self.inner.lock()?;
+        match inner.get_bounds() {
+            Ok(Some(rect)) => {
+                debug!("Successfully got bounds: {:?}", rect);
+                Python::with_gil(|py| Ok(Some(Py::new(py, PyRect { inner: rect })?)))
+            },
+            Ok(None) => Ok(None),
+            Err(e) => {
+                warn!("Failed to get bounds: {}", e);
+                Ok(None)
+            }
+        }
+    }
+
     /// Get all properties of the element
     /// 
+    /// Without `keys` only a few essential properties are returned, which is
+    /// fast. Pass `keys` to read exactly those properties instead, including
+    /// heavier ones such as "help_text", "accelerator_key", "is_password",
+    /// "orientation" or "localized_control_type"; unknown keys are skipped
+    /// with a warning.
+    /// 
+    /// Args:
+    ///     keys (list[str], optional): The property names to read
+    /// 
     /// Returns:
-    ///     dict: Dictionary containing all element properties
+    ///     dict: Dictionary containing the element properties
     /// 
     /// # Examples
     /// 
@@ -284,11 +723,18 @@ impl PyUIElement {
     /// element = window.find_elements(PyUIQuery.by_name("Login"))[0]
     /// props = element.get_properties()
     /// print(f"Properties: {props}")
+    /// 
+    /// extra = element.get_properties(keys=["help_text", "is_password"])
     /// ```
-    fn get_properties(&self) -> PyResult<HashMap<String, String>> {
+    #[pyo3(signature = (keys=None))]
+    fn get_properties(&self, keys: Option<Vec<String>>) -> PyResult<HashMap<String, String>> {
         debug!("Getting properties for UI element");
-        let inner = self.inner.0.lock().unwrap();
-        match inner.get_properties() {
+        let inner = self.inner.lock()?;
+        let result = match &keys {
+            Some(keys) => inner.get_properties_filtered(&keys.iter().map(String::as_str).collect::<Vec<_>>()),
+            None => inner.get_properties(),
+        };
+        match result {
             Ok(props) => {
                 debug!("Successfully got {} properties", props.len());
                 Ok(props)
@@ -302,6 +748,10 @@ impl PyUIElement {
 
     /// Get child elements of this element
     /// 
+    /// Args:
+    ///     retries (int): Extra attempts after transient failures, see
+    ///         PyWindow.find_elements
+    /// 
     /// Returns:
     ///     list[PyUIElement]: List of child elements
     /// 
@@ -313,11 +763,12 @@ impl PyUIElement {
     /// for item in menu_items:
     ///     print(f"Menu item: {item.name}")
     /// ```
-    fn get_children(&self) -> PyResult<Vec<Py<PyUIElement>>> {
+    #[pyo3(signature = (retries=0))]
+    fn get_children(&self, retries: u32) -> PyResult<Vec<Py<PyUIElement>>> {
         debug!("Getting children for UI element");
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
-            match inner.get_children() {
+            let inner = self.inner.lock()?;
+            match with_retry(retries.saturating_add(1), DEFAULT_RETRY_DELAY, || inner.get_children()) {
                 Ok(children) => {
                     debug!("Successfully got {} children", children.len());
             Ok(children.into_iter()
@@ -328,234 +779,2085 @@ impl PyUIElement {
                 },
                 Err(e) => {
                     warn!("Failed to get children: {}", e);
-                    Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+                    Err(to_py_err(e))
                 }
             }
         })
     }
 
-    /// Set the text content of the element
+    /// Capture this element and its descendants in one go
     /// 
-    /// This method completely replaces the current text content.
+    /// Much faster than walking `get_children` recursively: on Windows the
+    /// properties of the whole subtree are fetched in a few calls instead of
+    /// one per node.
     /// 
     /// Args:
-    ///     text (str): The text to set
+    ///     max_depth (int): Levels below this element to capture; 0 captures
+    ///         only the element itself
+    /// 
+    /// Returns:
+    ///     PyUITreeNode: This element, with its descendants as children
     /// 
     /// Raises:
-    ///     RuntimeError: If the text cannot be set
+    ///     RuntimeError: If the element cannot be read
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
-    /// text_field.set_text("Hello, World!")
+    /// tree = list_view.get_subtree(2)
+    /// for row in tree.children:
+    ///     print(row.name, [cell.name for cell in row.children])
     /// ```
-    fn set_text(&self, text: &str) -> PyResult<()> {
-        let inner = self.inner.0.lock().unwrap();
-        inner.set_text(text)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    fn get_subtree(&self, max_depth: u32) -> PyResult<Py<PyUITreeNode>> {
+        let node = {
+            let inner = self.inner.lock()?;
+            inner.get_subtree(max_depth).map_err(to_py_err)?
+        };
+        Python::with_gil(|py| Py::new(py, PyUITreeNode { inner: node }))
     }
 
-    /// Append text to the element's current content
-    /// 
-    /// Args:
-    ///     text (str): The text to append
-    ///     position (str): Where to append ("CurrentCursor", "EndOfLine", "EndOfText")
+    /// Get the parent of this element
     /// 
-    /// Raises:
-    ///     RuntimeError: If the text cannot be appended
+    /// Returns:
+    ///     PyUIElement | None: The parent element, or None for the desktop root
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
-    /// text_field.append_text(" - Additional text", "EndOfText")
+    /// # From a table cell up to its row
+    /// row = cell.get_parent()
     /// ```
-    fn append_text(&self, text: &str, position: &str) -> PyResult<()> {
-        let inner = self.inner.0.lock().unwrap();
-        let append_pos = match position {
-            "CurrentCursor" => AppendPosition::CurrentCursor,
-            "EndOfLine" => AppendPosition::EndOfLine,
-            "EndOfText" => AppendPosition::EndOfText,
-            _ => AppendPosition::EndOfText, // Default
+    fn get_parent(&self) -> PyResult<Option<Py<PyUIElement>>> {
+        let parent = {
+            let inner = self.inner.lock()?;
+            inner.get_parent().map_err(to_py_err)?
         };
-        inner.append_text(text, append_pos)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+        match parent {
+            Some(element) => Python::with_gil(|py| Ok(Some(Py::new(py, PyUIElement {
+                inner: Arc::new(ThreadSafe::new(element))
+            })?))),
+            None => Ok(None),
+        }
     }
 
-    /// Get the text content of the element
+    /// Snapshot this element's identifying properties for repeated reads
+    /// 
+    /// The returned element answers name, control_type, bounds, is_enabled
+    /// and get_properties() from values read now, without
+    /// querying the UI again; everything else still goes to the live element.
+    /// The snapshot goes stale if the UI changes: call refresh() to read it
+    /// again.
     /// 
     /// Returns:
-    ///     str: The current text content, empty string if none
+    ///     PyUIElement: A cached copy of this element
+    /// 
+    /// Raises:
+    ///     UiAutomationError: If the properties cannot be read
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
-    /// current_text = text_field.get_text()
-    /// print(f"Current text: {current_text}")
+    /// rows = [row.cached() for row in window.find_elements(PyUIQuery.by_type("DataItem"))]
+    /// rows.sort(key=lambda row: row.name)
     /// ```
-    fn get_text(&self) -> PyResult<String> {
-        debug!("Getting text from UI element");
-        let inner = self.inner.0.lock().unwrap();
-        match inner.get_text() {
-            Ok(text) => {
-                debug!("Successfully got text: {}", text);
-                Ok(text)
-            },
-            Err(e) => {
-                warn!("Failed to get text: {}", e);
-                Ok(String::new())
-            }
-        }
+    fn cached(&self) -> PyResult<Py<PyUIElement>> {
+        let cached = {
+            let inner = self.inner.lock()?;
+            inner.cached().map_err(to_py_err)?
+        };
+        Python::with_gil(|py| Py::new(py, PyUIElement {
+            inner: Arc::new(ThreadSafe::new(Box::new(cached) as Box<dyn UIElement>))
+        }))
     }
 
-    /// Click the element
+    /// Read the snapshot of an element returned by cached() again
     /// 
-    /// Performs a mouse click on the element.
+    /// Live elements have nothing to refresh, so this does nothing for them.
     /// 
     /// Raises:
-    ///     RuntimeError: If the element cannot be clicked
+    ///     UiAutomationError: If the properties cannot be read; the previous
+    ///         snapshot is kept
+    fn refresh(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        match inner.as_any().downcast_ref::<CachedElement>() {
+            Some(cached) => cached.refresh().map_err(to_py_err),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether this element serves its properties from a snapshot, see cached()
+    #[getter]
+    fn is_cached(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        Ok(inner.as_any().is::<CachedElement>())
+    }
+
+    /// Get the position of this element among its parent's children
+    /// 
+    /// Returns:
+    ///     int | None: Zero-based index among the siblings, or None without a parent
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// button = window.find_elements(PyUIQuery.by_name("OK"))[0]
-    /// button.click()
+    /// column = cell.index_in_parent()
     /// ```
-    fn click(&self) -> PyResult<()> {
-        debug!("Attempting to click UI element");
-        let inner = self.inner.0.lock().unwrap();
-        match inner.click() {
-            Ok(()) => {
-                debug!("Successfully clicked UI element");
-                Ok(())
-            },
+    fn index_in_parent(&self) -> PyResult<Option<usize>> {
+        let inner = self.inner.lock()?;
+        inner.index_in_parent()
+            .map_err(to_py_err)
+    }
+
+    /// Set the text content of the element
+    /// 
+    /// This method completely replaces the current text content.
+    /// 
+    /// Args:
+    ///     text (str): The text to set
+    ///     interval_ms (int | None): Delay between simulated keystrokes (default 30)
+    ///     verify (bool | None): Read the text back and raise on a mismatch (default False)
+    ///     correction (str | None): "word_by_word" to retype on a mismatch, or "none"
+    ///         to trust the first attempt (default "word_by_word")
+    ///     use_value_pattern (bool | None): Replace the value directly when the
+    ///         control allows it instead of typing (default True)
+    /// 
+    /// Raises:
+    ///     ValueError: If `correction` is not a known mode
+    ///     RuntimeError: If the text cannot be set, or verification found a mismatch
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
+    /// text_field.set_text("Hello, World!")
+    /// 
+    /// # Trusted input into a responsive control
+    /// text_field.set_text("fast path", interval_ms=5, correction="none")
+    /// ```
+    #[pyo3(signature = (text, interval_ms=None, verify=None, correction=None, use_value_pattern=None))]
+    fn set_text(
+        &self,
+        text: &str,
+        interval_ms: Option<u32>,
+        verify: Option<bool>,
+        correction: Option<&str>,
+        use_value_pattern: Option<bool>,
+    ) -> PyResult<()> {
+        let defaults = TextInputOptions::default();
+        let options = TextInputOptions {
+            interval_ms: interval_ms.unwrap_or(defaults.interval_ms),
+            verify: verify.unwrap_or(defaults.verify),
+            correction: match correction {
+                None => defaults.correction,
+                Some("word_by_word") => CorrectionMode::WordByWord,
+                Some("none") => CorrectionMode::None,
+                Some(other) => return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown correction mode '{}', expected 'word_by_word' or 'none'", other
+                ))),
+            },
+            use_value_pattern_if_available: use_value_pattern.unwrap_or(defaults.use_value_pattern_if_available),
+            secret: defaults.secret,
+        };
+
+        let inner = self.inner.lock()?;
+        inner.set_text_with_options(text, &options)
+            .map_err(to_py_err)
+    }
+
+    /// Type a password into the element without logging it
+    /// 
+    /// Like `set_text`, but the text is never written to the logs and never
+    /// read back to check or correct it, whether or not the control reports
+    /// itself as a password field.
+    /// 
+    /// Args:
+    ///     text (str): The password to set
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the text cannot be set
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// password = window.find_first(PyUIQuery.by_automation_id("PasswordBox"))
+    /// password.set_password(os.environ["APP_PASSWORD"])
+    /// ```
+    fn set_password(&self, text: &str) -> PyResult<()> {
+        let options = TextInputOptions { secret: true, ..TextInputOptions::default() };
+        let inner = self.inner.lock()?;
+        inner.set_text_with_options(text, &options)
+            .map_err(to_py_err)
+    }
+
+    /// Check whether the element is a password field
+    /// 
+    /// Returns:
+    ///     bool: True if the element holds protected content such as a password
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// if field.is_password():
+    ///     field.set_password(secret)
+    /// ```
+    fn is_password(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_password()
+            .map_err(to_py_err)
+    }
+
+    /// Remove all text from an editable element
+    /// 
+    /// Replaces the value directly when possible, which is much faster than
+    /// set_text(""). Otherwise it sends select-all and delete keystrokes and
+    /// checks that the field is empty afterwards.
+    /// 
+    /// Raises:
+    ///     ValueError: If the element is not editable
+    ///     RuntimeError: If the field could not be emptied
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// search_box.clear()
+    /// ```
+    fn clear(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.clear()
+            .map_err(to_py_err)
+    }
+
+    /// Select all the text in the element
+    /// 
+    /// Raises:
+    ///     PatternUnsupportedError: If the element's text cannot be selected
+    ///     RuntimeError: If the selection failed
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// editor.select_all()
+    /// selection = window.get_selected_text()
+    /// ```
+    fn select_all(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.select_all()
+            .map_err(to_py_err)
+    }
+
+    /// Get the names of the control patterns this element supports
+    /// 
+    /// Returns:
+    ///     list[str]: Pattern names such as "Value", "Text", "Toggle", "Invoke",
+    ///         "ExpandCollapse", "Scroll", "ScrollItem", "SelectionItem"
+    /// 
+    /// Raises:
+    ///     RuntimeError: If pattern availability cannot be read
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// if "Value" in element.supported_patterns():
+    ///     element.set_value("42")
+    /// ```
+    fn supported_patterns(&self) -> PyResult<Vec<String>> {
+        let inner = self.inner.lock()?;
+        inner.supported_patterns()
+            .map_err(to_py_err)
+    }
+
+    /// Get the value of the element through its Value pattern
+    /// 
+    /// Returns:
+    ///     str: The current value
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no Value pattern
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// search = window.find_elements(PyUIQuery.by_automation_id("SearchBox"))[0]
+    /// print(search.get_value())
+    /// ```
+    fn get_value(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_value()
+            .map_err(to_py_err)
+    }
+
+    /// Replace the value of the element directly, without simulating keystrokes
+    /// 
+    /// Args:
+    ///     value (str): The new value
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no Value pattern or its value is read-only
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// search = window.find_elements(PyUIQuery.by_automation_id("SearchBox"))[0]
+    /// search.set_value("quarterly report")
+    /// ```
+    fn set_value(&self, value: &str) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.set_value(value)
+            .map_err(to_py_err)
+    }
+
+    /// Append text to the element's current content
+    /// 
+    /// Args:
+    ///     text (str): The text to append
+    ///     position (str): Where to append ("CurrentCursor", "EndOfLine", "EndOfText")
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the text cannot be appended
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
+    /// text_field.append_text(" - Additional text", "EndOfText")
+    /// ```
+    fn append_text(&self, text: &str, position: &str) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        let append_pos = match position {
+            "CurrentCursor" => AppendPosition::CurrentCursor,
+            "EndOfLine" => AppendPosition::EndOfLine,
+            "EndOfText" => AppendPosition::EndOfText,
+            _ => AppendPosition::EndOfText, // Default
+        };
+        inner.append_text(text, append_pos)
+            .map_err(to_py_err)
+    }
+
+    /// Insert text at a character offset
+    /// 
+    /// Offsets past the end of the current text insert at the end.
+    /// 
+    /// Args:
+    ///     offset (int): Character offset to insert at (0 = start of text)
+    ///     text (str): The text to insert
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element is not editable or the caret cannot be placed
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
+    /// text_field.set_text("Hello world")
+    /// text_field.insert_text_at(5, ",")  # "Hello, world"
+    /// ```
+    fn insert_text_at(&self, offset: usize, text: &str) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.insert_text_at(offset, text)
+            .map_err(to_py_err)
+    }
+
+    /// Get the text content of the element
+    /// 
+    /// Returns:
+    ///     str: The current text content, empty string if none
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// text_field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
+    /// current_text = text_field.get_text()
+    /// print(f"Current text: {current_text}")
+    /// ```
+    fn get_text(&self) -> PyResult<String> {
+        debug!("Getting text from UI element");
+        let inner = self.inner.lock()?;
+        match inner.get_text() {
+            Ok(text) => {
+                debug!("Successfully got text: {}", text);
+                Ok(text)
+            },
+            Err(e) => {
+                warn!("Failed to get text: {}", e);
+                Ok(String::new())
+            }
+        }
+    }
+
+    /// Get at most `max_chars` characters of the element's text
+    /// 
+    /// Prefer this to `get_text` on large documents, which can hold megabytes.
+    /// 
+    /// Args:
+    ///     max_chars (int): Maximum number of characters to return
+    /// 
+    /// Returns:
+    ///     tuple[str, bool]: The text, and whether it was truncated
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// preview, truncated = editor.get_text_limited(200)
+    /// print(preview + ("..." if truncated else ""))
+    /// ```
+    fn get_text_limited(&self, max_chars: usize) -> PyResult<(String, bool)> {
+        let inner = self.inner.lock()?;
+        inner.get_text_limited(max_chars)
+            .map_err(to_py_err)
+    }
+
+    /// Compare the element's text with the text expected in it
+    /// 
+    /// Line endings are normalized to "\n" before comparing. Use it after
+    /// `set_text` to decide whether a mismatch is acceptable.
+    /// 
+    /// Args:
+    ///     expected (str): The text the element should contain
+    /// 
+    /// Returns:
+    ///     PyTextVerification: Whether the text matches, and where it differs
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the text cannot be read
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// field.set_text("Demain, réunion")
+    /// check = field.verify_text("Demain, réunion")
+    /// for index, expected, actual in check.mismatches:
+    ///     print(f"Character {index}: expected {expected!r}, got {actual!r}")
+    /// ```
+    fn verify_text(&self, expected: &str) -> PyResult<PyTextVerification> {
+        let inner = self.inner.lock()?;
+        inner.verify_text(expected)
+            .map(|verification| PyTextVerification { inner: verification })
+            .map_err(to_py_err)
+    }
+
+    /// Get the text content of the element by copying it through the clipboard
+    /// 
+    /// Use this for editors whose `get_text` is slow or empty (Electron,
+    /// Chromium). It focuses the element and replaces its selection with
+    /// select-all; the previous clipboard text is restored afterwards.
+    /// 
+    /// Returns:
+    ///     str: The copied text
+    /// 
+    /// Raises:
+    ///     UiTimeoutError: If the copy did not reach the clipboard in time
+    ///     UiAutomationError: If the element cannot be focused or the clipboard is unavailable
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// editor = window.find_first(PyUIQuery.by_type("Document"))
+    /// text = editor.get_text_clipboard()
+    /// ```
+    fn get_text_clipboard(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_text_via_clipboard()
+            .map_err(to_py_err)
+    }
+
+    /// Click the element
+    /// 
+    /// Performs a mouse click on the element.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element cannot be clicked
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// button = window.find_elements(PyUIQuery.by_name("OK"))[0]
+    /// button.click()
+    /// ```
+    fn click(&self) -> PyResult<()> {
+        debug!("Attempting to click UI element");
+        let inner = self.inner.lock()?;
+        match inner.click() {
+            Ok(()) => {
+                debug!("Successfully clicked UI element");
+                Ok(())
+            },
             Err(e) => {
                 warn!("Failed to click UI element: {}", e);
-                Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+                Err(to_py_err(e))
             }
         }
     }
-}
 
-// =============================================================================
-// WINDOW CLASS
-// =============================================================================
+    /// Activate the element without moving the mouse
+    /// 
+    /// Uses the element's accessibility action (the Invoke pattern on Windows)
+    /// instead of a synthesized click, so it works for occluded or offscreen
+    /// elements and on locked sessions where mouse input is dropped.
+    /// 
+    /// Raises:
+    ///     PatternUnsupportedError: If the element cannot be invoked
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.find_first(PyUIQuery.by_name("Save")).invoke()
+    /// ```
+    fn invoke(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.invoke()
+            .map_err(to_py_err)
+    }
 
-/// Represents a window in a desktop application
-/// 
-/// This class provides methods to interact with windows, find elements
-/// within them, and control window state.
-/// 
-/// # Examples
-/// 
-/// ```python
-/// # Get the active window and interact with it
-/// automation = PyAutomation()
-/// window = automation.active_window()
-/// print(f"Window title: {window.title}")
-/// 
-/// # Find elements in the window
-/// buttons = window.find_elements(PyUIQuery.by_type("Button"))
-/// if buttons:
-///     buttons[0].click()
-/// ```
-#[pyclass]
-pub struct PyWindow {
-    inner: Arc<ThreadSafe<Box<dyn Window>>>
-}
+    /// Right-click the element
+    /// 
+    /// Performs a mouse right-click on the element, typically opening its context menu.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no bounds or cannot be right-clicked
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// item = window.find_elements(PyUIQuery.by_name("report.txt"))[0]
+    /// item.right_click()
+    /// ```
+    fn right_click(&self) -> PyResult<()> {
+        debug!("Attempting to right-click UI element");
+        let inner = self.inner.lock()?;
+        match inner.right_click() {
+            Ok(()) => {
+                debug!("Successfully right-clicked UI element");
+                Ok(())
+            },
+            Err(e) => {
+                warn!("Failed to right-click UI element: {}", e);
+                Err(to_py_err(e))
+            }
+        }
+    }
+
+    /// Double-click the element
+    /// 
+    /// Performs a mouse double-click on the element, e.g. to open a file or list entry.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no bounds or cannot be double-clicked
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// item = window.find_elements(PyUIQuery.by_name("report.txt"))[0]
+    /// item.double_click()
+    /// ```
+    fn double_click(&self) -> PyResult<()> {
+        debug!("Attempting to double-click UI element");
+        let inner = self.inner.lock()?;
+        match inner.double_click() {
+            Ok(()) => {
+                debug!("Successfully double-clicked UI element");
+                Ok(())
+            },
+            Err(e) => {
+                warn!("Failed to double-click UI element: {}", e);
+                Err(to_py_err(e))
+            }
+        }
+    }
+
+    /// Give keyboard focus to the element without clicking it
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element cannot accept focus (e.g. a static text label)
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// field = window.find_elements(PyUIQuery.by_type("Edit"))[0]
+    /// field.set_focus()
+    /// ```
+    fn set_focus(&self) -> PyResult<()> {
+        debug!("Setting focus on UI element");
+        let inner = self.inner.lock()?;
+        inner.set_focus()
+            .map_err(to_py_err)
+    }
+
+    /// Get the checked state of a CheckBox, RadioButton or toggle button
+    /// 
+    /// Returns:
+    ///     bool | None: True if checked, False if unchecked, None if the element
+    ///         cannot be toggled
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element is in an indeterminate (tri-state) state
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// checkbox = window.find_elements(PyUIQuery.by_name("Word wrap"))[0]
+    /// print(checkbox.toggle_state)
+    /// ```
+    #[getter]
+    fn toggle_state(&self) -> PyResult<Option<bool>> {
+        debug!("Getting toggle state for UI element");
+        let inner = self.inner.lock()?;
+        inner.get_toggle_state()
+            .map_err(to_py_err)
+    }
+
+    /// Set the checked state of a CheckBox, RadioButton or toggle button
+    /// 
+    /// Does nothing if the element is already in the requested state.
+    /// 
+    /// Args:
+    ///     checked (bool): The desired checked state
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element cannot be toggled or is in an indeterminate state
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// checkbox = window.find_elements(PyUIQuery.by_name("Word wrap"))[0]
+    /// checkbox.set_toggle_state(True)
+    /// ```
+    fn set_toggle_state(&self, checked: bool) -> PyResult<()> {
+        debug!("Setting toggle state for UI element to {}", checked);
+        let inner = self.inner.lock()?;
+        inner.set_toggle_state(checked)
+            .map_err(to_py_err)
+    }
+
+    /// Get the options offered by a ComboBox or list control
+    /// 
+    /// Returns:
+    ///     list[str]: The names of the available options, in display order
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element does not offer selectable options
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// combo = window.find_elements(PyUIQuery.by_type("ComboBox"))[0]
+    /// print(combo.get_selection_options())
+    /// ```
+    fn get_selection_options(&self) -> PyResult<Vec<String>> {
+        debug!("Getting selection options for UI element");
+        let inner = self.inner.lock()?;
+        inner.get_selection_options()
+            .map_err(to_py_err)
+    }
+
+    /// Select an option by name in a ComboBox or list control
+    /// 
+    /// Editable combo boxes fall back to typing the value when no option matches.
+    /// 
+    /// Args:
+    ///     value (str): The exact name of the option to select
+    /// 
+    /// Raises:
+    ///     RuntimeError: If no option matches and the control does not accept free text
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// combo = window.find_elements(PyUIQuery.by_type("ComboBox"))[0]
+    /// combo.select_option("UTF-8")
+    /// ```
+    fn select_option(&self, value: &str) -> PyResult<()> {
+        debug!("Selecting option '{}' in UI element", value);
+        let inner = self.inner.lock()?;
+        inner.select_option(value)
+            .map_err(to_py_err)
+    }
+
+    /// Get the selected items of a list, tree or other selection container
+    /// 
+    /// Returns:
+    ///     list[PyUIElement]: The selected items, empty if nothing is selected
+    /// 
+    /// Raises:
+    ///     PatternUnsupportedError: If the element is not a selection container
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// files = window.find_first(PyUIQuery.by_type("List"))
+    /// print([item.name for item in files.get_selected_items()])
+    /// ```
+    fn get_selected_items(&self) -> PyResult<Vec<Py<PyUIElement>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            inner.get_selected_items()
+                .map_err(to_py_err)?
+                .into_iter()
+                .map(|item| Py::new(py, PyUIElement { inner: Arc::new(ThreadSafe::new(item)) }))
+                .collect()
+        })
+    }
+
+    /// Select this item of a list, tree or other selection container
+    /// 
+    /// In a container that allows a single selected item, add_to_selection=True
+    /// behaves like a plain select and replaces the selection.
+    /// 
+    /// Args:
+    ///     add_to_selection (bool): Keep the items that are already selected
+    /// 
+    /// Raises:
+    ///     PatternUnsupportedError: If the element is not a selectable item
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// items = files.get_children()
+    /// items[0].select_item()
+    /// items[2].select_item(add_to_selection=True)
+    /// ```
+    #[pyo3(signature = (add_to_selection=false))]
+    fn select_item(&self, add_to_selection: bool) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.select_item(add_to_selection)
+            .map_err(to_py_err)
+    }
+
+    /// Remove this item from its container's selection
+    /// 
+    /// Raises:
+    ///     PatternUnsupportedError: If the element is not a selectable item
+    ///     RuntimeError: If the container requires a selection and this is its
+    ///         only selected item
+    fn deselect_item(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.deselect_item()
+            .map_err(to_py_err)
+    }
+
+    /// Get whether a tree item, menu or combo box is showing its children
+    /// 
+    /// Returns:
+    ///     str | None: "collapsed", "expanded", "partially_expanded" or "leaf",
+    ///         or None if the element cannot be expanded
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the state could not be read
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// folder = window.find_first(PyUIQuery.by_name("Documents"))
+    /// if folder.get_expand_state() == "collapsed":
+    ///     folder.expand()
+    /// ```
+    fn get_expand_state(&self) -> PyResult<Option<&'static str>> {
+        let inner = self.inner.lock()?;
+        Ok(inner.get_expand_state()
+            .map_err(to_py_err)?
+            .map(|state| match state {
+                ExpandState::Collapsed => "collapsed",
+                ExpandState::Expanded => "expanded",
+                ExpandState::PartiallyExpanded => "partially_expanded",
+                ExpandState::LeafNode => "leaf",
+            }))
+    }
+
+    /// Show the children of a tree item, menu or combo box
+    /// 
+    /// Does nothing if the element is already expanded.
+    /// 
+    /// Raises:
+    ///     ValueError: If the element is a leaf node with nothing to expand
+    ///     PatternUnsupportedError: If the element cannot be expanded
+    fn expand(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.expand()
+            .map_err(to_py_err)
+    }
+
+    /// Hide the children of a tree item, menu or combo box
+    /// 
+    /// Does nothing if the element is already collapsed or is a leaf node.
+    /// 
+    /// Raises:
+    ///     PatternUnsupportedError: If the element cannot be collapsed
+    fn collapse(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.collapse()
+            .map_err(to_py_err)
+    }
+
+    /// Get the numeric value and bounds of a slider, progress bar or spinner
+    /// 
+    /// Returns:
+    ///     PyRangeValue | None: The current value with its bounds, or None if the
+    ///         element has no range value
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the range value could not be read
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// progress = window.find_elements(PyUIQuery.by_type("ProgressBar"))[0]
+    /// range = progress.get_range_value()
+    /// if range is not None:
+    ///     print(f"{range.fraction() * 100:.0f}% done")
+    /// ```
+    fn get_range_value(&self) -> PyResult<Option<PyRangeValue>> {
+        let inner = self.inner.lock()?;
+        Ok(inner.get_range_value()
+            .map_err(to_py_err)?
+            .map(|range| PyRangeValue { inner: range }))
+    }
+
+    /// Set the numeric value of a slider or spinner
+    /// 
+    /// Values outside the control's range are clamped to its minimum or maximum.
+    /// 
+    /// Args:
+    ///     value (float): The new value
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no range value or it is read-only
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// volume = window.find_elements(PyUIQuery.by_name("Volume"))[0]
+    /// volume.set_range_value(75)
+    /// ```
+    fn set_range_value(&self, value: f64) -> PyResult<()> {
+        debug!("Setting range value of UI element to {}", value);
+        let inner = self.inner.lock()?;
+        inner.set_range_value(value)
+            .map_err(to_py_err)
+    }
+
+    /// Move the mouse cursor over the center of the element without clicking
+    /// 
+    /// Note that this moves the real system cursor.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no on-screen bounds
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// button = window.find_elements(PyUIQuery.by_name("Bold"))[0]
+    /// button.hover()
+    /// ```
+    fn hover(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.hover()
+            .map_err(to_py_err)
+    }
+
+    /// Move the mouse cursor over the element and keep it there
+    /// 
+    /// Args:
+    ///     dwell_ms (int): How long to leave the cursor over the element, in milliseconds
+    ///     hover_restore (bool): Move the cursor back to where it was afterwards
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has no on-screen bounds
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// button.hover_for(800, True)   # Let the tooltip render, then restore the cursor
+    /// ```
+    fn hover_for(&self, dwell_ms: u64, hover_restore: bool) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.hover_for(Duration::from_millis(dwell_ms), hover_restore)
+            .map_err(to_py_err)
+    }
+
+    /// Focus the element and press a key chord
+    /// 
+    /// Args:
+    ///     chord (PyKeyChord): The keys to press
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If keys cannot be synthesized in this
+    ///         interaction mode or on this platform
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// editor.send_chord(PyKeyChord(PyKey.END, ctrl=True))   # Jump to the end
+    /// ```
+    fn send_chord(&self, chord: PyRef<PyKeyChord>) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.send_chord(&chord.inner)
+            .map_err(to_py_err)
+    }
+
+    /// Scroll the content of the element
+    /// 
+    /// Args:
+    ///     direction (str): Direction to scroll ("Up", "Down", "Left", "Right")
+    ///     amount (float): Distance as a percentage (0-100) of the scrollable range
+    /// 
+    /// Raises:
+    ///     ValueError: If the direction is not recognized
+    ///     RuntimeError: If the element cannot scroll in that direction
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// results = window.find_elements(PyUIQuery.by_type("List"))[0]
+    /// results.scroll("Down", 25.0)
+    /// ```
+    fn scroll(&self, direction: &str, amount: f64) -> PyResult<()> {
+        let direction = match direction {
+            "Up" => ScrollDirection::Up,
+            "Down" => ScrollDirection::Down,
+            "Left" => ScrollDirection::Left,
+            "Right" => ScrollDirection::Right,
+            other => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Invalid scroll direction '{}', expected Up, Down, Left or Right", other)
+            )),
+        };
+        let inner = self.inner.lock()?;
+        inner.scroll(direction, amount)
+            .map_err(to_py_err)
+    }
+
+    /// Scroll the containing view until the element is visible
+    /// 
+    /// Does nothing if the element is already on screen.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element is offscreen and cannot be scrolled into view
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// item = window.find_elements(PyUIQuery.by_name("Last entry"))[0]
+    /// item.scroll_into_view()
+    /// item.click()
+    /// ```
+    fn scroll_into_view(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.scroll_into_view()
+            .map_err(to_py_err)
+    }
+
+    /// Check whether any part of the element lies inside the window's rectangle
+    /// 
+    /// Unlike `is_offscreen`, this also catches elements scrolled partly
+    /// out of view when combined with `visible_fraction`.
+    /// 
+    /// Args:
+    ///     window (PyWindow): The window whose rectangle is the viewport
+    /// 
+    /// Returns:
+    ///     bool: True if the element's bounds intersect the window
+    fn is_in_viewport(&self, window: PyRef<PyWindow>) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        let window = window.inner.lock()?;
+        inner.is_in_viewport(window.as_ref())
+            .map_err(to_py_err)
+    }
+
+    /// Get the fraction of the element's area inside the window's rectangle
+    /// 
+    /// Args:
+    ///     window (PyWindow): The window whose rectangle is the viewport
+    /// 
+    /// Returns:
+    ///     float: From 0.0 (outside, or no bounds) to 1.0 (entirely inside)
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// if row.visible_fraction(window) < 1.0:
+    ///     row.scroll_into_view()
+    /// row.click()
+    /// ```
+    fn visible_fraction(&self, window: PyRef<PyWindow>) -> PyResult<f64> {
+        let inner = self.inner.lock()?;
+        let window = window.inner.lock()?;
+        inner.visible_fraction(window.as_ref())
+            .map_err(to_py_err)
+    }
+
+    /// Draw a colored outline around the element on the screen
+    /// 
+    /// Use it while writing selectors to see which element a query matched.
+    /// Blocks while the outline is shown; offscreen elements are skipped.
+    /// 
+    /// Args:
+    ///     duration_ms (int): How long to show the outline, in milliseconds
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If the platform cannot draw on the screen
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.find_first(PyUIQuery.by_name("Save")).highlight(2000)
+    /// ```
+    #[pyo3(signature = (duration_ms=1000))]
+    fn highlight(&self, duration_ms: u64) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.highlight(Duration::from_millis(duration_ms))
+            .map_err(to_py_err)
+    }
+
+    /// Capture the pixels of the element as a PNG image
+    /// 
+    /// Returns:
+    ///     bytes: PNG-encoded image data
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the element has zero-area bounds or is entirely offscreen
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// button = window.find_elements(PyUIQuery.by_name("Save"))[0]
+    /// with open("button.png", "wb") as f:
+    ///     f.write(button.capture_png())
+    /// ```
+    fn capture_png(&self, py: Python<'_>) -> PyResult<PyObject> {
+        debug!("Capturing UI element as PNG");
+        let inner = self.inner.lock()?;
+        let png = inner.capture_image()
+            .map_err(to_py_err)?;
+        Ok(PyBytes::new_bound(py, &png).into())
+    }
+
+    /// Compare two elements by runtime ID
+    /// 
+    /// Runtime IDs can be reused once an element is destroyed, so equality is
+    /// only meaningful for live elements captured close together in time.
+    /// Elements without a runtime ID compare unequal to everything else.
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// if window.focused_element() == button:
+    ///     print("Focus stayed on the button")
+    /// ```
+    fn __eq__(&self, other: PyRef<PyUIElement>) -> PyResult<bool> {
+        if Arc::ptr_eq(&self.inner, &other.inner) {
+            return Ok(true);
+        }
+        let inner = self.inner.lock()?;
+        let other_inner = other.inner.lock()?;
+        Ok(same_element(inner.as_ref(), other_inner.as_ref()).unwrap_or(false))
+    }
+
+    /// Hash the element by runtime ID, consistently with `==`
+    fn __hash__(&self) -> PyResult<u64> {
+        use std::hash::{Hash, Hasher};
+
+        let inner = self.inner.lock()?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        inner.runtime_id().unwrap_or_default().hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
+
+// =============================================================================
+// GEOMETRY CLASSES
+// =============================================================================
+
+/// Represents a rectangle in screen coordinates
+/// 
+/// This class exposes the position and size of a UI element on screen,
+/// useful for computing click coordinates and layout assertions.
+/// 
+/// # Examples
+/// 
+/// ```python
+/// rect = element.bounds
+/// if rect is not None:
+///     print(f"{rect.width()}x{rect.height()} at ({rect.left}, {rect.top})")
+/// ```
+#[pyclass]
+pub struct PyRect {
+    inner: Rect
+}
+
+#[pymethods]
+impl PyRect {
+    /// Create a rectangle from its edges
+    /// 
+    /// Args:
+    ///     left (int): The left edge in screen pixels
+    ///     top (int): The top edge in screen pixels
+    ///     right (int): The right edge in screen pixels
+    ///     bottom (int): The bottom edge in screen pixels
+    #[new]
+    fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        PyRect { inner: Rect::new(left, top, right, bottom) }
+    }
+
+    /// Get the x-coordinate of the left edge
+    /// 
+    /// Returns:
+    ///     int: The left edge in screen pixels
+    #[getter]
+    fn left(&self) -> i32 {
+        self.inner.left
+    }
+
+    /// Get the y-coordinate of the top edge
+    /// 
+    /// Returns:
+    ///     int: The top edge in screen pixels
+    #[getter]
+    fn top(&self) -> i32 {
+        self.inner.top
+    }
+
+    /// Get the x-coordinate of the right edge
+    /// 
+    /// Returns:
+    ///     int: The right edge in screen pixels
+    #[getter]
+    fn right(&self) -> i32 {
+        self.inner.right
+    }
+
+    /// Get the y-coordinate of the bottom edge
+    /// 
+    /// Returns:
+    ///     int: The bottom edge in screen pixels
+    #[getter]
+    fn bottom(&self) -> i32 {
+        self.inner.bottom
+    }
+
+    /// Get the width of the rectangle
+    /// 
+    /// Returns:
+    ///     int: The width in pixels
+    fn width(&self) -> i32 {
+        self.inner.width()
+    }
+
+    /// Get the height of the rectangle
+    /// 
+    /// Returns:
+    ///     int: The height in pixels
+    fn height(&self) -> i32 {
+        self.inner.height()
+    }
+
+    /// Get the center point of the rectangle
+    /// 
+    /// Returns:
+    ///     tuple[int, int]: The (x, y) center coordinates
+    fn center(&self) -> (i32, i32) {
+        self.inner.center()
+    }
+
+    /// Multiply every coordinate by a factor, rounding to the nearest pixel
+    /// 
+    /// Args:
+    ///     factor (float): The scale factor, e.g. 1.5 for 150%
+    /// 
+    /// Returns:
+    ///     PyRect: The scaled rectangle
+    fn scale(&self, factor: f64) -> PyRect {
+        PyRect { inner: self.inner.scale(factor) }
+    }
+
+    /// Convert from logical pixels to physical pixels on a monitor with the given DPI
+    /// 
+    /// Args:
+    ///     dpi (int): The monitor DPI, e.g. window.get_dpi(); 96 means 100% scaling
+    /// 
+    /// Returns:
+    ///     PyRect: The rectangle in physical pixels
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// PyRect(10, 10, 110, 50).to_physical(144)  # Rect(left=15, top=15, right=165, bottom=75)
+    /// ```
+    fn to_physical(&self, dpi: u32) -> PyRect {
+        PyRect { inner: logical_to_physical(&self.inner, dpi) }
+    }
+
+    /// Convert from physical pixels to logical pixels on a monitor with the given DPI
+    /// 
+    /// Args:
+    ///     dpi (int): The monitor DPI; 96 means 100% scaling
+    /// 
+    /// Returns:
+    ///     PyRect: The rectangle in logical pixels
+    fn to_logical(&self, dpi: u32) -> PyRect {
+        PyRect { inner: physical_to_logical(&self.inner, dpi) }
+    }
+
+    /// Get a string representation of the rectangle
+    /// 
+    /// Returns:
+    ///     str: Human-readable representation
+    fn __repr__(&self) -> String {
+        format!(
+            "Rect(left={}, top={}, right={}, bottom={})",
+            self.inner.left,
+            self.inner.top,
+            self.inner.right,
+            self.inner.bottom
+        )
+    }
+}
+
+/// The numeric value of a slider, progress bar or spinner, with its bounds
+/// 
+/// # Examples
+/// 
+/// ```python
+/// slider = window.find_elements(PyUIQuery.by_type("Slider"))[0]
+/// range = slider.get_range_value()
+/// print(f"{range.value} in [{range.min}, {range.max}]")
+/// ```
+#[pyclass]
+pub struct PyRangeValue {
+    inner: RangeValue
+}
+
+#[pymethods]
+impl PyRangeValue {
+    /// Get the current value
+    /// 
+    /// Returns:
+    ///     float: The current value
+    #[getter]
+    fn value(&self) -> f64 {
+        self.inner.value
+    }
+
+    /// Get the smallest accepted value
+    /// 
+    /// Returns:
+    ///     float: The minimum of the range
+    #[getter]
+    fn min(&self) -> f64 {
+        self.inner.min
+    }
+
+    /// Get the largest accepted value
+    /// 
+    /// Returns:
+    ///     float: The maximum of the range
+    #[getter]
+    fn max(&self) -> f64 {
+        self.inner.max
+    }
+
+    /// Get the step used for small changes, such as arrow keys
+    /// 
+    /// Returns:
+    ///     float: The small change step
+    #[getter]
+    fn small_change(&self) -> f64 {
+        self.inner.small_change
+    }
+
+    /// Get the step used for large changes, such as Page Up
+    /// 
+    /// Returns:
+    ///     float: The large change step
+    #[getter]
+    fn large_change(&self) -> f64 {
+        self.inner.large_change
+    }
+
+    /// Get the position of the value within the range
+    /// 
+    /// Returns:
+    ///     float: From 0.0 at the minimum to 1.0 at the maximum
+    fn fraction(&self) -> f64 {
+        self.inner.fraction()
+    }
+
+    /// Get a string representation of the range value
+    /// 
+    /// Returns:
+    ///     str: Human-readable representation
+    fn __repr__(&self) -> String {
+        format!("RangeValue(value={}, min={}, max={})", self.inner.value, self.inner.min, self.inner.max)
+    }
+}
+
+// =============================================================================
+// WINDOW CLASS
+// =============================================================================
+
+/// Represents a window in a desktop application
+/// 
+/// This class provides methods to interact with windows, find elements
+/// within them, and control window state.
+/// 
+/// # Examples
+/// 
+/// ```python
+/// # Get the active window and interact with it
+/// automation = PyAutomation()
+/// window = automation.active_window()
+/// print(f"Window title: {window.title}")
+/// 
+/// # Find elements in the window
+/// buttons = window.find_elements(PyUIQuery.by_type("Button"))
+/// if buttons:
+///     buttons[0].click()
+/// ```
+#[pyclass]
+pub struct PyWindow {
+    inner: Arc<ThreadSafe<Box<dyn Window>>>
+}
+
+#[pymethods]
+impl PyWindow {
+    /// Get the window title
+    /// 
+    /// Returns:
+    ///     str: The window's title text
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the title cannot be retrieved
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window = automation.active_window()
+    /// print(f"Current window: {window.title}")
+    /// ```
+    #[getter]
+    fn title(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_title()
+            .map_err(to_py_err)
+    }
+
+    /// Get the window class name
+    /// 
+    /// Returns:
+    ///     str: The window class (e.g. "Notepad")
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the class name cannot be retrieved
+    #[getter]
+    fn class_name(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_class_name()
+            .map_err(to_py_err)
+    }
+
+    /// Get the ID of the process that owns the window
+    /// 
+    /// Returns:
+    ///     int: The owning process ID
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the process ID cannot be retrieved
+    #[getter]
+    fn process_id(&self) -> PyResult<u32> {
+        let inner = self.inner.lock()?;
+        inner.get_process_id()
+            .map_err(to_py_err)
+    }
+
+    /// Get the ID of the thread that created the window
+    /// 
+    /// Returns:
+    ///     int: The creating thread ID
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the thread ID cannot be retrieved
+    #[getter]
+    fn thread_id(&self) -> PyResult<u32> {
+        let inner = self.inner.lock()?;
+        inner.get_thread_id()
+            .map_err(to_py_err)
+    }
+
+    /// Get the executable name of the owning process
+    /// 
+    /// Returns:
+    ///     str: The process name (e.g. "notepad.exe")
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the process cannot be queried
+    #[getter]
+    fn process_name(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_process_name()
+            .map_err(to_py_err)
+    }
+
+    /// Get the full executable path of the owning process
+    /// 
+    /// Returns:
+    ///     str: The absolute path to the executable
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the process cannot be queried
+    #[getter]
+    fn process_path(&self) -> PyResult<String> {
+        let inner = self.inner.lock()?;
+        inner.get_process_path()
+            .map_err(to_py_err)
+    }
+
+    /// Check whether the window is visible
+    /// 
+    /// Returns:
+    ///     bool: True if the window is visible
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the visibility cannot be determined
+    #[getter]
+    fn is_visible(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_visible()
+            .map_err(to_py_err)
+    }
+
+    /// Check whether the window is minimized
+    /// 
+    /// Returns:
+    ///     bool: True if the window is minimized
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window state cannot be determined
+    #[getter]
+    fn is_minimized(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_minimized()
+            .map_err(to_py_err)
+    }
+
+    /// Check whether the window is maximized
+    /// 
+    /// Returns:
+    ///     bool: True if the window is maximized
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window state cannot be determined
+    #[getter]
+    fn is_maximized(&self) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_maximized()
+            .map_err(to_py_err)
+    }
+
+    /// Get the DPI of the monitor the window is on
+    /// 
+    /// Returns:
+    ///     int: The DPI (96 at 100% scaling)
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the DPI cannot be retrieved
+    #[getter]
+    fn dpi(&self) -> PyResult<u32> {
+        let inner = self.inner.lock()?;
+        inner.get_dpi()
+            .map_err(to_py_err)
+    }
+
+    /// Get the window rectangle in screen coordinates
+    /// 
+    /// Returns:
+    ///     PyRect: The window's outer bounds
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the rectangle cannot be retrieved
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// rect = window.rect
+    /// print(f"{window.process_name} (PID {window.process_id}) at {rect} on {window.dpi} DPI")
+    /// ```
+    #[getter]
+    fn rect(&self) -> PyResult<Py<PyRect>> {
+        let rect = {
+            let inner = self.inner.lock()?;
+            inner.get_rect().map_err(to_py_err)?
+        };
+        Python::with_gil(|py| Py::new(py, PyRect { inner: rect }))
+    }
+
+    /// Move the window so its top-left corner is at (x, y)
+    /// 
+    /// Coordinates are physical screen pixels, like element bounds.
+    /// 
+    /// Args:
+    ///     x (int): New left edge
+    ///     y (int): New top edge
+    /// 
+    /// Raises:
+    ///     ValueError: If the window is minimized
+    fn move_to(&self, x: i32, y: i32) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.move_to(x, y)
+            .map_err(to_py_err)
+    }
+
+    /// Resize the window, keeping its top-left corner in place
+    /// 
+    /// Args:
+    ///     width (int): New width in physical pixels
+    ///     height (int): New height in physical pixels
+    /// 
+    /// Raises:
+    ///     ValueError: If the window is minimized or the size is not positive
+    fn resize(&self, width: i32, height: i32) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.resize(width, height)
+            .map_err(to_py_err)
+    }
+
+    /// Move and resize the window in one step
+    /// 
+    /// Args:
+    ///     rect (PyRect): The screen rectangle the window should cover
+    /// 
+    /// Raises:
+    ///     ValueError: If the window is minimized or the rectangle is empty
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.set_rect(PyRect(100, 100, 900, 700))
+    /// ```
+    fn set_rect(&self, rect: PyRef<PyRect>) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.set_rect(rect.inner.clone())
+            .map_err(to_py_err)
+    }
+
+    /// Get the complete UI tree for this window
+    /// 
+    /// This returns a hierarchical representation of all UI elements
+    /// in the window, useful for debugging and understanding the UI structure.
+    /// 
+    /// Returns:
+    ///     PyUITree: Complete tree structure of the window's UI elements
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the UI tree cannot be retrieved
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window = automation.active_window()
+    /// tree = window.get_ui_tree()
+    /// print(f"Window: {tree.window_title}")
+    /// print(f"Root element: {tree.root.name}")
+    /// ```
+    fn get_ui_tree(&self) -> PyResult<Py<PyUITree>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let tree = inner.get_ui_tree()
+                .map_err(to_py_err)?;
+            Py::new(py, PyUITree { inner: tree })
+        })
+    }
+
+    /// Get the UI tree for this window with custom limits
+    /// 
+    /// Use this for deep UIs (IDEs, browsers) that `get_ui_tree` truncates.
+    /// 
+    /// Args:
+    ///     max_depth (int | None): Maximum depth to capture, None for unlimited
+    ///     max_children_per_node (int | None): Maximum children per node, None for unlimited
+    ///     include_bounds (bool): Look up the bounding rectangle of every node
+    ///     include_properties (bool): Collect all properties instead of just name and control type
+    /// 
+    /// At most 10,000 nodes are captured; check `tree.truncated` to see whether
+    /// the budget cut the capture short.
+    /// 
+    /// The GIL is released during the capture, so another Python thread can
+    /// stop it through `cancel`.
+    /// 
+    /// Returns:
+    ///     PyUITree: Tree structure of the window's UI elements
+    /// 
+    /// Raises:
+    ///     CancelledError: If `cancel` was cancelled during the capture
+    ///     RuntimeError: If the UI tree cannot be retrieved
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # Full structure only, skipping the expensive bounds lookups
+    /// tree = window.get_ui_tree_with_options(None, None, False, False)
+    /// 
+    /// # Give up after two seconds
+    /// cancel = PyCancellationToken()
+    /// threading.Timer(2.0, cancel.cancel).start()
+    /// tree = window.get_ui_tree_with_options(None, None, True, False, cancel=cancel)
+    /// ```
+    #[pyo3(signature = (max_depth, max_children_per_node, include_bounds, include_properties, cancel=None))]
+    fn get_ui_tree_with_options(
+        &self,
+        max_depth: Option<u32>,
+        max_children_per_node: Option<usize>,
+        include_bounds: bool,
+        include_properties: bool,
+        cancel: Option<PyRef<PyCancellationToken>>,
+    ) -> PyResult<Py<PyUITree>> {
+        let options = UITreeOptions {
+            max_depth,
+            max_children_per_node,
+            include_bounds,
+            include_properties,
+            cancel: cancel.map(|cancel| cancel.inner.clone()),
+            ..UITreeOptions::default()
+        };
+        Python::with_gil(|py| {
+            let tree = py.allow_threads(|| {
+                let inner = self.inner.lock()?;
+                inner.get_ui_tree_with_options(&options)
+                    .map_err(to_py_err)
+            })?;
+            Py::new(py, PyUITree { inner: tree })
+        })
+    }
+
+    /// Capture the window's UI tree, with properties, and save it as JSON
+    /// 
+    /// Load it later with `PyUITree.load` to try queries without the
+    /// application running.
+    /// 
+    /// Args:
+    ///     path (str): File to write
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the tree cannot be captured or the file cannot be written
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.save_ui_tree("notepad.json")
+    /// ```
+    fn save_ui_tree(&self, path: &str) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.save_ui_tree(path)
+            .map_err(to_py_err)
+    }
+
+    /// Find UI elements in the window matching a query
+    /// 
+    /// This is the primary method for locating specific UI elements
+    /// within the window using various search criteria.
+    /// 
+    /// The search already retries briefly when the UI changes while it runs.
+    /// Pass `retries` to keep trying longer on a UI that is still loading.
+    /// Only ElementNotFoundError, for an element that vanished mid-call, is
+    /// retried; UiTimeoutError and errors such as an unsupported pattern or a
+    /// malformed query are raised at once.
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): Query object specifying search criteria
+    ///     retries (int): Extra attempts after transient failures, 50 ms apart
+    /// 
+    /// Returns:
+    ///     list[PyUIElement]: List of matching elements
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the search fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # Find all buttons
+    /// buttons = window.find_elements(PyUIQuery.by_type("Button"))
+    /// 
+    /// # Keep trying for a while on a UI that is being rebuilt
+    /// rows = window.find_elements(PyUIQuery.by_type("DataItem"), retries=10)
+    /// 
+    /// # Find element by name
+    /// save_btn = window.find_elements(PyUIQuery.by_name("Save"))
+    /// 
+    /// # Find enabled edit controls
+    /// edits = window.find_elements(PyUIQuery.by_type("Edit"))
+    /// enabled_edits = [e for e in edits if e.is_enabled]
+    /// ```
+    #[pyo3(signature = (query, retries=0))]
+    fn find_elements(&self, query: &PyUIQuery, retries: u32) -> PyResult<Vec<Py<PyUIElement>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let elements = with_retry(retries.saturating_add(1), DEFAULT_RETRY_DELAY, || inner.find_elements(&query.inner))
+                .map_err(to_py_err)?;
+            
+            Ok(elements.into_iter()
+                .map(|element| Py::new(py, PyUIElement { 
+                    inner: Arc::new(ThreadSafe::new(element))
+                }).unwrap())
+                .collect())
+        })
+    }
+
+    /// Run several queries over a single walk of the window
+    /// 
+    /// Each element of the window is visited once and tested against every
+    /// query, instead of one walk per find_elements() call. Queries that depend
+    /// on more than the element itself (by_text, not, and relational queries
+    /// on Windows) still get a walk of their own.
+    /// 
+    /// Args:
+    ///     queries (dict[str, PyUIQuery]): The queries to run, keyed by any label
+    /// 
+    /// Returns:
+    ///     dict[str, list[PyUIElement]]: The matches of each query under its label, in document order
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window's elements cannot be enumerated
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// found = window.find_many({
+    ///     "save": PyUIQuery.by_name("Save"),
+    ///     "cancel": PyUIQuery.by_name("Cancel"),
+    /// })
+    /// if found["save"]:
+    ///     found["save"][0].click()
+    /// ```
+    fn find_many(&self, queries: HashMap<String, PyRef<PyUIQuery>>) -> PyResult<HashMap<String, Vec<Py<PyUIElement>>>> {
+        let queries: HashMap<String, UIQuery> = queries.into_iter()
+            .map(|(label, query)| (label, query.inner.clone()))
+            .collect();
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let found = inner.find_many(&queries).map_err(to_py_err)?;
+            found.into_iter()
+                .map(|(label, elements)| {
+                    let elements = elements.into_iter()
+                        .map(|element| Py::new(py, PyUIElement { inner: Arc::new(ThreadSafe::new(element)) }))
+                        .collect::<PyResult<Vec<_>>>()?;
+                    Ok((label, elements))
+                })
+                .collect()
+        })
+    }
+
+    /// Find UI elements matching a query, along with each one's path from the window
+    /// 
+    /// The path tells apart elements that share a name and can be passed to
+    /// find_by_path() later. It has one "ControlType[index]" segment per level
+    /// below the window, where the zero-based index counts only siblings of
+    /// the same control type, e.g. ["Pane[0]", "ToolBar[0]", "Button[1]"].
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): Query object specifying search criteria
+    /// 
+    /// Returns:
+    ///     list[tuple[PyUIElement, list[str]]]: Matching elements and their paths, in document order
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If paths are not implemented for this platform
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// for element, path in window.find_elements_with_path(PyUIQuery.by_name("Delete")):
+    ///     print("/".join(path))
+    /// ```
+    fn find_elements_with_path(&self, py: Python<'_>, query: &PyUIQuery) -> PyResult<Vec<(Py<PyUIElement>, Vec<String>)>> {
+        let inner = self.inner.lock()?;
+        inner.find_elements_with_path(&query.inner)
+            .map_err(to_py_err)?
+            .into_iter()
+            .map(|(element, path)| Ok((Py::new(py, PyUIElement { inner: Arc::new(ThreadSafe::new(element)) })?, path)))
+            .collect()
+    }
+
+    /// Find the element at a path returned by find_elements_with_path()
+    /// 
+    /// Args:
+    ///     path (list[str]): "ControlType[index]" segments from the window down
+    /// 
+    /// Returns:
+    ///     PyUIElement | None: The element, or None if the path no longer leads anywhere
+    /// 
+    /// Raises:
+    ///     ValueError: If a segment is malformed
+    fn find_by_path(&self, py: Python<'_>, path: Vec<String>) -> PyResult<Option<Py<PyUIElement>>> {
+        let inner = self.inner.lock()?;
+        match inner.find_by_path(&path).map_err(to_py_err)? {
+            Some(element) => Ok(Some(Py::new(py, PyUIElement {
+                inner: Arc::new(ThreadSafe::new(element))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Find the first UI element matching a query
+    /// 
+    /// Stops searching at the first match, which is faster than
+    /// find_elements() when only one element is needed.
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): Query object specifying search criteria
+    ///     retries (int): Extra attempts after transient failures, see find_elements
+    /// 
+    /// Returns:
+    ///     PyUIElement | None: The first matching element, or None if nothing matches
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the search fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// save_btn = window.find_first(PyUIQuery.by_name("Save"))
+    /// if save_btn is not None:
+    ///     save_btn.click()
+    /// ```
+    #[pyo3(signature = (query, retries=0))]
+    fn find_first(&self, query: &PyUIQuery, retries: u32) -> PyResult<Option<Py<PyUIElement>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            match with_retry(retries.saturating_add(1), DEFAULT_RETRY_DELAY, || inner.find_first(&query.inner)).map_err(to_py_err)? {
+                Some(element) => Ok(Some(Py::new(py, PyUIElement {
+                    inner: Arc::new(ThreadSafe::new(element))
+                })?)),
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Fill several fields of the window in one go
+    /// 
+    /// Activates the window once, then sets the value of the first element
+    /// matching each query. A field that fails does not stop the others.
+    /// 
+    /// Args:
+    ///     pairs (list[tuple[PyUIQuery, str]]): The fields to fill, in order
+    /// 
+    /// Returns:
+    ///     list[Exception | None]: None for each field that was filled, or the
+    ///         error raised for it, in the order of `pairs`
+    /// 
+    /// Raises:
+    ///     UiAutomationError: If the window cannot be activated
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// errors = window.fill_form([
+    ///     (PyUIQuery.by_automation_id("username"), "alice"),
+    ///     (PyUIQuery.by_automation_id("password"), "hunter2"),
+    /// ])
+    /// if any(errors):
+    ///     print("Some fields could not be filled:", errors)
+    /// ```
+    fn fill_form(&self, py: Python<'_>, pairs: Vec<(PyRef<'_, PyUIQuery>, String)>) -> PyResult<Vec<Option<PyObject>>> {
+        let pairs: Vec<(UIQuery, String)> = pairs.into_iter()
+            .map(|(query, value)| (query.inner.clone(), value))
+            .collect();
+        let inner = self.inner.lock()?;
+        let results = inner.fill_form(&pairs).map_err(to_py_err)?;
+        Ok(results.into_iter()
+            .map(|result| result.err().map(|e| to_py_err(e).into_value(py).into_py(py)))
+            .collect())
+    }
+
+    /// Get the window's menu bar
+    /// 
+    /// The system menu bar in a classic window's title bar is skipped.
+    /// 
+    /// Returns:
+    ///     PyUIElement | None: The menu bar, or None if the window has none
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the search fails
+    fn get_menu_bar(&self, py: Python<'_>) -> PyResult<Option<Py<PyUIElement>>> {
+        let inner = self.inner.lock()?;
+        match inner.get_menu_bar().map_err(to_py_err)? {
+            Some(element) => Ok(Some(Py::new(py, PyUIElement {
+                inner: Arc::new(ThreadSafe::new(element))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Open a chain of menus and invoke the last item
+    /// 
+    /// Menus are opened with the ExpandCollapse pattern and the last item is
+    /// invoked, without any mouse input. Names must match exactly.
+    /// 
+    /// Args:
+    ///     path (list[str]): Item names from the menu bar down, e.g. ["File", "New"]
+    /// 
+    /// Raises:
+    ///     ElementNotFoundError: If the window has no menu bar or an item is
+    ///         missing; the message names the step that failed
+    ///     ValueError: If path is empty
+    ///     UiAutomationError: If an item cannot be opened or invoked
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.invoke_menu_path(["File", "New"])
+    /// ```
+    fn invoke_menu_path(&self, path: Vec<String>) -> PyResult<()> {
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        let inner = self.inner.lock()?;
+        inner.invoke_menu_path(&path).map_err(to_py_err)
+    }
+
+    /// Find UI elements in the window, waiting for them to appear
+    /// 
+    /// Repeats the search until at least one element matches or the timeout
+    /// elapses. Useful right after activating a window whose UI loads lazily.
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): Query object specifying search criteria
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     interval_ms (int): Delay between searches, in milliseconds
+    ///     cancel (PyCancellationToken, optional): Stops the wait early when cancelled
+    ///         from another thread; the GIL is released while waiting
+    /// 
+    /// Returns:
+    ///     list[PyUIElement]: List of matching elements (empty if the timeout elapsed)
+    /// 
+    /// Raises:
+    ///     CancelledError: If `cancel` was cancelled before anything matched
+    ///     RuntimeError: If a search fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.activate()
+    /// buttons = window.find_elements_wait(PyUIQuery.by_name("Save"), 5000, 200)
+    /// if buttons:
+    ///     buttons[0].click()
+    /// ```
+    #[pyo3(signature = (query, timeout_ms, interval_ms, cancel=None))]
+    fn find_elements_wait(&self, query: &PyUIQuery, timeout_ms: u64, interval_ms: u64, cancel: Option<PyRef<PyCancellationToken>>) -> PyResult<Vec<Py<PyUIElement>>> {
+        let cancel = cancel.map(|cancel| cancel.inner.clone()).unwrap_or_default();
+        Python::with_gil(|py| {
+            let elements = py.allow_threads(|| {
+                let inner = self.inner.lock()?;
+                inner.find_elements_wait_cancellable(
+                    &query.inner,
+                    Duration::from_millis(timeout_ms),
+                    Duration::from_millis(interval_ms),
+                    &cancel,
+                ).map_err(to_py_err)
+                // Boxed elements are only Send once wrapped, see `ComShared`
+                .map(|elements| elements.into_iter().map(ThreadSafe::new).collect::<Vec<_>>())
+            })?;
+            
+            Ok(elements.into_iter()
+                .map(|element| Py::new(py, PyUIElement { 
+                    inner: Arc::new(element)
+                }).unwrap())
+                .collect())
+        })
+    }
 
-#[pymethods]
-impl PyWindow {
-    /// Get the window title
+    /// Wait until the window's UI stops changing
     /// 
-    /// Returns:
-    ///     str: The window's title text
+    /// Samples the window until two consecutive samples (by default) are
+    /// identical. A sample is the number of nodes in the top two levels of the
+    /// UI tree plus the focused element. Best-effort: returns normally even if
+    /// the UI is still changing when the timeout elapses.
+    /// 
+    /// Args:
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    ///     interval_ms (int, optional): Delay between samples, in milliseconds (default 100)
+    ///     stable_samples (int, optional): Identical samples in a row needed to count as ready (default 2, at least 2)
     /// 
     /// Raises:
-    ///     RuntimeError: If the title cannot be retrieved
+    ///     RuntimeError: If sampling fails
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// window = automation.active_window()
-    /// print(f"Current window: {window.title}")
+    /// window.activate()
+    /// window.wait_until_ready(3000)
+    /// tree = window.get_ui_tree()
     /// ```
-    #[getter]
-    fn title(&self) -> PyResult<String> {
-        let inner = self.inner.0.lock().unwrap();
-        inner.get_title()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    #[pyo3(signature = (timeout_ms, interval_ms=None, stable_samples=None))]
+    fn wait_until_ready(&self, timeout_ms: u64, interval_ms: Option<u64>, stable_samples: Option<u32>) -> PyResult<()> {
+        let defaults = ReadinessOptions::default();
+        let options = ReadinessOptions {
+            poll_interval: interval_ms.map(Duration::from_millis).unwrap_or(defaults.poll_interval),
+            stable_samples: stable_samples.unwrap_or(defaults.stable_samples),
+        };
+        let inner = self.inner.lock()?;
+        inner.wait_until_ready_with_options(Duration::from_millis(timeout_ms), &options).map_err(to_py_err)
     }
 
-    /// Get the complete UI tree for this window
+    /// Send a key sequence to the window
     /// 
-    /// This returns a hierarchical representation of all UI elements
-    /// in the window, useful for debugging and understanding the UI structure.
+    /// Activates the window, then types the sequence. Special keys are written
+    /// in braces and modify the key that follows, e.g. `{CTRL}s` for Ctrl+S,
+    /// `{F5}`, `{ALT}{F4}` or `{ENTER}`. Literal braces are quoted as `{{}`.
     /// 
-    /// Returns:
-    ///     PyUITree: Complete tree structure of the window's UI elements
+    /// Args:
+    ///     keys (str): The key sequence to send
     /// 
     /// Raises:
-    ///     RuntimeError: If the UI tree cannot be retrieved
+    ///     RuntimeError: If the sequence is empty or malformed, or the window
+    ///         cannot be activated
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// window = automation.active_window()
-    /// tree = window.get_ui_tree()
-    /// print(f"Window: {tree.window_title}")
-    /// print(f"Root element: {tree.root.name}")
+    /// window.send_keys("{CTRL}s")   # Save
+    /// window.send_keys("{F5}")      # Refresh
     /// ```
-    fn get_ui_tree(&self) -> PyResult<Py<PyUITree>> {
-        Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
-            let tree = inner.get_ui_tree()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            Ok(Py::new(py, PyUITree { inner: tree })?)
-        })
+    fn send_keys(&self, keys: &str) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.send_keys(keys)
+            .map_err(to_py_err)
     }
 
-    /// Find UI elements in the window matching a query
+    /// Send a key chord to the window
     /// 
-    /// This is the primary method for locating specific UI elements
-    /// within the window using various search criteria.
+    /// A typed alternative to `send_keys`, see `PyKeyChord`.
     /// 
     /// Args:
-    ///     query (PyUIQuery): Query object specifying search criteria
-    /// 
-    /// Returns:
-    ///     list[PyUIElement]: List of matching elements
+    ///     chord (PyKeyChord): The keys to press
     /// 
     /// Raises:
-    ///     RuntimeError: If the search fails
+    ///     RuntimeError: If the window cannot be activated
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// # Find all buttons
-    /// buttons = window.find_elements(PyUIQuery.by_type("Button"))
+    /// window.send_chord(PyKeyChord(PyKey.f(4), alt=True))   # Alt+F4
+    /// ```
+    fn send_chord(&self, chord: PyRef<PyKeyChord>) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.send_chord(&chord.inner)
+            .map_err(to_py_err)
+    }
+
+    /// Capture the pixels of the window as a PNG image
     /// 
-    /// # Find element by name
-    /// save_btn = window.find_elements(PyUIQuery.by_name("Save"))
+    /// Returns:
+    ///     bytes: PNG-encoded image data
     /// 
-    /// # Find enabled edit controls
-    /// edits = window.find_elements(PyUIQuery.by_type("Edit"))
-    /// enabled_edits = [e for e in edits if e.is_enabled]
+    /// Raises:
+    ///     RuntimeError: If the window has zero-area bounds or is entirely offscreen
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// with open("window.png", "wb") as f:
+    ///     f.write(window.capture_png())
     /// ```
-    fn find_elements(&self, query: &PyUIQuery) -> PyResult<Vec<Py<PyUIElement>>> {
-        Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
-            let elements = inner.find_elements(&query.inner)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            
-            Ok(elements.into_iter()
-                .map(|element| Py::new(py, PyUIElement { 
-                    inner: Arc::new(ThreadSafe::new(element))
-                }).unwrap())
-                .collect())
-        })
+    fn capture_png(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let inner = self.inner.lock()?;
+        let png = inner.capture_image()
+            .map_err(to_py_err)?;
+        Ok(PyBytes::new_bound(py, &png).into())
     }
 
     /// Activate the window (bring it to the foreground)
@@ -577,47 +2879,442 @@ impl PyWindow {
     ///     window.activate()
     /// ```
     fn activate(&self) -> PyResult<()> {
-        let inner = self.inner.0.lock().unwrap();
+        let inner = self.inner.lock()?;
         inner.activate()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+            .map_err(to_py_err)
+    }
+
+    /// Bring the window to the top of the Z-order
+    /// 
+    /// This method brings the window to the top without necessarily
+    /// giving it focus (unlike activate()).
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window cannot be brought to top
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.bring_to_top()
+    /// ```
+    fn bring_to_top(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.bring_to_top()
+            .map_err(to_py_err)
+    }
+
+    /// Set the window as the foreground window
+    /// 
+    /// This method attempts to make the window the foreground window,
+    /// which may be restricted by the operating system.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window cannot be set as foreground
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.set_foreground()
+    /// ```
+    fn set_foreground(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.set_foreground()
+            .map_err(to_py_err)
+    }
+
+    /// Minimize the window to the taskbar
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window cannot be minimized
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.minimize()
+    /// ```
+    fn minimize(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.minimize()
+            .map_err(to_py_err)
+    }
+
+    /// Maximize the window
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window cannot be maximized
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.maximize()
+    /// ```
+    fn maximize(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.maximize()
+            .map_err(to_py_err)
+    }
+
+    /// Restore the window from a minimized or maximized state
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window cannot be restored
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.restore()
+    /// ```
+    fn restore(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.restore()
+            .map_err(to_py_err)
+    }
+
+    /// Close the window without terminating the owning process
+    /// 
+    /// Waits until the window is gone or a timeout elapses.
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window is still open after the timeout
+    ///         (e.g. a "save changes?" prompt is blocking it)
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.close()
+    /// ```
+    fn close(&self) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.close()
+            .map_err(to_py_err)
+    }
+
+    /// Wait for the window to close
+    /// 
+    /// Args:
+    ///     timeout_ms (int): Maximum time to wait, in milliseconds
+    /// 
+    /// Returns:
+    ///     bool: True once the window is gone, False if it is still open after the timeout
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the window's state cannot be read
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// dialog.find_first(PyUIQuery.by_name("OK")).click()
+    /// if not dialog.wait_for_close(5000):
+    ///     print("The dialog is still open")
+    /// ```
+    fn wait_for_close(&self, timeout_ms: u64) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.wait_for_close(Duration::from_millis(timeout_ms))
+            .map_err(to_py_err)
+    }
+
+    /// Get the element with keyboard focus inside this window
+    /// 
+    /// Unlike PyAutomation.focused_element(), the lookup is scoped to this
+    /// window rather than the whole desktop.
+    /// 
+    /// Returns:
+    ///     PyUIElement: The focused element within the window
+    /// 
+    /// Raises:
+    ///     RuntimeError: If no element in the window has focus
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window = automation.active_window()
+    /// element = window.focused_element()
+    /// print(f"Focused in '{window.title}': {element.name}")
+    /// ```
+    fn focused_element(&self) -> PyResult<Py<PyUIElement>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let element = inner.get_focused_element()
+                .map_err(to_py_err)?;
+            Py::new(py, PyUIElement {
+                inner: Arc::new(ThreadSafe::new(element))
+            })
+        })
+    }
+
+    /// Get the dialogs and tool windows that belong to this window
+    /// 
+    /// Returns the visible windows this window owns, such as a "Save As"
+    /// dialog or a message box (topmost first), followed by child windows
+    /// that UI Automation exposes as windows, such as MDI documents. Plain
+    /// controls are not included; use `find_elements` for those.
+    /// 
+    /// Returns:
+    ///     list[PyWindow]: The owned and child windows (may be empty)
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the windows cannot be enumerated
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window.send_keys("{CTRL}{SHIFT}s")
+    /// time.sleep(0.5)
+    /// dialog = next(w for w in window.get_child_windows() if w.title == "Save As")
+    /// ```
+    fn get_child_windows(&self) -> PyResult<Vec<Py<PyWindow>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let windows = inner.get_child_windows()
+                .map_err(to_py_err)?;
+            
+            windows.into_iter()
+                .map(|window| Py::new(py, PyWindow {
+                    inner: Arc::new(ThreadSafe::new(window))
+                }))
+                .collect()
+        })
+    }
+
+    /// Get an identifier for the native window behind this object
+    /// 
+    /// Two objects for the same window return the same id, so ids from two
+    /// `get_all_windows()` snapshots show which windows opened or closed in
+    /// between. Ids may be reused once a window is destroyed.
+    /// 
+    /// Returns:
+    ///     int: The window id (the HWND value on Windows)
+    /// 
+    /// Raises:
+    ///     UnsupportedOperationError: If the platform has no stable window id
+    ///     UiAutomationError: If the window no longer exists
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// before = {w.window_id() for w in automation.get_all_windows()}
+    /// window.send_keys("{CTRL}o")
+    /// time.sleep(0.5)
+    /// opened = [w for w in automation.get_all_windows() if w.window_id() not in before]
+    /// ```
+    fn window_id(&self) -> PyResult<u64> {
+        let inner = self.inner.lock()?;
+        inner.window_id()
+            .map_err(to_py_err)
+    }
+
+    /// Compare two windows by window id
+    /// 
+    /// Windows without an id compare unequal to everything else.
+    fn __eq__(&self, other: PyRef<PyWindow>) -> PyResult<bool> {
+        if Arc::ptr_eq(&self.inner, &other.inner) {
+            return Ok(true);
+        }
+        let inner = self.inner.lock()?;
+        let other_inner = other.inner.lock()?;
+        Ok(same_window(inner.as_ref(), other_inner.as_ref()).unwrap_or(false))
+    }
+
+    /// Hash the window by window id, consistently with `==`
+    fn __hash__(&self) -> PyResult<u64> {
+        let inner = self.inner.lock()?;
+        Ok(inner.window_id().unwrap_or_default())
+    }
+
+    /// Get the text currently selected in this window
+    /// Get the text currently selected in this window
+    /// 
+    /// Returns:
+    ///     PySelectedTextInfo | None: The selection, or None if nothing is selected
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the selection cannot be read
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// selection = window.get_selected_text()
+    /// if selection is not None:
+    ///     print(f"Selected '{selection.text}' ({selection.start_offset}-{selection.end_offset})")
+    /// ```
+    fn get_selected_text(&self) -> PyResult<Option<Py<PySelectedTextInfo>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            match inner.get_selected_text().map_err(to_py_err)? {
+                Some(selection) => Ok(Some(Py::new(py, PySelectedTextInfo { inner: selection })?)),
+                None => Ok(None),
+            }
+        })
+    }
+}
+
+// =============================================================================
+// TEXT SELECTION CLASS
+// =============================================================================
+
+/// Represents text the user has selected in an element
+/// 
+/// # Examples
+/// 
+/// ```python
+/// selection = window.get_selected_text()
+/// if selection is not None and selection.bounds is not None:
+///     print(f"'{selection.text}' at {selection.bounds.center()}")
+/// ```
+#[pyclass]
+pub struct PySelectedTextInfo {
+    inner: SelectedTextInfo
+}
+
+#[pymethods]
+impl PySelectedTextInfo {
+    /// Get the selected text
+    /// 
+    /// Returns:
+    ///     str: The selected text content
+    #[getter]
+    fn text(&self) -> String {
+        self.inner.text.clone()
+    }
+
+    /// Get the character offset where the selection starts
+    /// 
+    /// Returns:
+    ///     int: Offset from the start of the element's text
+    #[getter]
+    fn start_offset(&self) -> i32 {
+        self.inner.start_offset
+    }
+
+    /// Get the character offset where the selection ends
+    /// 
+    /// Returns:
+    ///     int: Offset from the start of the element's text (exclusive)
+    #[getter]
+    fn end_offset(&self) -> i32 {
+        self.inner.end_offset
+    }
+
+    /// Get the screen bounds of the selected text
+    /// 
+    /// Returns:
+    ///     PyRect | None: The selection bounds, or None if not available
+    #[getter]
+    fn bounds(&self) -> PyResult<Option<Py<PyRect>>> {
+        match self.inner.bounds.clone() {
+            Some(rect) => Python::with_gil(|py| Ok(Some(Py::new(py, PyRect { inner: rect })?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the name of the element containing the selection
+    /// 
+    /// Returns:
+    ///     str | None: The element's name, or None if it is unknown
+    #[getter]
+    fn element_name(&self) -> Option<String> {
+        self.inner.element_info.as_ref().map(|info| info.name.clone())
     }
 
-    /// Bring the window to the top of the Z-order
+    /// Get the control type of the element containing the selection
     /// 
-    /// This method brings the window to the top without necessarily
-    /// giving it focus (unlike activate()).
+    /// Returns:
+    ///     str | None: The element's control type, or None if it is unknown
+    #[getter]
+    fn element_type(&self) -> Option<String> {
+        self.inner.element_info.as_ref().map(|info| info.control_type.clone())
+    }
+
+    /// Get a string representation of the selection
     /// 
-    /// Raises:
-    ///     RuntimeError: If the window cannot be brought to top
+    /// Returns:
+    ///     str: Human-readable representation
+    fn __repr__(&self) -> String {
+        format!(
+            "SelectedTextInfo(text={:?}, start_offset={}, end_offset={})",
+            self.inner.text,
+            self.inner.start_offset,
+            self.inner.end_offset
+        )
+    }
+}
+
+/// The result of comparing an element's text with the expected text
+/// 
+/// # Examples
+/// 
+/// ```python
+/// check = field.verify_text("réunion")
+/// if not check.matches:
+///     print(check.mismatches, check.expected_len, check.actual_len)
+/// ```
+#[pyclass]
+pub struct PyTextVerification {
+    inner: TextVerification
+}
+
+#[pymethods]
+impl PyTextVerification {
+    /// Whether the texts are identical after normalizing line endings
     /// 
-    /// # Examples
+    /// Returns:
+    ///     bool: True if the element holds exactly the expected text
+    #[getter]
+    fn matches(&self) -> bool {
+        self.inner.matches
+    }
+
+    /// The positions where the texts differ
     /// 
-    /// ```python
-    /// window.bring_to_top()
-    /// ```
-    fn bring_to_top(&self) -> PyResult<()> {
-        let inner = self.inner.0.lock().unwrap();
-        inner.bring_to_top()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    /// Only positions present in both texts are listed; compare
+    /// `expected_len` and `actual_len` for missing or extra characters.
+    /// 
+    /// Returns:
+    ///     list[tuple[int, str, str]]: (character index, expected, actual) for each difference
+    #[getter]
+    fn mismatches(&self) -> Vec<(usize, char, char)> {
+        self.inner.mismatches.clone()
     }
 
-    /// Set the window as the foreground window
+    /// Length of the expected text in characters
     /// 
-    /// This method attempts to make the window the foreground window,
-    /// which may be restricted by the operating system.
+    /// Returns:
+    ///     int: Number of characters expected
+    #[getter]
+    fn expected_len(&self) -> usize {
+        self.inner.expected_len
+    }
+
+    /// Length of the element's text in characters
     /// 
-    /// Raises:
-    ///     RuntimeError: If the window cannot be set as foreground
+    /// Returns:
+    ///     int: Number of characters found
+    #[getter]
+    fn actual_len(&self) -> usize {
+        self.inner.actual_len
+    }
+
+    /// The element's text, with line endings normalized
     /// 
-    /// # Examples
+    /// Returns:
+    ///     str: The text that was compared
+    #[getter]
+    fn actual(&self) -> String {
+        self.inner.actual.clone()
+    }
+
+    /// Get a string representation of the comparison
     /// 
-    /// ```python
-    /// window.set_foreground()
-    /// ```
-    fn set_foreground(&self) -> PyResult<()> {
-        let inner = self.inner.0.lock().unwrap();
-        inner.set_foreground()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    /// Returns:
+    ///     str: Human-readable representation
+    fn __repr__(&self) -> String {
+        format!(
+            "TextVerification(matches={}, mismatches={}, expected_len={}, actual_len={})",
+            if self.inner.matches { "True" } else { "False" },
+            self.inner.mismatches.len(),
+            self.inner.expected_len,
+            self.inner.actual_len
+        )
     }
 }
 
@@ -700,6 +3397,65 @@ impl PyUITree {
     fn timestamp(&self) -> String {
         self.inner.timestamp.to_rfc3339()
     }
+
+    /// Whether the capture stopped early because the node budget ran out
+    /// 
+    /// Returns:
+    ///     bool: True if the tree is missing nodes beyond the budget
+    #[getter]
+    fn truncated(&self) -> bool {
+        self.inner.truncated
+    }
+
+    /// Load a tree saved with `PyWindow.save_ui_tree` or `PyUITree.save`
+    /// 
+    /// Args:
+    ///     path (str): File to read
+    /// 
+    /// Returns:
+    ///     PyUITree: The captured tree
+    /// 
+    /// Raises:
+    ///     ValueError: If the file is not a saved UI tree
+    ///     RuntimeError: If the file cannot be read
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        Ok(PyUITree { inner: UITree::load(path).map_err(to_py_err)? })
+    }
+
+    /// Save the tree as JSON
+    /// 
+    /// Args:
+    ///     path (str): File to write
+    fn save(&self, path: &str) -> PyResult<()> {
+        self.inner.save(path).map_err(to_py_err)
+    }
+
+    /// Find the captured nodes matching a query, root first, in document order
+    /// 
+    /// Only what the capture recorded can match: automation ids and class
+    /// names need a tree captured with properties.
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query to match
+    /// 
+    /// Returns:
+    ///     list[PyUITreeNode]: The matching nodes
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// tree = PyUITree.load("notepad.json")
+    /// for node in tree.find(PyUIQuery.by_type("MenuItem")):
+    ///     print(node.name)
+    /// ```
+    fn find(&self, query: &PyUIQuery) -> PyResult<Vec<Py<PyUITreeNode>>> {
+        Python::with_gil(|py| {
+            self.inner.find(&query.inner).into_iter()
+                .map(|node| Py::new(py, PyUITreeNode { inner: node.clone() }))
+                .collect()
+        })
+    }
 }
 
 /// Represents a node in the UI tree hierarchy
@@ -781,22 +3537,379 @@ impl PyUITreeNode {
         })
     }
 
-    /// Check if the element is enabled for interaction
-    /// 
-    /// Returns:
-    ///     bool: True if the element can be interacted with
-    #[getter]
-    fn is_enabled(&self) -> bool {
-        self.inner.is_enabled
+    /// Check if the element is enabled for interaction
+    /// 
+    /// Returns:
+    ///     bool: True if the element can be interacted with
+    #[getter]
+    fn is_enabled(&self) -> bool {
+        self.inner.is_enabled
+    }
+
+    /// Check if the element is visible on screen
+    /// 
+    /// Returns:
+    ///     bool: True if the element is visible
+    #[getter]
+    fn is_visible(&self) -> bool {
+        self.inner.is_visible
+    }
+
+    /// Get the screen bounds captured with the tree
+    /// 
+    /// Reading bounds from the snapshot avoids a live query per element, which
+    /// makes spatial reasoning over a whole tree much faster.
+    /// 
+    /// Returns:
+    ///     PyRect | None: The element's screen bounds, or None if they were not captured
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # Find the topmost button without re-querying the window
+    /// def buttons(node):
+    ///     if node.control_type == "Button" and node.bounds is not None:
+    ///         yield node
+    ///     for child in node.children:
+    ///         yield from buttons(child)
+    /// 
+    /// topmost = min(buttons(window.get_ui_tree().root), key=lambda n: n.bounds.top, default=None)
+    /// ```
+    #[getter]
+    fn bounds(&self) -> PyResult<Option<Py<PyRect>>> {
+        match self.inner.bounds.clone() {
+            Some(rect) => Python::with_gil(|py| Ok(Some(Py::new(py, PyRect { inner: rect })?))),
+            None => Ok(None),
+        }
+    }
+}
+
+// =============================================================================
+// KEYBOARD CLASSES
+// =============================================================================
+
+/// A single key, for building key chords
+/// 
+/// Named keys are class attributes (`PyKey.ENTER`, `PyKey.HOME`,
+/// `PyKey.PAGE_DOWN`, ...); characters and function keys are created with
+/// `PyKey.char()` and `PyKey.f()`.
+/// 
+/// # Examples
+/// 
+/// ```python
+/// chord = PyKeyChord(PyKey.HOME, ctrl=True, shift=True)
+/// editor.send_chord(chord)
+/// ```
+#[pyclass]
+#[derive(Clone)]
+pub struct PyKey {
+    inner: Key
+}
+
+#[pymethods]
+impl PyKey {
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn ENTER() -> PyKey {
+        PyKey { inner: Key::Enter }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn TAB() -> PyKey {
+        PyKey { inner: Key::Tab }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn ESCAPE() -> PyKey {
+        PyKey { inner: Key::Escape }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn SPACE() -> PyKey {
+        PyKey { inner: Key::Space }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn BACKSPACE() -> PyKey {
+        PyKey { inner: Key::Backspace }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn DELETE() -> PyKey {
+        PyKey { inner: Key::Delete }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn INSERT() -> PyKey {
+        PyKey { inner: Key::Insert }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn HOME() -> PyKey {
+        PyKey { inner: Key::Home }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn END() -> PyKey {
+        PyKey { inner: Key::End }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn PAGE_UP() -> PyKey {
+        PyKey { inner: Key::PageUp }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn PAGE_DOWN() -> PyKey {
+        PyKey { inner: Key::PageDown }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn LEFT() -> PyKey {
+        PyKey { inner: Key::Left }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn RIGHT() -> PyKey {
+        PyKey { inner: Key::Right }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn UP() -> PyKey {
+        PyKey { inner: Key::Up }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn DOWN() -> PyKey {
+        PyKey { inner: Key::Down }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn PAUSE() -> PyKey {
+        PyKey { inner: Key::Pause }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn CAPS_LOCK() -> PyKey {
+        PyKey { inner: Key::CapsLock }
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn PRINT_SCREEN() -> PyKey {
+        PyKey { inner: Key::PrintScreen }
+    }
+
+    /// Create a key for a printable character
+    /// 
+    /// Characters are typed as written, so use lowercase letters for
+    /// shortcuts: `PyKey.char("A")` presses Shift as well.
+    /// 
+    /// Args:
+    ///     character (str): A single character
+    /// 
+    /// Raises:
+    ///     ValueError: If the string is not exactly one printable character
+    #[staticmethod]
+    fn char(character: &str) -> PyResult<Self> {
+        let mut chars = character.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_control() => Ok(PyKey { inner: Key::Char(c) }),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Expected a single printable character, got {:?}", character))),
+        }
+    }
+
+    /// Create a function key
+    /// 
+    /// Args:
+    ///     number (int): The function key number, 1 to 24
+    /// 
+    /// Raises:
+    ///     ValueError: If there is no such function key
+    #[staticmethod]
+    fn f(number: u8) -> PyResult<Self> {
+        let key = Key::F(number);
+        key.to_key_string().map_err(to_py_err)?;
+        Ok(PyKey { inner: key })
+    }
+
+    /// Look up a key by name, e.g. "enter", "page_down", "F5" or "a"
+    /// 
+    /// Args:
+    ///     name (str): The key name, case-insensitive
+    /// 
+    /// Raises:
+    ///     ValueError: If no key has that name
+    #[staticmethod]
+    fn from_name(name: &str) -> PyResult<Self> {
+        Ok(PyKey { inner: Key::from_name(name).map_err(to_py_err)? })
+    }
+
+    fn __eq__(&self, other: PyRef<PyKey>) -> bool {
+        self.inner == other.inner
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyKey({})", KeyChord::new(self.inner))
+    }
+}
+
+/// Accept either a `PyKey` or a key name
+fn key_from_py(key: &Bound<'_, PyAny>) -> PyResult<Key> {
+    if let Ok(key) = key.extract::<PyRef<PyKey>>() {
+        return Ok(key.inner);
+    }
+    match key.extract::<String>() {
+        Ok(name) => Key::from_name(&name).map_err(to_py_err),
+        Err(_) => Err(pyo3::exceptions::PyTypeError::new_err("Expected a PyKey or a key name")),
+    }
+}
+
+/// Keys pressed together while modifiers are held, e.g. Ctrl+Shift+Home
+/// 
+/// Chords are checked when they are built, so a misspelled key raises at
+/// once instead of typing something unexpected. Keys can be given as
+/// `PyKey` objects or by name.
+/// 
+/// # Examples
+/// 
+/// ```python
+/// window.send_chord(PyKeyChord("s", ctrl=True))                      # Save
+/// editor.send_chord(PyKeyChord(PyKey.HOME, ctrl=True, shift=True))   # Select to start
+/// print(PyKeyChord("a", ctrl=True).then("c").key_string())           # {CTRL}(ac)
+/// ```
+#[pyclass]
+#[derive(Clone)]
+pub struct PyKeyChord {
+    inner: KeyChord
+}
+
+#[pymethods]
+impl PyKeyChord {
+    /// Create a chord pressing one key
+    /// 
+    /// Args:
+    ///     key (PyKey | str): The key to press
+    ///     ctrl (bool): Hold Ctrl (default False)
+    ///     alt (bool): Hold Alt (default False)
+    ///     shift (bool): Hold Shift (default False)
+    ///     win (bool): Hold the Windows key (default False)
+    /// 
+    /// Raises:
+    ///     ValueError: If the key cannot be sent
+    #[new]
+    #[pyo3(signature = (key, ctrl=false, alt=false, shift=false, win=false))]
+    fn new(key: &Bound<'_, PyAny>, ctrl: bool, alt: bool, shift: bool, win: bool) -> PyResult<Self> {
+        let mut modifiers = Modifiers::NONE;
+        for (held, modifier) in [(ctrl, Modifiers::CTRL), (alt, Modifiers::ALT), (shift, Modifiers::SHIFT), (win, Modifiers::WIN)] {
+            if held {
+                modifiers = modifiers | modifier;
+            }
+        }
+        let inner = KeyChord::new(key_from_py(key)?).with(modifiers);
+        inner.to_key_string().map_err(to_py_err)?;
+        Ok(PyKeyChord { inner })
+    }
+
+    /// Return a chord that also presses `key` afterwards, still holding the modifiers
+    /// 
+    /// Args:
+    ///     key (PyKey | str): The key to press next
+    /// 
+    /// Returns:
+    ///     PyKeyChord: The extended chord; this one is unchanged
+    fn then(&self, key: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyKeyChord { inner: self.inner.clone().then(key_from_py(key)?) })
+    }
+
+    /// Get the chord in the `send_keys` syntax
+    /// 
+    /// Returns:
+    ///     str: e.g. "{CTRL}{SHIFT}{HOME}"
+    fn key_string(&self) -> PyResult<String> {
+        self.inner.to_key_string().map_err(to_py_err)
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyKeyChord({})", self.inner)
+    }
+}
+
+// =============================================================================
+// CANCELLATION CLASS
+// =============================================================================
+
+/// A handle for stopping a long-running call from another thread
+/// 
+/// Pass it as `cancel` to get_ui_tree_with_options, find_elements_wait or
+/// text_elements_to_json, then call `cancel()` from another thread. The call
+/// stops at its next node or poll and raises CancelledError. Those calls
+/// release the GIL while they run, so a Python thread can do the cancelling.
+/// 
+/// # Examples
+/// 
+/// ```python
+/// import threading
+/// from uia_interaction import PyCancellationToken, CancelledError
+/// 
+/// cancel = PyCancellationToken()
+/// threading.Timer(2.0, cancel.cancel).start()
+/// try:
+///     tree = window.get_ui_tree_with_options(None, None, True, False, cancel=cancel)
+/// except CancelledError:
+///     print("Capture took too long")
+/// ```
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct PyCancellationToken {
+    inner: CancellationToken
+}
+
+#[pymethods]
+impl PyCancellationToken {
+    /// Create a token that is not cancelled yet
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop every call this token was passed to
+    fn cancel(&self) {
+        self.inner.cancel();
     }
 
-    /// Check if the element is visible on screen
+    /// Whether cancel() has been called
     /// 
     /// Returns:
-    ///     bool: True if the element is visible
+    ///     bool: True once the token is cancelled
     #[getter]
-    fn is_visible(&self) -> bool {
-        self.inner.is_visible
+    fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyCancellationToken(cancelled={})", self.inner.is_cancelled())
     }
 }
 
@@ -841,21 +3954,39 @@ impl PyUIQuery {
     /// Create a query to find elements by name/label
     /// 
     /// Args:
-    ///     name (str): The name or label to search for (exact match)
+    ///     name (str): The name or label to search for
+    ///     match (str, optional): How to compare names: "exact" (default), "contains",
+    ///         "starts_with", "ends_with" or "case_insensitive"
     /// 
     /// Returns:
     ///     PyUIQuery: Query object for finding elements by name
     /// 
+    /// Raises:
+    ///     ValueError: If match is not one of the modes above
+    /// 
     /// # Examples
     /// 
     /// ```python
     /// # Find the "Save" button
     /// save_query = PyUIQuery.by_name("Save")
     /// save_buttons = window.find_elements(save_query)
+    /// 
+    /// # Find "Save", "Save As..." and "Save all"
+    /// save_items = window.find_elements(PyUIQuery.by_name("Save", match="starts_with"))
     /// ```
     #[staticmethod]
-    fn by_name(name: String) -> Self {
-        Self { inner: UIQuery::ByName(name) }
+    #[pyo3(signature = (name, r#match=None))]
+    fn by_name(name: String, r#match: Option<&str>) -> PyResult<Self> {
+        let mode = match r#match {
+            None | Some("exact") => return Ok(Self { inner: UIQuery::ByName(name) }),
+            Some("contains") => NameMatch::Contains,
+            Some("starts_with") => NameMatch::StartsWith,
+            Some("ends_with") => NameMatch::EndsWith,
+            Some("case_insensitive") => NameMatch::CaseInsensitive,
+            Some(other) => return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown name match '{}': expected exact, contains, starts_with, ends_with or case_insensitive", other))),
+        };
+        Ok(Self { inner: UIQuery::ByNameMatch(name, mode) })
     }
 
     /// Create a query to find elements by control type
@@ -882,6 +4013,28 @@ impl PyUIQuery {
         Self { inner: UIQuery::ByType(control_type) }
     }
 
+    /// Create a query to find elements of any of several control types
+    /// 
+    /// Searches once for all the types, so the results come in document order
+    /// without duplicates, and faster than `or_` of several `by_type` queries.
+    /// 
+    /// Args:
+    ///     control_types (list[str]): The control types to search for (e.g., ["Edit", "Document", "ComboBox"])
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object for finding elements of any of the types
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # Find every text input
+    /// inputs = window.find_elements(PyUIQuery.by_types(["Edit", "Document", "ComboBox"]))
+    /// ```
+    #[staticmethod]
+    fn by_types(control_types: Vec<String>) -> Self {
+        Self { inner: UIQuery::ByTypes(control_types) }
+    }
+
     /// Create a query to find elements by a specific property
     /// 
     /// Args:
@@ -907,6 +4060,125 @@ impl PyUIQuery {
         Self { inner: UIQuery::ByProperty(key, value) }
     }
 
+    /// Create a query to find elements by automation ID
+    /// 
+    /// Automation IDs are stable across localization and UI redesigns, making
+    /// this the most reliable way to locate a specific element.
+    /// 
+    /// Args:
+    ///     automation_id (str): The automation ID to search for (case-sensitive exact match)
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object for finding elements by automation ID
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// save_buttons = window.find_elements(PyUIQuery.by_automation_id("SaveButton"))
+    /// ```
+    #[staticmethod]
+    fn by_automation_id(automation_id: String) -> Self {
+        Self { inner: UIQuery::ByAutomationId(automation_id) }
+    }
+
+    /// Create a query to find elements by class name
+    /// 
+    /// Useful for telling apart panes that share a control type, such as the
+    /// render widgets of Electron/Chromium applications.
+    /// 
+    /// Args:
+    ///     class_name (str): The class name to search for (case-sensitive exact match)
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object for finding elements by class name
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// panes = window.find_elements(PyUIQuery.by_class_name("Chrome_RenderWidgetHostHWND"))
+    /// ```
+    #[staticmethod]
+    fn by_class_name(class_name: String) -> Self {
+        Self { inner: UIQuery::ByClassName(class_name) }
+    }
+
+    /// Create a query to find elements by UI Automation control type identifier
+    /// 
+    /// Unlike by_type, an identifier can never silently fall back to another
+    /// control type.
+    /// 
+    /// Args:
+    ///     control_type_id (int): The control type identifier, e.g. 50015 for Slider
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object for finding elements by control type
+    /// 
+    /// Raises:
+    ///     ValueError: If the identifier is not a known control type
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// sliders = window.find_elements(PyUIQuery.by_control_type_id(50015))
+    /// ```
+    #[staticmethod]
+    fn by_control_type_id(control_type_id: i32) -> PyResult<Self> {
+        if control_type::control_type_name(control_type_id).is_none() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown control type id {}", control_type_id)));
+        }
+        Ok(Self { inner: UIQuery::ByControlTypeId(control_type_id) })
+    }
+
+    /// Create a query to find elements whose text content contains a substring
+    /// 
+    /// Matches the text returned by `PyUIElement.get_text()` (the Value or Text
+    /// pattern), not the name, so it finds paragraphs in documents and read-only
+    /// text. Every text-bearing element has to be read, which makes this much
+    /// slower than by_name or by_automation_id; from a window the search only
+    /// descends 20 levels.
+    /// 
+    /// Args:
+    ///     text (str): Substring to look for (case-sensitive)
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object for finding elements by text content
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// paragraphs = window.find_elements(PyUIQuery.by_text("Total due"))
+    /// ```
+    #[staticmethod]
+    fn by_text(text: String) -> Self {
+        Self { inner: UIQuery::ByText(text) }
+    }
+
+    /// Create a query to find elements whose name matches a regular expression
+    /// 
+    /// The pattern is unanchored, so "Notepad" matches "Document1 — Notepad";
+    /// use ^...$ to require a full match.
+    /// 
+    /// Args:
+    ///     pattern (str): Regular expression to match against element names
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object for finding elements by name pattern
+    /// 
+    /// Raises:
+    ///     ValueError: If the pattern is not a valid regular expression
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// inbox = window.find_elements(PyUIQuery.by_name_regex(r"^Inbox \(\d+\)$"))
+    /// ```
+    #[staticmethod]
+    fn by_name_regex(pattern: &str) -> PyResult<Self> {
+        let inner = UIQuery::by_name_regex(pattern)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(Self { inner })
+    }
+
     /// Create a query that matches elements satisfying ALL of the given queries
     /// 
     /// Args:
@@ -930,51 +4202,236 @@ impl PyUIQuery {
     /// matching_buttons = window.find_elements(complex_query)
     /// ```
     #[staticmethod]
-    fn and_(queries: Vec<Py<PyUIQuery>>) -> PyResult<Self> {
-        Python::with_gil(|py| {
-            let inner_queries: Vec<UIQuery> = queries.iter()
-                .map(|q| q.borrow(py).inner.clone())
-                .collect();
-            Ok(Self { inner: UIQuery::And(inner_queries) })
-        })
+    fn and_(queries: Vec<Py<PyUIQuery>>) -> PyResult<Self> {
+        Python::with_gil(|py| {
+            let inner_queries: Vec<UIQuery> = queries.iter()
+                .map(|q| q.borrow(py).inner.clone())
+                .collect();
+            Ok(Self { inner: UIQuery::And(inner_queries) })
+        })
+    }
+
+    /// Create a query that matches elements satisfying ANY of the given queries
+    /// 
+    /// Args:
+    ///     queries (list[PyUIQuery]): List of queries where at least one must match
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object that requires at least one condition to be met
+    /// 
+    /// Raises:
+    ///     RuntimeError: If query construction fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # Find either "OK" or "Cancel" buttons
+    /// choice_query = PyUIQuery.or_([
+    ///     PyUIQuery.by_name("OK"),
+    ///     PyUIQuery.by_name("Cancel")
+    /// ])
+    /// action_buttons = window.find_elements(choice_query)
+    /// 
+    /// # Find any text input controls (Edit or Document)
+    /// text_query = PyUIQuery.or_([
+    ///     PyUIQuery.by_type("Edit"),
+    ///     PyUIQuery.by_type("Document")
+    /// ])
+    /// text_controls = window.find_elements(text_query)
+    /// ```
+    #[staticmethod]
+    fn or_(queries: Vec<Py<PyUIQuery>>) -> PyResult<Self> {
+        Python::with_gil(|py| {
+            let inner_queries: Vec<UIQuery> = queries.iter()
+                .map(|q| q.borrow(py).inner.clone())
+                .collect();
+            Ok(Self { inner: UIQuery::Or(inner_queries) })
+        })
+    }
+
+    /// Create a query that matches only the Nth match of another query
+    /// 
+    /// Matches are ordered as they appear in the UI tree (pre-order, depth-first).
+    /// An index past the last match yields no elements rather than an error.
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query whose matches to index into
+    ///     index (int): Zero-based position of the match to keep
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching at most one element
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # The third button in the window
+    /// third = window.find_elements(PyUIQuery.nth(PyUIQuery.by_type("Button"), 2))
+    /// ```
+    #[staticmethod]
+    fn nth(query: &PyUIQuery, index: usize) -> Self {
+        Self { inner: UIQuery::Nth(Box::new(query.inner.clone()), index) }
+    }
+
+    /// Create a query that keeps at most the first N matches of another query
+    /// 
+    /// Matches are ordered as they appear in the UI tree (pre-order, depth-first).
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query whose matches to limit
+    ///     count (int): Maximum number of matches to keep
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching at most `count` elements
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// first_five = window.find_elements(PyUIQuery.limit(PyUIQuery.by_type("ListItem"), 5))
+    /// ```
+    #[staticmethod]
+    fn limit(query: &PyUIQuery, count: usize) -> Self {
+        Self { inner: UIQuery::Limit(Box::new(query.inner.clone()), count) }
+    }
+
+    /// Create a query that keeps only the on-screen matches of another query
+    /// 
+    /// Elements scrolled out of view or inside hidden containers are excluded.
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query whose matches to filter
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching visible elements only
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// rows = window.find_elements(PyUIQuery.visible(PyUIQuery.by_type("ListItem")))
+    /// ```
+    #[staticmethod]
+    fn visible(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Visible(Box::new(query.inner.clone())) }
+    }
+
+    /// Create a query that keeps only the enabled matches of another query
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query whose matches to filter
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching enabled elements only
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// buttons = window.find_elements(PyUIQuery.enabled(PyUIQuery.by_type("Button")))
+    /// ```
+    #[staticmethod]
+    fn enabled(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Enabled(Box::new(query.inner.clone())) }
+    }
+
+    /// Create a query that matches elements NOT matching another query
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query to negate
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching everything `query` does not
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// non_buttons = window.find_elements(PyUIQuery.not_(PyUIQuery.by_type("Button")))
+    /// ```
+    #[staticmethod]
+    fn not_(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Not(Box::new(query.inner.clone())) }
+    }
+
+    /// Create a query that matches elements with a direct child matching another query
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query a child must match
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching parents of such children
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// # Toolbars that contain a "Save" button
+    /// toolbars = window.find_elements(PyUIQuery.and_([
+    ///     PyUIQuery.by_type("ToolBar"),
+    ///     PyUIQuery.child(PyUIQuery.by_name("Save"))
+    /// ]))
+    /// ```
+    #[staticmethod]
+    fn child(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Child(Box::new(query.inner.clone())) }
+    }
+
+    /// Create a query that matches elements with any descendant matching another query
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query a descendant must match
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching containers of such descendants
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// panes = window.find_elements(PyUIQuery.and_([
+    ///     PyUIQuery.by_type("Pane"),
+    ///     PyUIQuery.descendant(PyUIQuery.by_type("Edit"))
+    /// ]))
+    /// ```
+    #[staticmethod]
+    fn descendant(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Descendant(Box::new(query.inner.clone())) }
+    }
+
+    /// Create a query that matches elements whose direct parent matches another query
+    /// 
+    /// Args:
+    ///     query (PyUIQuery): The query the parent must match
+    /// 
+    /// Returns:
+    ///     PyUIQuery: Query object matching children of such parents
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// tool_buttons = window.find_elements(PyUIQuery.and_([
+    ///     PyUIQuery.by_type("Button"),
+    ///     PyUIQuery.parent(PyUIQuery.by_type("ToolBar"))
+    /// ]))
+    /// ```
+    #[staticmethod]
+    fn parent(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Parent(Box::new(query.inner.clone())) }
     }
 
-    /// Create a query that matches elements satisfying ANY of the given queries
+    /// Create a query that matches elements with any ancestor matching another query
     /// 
     /// Args:
-    ///     queries (list[PyUIQuery]): List of queries where at least one must match
+    ///     query (PyUIQuery): The query an ancestor must match
     /// 
     /// Returns:
-    ///     PyUIQuery: Query object that requires at least one condition to be met
-    /// 
-    /// Raises:
-    ///     RuntimeError: If query construction fails
+    ///     PyUIQuery: Query object matching elements nested inside such ancestors
     /// 
     /// # Examples
     /// 
     /// ```python
-    /// # Find either "OK" or "Cancel" buttons
-    /// choice_query = PyUIQuery.or_([
-    ///     PyUIQuery.by_name("OK"),
-    ///     PyUIQuery.by_name("Cancel")
-    /// ])
-    /// action_buttons = window.find_elements(choice_query)
-    /// 
-    /// # Find any text input controls (Edit or Document)
-    /// text_query = PyUIQuery.or_([
-    ///     PyUIQuery.by_type("Edit"),
-    ///     PyUIQuery.by_type("Document")
-    /// ])
-    /// text_controls = window.find_elements(text_query)
+    /// dialog_buttons = window.find_elements(PyUIQuery.and_([
+    ///     PyUIQuery.by_type("Button"),
+    ///     PyUIQuery.ancestor(PyUIQuery.by_name("Settings"))
+    /// ]))
     /// ```
     #[staticmethod]
-    fn or_(queries: Vec<Py<PyUIQuery>>) -> PyResult<Self> {
-        Python::with_gil(|py| {
-            let inner_queries: Vec<UIQuery> = queries.iter()
-                .map(|q| q.borrow(py).inner.clone())
-                .collect();
-            Ok(Self { inner: UIQuery::Or(inner_queries) })
-        })
+    fn ancestor(query: &PyUIQuery) -> Self {
+        Self { inner: UIQuery::Ancestor(Box::new(query.inner.clone())) }
     }
 }
 
@@ -1072,6 +4529,140 @@ impl PyApplicationInfo {
     }
 }
 
+/// An application launched by PyApplicationManager.launch_application
+/// 
+/// Use it as a context manager to have the process terminated when the block
+/// exits, even on errors: the main window is activated on entry and the
+/// process is killed on exit unless `keep_alive` is set. A process that has
+/// already exited (for example because the script closed its window) is not
+/// an error.
+/// 
+/// Every PyApplicationInfo attribute (process_id, process_name,
+/// main_window_title, ...) is available directly on this object.
+/// 
+/// # Examples
+/// 
+/// ```python
+/// app_manager = PyApplicationManager()
+/// with app_manager.launch_application("notepad.exe") as app:
+///     window = app_manager.get_window_by_process_id(app.process_id)
+///     window.send_keys("Hello")
+/// # Notepad has been terminated here
+/// 
+/// with app_manager.launch_application("notepad.exe", keep_alive=True) as app:
+///     ...
+/// # Notepad is still running
+/// ```
+#[pyclass]
+pub struct PyManagedApplication {
+    info: ApplicationInfo,
+    manager: Arc<ThreadSafe<Box<dyn ApplicationManager>>>,
+    keep_alive: bool,
+}
+
+#[pymethods]
+impl PyManagedApplication {
+    /// Get the information captured when the application was launched
+    /// 
+    /// Returns:
+    ///     PyApplicationInfo: Process and main window details
+    #[getter]
+    fn info(&self) -> PyApplicationInfo {
+        PyApplicationInfo { inner: self.info.clone() }
+    }
+
+    /// Whether leaving a `with` block leaves the process running
+    /// 
+    /// Returns:
+    ///     bool: True if the process is not terminated on exit
+    #[getter]
+    fn keep_alive(&self) -> bool {
+        self.keep_alive
+    }
+
+    #[setter]
+    fn set_keep_alive(&mut self, keep_alive: bool) {
+        self.keep_alive = keep_alive;
+    }
+
+    /// Terminate the process now
+    /// 
+    /// Does nothing if the process has already exited.
+    /// 
+    /// Raises:
+    ///     AccessDeniedError: If the process may not be terminated
+    fn terminate(&self) -> PyResult<()> {
+        let inner = self.manager.lock()?;
+        match inner.kill_application(self.info.process_id) {
+            Ok(()) => Ok(()),
+            // The application may have been closed already, by the script or the user
+            Err(_) if !inner.is_process_running(self.info.process_id).unwrap_or(true) => Ok(()),
+            Err(e) => Err(to_py_err(e)),
+        }
+    }
+
+    /// Re-read the main window details of the process and update `info`
+    /// 
+    /// Returns:
+    ///     PyApplicationInfo: The current details of the process
+    /// 
+    /// Raises:
+    ///     ElementNotFoundError: If the process has exited
+    fn refresh(&mut self) -> PyResult<PyApplicationInfo> {
+        let info = {
+            let inner = self.manager.lock()?;
+            inner.refresh_application(self.info.process_id).map_err(to_py_err)?
+        };
+        self.info = info;
+        Ok(self.info())
+    }
+
+    /// Activate the main window and return the application itself
+    fn __enter__(slf: PyRef<'_, Self>) -> PyResult<PyRef<'_, Self>> {
+        let activated = {
+            let inner = slf.manager.lock()?;
+            inner.get_window_by_process_id(slf.info.process_id)
+                .and_then(|window| window.activate())
+        };
+        // Foreground changes can be refused by the OS; the app is still usable
+        if let Err(e) = activated {
+            warn!("Could not activate launched application {}: {}", slf.info.process_id, e);
+        }
+        Ok(slf)
+    }
+
+    /// Terminate the process unless `keep_alive` is set; exceptions are never suppressed
+    fn __exit__(
+        &self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if !self.keep_alive {
+            self.terminate()?;
+        }
+        Ok(false)
+    }
+
+    /// Forward any other attribute to the PyApplicationInfo
+    fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        Py::new(py, self.info())?.getattr(py, name)
+    }
+
+    /// Get a string representation of the managed application
+    /// 
+    /// Returns:
+    ///     str: Human-readable representation
+    fn __repr__(&self) -> String {
+        format!(
+            "ManagedApplication(process_id={}, process_name='{}', keep_alive={})",
+            self.info.process_id,
+            self.info.process_name,
+            if self.keep_alive { "True" } else { "False" }
+        )
+    }
+}
+
 /// Manager for discovering and interacting with running applications
 /// 
 /// This class provides methods to find running applications, get their windows,
@@ -1114,7 +4705,7 @@ impl PyApplicationManager {
     #[new]
     pub fn new() -> PyResult<Self> {
         let manager = ApplicationManagerFactory::new()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            .map_err(to_py_err)?;
         Ok(Self { 
             inner: Arc::new(ThreadSafe::new(manager))
         })
@@ -1122,6 +4713,8 @@ impl PyApplicationManager {
 
     /// Get information about all running applications
     /// 
+    /// Each process is listed once, sorted by process ID.
+    /// 
     /// Returns:
     ///     list[PyApplicationInfo]: List of all running applications
     /// 
@@ -1141,9 +4734,9 @@ impl PyApplicationManager {
     /// ```
     fn get_all_applications(&self) -> PyResult<Vec<Py<PyApplicationInfo>>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let apps = inner.get_all_applications()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+                .map_err(to_py_err)?;
             
             Ok(apps.into_iter()
                 .map(|app| Py::new(py, PyApplicationInfo { inner: app }).unwrap())
@@ -1176,9 +4769,9 @@ impl PyApplicationManager {
     /// ```
     fn find_applications_by_name(&self, name: &str) -> PyResult<Vec<Py<PyApplicationInfo>>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let apps = inner.find_applications_by_name(name)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+                .map_err(to_py_err)?;
             
             Ok(apps.into_iter()
                 .map(|app| Py::new(py, PyApplicationInfo { inner: app }).unwrap())
@@ -1211,9 +4804,40 @@ impl PyApplicationManager {
     /// ```
     fn find_applications_by_title(&self, title: &str) -> PyResult<Vec<Py<PyApplicationInfo>>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let apps = inner.find_applications_by_title(title)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+                .map_err(to_py_err)?;
+            
+            Ok(apps.into_iter()
+                .map(|app| Py::new(py, PyApplicationInfo { inner: app }).unwrap())
+                .collect())
+        })
+    }
+
+    /// Find applications by executable path
+    /// 
+    /// Matches a case-insensitive substring of the full executable path, which
+    /// tells apart executables that share a file name.
+    /// 
+    /// Args:
+    ///     path_substring (str): Part of the executable path to search for
+    /// 
+    /// Returns:
+    ///     list[PyApplicationInfo]: List of matching applications
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the search fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// apps = app_manager.find_applications_by_path(r"C:\Program Files\MyApp")
+    /// ```
+    fn find_applications_by_path(&self, path_substring: &str) -> PyResult<Vec<Py<PyApplicationInfo>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let apps = inner.find_applications_by_path(path_substring)
+                .map_err(to_py_err)?;
             
             Ok(apps.into_iter()
                 .map(|app| Py::new(py, PyApplicationInfo { inner: app }).unwrap())
@@ -1221,6 +4845,150 @@ impl PyApplicationManager {
         })
     }
 
+    /// Launch an application and wait for its main window
+    /// 
+    /// Args:
+    ///     path (str): Executable path or name (resolved through PATH)
+    ///     args (list[str]): Command-line arguments to pass to the process
+    ///     keep_alive (bool): Leave the process running when a `with` block exits
+    /// 
+    /// Returns:
+    ///     PyManagedApplication: The launched application, usable as a context
+    ///         manager that terminates it on exit
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the process cannot be started or shows no window
+    ///         before the timeout
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// with app_manager.launch_application("notepad.exe") as app:
+    ///     window = app_manager.get_window_by_process_id(app.process_id)
+    ///     window.send_keys("Hello")
+    /// ```
+    #[pyo3(signature = (path, args=Vec::new(), keep_alive=false))]
+    fn launch_application(&self, path: &str, args: Vec<String>, keep_alive: bool) -> PyResult<Py<PyManagedApplication>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let app = inner.launch_application(path, &args)
+                .map_err(to_py_err)?;
+            
+            Py::new(py, PyManagedApplication {
+                info: app,
+                manager: self.inner.clone(),
+                keep_alive,
+            })
+        })
+    }
+
+    /// Launch an application and wait for its window to appear
+    /// 
+    /// Spawns the process and returns its first visible top-level window whose
+    /// title contains `title_contains` (or any window if it is None). If the
+    /// wait fails, the process is terminated so no orphan is left running.
+    /// 
+    /// Args:
+    ///     path (str): Path to the executable (or a name resolvable via PATH)
+    ///     title_contains (str | None): Substring the window title must contain
+    ///     timeout_ms (int): How long to wait for the window, in milliseconds
+    /// 
+    /// Returns:
+    ///     PyWindow: The matching window
+    /// 
+    /// Raises:
+    ///     TimeoutError: If no matching window appears within the timeout
+    ///     UiAutomationError: If the process cannot be started or exits early
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// window = app_manager.launch_and_wait("notepad.exe", title_contains="Notepad")
+    /// window.activate()
+    /// ```
+    #[pyo3(signature = (path, title_contains=None, timeout_ms=10000))]
+    fn launch_and_wait(&self, path: &str, title_contains: Option<&str>, timeout_ms: u64) -> PyResult<Py<PyWindow>> {
+        Python::with_gil(|py| {
+            // Other Python threads keep running while the window is awaited
+            let window = py.allow_threads(|| {
+                let inner = self.inner.lock()?;
+                inner.launch_and_wait(path, &[], title_contains, Duration::from_millis(timeout_ms))
+                    .map_err(|error| match error {
+                        UiError::Timeout(message) => pyo3::exceptions::PyTimeoutError::new_err(message),
+                        other => to_py_err(other),
+                    })
+                    // Boxed windows are only Send once wrapped, see `ComShared`
+                    .map(ThreadSafe::new)
+            })?;
+
+            Py::new(py, PyWindow {
+                inner: Arc::new(window)
+            })
+        })
+    }
+
+    /// Terminate a running process
+    /// 
+    /// Args:
+    ///     pid (int): The process ID to terminate
+    /// 
+    /// Raises:
+    ///     AccessDeniedError: If the process is protected or owned by another user
+    ///     ValueError: If no process has this ID
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// app = app_manager.launch_application("notepad.exe", [])
+    /// app_manager.kill_application(app.process_id)
+    /// ```
+    fn kill_application(&self, pid: u32) -> PyResult<()> {
+        let inner = self.inner.lock()?;
+        inner.kill_application(pid)
+            .map_err(to_py_err)
+    }
+
+    /// Check whether a process is still running
+    /// 
+    /// Args:
+    ///     pid (int): The process ID to check
+    /// 
+    /// Returns:
+    ///     bool: True if the process is running, False if it has exited
+    /// 
+    /// Raises:
+    ///     AccessDeniedError: If the process exists but may not be queried
+    fn is_process_running(&self, pid: u32) -> PyResult<bool> {
+        let inner = self.inner.lock()?;
+        inner.is_process_running(pid)
+            .map_err(to_py_err)
+    }
+
+    /// Re-read the main window details of one process
+    /// 
+    /// Args:
+    ///     pid (int): The process ID to look up
+    /// 
+    /// Returns:
+    ///     PyApplicationInfo: The current details of the process
+    /// 
+    /// Raises:
+    ///     ElementNotFoundError: If the process has exited
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// app = app_manager.launch_application("notepad.exe", [])
+    /// app = app_manager.refresh(app.process_id)
+    /// print(app.main_window_title)
+    /// ```
+    fn refresh(&self, pid: u32) -> PyResult<PyApplicationInfo> {
+        let inner = self.inner.lock()?;
+        inner.refresh_application(pid)
+            .map(|info| PyApplicationInfo { inner: info })
+            .map_err(to_py_err)
+    }
+
     /// Get a window from an application by process ID
     /// 
     /// Creates a Window object for the main window of the application
@@ -1246,13 +5014,93 @@ impl PyApplicationManager {
     /// ```
     fn get_window_by_process_id(&self, process_id: u32) -> PyResult<Py<PyWindow>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let window = inner.get_window_by_process_id(process_id)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+                .map_err(to_py_err)?;
             
-            Ok(Py::new(py, PyWindow { 
+            Py::new(py, PyWindow { 
                 inner: Arc::new(ThreadSafe::new(window))
-            })?)
+            })
+        })
+    }
+
+    /// Get every main window of an application by process ID
+    /// 
+    /// Useful for applications such as browsers that own several top-level
+    /// windows under one process.
+    /// 
+    /// Args:
+    ///     process_id (int): The process ID of the application
+    /// 
+    /// Returns:
+    ///     list[PyWindow]: All visible, titled, top-level windows of the process
+    /// 
+    /// Raises:
+    ///     RuntimeError: If a window cannot be accessed
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// for window in app_manager.get_windows_by_process_id(app.process_id):
+    ///     print(window.title)
+    /// ```
+    fn get_windows_by_process_id(&self, process_id: u32) -> PyResult<Vec<Py<PyWindow>>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let windows = inner.get_windows_by_process_id(process_id)
+                .map_err(to_py_err)?;
+            
+            windows.into_iter()
+                .map(|window| Py::new(py, PyWindow { 
+                    inner: Arc::new(ThreadSafe::new(window))
+                }))
+                .collect()
+        })
+    }
+
+    /// Find elements matching a query in every window of an application
+    /// 
+    /// Searches each top-level window of the process, including owned
+    /// popups, which is as slow as one `find_elements` call per window.
+    /// Prefer `PyWindow.find_elements` when the window is known.
+    /// 
+    /// Args:
+    ///     process_id (int): The process ID of the application
+    ///     query (PyUIQuery): The search criteria
+    /// 
+    /// Returns:
+    ///     list[tuple[PyWindow, PyUIElement]]: Each match with the window it was
+    ///         found in; windows in Z-order (topmost first), elements in
+    ///         document order within a window
+    /// 
+    /// Raises:
+    ///     RuntimeError: If the windows cannot be listed or a search fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```python
+    /// for window, element in app_manager.find_elements_in_application(pid, PyUIQuery.by_name("OK")):
+    ///     print(f"{window.title}: {element.name}")
+    /// ```
+    fn find_elements_in_application(&self, process_id: u32, query: &PyUIQuery) -> PyResult<Vec<(Py<PyWindow>, Py<PyUIElement>)>> {
+        Python::with_gil(|py| {
+            let inner = self.inner.lock()?;
+            let found = inner.find_elements_in_application(process_id, &query.inner)
+                .map_err(to_py_err)?;
+            
+            let mut results = Vec::new();
+            for matches in found {
+                let window = Py::new(py, PyWindow {
+                    inner: Arc::new(ThreadSafe::new(matches.window))
+                })?;
+                for element in matches.elements {
+                    let element = Py::new(py, PyUIElement {
+                        inner: Arc::new(ThreadSafe::new(element))
+                    })?;
+                    results.push((window.clone_ref(py), element));
+                }
+            }
+            Ok(results)
         })
     }
 
@@ -1282,31 +5130,233 @@ impl PyApplicationManager {
     /// ```
     fn get_window_by_process_name(&self, name: &str) -> PyResult<Py<PyWindow>> {
         Python::with_gil(|py| {
-            let inner = self.inner.0.lock().unwrap();
+            let inner = self.inner.lock()?;
             let window = inner.get_window_by_process_name(name)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+                .map_err(to_py_err)?;
             
-            Ok(Py::new(py, PyWindow { 
+            Py::new(py, PyWindow { 
                 inner: Arc::new(ThreadSafe::new(window))
-            })?)
+            })
         })
     }
 }
 
+// =============================================================================
+// MODULE FUNCTIONS
+// =============================================================================
+
+/// Extract a window's text elements as a JSON array
+/// 
+/// Each element is an object with the keys text, name, control_type,
+/// localized_control_type, automation_id, class_name, bounds, is_selected,
+/// is_editable, is_visible, is_enabled, parent_name and depth. `bounds` is an object with left, top,
+/// right and bottom, or null when the element has no on-screen position.
+/// 
+/// The keyword arguments are the fields of the Rust `TextExtractionOptions`
+/// and default to its defaults, so the JSON describes exactly the elements
+/// that extraction selects.
+/// 
+/// Args:
+///     window (PyWindow): The window to extract text from
+///     include_hidden (bool): Include elements that are not visible on screen
+///     include_disabled (bool): Include elements that are disabled
+///     min_text_length (int): Minimum text length to include (0 = include empty)
+///     control_types (list[str] | None): Only include these control types (None = all)
+///     max_depth (int | None): Maximum depth in the UI tree to traverse (None = unlimited)
+///     include_names_as_text (bool): Use element names as text when the text is empty
+///     sort_by_position (bool): Return elements in reading order instead of tree order
+///     row_tolerance (int): Pixels within which element tops count as one row when sorting
+///     cancel (PyCancellationToken, optional): Stops the extraction early when
+///         cancelled from another thread; the GIL is released meanwhile
+/// 
+/// Returns:
+///     str: JSON array of text elements, in document order unless sorted by position
+/// 
+/// Raises:
+///     CancelledError: If `cancel` was cancelled during the extraction
+///     RuntimeError: If the text elements cannot be extracted
+/// 
+/// # Examples
+/// 
+/// ```python
+/// import json
+/// from uia_interaction import PyAutomation, text_elements_to_json
+/// 
+/// window = PyAutomation().active_window()
+/// for element in json.loads(text_elements_to_json(window)):
+///     print(element["control_type"], element["text"])
+/// 
+/// # Only the edit fields, top to bottom
+/// fields = json.loads(text_elements_to_json(window, control_types=["Edit"], sort_by_position=True))
+/// ```
+#[pyfunction]
+#[pyo3(signature = (
+    window,
+    include_hidden=false,
+    include_disabled=true,
+    min_text_length=1,
+    control_types=None,
+    max_depth=Some(20),
+    include_names_as_text=true,
+    sort_by_position=false,
+    row_tolerance=DEFAULT_ROW_TOLERANCE,
+    cancel=None,
+))]
+#[allow(clippy::too_many_arguments)]
+fn text_elements_to_json(
+    py: Python<'_>,
+    window: &PyWindow,
+    include_hidden: bool,
+    include_disabled: bool,
+    min_text_length: usize,
+    control_types: Option<Vec<String>>,
+    max_depth: Option<u32>,
+    include_names_as_text: bool,
+    sort_by_position: bool,
+    row_tolerance: i32,
+    cancel: Option<PyRef<PyCancellationToken>>,
+) -> PyResult<String> {
+    let options = TextExtractionOptions {
+        include_hidden,
+        include_disabled,
+        min_text_length,
+        control_types,
+        max_depth,
+        include_names_as_text,
+        sort_by_position,
+        row_tolerance,
+        cancel: cancel.map(|cancel| cancel.inner.clone()),
+    };
+    py.allow_threads(|| {
+        let inner = window.inner.lock()?;
+        let elements = inner.get_text_elements(&options)
+            .map_err(to_py_err)?;
+        core_text_elements_to_json(&elements)
+            .map_err(to_py_err)
+    })
+}
+
 // =============================================================================
 // MODULE REGISTRATION
 // =============================================================================
 
 /// Register all Python classes and create the uia_interaction module
 #[pymodule]
-pub fn uia_interaction(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+pub fn uia_interaction(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAutomation>()?;
     m.add_class::<PyWindow>()?;
     m.add_class::<PyUIElement>()?;
+    m.add_class::<PyRect>()?;
+    m.add_class::<PyRangeValue>()?;
+    m.add_class::<PySelectedTextInfo>()?;
+    m.add_class::<PyTextVerification>()?;
     m.add_class::<PyUITree>()?;
     m.add_class::<PyUITreeNode>()?;
+    m.add_class::<PyKey>()?;
+    m.add_class::<PyKeyChord>()?;
+    m.add_class::<PyCancellationToken>()?;
     m.add_class::<PyUIQuery>()?;
     m.add_class::<PyApplicationInfo>()?;
+    m.add_class::<PyManagedApplication>()?;
     m.add_class::<PyApplicationManager>()?;
+    m.add_function(wrap_pyfunction!(text_elements_to_json, m)?)?;
+    m.add("UiAutomationError", py.get_type_bound::<UiAutomationError>())?;
+    m.add("ElementNotFoundError", py.get_type_bound::<ElementNotFoundError>())?;
+    m.add("WindowNotFoundError", py.get_type_bound::<WindowNotFoundError>())?;
+    m.add("PatternUnsupportedError", py.get_type_bound::<PatternUnsupportedError>())?;
+    m.add("AccessDeniedError", py.get_type_bound::<AccessDeniedError>())?;
+    m.add("UiTimeoutError", py.get_type_bound::<UiTimeoutError>())?;
+    m.add("UnsupportedOperationError", py.get_type_bound::<UnsupportedOperationError>())?;
+    m.add("CancelledError", py.get_type_bound::<CancelledError>())?;
     Ok(())
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SAFETY: a plain integer has no apartment affinity; it only stands in for a handle below
+    unsafe impl ComShared for i32 {}
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_lock_refuses_single_threaded_apartment() {
+        use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED};
+
+        let shared = Arc::new(ThreadSafe::new(0));
+        let on_sta = shared.clone();
+        let refused = std::thread::spawn(move || {
+            unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED).ok().unwrap() };
+            let refused = on_sta.lock().is_err();
+            unsafe { CoUninitialize() };
+            refused
+        }).join().unwrap();
+        assert!(refused);
+
+        // A thread that never initialized COM joins the MTA
+        let on_fresh_thread = shared.clone();
+        assert!(std::thread::spawn(move || on_fresh_thread.lock().is_ok()).join().unwrap());
+    }
+
+    #[test]
+    fn test_relational_builders_wrap_the_given_query() {
+        let button = PyUIQuery::by_type("Button".to_string());
+        let is_button = |query: &UIQuery| matches!(query, UIQuery::ByType(t) if t == "Button");
+
+        assert!(matches!(&PyUIQuery::not_(&button).inner, UIQuery::Not(q) if is_button(q)));
+        assert!(matches!(&PyUIQuery::child(&button).inner, UIQuery::Child(q) if is_button(q)));
+        assert!(matches!(&PyUIQuery::descendant(&button).inner, UIQuery::Descendant(q) if is_button(q)));
+        assert!(matches!(&PyUIQuery::parent(&button).inner, UIQuery::Parent(q) if is_button(q)));
+        assert!(matches!(&PyUIQuery::ancestor(&button).inner, UIQuery::Ancestor(q) if is_button(q)));
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_not_query_filters_out_buttons_from_python() {
+        Python::with_gil(|py| -> PyResult<()> {
+            let module = PyModule::new_bound(py, "uia_interaction")?;
+            uia_interaction(py, &module)?;
+            py.import_bound("sys")?.getattr("modules")?.set_item("uia_interaction", &module)?;
+
+            py.run_bound(r#"
+from uia_interaction import PyAutomation, PyUIQuery
+
+window = PyAutomation().active_window()
+non_buttons = window.find_elements(PyUIQuery.not_(PyUIQuery.by_type("Button")))
+assert non_buttons
+assert all(element.control_type != "Button" for element in non_buttons)
+"#, None, None)
+        }).unwrap();
+    }
+
+    #[test]
+    #[ignore] // Requires a desktop session
+    fn test_focused_element_from_many_python_threads() {
+        Python::with_gil(|py| -> PyResult<()> {
+            let module = PyModule::new_bound(py, "uia_interaction")?;
+            uia_interaction(py, &module)?;
+            py.import_bound("sys")?.getattr("modules")?.set_item("uia_interaction", &module)?;
+
+            py.run_bound(r#"
+import threading
+from uia_interaction import PyAutomation
+
+automation = PyAutomation()
+errors = []
+
+def hammer():
+    try:
+        for _ in range(200):
+            automation.focused_element().name
+    except Exception as e:
+        errors.append(e)
+
+threads = [threading.Thread(target=hammer) for _ in range(8)]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
+assert not errors, errors
+"#, None, None)
+        }).unwrap();
+    }
+}
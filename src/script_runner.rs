@@ -12,7 +12,7 @@ pub fn run_script(path: &Path) -> Result<()> {
         let globals = PyDict::new_bound(py);
 
         // Create and inject the automation object
-        let automation = Py::new(py, PyAutomation::new()?)?;
+        let automation = Py::new(py, PyAutomation::new(None, None)?)?;
         globals.set_item("automation", automation)?;
 
         // Load the script as a module
@@ -0,0 +1,70 @@
+//! Optional timing of slow UI Automation calls
+//!
+//! Hot paths open an [`OperationTimer`] and mark each phase as it completes.
+//! With the `timing` feature enabled, the elapsed milliseconds of every phase
+//! and of the whole operation are logged at `debug` level:
+//!
+//! ```text
+//! find_elements: native FindAll took 412 ms
+//! find_elements: wrap results took 3 ms
+//! find_elements: finished in 415 ms
+//! ```
+//!
+//! The `tracing` feature additionally opens a `tracing` span per operation and
+//! emits each phase as a `tracing` event. Without either feature the timer is
+//! a zero-sized type and every call compiles to nothing.
+
+#[cfg(feature = "timing")]
+use std::time::Instant;
+
+/// Times one operation and the phases it goes through
+pub(crate) struct OperationTimer {
+    #[cfg(feature = "timing")]
+    operation: &'static str,
+    #[cfg(feature = "timing")]
+    start: Instant,
+    #[cfg(feature = "timing")]
+    phase_start: Instant,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl OperationTimer {
+    /// Start timing `operation`
+    #[inline]
+    pub(crate) fn start(#[allow(unused_variables)] operation: &'static str) -> Self {
+        OperationTimer {
+            #[cfg(feature = "timing")]
+            operation,
+            #[cfg(feature = "timing")]
+            start: Instant::now(),
+            #[cfg(feature = "timing")]
+            phase_start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            _span: tracing::debug_span!("ui_automation", operation).entered(),
+        }
+    }
+
+    /// Record that `phase` finished, timing it from the previous phase (or the start)
+    #[inline]
+    pub(crate) fn phase(&mut self, #[allow(unused_variables)] phase: &'static str) {
+        #[cfg(feature = "timing")]
+        {
+            let elapsed_ms = self.phase_start.elapsed().as_millis();
+            log::debug!("{}: {} took {} ms", self.operation, phase, elapsed_ms);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(phase, elapsed_ms = elapsed_ms as u64, "phase finished");
+            self.phase_start = Instant::now();
+        }
+    }
+}
+
+#[cfg(feature = "timing")]
+impl Drop for OperationTimer {
+    fn drop(&mut self) {
+        let elapsed_ms = self.start.elapsed().as_millis();
+        log::debug!("{}: finished in {} ms", self.operation, elapsed_ms);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(elapsed_ms = elapsed_ms as u64, "operation finished");
+    }
+}